@@ -0,0 +1,2187 @@
+use pallas_codec::minicbor::data::Int;
+use pallas_codec::minicbor::encode::{self, Encode, Encoder, Write};
+use pallas_codec::minicbor::{decode, Decode, Decoder};
+use pallas_codec::utils::{Nullable, Set};
+
+use crate::haskell_types::{
+    AllegraUtxoPredFailure, AlonzoPurposeAsIx, AlonzoUtxoPredFailure, AlonzoUtxosPredFailure,
+    AlonzoUtxowPredFailure, ApplyByronTxErr, ApplyConwayTxPredError, ApplyEraTxErr, ApplyTxErr,
+    BabbageContextError, BabbageUtxoPredFailure, BabbageUtxowPredFailure, ByronLovelaceError,
+    ByronNetworkMagic, ByronTxValidationError, ByronUtxoError, ByronUtxoValidationError,
+    CollectError, ConwayContextError, ConwayDelegCert, ConwayGovCert, ConwayPlutusPurpose,
+    ConwayPurposeAsIx, ConwayPurposeTag, ConwayTxCert, ConwayUtxoPredFailure,
+    ConwayUtxoWPredFailure, ConwayUtxosPredFailure, Credential, DRep, Delegatee, EraPredFailure,
+    FailureDescription, Language, PlutusPurpose, PoolCert, PoolParams, RawCbor, RedeemerPointer,
+    ScriptPurpose, ShelleyBasedEra, ShelleyDelegsPredFailure, ShelleyDelplPredFailure,
+    ShelleyLedgerPredFailure, ShelleyPpupPredFailure, ShelleyUtxoPredFailure,
+    ShelleyUtxowPredFailure, TagMismatchDescription, TxOutSource, TxValidationError, Utxo,
+    VotingPeriod,
+};
+
+impl<'b> Decode<'b, ()> for TxValidationError {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+
+        // Byron is the first era of the hard fork combinator, not a Shelley-based one
+        let mut probe = d.clone();
+        probe.array()?;
+        if probe.u16()? == 0 {
+            d.array()?;
+            d.u16()?;
+            let error = d.decode()?;
+            return Ok(TxValidationError::ByronTxValidationError { error });
+        }
+
+        let era = d.decode()?;
+
+        use ShelleyBasedEra::*;
+        use TxValidationError::*;
+
+        let error = match era {
+            ShelleyBasedEraShelley => ShelleyEraTxValidationError {
+                error: d.decode()?,
+                era,
+            },
+            ShelleyBasedEraAllegra | ShelleyBasedEraMary => AllegraTxValidationError {
+                error: d.decode()?,
+                era,
+            },
+            ShelleyBasedEraAlonzo => AlonzoTxValidationError {
+                error: d.decode()?,
+                era,
+            },
+            ShelleyBasedEraBabbage => BabbageTxValidationError {
+                error: d.decode()?,
+                era,
+            },
+            ShelleyBasedEraConway => ShelleyTxValidationError {
+                error: d.decode()?,
+                era,
+            },
+        };
+
+        Ok(error)
+    }
+}
+
+impl Encode<()> for TxValidationError {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.array(1)?;
+
+        use TxValidationError::*;
+
+        match self {
+            ByronTxValidationError { error } => e.array(2)?.u16(0)?.encode(error)?,
+            ShelleyTxValidationError { error, era } => e.encode(era)?.encode(error)?,
+            BabbageTxValidationError { error, era } => e.encode(era)?.encode(error)?,
+            AlonzoTxValidationError { error, era } => e.encode(era)?.encode(error)?,
+            AllegraTxValidationError { error, era } => e.encode(era)?.encode(error)?,
+            ShelleyEraTxValidationError { error, era } => e.encode(era)?.encode(error)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ApplyTxErr {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        let errors = d.array_iter::<ApplyConwayTxPredError>()?.collect();
+
+        match errors {
+            Ok(errors) => Ok(ApplyTxErr(errors)),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl Encode<()> for ApplyTxErr {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.encode(&self.0)?;
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ApplyConwayTxPredError {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+
+        let error = d.u16()?;
+
+        use ApplyConwayTxPredError::*;
+
+        match error {
+            1 => Ok(UtxowFailure(d.decode()?)),
+            2 => Ok(CertsFailure(d.decode()?)),
+            3 => Ok(GovFailure(d.decode()?)),
+            // TODO Fix this
+            // 4 => Ok(WdrlNotDelegatedToDRep(d.decode()?)),
+            5 => Ok(TreasuryValueMismatch(d.decode()?)),
+            6 => Ok(TxRefScriptsSizeTooBig(d.decode()?)),
+            7 => Ok(MempoolFailure(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ApplyTxPredError: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ApplyConwayTxPredError {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ApplyConwayTxPredError::*;
+
+        match self {
+            UtxowFailure(x) => e.array(2)?.u16(1)?.encode(x)?,
+            CertsFailure(x) => e.array(2)?.u16(2)?.encode(x)?,
+            GovFailure(x) => e.array(2)?.u16(3)?.encode(x)?,
+            WdrlNotDelegatedToDRep(x) => e.array(2)?.u16(4)?.encode(x)?,
+            TreasuryValueMismatch(x) => e.array(2)?.u16(5)?.encode(x)?,
+            TxRefScriptsSizeTooBig(x) => e.array(2)?.u16(6)?.encode(x)?,
+            MempoolFailure(x) => e.array(2)?.u16(7)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ConwayUtxoWPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ConwayUtxoWPredFailure::*;
+
+        match error {
+            0 => Ok(UtxoFailure(d.decode()?)),
+            1 => Ok(InvalidWitnessesUTXOW(d.decode()?)),
+            2 => Ok(MissingVKeyWitnessesUTXOW(d.decode()?)),
+            3 => Ok(MissingScriptWitnessesUTXOW(d.decode()?)),
+            4 => Ok(ScriptWitnessNotValidatingUTXOW(d.decode()?)),
+            5 => Ok(MissingTxBodyMetadataHash(d.decode()?)),
+            6 => Ok(MissingTxMetadata(d.decode()?)),
+            7 => Ok(ConflictingMetadataHash(d.decode()?, d.decode()?)),
+            8 => Ok(InvalidMetadata()),
+            9 => Ok(ExtraneousScriptWitnessesUTXOW(d.decode()?)),
+            10 => Ok(MissingRedeemers(d.decode()?)),
+            11 => Ok(MissingRequiredDatums(d.decode()?, d.decode()?)),
+            12 => Ok(NotAllowedSupplementalDatums(d.decode()?, d.decode()?)),
+            13 => Ok(PPViewHashesDontMatch(d.decode()?)),
+            14 => Ok(UnspendableUTxONoDatumHash(d.decode()?)),
+            15 => Ok(ExtraRedeemers(d.decode()?)),
+            16 => Ok(MalformedScriptWitnesses(d.decode()?)),
+            17 => Ok(MalformedReferenceScripts(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ConwayUtxoWPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ConwayUtxoWPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ConwayUtxoWPredFailure::*;
+
+        match self {
+            UtxoFailure(x) => e.array(2)?.u16(0)?.encode(x)?,
+            InvalidWitnessesUTXOW(x) => e.array(2)?.u16(1)?.encode(x)?,
+            MissingVKeyWitnessesUTXOW(x) => e.array(2)?.u16(2)?.encode(x)?,
+            MissingScriptWitnessesUTXOW(x) => e.array(2)?.u16(3)?.encode(x)?,
+            ScriptWitnessNotValidatingUTXOW(x) => e.array(2)?.u16(4)?.encode(x)?,
+            MissingTxBodyMetadataHash(x) => e.array(2)?.u16(5)?.encode(x)?,
+            MissingTxMetadata(x) => e.array(2)?.u16(6)?.encode(x)?,
+            ConflictingMetadataHash(x, y) => e.array(3)?.u16(7)?.encode(x)?.encode(y)?,
+            InvalidMetadata() => e.array(1)?.u16(8)?,
+            ExtraneousScriptWitnessesUTXOW(x) => e.array(2)?.u16(9)?.encode(x)?,
+            MissingRedeemers(x) => e.array(2)?.u16(10)?.encode(x)?,
+            MissingRequiredDatums(x, y) => e.array(3)?.u16(11)?.encode(x)?.encode(y)?,
+            NotAllowedSupplementalDatums(x, y) => e.array(3)?.u16(12)?.encode(x)?.encode(y)?,
+            PPViewHashesDontMatch(x) => e.array(2)?.u16(13)?.encode(x)?,
+            UnspendableUTxONoDatumHash(x) => e.array(2)?.u16(14)?.encode(x)?,
+            ExtraRedeemers(x) => e.array(2)?.u16(15)?.encode(x)?,
+            MalformedScriptWitnesses(x) => e.array(2)?.u16(16)?.encode(x)?,
+            MalformedReferenceScripts(x) => e.array(2)?.u16(17)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ConwayUtxoPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ConwayUtxoPredFailure::*;
+
+        match error {
+            0 => Ok(UtxosFailure(d.decode()?)),
+            1 => Ok(BadInputsUTxO(d.decode()?)),
+            2 => Ok(OutsideValidityIntervalUTxO(d.decode()?, d.decode()?)),
+            3 => Ok(MaxTxSizeUTxO(d.decode()?)),
+            4 => Ok(InputSetEmptyUTxO()),
+            5 => Ok(FeeTooSmallUTxO(d.decode()?, d.decode()?)),
+            6 => Ok(ValueNotConservedUTxO(d.decode()?, d.decode()?)),
+            7 => Ok(WrongNetwork(d.decode()?, d.decode()?)),
+            8 => Ok(WrongNetworkWithdrawal(d.decode()?, d.decode()?)),
+            9 => Ok(OutputTooSmallUTxO(d.decode()?)),
+            10 => Ok(OutputBootAddrAttrsTooBig(d.decode()?)),
+            11 => Ok(OutputTooBigUTxO(d.decode()?)),
+            12 => Ok(InsufficientCollateral(d.decode()?, d.decode()?)),
+            13 => Ok(ScriptsNotPaidUTxO(d.decode()?)),
+            14 => Ok(ExUnitsTooBigUTxO(d.decode()?)),
+            15 => Ok(CollateralContainsNonADA(d.decode()?)),
+            16 => Ok(WrongNetworkInTxBody()),
+            17 => Ok(OutsideForecast(d.decode()?)),
+            18 => Ok(TooManyCollateralInputs(d.decode()?)),
+            19 => Ok(NoCollateralInputs()),
+            20 => Ok(IncorrectTotalCollateralField(d.decode()?, d.decode()?)),
+            21 => Ok(BabbageOutputTooSmallUTxO(d.decode()?)),
+            22 => Ok(BabbageNonDisjointRefInputs(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ConwayUtxoPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ConwayUtxoPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ConwayUtxoPredFailure::*;
+
+        match self {
+            UtxosFailure(x) => e.array(2)?.u16(0)?.encode(x)?,
+            BadInputsUTxO(x) => e.array(2)?.u16(1)?.encode(x)?,
+            OutsideValidityIntervalUTxO(x, y) => e.array(3)?.u16(2)?.encode(x)?.encode(y)?,
+            MaxTxSizeUTxO(x) => e.array(2)?.u16(3)?.encode(x)?,
+            InputSetEmptyUTxO() => e.array(1)?.u16(4)?,
+            FeeTooSmallUTxO(x, y) => e.array(3)?.u16(5)?.encode(x)?.encode(y)?,
+            ValueNotConservedUTxO(x, y) => e.array(3)?.u16(6)?.encode(x)?.encode(y)?,
+            WrongNetwork(x, y) => e.array(3)?.u16(7)?.encode(x)?.encode(y)?,
+            WrongNetworkWithdrawal(x, y) => e.array(3)?.u16(8)?.encode(x)?.encode(y)?,
+            OutputTooSmallUTxO(x) => e.array(2)?.u16(9)?.encode(x)?,
+            OutputBootAddrAttrsTooBig(x) => e.array(2)?.u16(10)?.encode(x)?,
+            OutputTooBigUTxO(x) => e.array(2)?.u16(11)?.encode(x)?,
+            InsufficientCollateral(x, y) => e.array(3)?.u16(12)?.encode(x)?.encode(y)?,
+            ScriptsNotPaidUTxO(x) => e.array(2)?.u16(13)?.encode(x)?,
+            ExUnitsTooBigUTxO(x) => e.array(2)?.u16(14)?.encode(x)?,
+            CollateralContainsNonADA(x) => e.array(2)?.u16(15)?.encode(x)?,
+            WrongNetworkInTxBody() => e.array(1)?.u16(16)?,
+            OutsideForecast(x) => e.array(2)?.u16(17)?.encode(x)?,
+            TooManyCollateralInputs(x) => e.array(2)?.u16(18)?.encode(x)?,
+            NoCollateralInputs() => e.array(1)?.u16(19)?,
+            IncorrectTotalCollateralField(x, y) => e.array(3)?.u16(20)?.encode(x)?.encode(y)?,
+            BabbageOutputTooSmallUTxO(x) => e.array(2)?.u16(21)?.encode(x)?,
+            BabbageNonDisjointRefInputs(x) => e.array(2)?.u16(22)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ShelleyBasedEra {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let era = d.u16()?;
+
+        use ShelleyBasedEra::*;
+
+        match era {
+            1 => Ok(ShelleyBasedEraShelley),
+            2 => Ok(ShelleyBasedEraAllegra),
+            3 => Ok(ShelleyBasedEraMary),
+            4 => Ok(ShelleyBasedEraAlonzo),
+            5 => Ok(ShelleyBasedEraBabbage),
+            6 => Ok(ShelleyBasedEraConway),
+            _ => Err(decode::Error::message(format!(
+                "unknown era while decoding ShelleyBasedEra: {}",
+                era
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ShelleyBasedEra {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ShelleyBasedEra::*;
+
+        let era = match self {
+            ShelleyBasedEraShelley => 1,
+            ShelleyBasedEraAllegra => 2,
+            ShelleyBasedEraMary => 3,
+            ShelleyBasedEraAlonzo => 4,
+            ShelleyBasedEraBabbage => 5,
+            ShelleyBasedEraConway => 6,
+        };
+
+        // Followed by the error, within the same list
+        e.array(2)?.u16(era)?;
+        Ok(())
+    }
+}
+
+// not tested yet
+impl<'b> Decode<'b, ()> for PlutusPurpose {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        // d.array()?;
+        let purpose = d.u16()?;
+
+        use PlutusPurpose::*;
+
+        match purpose {
+            0 => Ok(Spending),
+            1 => Ok(Minting),
+            2 => Ok(Certifying),
+            3 => Ok(Rewarding),
+            _ => Err(decode::Error::message(format!(
+                "unknown purpose while decoding PlutusPurpose: {}",
+                purpose
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for PlutusPurpose {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use PlutusPurpose::*;
+
+        let purpose = match self {
+            Spending => 0,
+            Minting => 1,
+            Certifying => 2,
+            Rewarding => 3,
+        };
+
+        e.u16(purpose)?;
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for Utxo {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        let entries = d.map_iter()?.collect::<Result<_, _>>()?;
+        Ok(Utxo(entries))
+    }
+}
+
+impl Encode<()> for Utxo {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.map(self.0.len() as u64)?;
+
+        for (input, output) in &self.0 {
+            e.encode(input)?.encode(output)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'b, E: EraPredFailure> Decode<'b, ()> for ApplyEraTxErr<E> {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        let errors = d.array_iter::<ShelleyLedgerPredFailure<E>>()?.collect();
+
+        match errors {
+            Ok(errors) => Ok(ApplyEraTxErr(errors)),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl<E: EraPredFailure> Encode<()> for ApplyEraTxErr<E> {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.encode(&self.0)?;
+        Ok(())
+    }
+}
+
+impl<'b, E: EraPredFailure> Decode<'b, ()> for ShelleyLedgerPredFailure<E> {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ShelleyLedgerPredFailure::*;
+
+        match error {
+            0 => Ok(UtxowFailure(d.decode()?)),
+            1 => Ok(DelegsFailure(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ShelleyLedgerPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl<E: EraPredFailure> Encode<()> for ShelleyLedgerPredFailure<E> {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ShelleyLedgerPredFailure::*;
+
+        match self {
+            UtxowFailure(x) => e.array(2)?.u16(0)?.encode(x)?,
+            DelegsFailure(x) => e.array(2)?.u16(1)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for BabbageUtxowPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use BabbageUtxowPredFailure::*;
+
+        match error {
+            1 => Ok(AlonzoInBabbageUtxowPredFailure(d.decode()?)),
+            2 => Ok(UtxoFailure(d.decode()?)),
+            3 => Ok(MalformedScriptWitnesses(d.decode()?)),
+            4 => Ok(MalformedReferenceScripts(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding BabbageUtxowPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for BabbageUtxowPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use BabbageUtxowPredFailure::*;
+
+        match self {
+            AlonzoInBabbageUtxowPredFailure(x) => e.array(2)?.u16(1)?.encode(x)?,
+            UtxoFailure(x) => e.array(2)?.u16(2)?.encode(x)?,
+            MalformedScriptWitnesses(x) => e.array(2)?.u16(3)?.encode(x)?,
+            MalformedReferenceScripts(x) => e.array(2)?.u16(4)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b, U: Decode<'b, ()>> Decode<'b, ()> for AlonzoUtxowPredFailure<U> {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use AlonzoUtxowPredFailure::*;
+
+        match error {
+            0 => Ok(ShelleyInAlonzoUtxowPredfailure(d.decode()?)),
+            1 => Ok(MissingRedeemers(d.decode()?)),
+            2 => Ok(MissingRequiredDatums(d.decode()?, d.decode()?)),
+            3 => Ok(NotAllowedSupplementalDatums(d.decode()?, d.decode()?)),
+            4 => Ok(PPViewHashesDontMatch(
+                decode_strict_maybe(d)?,
+                decode_strict_maybe(d)?,
+            )),
+            5 => Ok(MissingRequiredSigners(d.decode()?)),
+            6 => Ok(UnspendableUTxONoDatumHash(d.decode()?)),
+            7 => Ok(ExtraRedeemers(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding AlonzoUtxowPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl<U: Encode<()>> Encode<()> for AlonzoUtxowPredFailure<U> {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use AlonzoUtxowPredFailure::*;
+
+        match self {
+            ShelleyInAlonzoUtxowPredfailure(x) => e.array(2)?.u16(0)?.encode(x)?,
+            MissingRedeemers(x) => e.array(2)?.u16(1)?.encode(x)?,
+            MissingRequiredDatums(x, y) => e.array(3)?.u16(2)?.encode(x)?.encode(y)?,
+            NotAllowedSupplementalDatums(x, y) => e.array(3)?.u16(3)?.encode(x)?.encode(y)?,
+            PPViewHashesDontMatch(x, y) => {
+                e.array(3)?.u16(4)?;
+                encode_strict_maybe(e, x)?;
+                encode_strict_maybe(e, y)?
+            }
+            MissingRequiredSigners(x) => e.array(2)?.u16(5)?.encode(x)?,
+            UnspendableUTxONoDatumHash(x) => e.array(2)?.u16(6)?.encode(x)?,
+            ExtraRedeemers(x) => e.array(2)?.u16(7)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b, U: Decode<'b, ()>> Decode<'b, ()> for ShelleyUtxowPredFailure<U> {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ShelleyUtxowPredFailure::*;
+
+        match error {
+            0 => Ok(InvalidWitnessesUTXOW(d.decode()?)),
+            1 => Ok(MissingVKeyWitnessesUTXOW(d.decode()?)),
+            2 => Ok(MissingScriptWitnessesUTXOW(d.decode()?)),
+            3 => Ok(ScriptWitnessNotValidatingUTXOW(d.decode()?)),
+            4 => Ok(UtxoFailure(d.decode()?)),
+            5 => Ok(MIRInsufficientGenesisSigsUTXOW(d.decode()?)),
+            6 => Ok(MissingTxBodyMetadataHash(d.decode()?)),
+            7 => Ok(MissingTxMetadata(d.decode()?)),
+            8 => Ok(ConflictingMetadataHash(d.decode()?, d.decode()?)),
+            9 => Ok(InvalidMetadata()),
+            10 => Ok(ExtraneousScriptWitnessesUTXOW(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ShelleyUtxowPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl<U: Encode<()>> Encode<()> for ShelleyUtxowPredFailure<U> {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ShelleyUtxowPredFailure::*;
+
+        match self {
+            InvalidWitnessesUTXOW(x) => e.array(2)?.u16(0)?.encode(x)?,
+            MissingVKeyWitnessesUTXOW(x) => e.array(2)?.u16(1)?.encode(x)?,
+            MissingScriptWitnessesUTXOW(x) => e.array(2)?.u16(2)?.encode(x)?,
+            ScriptWitnessNotValidatingUTXOW(x) => e.array(2)?.u16(3)?.encode(x)?,
+            UtxoFailure(x) => e.array(2)?.u16(4)?.encode(x)?,
+            MIRInsufficientGenesisSigsUTXOW(x) => e.array(2)?.u16(5)?.encode(x)?,
+            MissingTxBodyMetadataHash(x) => e.array(2)?.u16(6)?.encode(x)?,
+            MissingTxMetadata(x) => e.array(2)?.u16(7)?.encode(x)?,
+            ConflictingMetadataHash(x, y) => e.array(3)?.u16(8)?.encode(x)?.encode(y)?,
+            InvalidMetadata() => e.array(1)?.u16(9)?,
+            ExtraneousScriptWitnessesUTXOW(x) => e.array(2)?.u16(10)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ShelleyUtxoPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ShelleyUtxoPredFailure::*;
+
+        match error {
+            0 => Ok(BadInputsUTxO(d.decode()?)),
+            1 => Ok(ExpiredUTxO(d.decode()?, d.decode()?)),
+            2 => Ok(MaxTxSizeUTxO(d.decode()?, d.decode()?)),
+            3 => Ok(InputSetEmptyUTxO()),
+            4 => Ok(FeeTooSmallUTxO(d.decode()?, d.decode()?)),
+            5 => Ok(ValueNotConservedUTxO(d.decode()?, d.decode()?)),
+            6 => Ok(OutputTooSmallUTxO(d.decode()?)),
+            7 => Ok(UpdateFailure(d.decode()?)),
+            8 => Ok(WrongNetwork(d.decode()?, d.decode()?)),
+            9 => Ok(WrongNetworkWithdrawal(d.decode()?, d.decode()?)),
+            10 => Ok(OutputBootAddrAttrsTooBig(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ShelleyUtxoPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ShelleyUtxoPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ShelleyUtxoPredFailure::*;
+
+        match self {
+            BadInputsUTxO(x) => e.array(2)?.u16(0)?.encode(x)?,
+            ExpiredUTxO(x, y) => e.array(3)?.u16(1)?.encode(x)?.encode(y)?,
+            MaxTxSizeUTxO(x, y) => e.array(3)?.u16(2)?.encode(x)?.encode(y)?,
+            InputSetEmptyUTxO() => e.array(1)?.u16(3)?,
+            FeeTooSmallUTxO(x, y) => e.array(3)?.u16(4)?.encode(x)?.encode(y)?,
+            ValueNotConservedUTxO(x, y) => e.array(3)?.u16(5)?.encode(x)?.encode(y)?,
+            OutputTooSmallUTxO(x) => e.array(2)?.u16(6)?.encode(x)?,
+            UpdateFailure(x) => e.array(2)?.u16(7)?.encode(x)?,
+            WrongNetwork(x, y) => e.array(3)?.u16(8)?.encode(x)?.encode(y)?,
+            WrongNetworkWithdrawal(x, y) => e.array(3)?.u16(9)?.encode(x)?.encode(y)?,
+            OutputBootAddrAttrsTooBig(x) => e.array(2)?.u16(10)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for AllegraUtxoPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use AllegraUtxoPredFailure::*;
+
+        match error {
+            0 => Ok(BadInputsUTxO(d.decode()?)),
+            1 => Ok(OutsideValidityIntervalUTxO(d.decode()?, d.decode()?)),
+            2 => Ok(MaxTxSizeUTxO(d.decode()?, d.decode()?)),
+            3 => Ok(InputSetEmptyUTxO()),
+            4 => Ok(FeeTooSmallUTxO(d.decode()?, d.decode()?)),
+            5 => Ok(ValueNotConservedUTxO(d.decode()?, d.decode()?)),
+            6 => Ok(WrongNetwork(d.decode()?, d.decode()?)),
+            7 => Ok(WrongNetworkWithdrawal(d.decode()?, d.decode()?)),
+            8 => Ok(OutputTooSmallUTxO(d.decode()?)),
+            9 => Ok(UpdateFailure(d.decode()?)),
+            10 => Ok(OutputBootAddrAttrsTooBig(d.decode()?)),
+            11 => Ok(TriesToForgeADA()),
+            12 => Ok(OutputTooBigUTxO(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding AllegraUtxoPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for AllegraUtxoPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use AllegraUtxoPredFailure::*;
+
+        match self {
+            BadInputsUTxO(x) => e.array(2)?.u16(0)?.encode(x)?,
+            OutsideValidityIntervalUTxO(x, y) => e.array(3)?.u16(1)?.encode(x)?.encode(y)?,
+            MaxTxSizeUTxO(x, y) => e.array(3)?.u16(2)?.encode(x)?.encode(y)?,
+            InputSetEmptyUTxO() => e.array(1)?.u16(3)?,
+            FeeTooSmallUTxO(x, y) => e.array(3)?.u16(4)?.encode(x)?.encode(y)?,
+            ValueNotConservedUTxO(x, y) => e.array(3)?.u16(5)?.encode(x)?.encode(y)?,
+            WrongNetwork(x, y) => e.array(3)?.u16(6)?.encode(x)?.encode(y)?,
+            WrongNetworkWithdrawal(x, y) => e.array(3)?.u16(7)?.encode(x)?.encode(y)?,
+            OutputTooSmallUTxO(x) => e.array(2)?.u16(8)?.encode(x)?,
+            UpdateFailure(x) => e.array(2)?.u16(9)?.encode(x)?,
+            OutputBootAddrAttrsTooBig(x) => e.array(2)?.u16(10)?.encode(x)?,
+            TriesToForgeADA() => e.array(1)?.u16(11)?,
+            OutputTooBigUTxO(x) => e.array(2)?.u16(12)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for BabbageUtxoPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use BabbageUtxoPredFailure::*;
+
+        match error {
+            1 => Ok(AlonzoInBabbageUtxoPredFailure(d.decode()?)),
+            2 => Ok(IncorrectTotalCollateralField(d.decode()?, d.decode()?)),
+            3 => Ok(BabbageOutputTooSmallUTxO(d.decode()?)),
+            4 => Ok(BabbageNonDisjointRefInputs(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding BabbageUtxoPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for BabbageUtxoPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use BabbageUtxoPredFailure::*;
+
+        match self {
+            AlonzoInBabbageUtxoPredFailure(x) => e.array(2)?.u16(1)?.encode(x)?,
+            IncorrectTotalCollateralField(x, y) => e.array(3)?.u16(2)?.encode(x)?.encode(y)?,
+            BabbageOutputTooSmallUTxO(x) => e.array(2)?.u16(3)?.encode(x)?,
+            BabbageNonDisjointRefInputs(x) => e.array(2)?.u16(4)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for AlonzoUtxoPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use AlonzoUtxoPredFailure::*;
+
+        match error {
+            0 => Ok(BadInputsUTxO(d.decode()?)),
+            1 => Ok(OutsideValidityIntervalUTxO(d.decode()?, d.decode()?)),
+            2 => Ok(MaxTxSizeUTxO(d.decode()?, d.decode()?)),
+            3 => Ok(InputSetEmptyUTxO()),
+            4 => Ok(FeeTooSmallUTxO(d.decode()?, d.decode()?)),
+            5 => Ok(ValueNotConservedUTxO(d.decode()?, d.decode()?)),
+            6 => Ok(OutputTooSmallUTxO(d.decode()?)),
+            7 => Ok(UtxosFailure(d.decode()?)),
+            8 => Ok(WrongNetwork(d.decode()?, d.decode()?)),
+            9 => Ok(WrongNetworkWithdrawal(d.decode()?, d.decode()?)),
+            10 => Ok(OutputBootAddrAttrsTooBig(d.decode()?)),
+            11 => Ok(TriesToForgeADA()),
+            12 => Ok(OutputTooBigUTxO(d.decode()?)),
+            13 => Ok(InsufficientCollateral(d.decode()?, d.decode()?)),
+            14 => Ok(ScriptsNotPaidUTxO(d.decode()?)),
+            15 => Ok(ExUnitsTooBigUTxO(d.decode()?, d.decode()?)),
+            16 => Ok(CollateralContainsNonADA(d.decode()?)),
+            17 => Ok(WrongNetworkInTxBody(d.decode()?, d.decode()?)),
+            18 => Ok(OutsideForecast(d.decode()?)),
+            19 => Ok(TooManyCollateralInputs(d.decode()?, d.decode()?)),
+            20 => Ok(NoCollateralInputs()),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding AlonzoUtxoPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for AlonzoUtxoPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use AlonzoUtxoPredFailure::*;
+
+        match self {
+            BadInputsUTxO(x) => e.array(2)?.u16(0)?.encode(x)?,
+            OutsideValidityIntervalUTxO(x, y) => e.array(3)?.u16(1)?.encode(x)?.encode(y)?,
+            MaxTxSizeUTxO(x, y) => e.array(3)?.u16(2)?.encode(x)?.encode(y)?,
+            InputSetEmptyUTxO() => e.array(1)?.u16(3)?,
+            FeeTooSmallUTxO(x, y) => e.array(3)?.u16(4)?.encode(x)?.encode(y)?,
+            ValueNotConservedUTxO(x, y) => e.array(3)?.u16(5)?.encode(x)?.encode(y)?,
+            OutputTooSmallUTxO(x) => e.array(2)?.u16(6)?.encode(x)?,
+            UtxosFailure(x) => e.array(2)?.u16(7)?.encode(x)?,
+            WrongNetwork(x, y) => e.array(3)?.u16(8)?.encode(x)?.encode(y)?,
+            WrongNetworkWithdrawal(x, y) => e.array(3)?.u16(9)?.encode(x)?.encode(y)?,
+            OutputBootAddrAttrsTooBig(x) => e.array(2)?.u16(10)?.encode(x)?,
+            TriesToForgeADA() => e.array(1)?.u16(11)?,
+            OutputTooBigUTxO(x) => e.array(2)?.u16(12)?.encode(x)?,
+            InsufficientCollateral(x, y) => e.array(3)?.u16(13)?.encode(x)?.encode(y)?,
+            ScriptsNotPaidUTxO(x) => e.array(2)?.u16(14)?.encode(x)?,
+            ExUnitsTooBigUTxO(x, y) => e.array(3)?.u16(15)?.encode(x)?.encode(y)?,
+            CollateralContainsNonADA(x) => e.array(2)?.u16(16)?.encode(x)?,
+            WrongNetworkInTxBody(x, y) => e.array(3)?.u16(17)?.encode(x)?.encode(y)?,
+            OutsideForecast(x) => e.array(2)?.u16(18)?.encode(x)?,
+            TooManyCollateralInputs(x, y) => e.array(3)?.u16(19)?.encode(x)?.encode(y)?,
+            NoCollateralInputs() => e.array(1)?.u16(20)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for AlonzoUtxosPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use AlonzoUtxosPredFailure::*;
+
+        match error {
+            0 => Ok(ValidationTagMismatch(d.decode()?, d.decode()?)),
+            1 => Ok(CollectErrors(d.decode()?)),
+            2 => Ok(UpdateFailure(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding AlonzoUtxosPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for AlonzoUtxosPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use AlonzoUtxosPredFailure::*;
+
+        match self {
+            ValidationTagMismatch(x, y) => e.array(3)?.u16(0)?.encode(x)?.encode(y)?,
+            CollectErrors(x) => e.array(2)?.u16(1)?.encode(x)?,
+            UpdateFailure(x) => e.array(2)?.u16(2)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ConwayUtxosPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ConwayUtxosPredFailure::*;
+
+        match error {
+            0 => Ok(ValidationTagMismatch(d.decode()?, d.decode()?)),
+            1 => Ok(CollectErrors(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ConwayUtxosPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ConwayUtxosPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ConwayUtxosPredFailure::*;
+
+        match self {
+            ValidationTagMismatch(x, y) => e.array(3)?.u16(0)?.encode(x)?.encode(y)?,
+            CollectErrors(x) => e.array(2)?.u16(1)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ConwayPlutusPurpose {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let purpose = d.u16()?;
+
+        use ConwayPlutusPurpose::*;
+
+        match purpose {
+            0 => Ok(ConwaySpending(d.decode()?)),
+            1 => Ok(ConwayMinting(d.decode()?)),
+            2 => Ok(ConwayCertifying(d.decode()?)),
+            3 => Ok(ConwayRewarding(d.decode()?)),
+            4 => Ok(ConwayVoting(d.decode()?)),
+            5 => Ok(ConwayProposing(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown purpose while decoding ConwayPlutusPurpose: {}",
+                purpose
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ConwayPlutusPurpose {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ConwayPlutusPurpose::*;
+
+        match self {
+            ConwaySpending(x) => e.array(2)?.u16(0)?.encode(x)?,
+            ConwayMinting(x) => e.array(2)?.u16(1)?.encode(x)?,
+            ConwayCertifying(x) => e.array(2)?.u16(2)?.encode(x)?,
+            ConwayRewarding(x) => e.array(2)?.u16(3)?.encode(x)?,
+            ConwayVoting(x) => e.array(2)?.u16(4)?.encode(x)?,
+            ConwayProposing(x) => e.array(2)?.u16(5)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for TagMismatchDescription {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let tag = d.u16()?;
+
+        use TagMismatchDescription::*;
+
+        match tag {
+            0 => Ok(PassedUnexpectedly()),
+            1 => Ok(FailedUnexpectedly(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown tag while decoding TagMismatchDescription: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for TagMismatchDescription {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use TagMismatchDescription::*;
+
+        match self {
+            PassedUnexpectedly() => e.array(1)?.u16(0)?,
+            FailedUnexpectedly(x) => e.array(2)?.u16(1)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for FailureDescription {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let tag = d.u16()?;
+
+        use FailureDescription::*;
+
+        match tag {
+            1 => Ok(PlutusFailure(d.decode()?, d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown tag while decoding FailureDescription: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for FailureDescription {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use FailureDescription::*;
+
+        match self {
+            PlutusFailure(x, y) => e.array(3)?.u16(1)?.encode(x)?.encode(y)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b, P: Decode<'b, ()>, C: Decode<'b, ()>> Decode<'b, ()> for CollectError<P, C> {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use CollectError::*;
+
+        match error {
+            0 => Ok(NoRedeemer(d.decode()?)),
+            1 => Ok(NoWitness(d.decode()?)),
+            2 => Ok(NoCostModel(d.decode()?)),
+            3 => Ok(BadTranslation(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding CollectError: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl<P: Encode<()>, C: Encode<()>> Encode<()> for CollectError<P, C> {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use CollectError::*;
+
+        match self {
+            NoRedeemer(x) => e.array(2)?.u16(0)?.encode(x)?,
+            NoWitness(x) => e.array(2)?.u16(1)?.encode(x)?,
+            NoCostModel(x) => e.array(2)?.u16(2)?.encode(x)?,
+            BadTranslation(x) => e.array(2)?.u16(3)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ConwayContextError {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ConwayContextError::*;
+
+        match error {
+            8 => Ok(BabbageContextError(d.decode()?)),
+            10 => Ok(CertificateNotSupported(d.decode()?)),
+            11 => Ok(PlutusPurposeNotSupported(d.decode()?)),
+            12 => Ok(CurrentTreasuryFieldNotSupported(d.decode()?)),
+            13 => Ok(VotingProceduresFieldNotSupported(d.decode()?)),
+            14 => Ok(ProposalProceduresFieldNotSupported(d.decode()?)),
+            15 => Ok(TreasuryDonationFieldNotSupported(d.decode()?)),
+            16 => Ok(ReferenceInputsNotDisjointFromInputs(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ConwayContextError: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ConwayContextError {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ConwayContextError::*;
+
+        match self {
+            BabbageContextError(x) => e.array(2)?.u16(8)?.encode(x)?,
+            CertificateNotSupported(x) => e.array(2)?.u16(10)?.encode(x)?,
+            PlutusPurposeNotSupported(x) => e.array(2)?.u16(11)?.encode(x)?,
+            CurrentTreasuryFieldNotSupported(x) => e.array(2)?.u16(12)?.encode(x)?,
+            VotingProceduresFieldNotSupported(x) => e.array(2)?.u16(13)?.encode(x)?,
+            ProposalProceduresFieldNotSupported(x) => e.array(2)?.u16(14)?.encode(x)?,
+            TreasuryDonationFieldNotSupported(x) => e.array(2)?.u16(15)?.encode(x)?,
+            ReferenceInputsNotDisjointFromInputs(x) => e.array(2)?.u16(16)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b, I: Decode<'b, ()>> Decode<'b, ()> for BabbageContextError<I> {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use crate::haskell_types::AlonzoContextError as Alonzo;
+        use BabbageContextError::*;
+
+        // The Alonzo errors are flattened into the Babbage encoding
+        match error {
+            0 => Ok(ByronTxOutInContext(d.decode()?)),
+            1 => Ok(AlonzoContextError(Alonzo::TranslationLogicMissingInput(
+                d.decode()?,
+            ))),
+            2 => Ok(RedeemerPointerPointsToNothing(d.decode()?)),
+            4 => Ok(InlineDatumsNotSupported(d.decode()?)),
+            5 => Ok(ReferenceScriptsNotSupported(d.decode()?)),
+            6 => Ok(ReferenceInputsNotSupported(d.decode::<Set<_>>()?.to_vec())),
+            7 => Ok(AlonzoContextError(Alonzo::TimeTranslationPastHorizon(
+                d.decode()?,
+            ))),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding BabbageContextError: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl<I: Encode<()>> Encode<()> for BabbageContextError<I> {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use crate::haskell_types::AlonzoContextError as Alonzo;
+        use BabbageContextError::*;
+
+        match self {
+            ByronTxOutInContext(x) => e.array(2)?.u16(0)?.encode(x)?,
+            AlonzoContextError(Alonzo::TranslationLogicMissingInput(x)) => {
+                e.array(2)?.u16(1)?.encode(x)?
+            }
+            RedeemerPointerPointsToNothing(x) => e.array(2)?.u16(2)?.encode(x)?,
+            InlineDatumsNotSupported(x) => e.array(2)?.u16(4)?.encode(x)?,
+            ReferenceScriptsNotSupported(x) => e.array(2)?.u16(5)?.encode(x)?,
+            ReferenceInputsNotSupported(x) => e.array(2)?.u16(6)?.encode(x)?,
+            AlonzoContextError(Alonzo::TimeTranslationPastHorizon(x)) => {
+                e.array(2)?.u16(7)?.encode(x)?
+            }
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for TxOutSource {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let source = d.u16()?;
+
+        use TxOutSource::*;
+
+        match source {
+            0 => Ok(TxOutFromInput(d.decode()?)),
+            1 => Ok(TxOutFromOutput(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown tag while decoding TxOutSource: {}",
+                source
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for TxOutSource {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use TxOutSource::*;
+
+        match self {
+            TxOutFromInput(x) => e.array(2)?.u16(0)?.encode(x)?,
+            TxOutFromOutput(x) => e.array(2)?.u16(1)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for AlonzoPurposeAsIx {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        Ok(AlonzoPurposeAsIx {
+            tag: d.decode()?,
+            index: d.decode()?,
+        })
+    }
+}
+
+impl Encode<()> for AlonzoPurposeAsIx {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.array(2)?.encode(&self.tag)?.u32(self.index)?;
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ConwayPurposeAsIx {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let tag = d.u16()?;
+
+        use ConwayPurposeTag::*;
+
+        let tag = match tag {
+            0 => Spending,
+            1 => Minting,
+            2 => Certifying,
+            3 => Rewarding,
+            4 => Voting,
+            5 => Proposing,
+            _ => {
+                return Err(decode::Error::message(format!(
+                    "unknown tag while decoding ConwayPurposeAsIx: {}",
+                    tag
+                )))
+            }
+        };
+
+        Ok(ConwayPurposeAsIx {
+            tag,
+            index: d.decode()?,
+        })
+    }
+}
+
+impl Encode<()> for ConwayPurposeAsIx {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ConwayPurposeTag::*;
+
+        let tag = match self.tag {
+            Spending => 0,
+            Minting => 1,
+            Certifying => 2,
+            Rewarding => 3,
+            Voting => 4,
+            Proposing => 5,
+        };
+
+        e.array(2)?.u16(tag)?.u32(self.index)?;
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for Language {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        let language = d.u16()?;
+
+        use Language::*;
+
+        match language {
+            0 => Ok(PlutusV1),
+            1 => Ok(PlutusV2),
+            2 => Ok(PlutusV3),
+            _ => Err(decode::Error::message(format!(
+                "unknown language while decoding Language: {}",
+                language
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for Language {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use Language::*;
+
+        let language = match self {
+            PlutusV1 => 0,
+            PlutusV2 => 1,
+            PlutusV3 => 2,
+        };
+
+        e.u16(language)?;
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ScriptPurpose {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let purpose = d.u16()?;
+
+        use ScriptPurpose::*;
+
+        match purpose {
+            0 => Ok(Minting(d.decode()?)),
+            1 => Ok(Spending(d.decode()?)),
+            2 => Ok(Rewarding(d.decode()?)),
+            3 => Ok(Certifying(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown purpose while decoding ScriptPurpose: {}",
+                purpose
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ScriptPurpose {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ScriptPurpose::*;
+
+        match self {
+            Minting(x) => e.array(2)?.u16(0)?.encode(x)?,
+            Spending(x) => e.array(2)?.u16(1)?.encode(x)?,
+            Rewarding(x) => e.array(2)?.u16(2)?.encode(x)?,
+            Certifying(x) => e.array(2)?.u16(3)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for RedeemerPointer {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        Ok(RedeemerPointer {
+            tag: d.decode()?,
+            index: d.decode()?,
+        })
+    }
+}
+
+impl Encode<()> for RedeemerPointer {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.array(2)?.encode(&self.tag)?.u64(self.index)?;
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ShelleyPpupPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ShelleyPpupPredFailure::*;
+
+        match error {
+            0 => Ok(NonGenesisUpdatePPUP(d.decode()?, d.decode()?)),
+            1 => Ok(PPUpdateWrongEpoch(d.decode()?, d.decode()?, d.decode()?)),
+            2 => {
+                d.array()?;
+                Ok(PVCannotFollowPPUP(d.decode()?, d.decode()?))
+            }
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ShelleyPpupPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ShelleyPpupPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ShelleyPpupPredFailure::*;
+
+        match self {
+            NonGenesisUpdatePPUP(x, y) => e.array(3)?.u16(0)?.encode(x)?.encode(y)?,
+            PPUpdateWrongEpoch(x, y, z) => e.array(4)?.u16(1)?.encode(x)?.encode(y)?.encode(z)?,
+            PVCannotFollowPPUP(major, minor) => {
+                e.array(2)?.u16(2)?.array(2)?.u64(*major)?.u64(*minor)?
+            }
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for VotingPeriod {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        let period = d.u16()?;
+
+        use VotingPeriod::*;
+
+        match period {
+            0 => Ok(VoteForThisEpoch),
+            1 => Ok(VoteForNextEpoch),
+            _ => Err(decode::Error::message(format!(
+                "unknown voting period while decoding VotingPeriod: {}",
+                period
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for VotingPeriod {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use VotingPeriod::*;
+
+        let period = match self {
+            VoteForThisEpoch => 0,
+            VoteForNextEpoch => 1,
+        };
+
+        e.u16(period)?;
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ShelleyDelegsPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ShelleyDelegsPredFailure::*;
+
+        match error {
+            0 => Ok(DelegateeNotRegisteredDELEG(d.decode()?)),
+            // A map of reward accounts to the amounts they tried to withdraw
+            1 => Ok(WithdrawalsNotInRewardsDELEGS(
+                d.map_iter()?.collect::<Result<_, _>>()?,
+            )),
+            2 => Ok(DelplFailure(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ShelleyDelegsPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ShelleyDelegsPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ShelleyDelegsPredFailure::*;
+
+        match self {
+            DelegateeNotRegisteredDELEG(x) => e.array(2)?.u16(0)?.encode(x)?,
+            WithdrawalsNotInRewardsDELEGS(withdrawals) => {
+                e.array(2)?.u16(1)?.map(withdrawals.len() as u64)?;
+                for (account, coin) in withdrawals {
+                    e.encode(account)?.encode(coin)?;
+                }
+                e
+            }
+            DelplFailure(x) => e.array(2)?.u16(2)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ShelleyDelplPredFailure {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ShelleyDelplPredFailure::*;
+
+        match error {
+            0 => Ok(PoolFailure(d.decode()?)),
+            1 => Ok(DelegFailure(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ShelleyDelplPredFailure: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ShelleyDelplPredFailure {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ShelleyDelplPredFailure::*;
+
+        match self {
+            PoolFailure(x) => e.array(2)?.u16(0)?.encode(x)?,
+            DelegFailure(x) => e.array(2)?.u16(1)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for RawCbor {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        let start = d.position();
+        d.skip()?;
+        d.input()
+            .get(start..d.position())
+            .map(|raw| RawCbor(raw.to_vec()))
+            .ok_or_else(|| decode::Error::end_of_input().at(start))
+    }
+}
+
+impl Encode<()> for RawCbor {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.writer_mut()
+            .write_all(&self.0)
+            .map_err(encode::Error::write)?;
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for DRep {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let tag = d.u16()?;
+
+        use DRep::*;
+
+        match tag {
+            0 => Ok(DRepCredential(Credential::KeyHashObj(d.decode()?))),
+            1 => Ok(DRepCredential(Credential::ScriptHashObj(d.decode()?))),
+            2 => Ok(DRepAlwaysAbstain),
+            3 => Ok(DRepAlwaysNoConfidence),
+            _ => Err(decode::Error::message(format!(
+                "unknown tag while decoding DRep: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for DRep {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use DRep::*;
+
+        match self {
+            DRepCredential(Credential::KeyHashObj(x)) => e.array(2)?.u16(0)?.encode(x)?,
+            DRepCredential(Credential::ScriptHashObj(x)) => e.array(2)?.u16(1)?.encode(x)?,
+            DRepAlwaysAbstain => e.array(1)?.u16(2)?,
+            DRepAlwaysNoConfidence => e.array(1)?.u16(3)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for Credential {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let tag = d.u16()?;
+
+        use Credential::*;
+
+        match tag {
+            0 => Ok(KeyHashObj(d.decode()?)),
+            1 => Ok(ScriptHashObj(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown tag while decoding Credential: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for Credential {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use Credential::*;
+
+        match self {
+            KeyHashObj(x) => e.array(2)?.u16(0)?.encode(x)?,
+            ScriptHashObj(x) => e.array(2)?.u16(1)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ConwayTxCert {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let tag = d.u16()?;
+
+        use ConwayGovCert::*;
+        use ConwayTxCert::*;
+        use PoolCert::*;
+
+        match tag {
+            0 => Ok(ConwayTxCertDeleg(ConwayDelegCert::ConwayRegCert(
+                d.decode()?,
+                None,
+            ))),
+            1 => Ok(ConwayTxCertDeleg(ConwayDelegCert::ConwayUnRegCert(
+                d.decode()?,
+                None,
+            ))),
+            2 | 9 | 10 => {
+                let credential = d.decode()?;
+                let delegatee = decode_delegatee(d, tag)?;
+                Ok(ConwayTxCertDeleg(ConwayDelegCert::ConwayDelegCert(
+                    credential, delegatee,
+                )))
+            }
+            3 => Ok(ConwayTxCertPool(RegPool(PoolParams {
+                id: d.decode()?,
+                vrf: d.decode()?,
+                pledge: d.decode()?,
+                cost: d.decode()?,
+                margin: d.decode()?,
+                reward_account: d.decode()?,
+                owners: d.decode::<Set<_>>()?.to_vec(),
+                relays: d.decode()?,
+                metadata: decode_nullable(d)?,
+            }))),
+            4 => Ok(ConwayTxCertPool(RetirePool(d.decode()?, d.decode()?))),
+            7 => Ok(ConwayTxCertDeleg(ConwayDelegCert::ConwayRegCert(
+                d.decode()?,
+                Some(d.decode()?),
+            ))),
+            8 => Ok(ConwayTxCertDeleg(ConwayDelegCert::ConwayUnRegCert(
+                d.decode()?,
+                Some(d.decode()?),
+            ))),
+            11..=13 => {
+                let credential = d.decode()?;
+                let delegatee = decode_delegatee(d, tag)?;
+                Ok(ConwayTxCertDeleg(ConwayDelegCert::ConwayRegDelegCert(
+                    credential,
+                    delegatee,
+                    d.decode()?,
+                )))
+            }
+            14 => Ok(ConwayTxCertGov(ConwayAuthCommitteeHotKey(
+                d.decode()?,
+                d.decode()?,
+            ))),
+            15 => Ok(ConwayTxCertGov(ConwayResignCommitteeColdKey(
+                d.decode()?,
+                decode_nullable(d)?,
+            ))),
+            16 => Ok(ConwayTxCertGov(ConwayRegDRep(
+                d.decode()?,
+                d.decode()?,
+                decode_nullable(d)?,
+            ))),
+            17 => Ok(ConwayTxCertGov(ConwayUnRegDRep(d.decode()?, d.decode()?))),
+            18 => Ok(ConwayTxCertGov(ConwayUpdateDRep(
+                d.decode()?,
+                decode_nullable(d)?,
+            ))),
+            // 5 and 6 were genesis delegation and MIR certificates, removed in Conway
+            _ => Err(decode::Error::message(format!(
+                "unknown tag while decoding ConwayTxCert: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ConwayTxCert {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ConwayGovCert::*;
+        use ConwayTxCert::*;
+        use PoolCert::*;
+
+        match self {
+            ConwayTxCertDeleg(ConwayDelegCert::ConwayRegCert(x, None)) => {
+                e.array(2)?.u16(0)?.encode(x)?
+            }
+            ConwayTxCertDeleg(ConwayDelegCert::ConwayUnRegCert(x, None)) => {
+                e.array(2)?.u16(1)?.encode(x)?
+            }
+            ConwayTxCertDeleg(ConwayDelegCert::ConwayDelegCert(x, delegatee)) => {
+                let tag = match delegatee {
+                    Delegatee::DelegStake(_) => 2,
+                    Delegatee::DelegVote(_) => 9,
+                    Delegatee::DelegStakeVote(..) => 10,
+                };
+                e.array(2 + delegatee_len(delegatee))?.u16(tag)?.encode(x)?;
+                encode_delegatee(e, delegatee)?
+            }
+            ConwayTxCertPool(RegPool(params)) => e
+                .array(10)?
+                .u16(3)?
+                .encode(params.id)?
+                .encode(params.vrf)?
+                .encode(params.pledge)?
+                .encode(params.cost)?
+                .encode(&params.margin)?
+                .encode(&params.reward_account)?
+                .encode(&params.owners)?
+                .encode(&params.relays)?
+                .encode(&params.metadata)?,
+            ConwayTxCertPool(RetirePool(x, y)) => e.array(3)?.u16(4)?.encode(x)?.encode(y)?,
+            ConwayTxCertDeleg(ConwayDelegCert::ConwayRegCert(x, Some(y))) => {
+                e.array(3)?.u16(7)?.encode(x)?.encode(y)?
+            }
+            ConwayTxCertDeleg(ConwayDelegCert::ConwayUnRegCert(x, Some(y))) => {
+                e.array(3)?.u16(8)?.encode(x)?.encode(y)?
+            }
+            ConwayTxCertDeleg(ConwayDelegCert::ConwayRegDelegCert(x, delegatee, y)) => {
+                let tag = match delegatee {
+                    Delegatee::DelegStake(_) => 11,
+                    Delegatee::DelegVote(_) => 12,
+                    Delegatee::DelegStakeVote(..) => 13,
+                };
+                e.array(3 + delegatee_len(delegatee))?.u16(tag)?.encode(x)?;
+                encode_delegatee(e, delegatee)?.encode(y)?
+            }
+            ConwayTxCertGov(ConwayAuthCommitteeHotKey(x, y)) => {
+                e.array(3)?.u16(14)?.encode(x)?.encode(y)?
+            }
+            ConwayTxCertGov(ConwayResignCommitteeColdKey(x, y)) => {
+                e.array(3)?.u16(15)?.encode(x)?.encode(y)?
+            }
+            ConwayTxCertGov(ConwayRegDRep(x, y, z)) => {
+                e.array(4)?.u16(16)?.encode(x)?.encode(y)?.encode(z)?
+            }
+            ConwayTxCertGov(ConwayUnRegDRep(x, y)) => e.array(3)?.u16(17)?.encode(x)?.encode(y)?,
+            ConwayTxCertGov(ConwayUpdateDRep(x, y)) => e.array(3)?.u16(18)?.encode(x)?.encode(y)?,
+        };
+
+        Ok(())
+    }
+}
+
+/// Decodes the delegatee fields of a Conway delegation certificate, whose tag
+/// tells which of them follow the delegator's credential.
+pub(crate) fn decode_delegatee(d: &mut Decoder, cert_tag: u16) -> Result<Delegatee, decode::Error> {
+    use Delegatee::*;
+
+    match cert_tag {
+        2 | 11 => Ok(DelegStake(d.decode()?)),
+        9 | 12 => Ok(DelegVote(d.decode()?)),
+        10 | 13 => Ok(DelegStakeVote(d.decode()?, d.decode()?)),
+        _ => Err(decode::Error::message(format!(
+            "certificate tag {} has no delegatee",
+            cert_tag
+        ))),
+    }
+}
+
+/// The number of fields `decode_delegatee` reads.
+fn delegatee_len(delegatee: &Delegatee) -> u64 {
+    match delegatee {
+        Delegatee::DelegStakeVote(..) => 2,
+        _ => 1,
+    }
+}
+
+/// The inverse of `decode_delegatee`, once the certificate's tag is written.
+fn encode_delegatee<'a, W: Write>(
+    e: &'a mut Encoder<W>,
+    delegatee: &Delegatee,
+) -> Result<&'a mut Encoder<W>, encode::Error<W::Error>> {
+    use Delegatee::*;
+
+    match delegatee {
+        DelegStake(pool) => e.encode(pool),
+        DelegVote(drep) => e.encode(drep),
+        DelegStakeVote(pool, drep) => e.encode(pool)?.encode(drep),
+    }
+}
+
+/// Certificates encode optional fields as CBOR `null`, unlike `StrictMaybe`.
+fn decode_nullable<'b, T: Decode<'b, ()> + Clone>(
+    d: &mut Decoder<'b>,
+) -> Result<Option<T>, decode::Error> {
+    match d.decode()? {
+        Nullable::Some(x) => Ok(Some(x)),
+        _ => Ok(None),
+    }
+}
+
+/// The ledger encodes `StrictMaybe` as an empty or a one-element list.
+fn decode_strict_maybe<'b, T: Decode<'b, ()>>(
+    d: &mut Decoder<'b>,
+) -> Result<Option<T>, decode::Error> {
+    match d.array()? {
+        Some(0) => Ok(None),
+        _ => Ok(Some(d.decode()?)),
+    }
+}
+
+/// The inverse of `decode_strict_maybe`.
+fn encode_strict_maybe<'a, W: Write, T: Encode<()>>(
+    e: &'a mut Encoder<W>,
+    x: &Option<T>,
+) -> Result<&'a mut Encoder<W>, encode::Error<W::Error>> {
+    match x {
+        None => e.array(0),
+        Some(x) => e.array(1)?.encode(x),
+    }
+}
+
+impl<'b> Decode<'b, ()> for ApplyByronTxErr {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ApplyByronTxErr::*;
+
+        match error {
+            0 => Ok(MempoolTxErr(d.decode()?)),
+            1 => Ok(MempoolDlgErr(d.decode()?)),
+            2 => Ok(MempoolUpdateProposalErr(d.decode()?)),
+            3 => Ok(MempoolUpdateVoteErr(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ApplyByronTxErr: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ApplyByronTxErr {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ApplyByronTxErr::*;
+
+        match self {
+            MempoolTxErr(x) => e.array(2)?.u16(0)?.encode(x)?,
+            MempoolDlgErr(x) => e.array(2)?.u16(1)?.encode(x)?,
+            MempoolUpdateProposalErr(x) => e.array(2)?.u16(2)?.encode(x)?,
+            MempoolUpdateVoteErr(x) => e.array(2)?.u16(3)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ByronUtxoValidationError {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ByronUtxoValidationError::*;
+
+        match error {
+            0 => Ok(UTxOValidationTxValidationError(d.decode()?)),
+            1 => Ok(UTxOValidationUTxOError(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ByronUtxoValidationError: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ByronUtxoValidationError {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ByronUtxoValidationError::*;
+
+        match self {
+            UTxOValidationTxValidationError(x) => e.array(2)?.u16(0)?.encode(x)?,
+            UTxOValidationUTxOError(x) => e.array(2)?.u16(1)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ByronTxValidationError {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ByronTxValidationError::*;
+
+        match error {
+            0 => Ok(TxValidationLovelaceError(d.decode()?, d.decode()?)),
+            1 => Ok(TxValidationFeeTooSmall(
+                d.decode()?,
+                d.decode()?,
+                d.decode()?,
+            )),
+            2 => Ok(TxValidationWitnessWrongSignature(
+                d.decode()?,
+                d.decode()?,
+                d.decode()?,
+            )),
+            3 => Ok(TxValidationWitnessWrongKey(d.decode()?, d.decode()?)),
+            4 => Ok(TxValidationMissingInput(d.decode()?)),
+            5 => Ok(TxValidationNetworkMagicMismatch(d.decode()?, d.decode()?)),
+            6 => Ok(TxValidationTxTooLarge(d.decode()?, d.decode()?)),
+            7 => Ok(TxValidationUnknownAddressAttributes()),
+            8 => Ok(TxValidationUnknownAttributes()),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ByronTxValidationError: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ByronTxValidationError {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ByronTxValidationError::*;
+
+        match self {
+            TxValidationLovelaceError(x, y) => e.array(3)?.u16(0)?.encode(x)?.encode(y)?,
+            TxValidationFeeTooSmall(x, y, z) => {
+                e.array(4)?.u16(1)?.encode(x)?.encode(y)?.encode(z)?
+            }
+            TxValidationWitnessWrongSignature(x, y, z) => {
+                e.array(4)?.u16(2)?.encode(x)?.encode(y)?.encode(z)?
+            }
+            TxValidationWitnessWrongKey(x, y) => e.array(3)?.u16(3)?.encode(x)?.encode(y)?,
+            TxValidationMissingInput(x) => e.array(2)?.u16(4)?.encode(x)?,
+            TxValidationNetworkMagicMismatch(x, y) => e.array(3)?.u16(5)?.encode(x)?.encode(y)?,
+            TxValidationTxTooLarge(x, y) => e.array(3)?.u16(6)?.encode(x)?.encode(y)?,
+            TxValidationUnknownAddressAttributes() => e.array(1)?.u16(7)?,
+            TxValidationUnknownAttributes() => e.array(1)?.u16(8)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ByronUtxoError {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ByronUtxoError::*;
+
+        match error {
+            0 => Ok(UTxOMissingInput(d.decode()?)),
+            1 => Ok(UTxOOverlappingUnion()),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ByronUtxoError: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ByronUtxoError {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ByronUtxoError::*;
+
+        match self {
+            UTxOMissingInput(x) => e.array(2)?.u16(0)?.encode(x)?,
+            UTxOOverlappingUnion() => e.array(1)?.u16(1)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ByronLovelaceError {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let error = d.u16()?;
+
+        use ByronLovelaceError::*;
+
+        match error {
+            0 => Ok(LovelaceOverflow(d.decode()?)),
+            1 => Ok(LovelaceTooLarge(d.int()?.into())),
+            2 => Ok(LovelaceTooSmall(d.int()?.into())),
+            3 => Ok(LovelaceUnderflow(d.decode()?, d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown error tag while decoding ByronLovelaceError: {}",
+                error
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ByronLovelaceError {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ByronLovelaceError::*;
+
+        let int = |x: &i128| Int::try_from(*x).map_err(encode::Error::message);
+
+        match self {
+            LovelaceOverflow(x) => e.array(2)?.u16(0)?.encode(x)?,
+            LovelaceTooLarge(x) => e.array(2)?.u16(1)?.int(int(x)?)?,
+            LovelaceTooSmall(x) => e.array(2)?.u16(2)?.int(int(x)?)?,
+            LovelaceUnderflow(x, y) => e.array(3)?.u16(3)?.encode(x)?.encode(y)?,
+        };
+
+        Ok(())
+    }
+}
+
+impl<'b> Decode<'b, ()> for ByronNetworkMagic {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, decode::Error> {
+        d.array()?;
+        let tag = d.u16()?;
+
+        use ByronNetworkMagic::*;
+
+        match tag {
+            0 => Ok(NetworkMainOrStage),
+            1 => Ok(NetworkTestnet(d.decode()?)),
+            _ => Err(decode::Error::message(format!(
+                "unknown tag while decoding ByronNetworkMagic: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Encode<()> for ByronNetworkMagic {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        use ByronNetworkMagic::*;
+
+        match self {
+            NetworkMainOrStage => e.array(1)?.u16(0)?,
+            NetworkTestnet(x) => e.array(2)?.u16(1)?.encode(x)?,
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::haskell_types::{ConwayPlutusPurpose, TxValidationError};
+    use pallas_codec::minicbor;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use std::fmt::Debug;
+
+    /// Decoding what we encoded gives back the same value, which encodes to
+    /// the same bytes again.
+    fn assert_round_trip<T: for<'b> Decode<'b, ()> + Encode<()> + Debug>(cbor_hex: &str) {
+        let bytes = hex::decode(cbor_hex.replace(' ', "")).unwrap();
+        let decoded: T = minicbor::decode(&bytes).unwrap();
+
+        let encoded = minicbor::to_vec(&decoded).unwrap();
+        let redecoded: T = minicbor::decode(&encoded).unwrap_or_else(|e| {
+            panic!("failed to decode {}: {}", hex::encode(&encoded), e);
+        });
+
+        assert_eq!(format!("{:?}", redecoded), format!("{:?}", decoded));
+        assert_eq!(minicbor::to_vec(&redecoded).unwrap(), encoded);
+    }
+
+    // The submit errors of the decoding tests in `node::connection`, without the message tag
+    #[rstest]
+    #[case("818206828201820083061b00000002362a77301b0000000253b9c11d8201820083051a00028bfd18ad")]
+    #[case("818206828201820083051a000151351a00074b8582076162")]
+    #[case(concat!(
+        "818206818201820082 0d",
+        "a1825820222222222222222222222222222222222222222222222222222222222222222200",
+        "a200581d6011111111111111111111111111111111111111111111111111111111011a000f4240",
+    ))]
+    #[case("818206818201820082 15 81 82 82581d6011111111111111111111111111111111111111111111111111111111 01 1a000d1658")]
+    #[case(concat!(
+        "818206818201820082008201 82",
+        "82008200825820333333333333333333333333333333333333333333333333333333333333333301",
+        "8201581c44444444444444444444444444444444444444444444444444444444",
+    ))]
+    #[case("818205818200820282018305 1a00989680 1a004c4b40")]
+    #[case(concat!(
+        "818205828200820282018200818258200f00000000000000000000000000000000000000000000000000000000000001 03",
+        "8200820182008201 81581c00000000000000000000000000000000000000000000000000000001",
+    ))]
+    #[case("81820181820082048301 1903e8 1907d0")]
+    #[case("818203818200820481 0b")]
+    #[case("8182048282008200820481038201820058 1c00000000000000000000000000000000000000000000000000000001")]
+    #[case("81820082018200 8200d8185824825820 0f00000000000000000000000000000000000000000000000000000000000001 00")]
+    #[case("81820082008200840180 1a000f4240 1a00030d40")]
+    fn test_round_trip_tx_validation_error(#[case] cbor_hex: &str) {
+        assert_round_trip::<TxValidationError>(cbor_hex);
+    }
+
+    const KEY_HASH: &str = "11111111111111111111111111111111111111111111111111111111";
+    const TX_IN: &str = "8258205555555555555555555555555555555555555555555555555555555555555555 02";
+
+    #[rstest]
+    #[case(format!("82 00 8200581c{}", KEY_HASH))]
+    #[case(format!("83 07 8200581c{} 1a001e8480", KEY_HASH))]
+    #[case(format!("83 02 8200581c{} 581c{}", KEY_HASH, KEY_HASH))]
+    #[case(format!("84 0a 8200581c{} 581c{} 8103", KEY_HASH, KEY_HASH))]
+    #[case(format!("85 0d 8200581c{} 581c{} 8102 1a001e8480", KEY_HASH, KEY_HASH))]
+    #[case(format!("83 04 581c{} 05", KEY_HASH))]
+    #[case(format!(
+        concat!(
+            "8a 03 581c{} 5820{} 1a000f4240 1a1443fd00 d81e8202 1828 581de0{} 81581c{} ",
+            "83 8400190bb94401020304f6 8301f668 72656c61792e696f 8202 67 706f6f6c2e696f ",
+            "82 6c 68747470733a2f2f782e696f 5820{}"
+        ),
+        KEY_HASH,
+        "44".repeat(32),
+        KEY_HASH,
+        KEY_HASH,
+        "61".repeat(32)
+    ))]
+    #[case(format!("84 10 8200581c{} 1a1dcd6500 82 6c 68747470733a2f2f642e696f 5820{}", KEY_HASH, "dd".repeat(32)))]
+    #[case(format!("83 0e 8200581c{} 8201581c{}", KEY_HASH, KEY_HASH))]
+    #[case(format!("83 0f 8200581c{} f6", KEY_HASH))]
+    fn test_round_trip_conway_tx_cert(#[case] cbor_hex: String) {
+        assert_round_trip::<ConwayTxCert>(&cbor_hex);
+    }
+
+    #[rstest]
+    #[case("82 02 02")]
+    #[case(format!("82 00 8200 {}", TX_IN))]
+    #[case(format!("82 03 8208 8201 {}", TX_IN))]
+    #[case(format!("82 03 8208 8200 8200 {}", TX_IN))]
+    #[case("82 03 8208 8202 8205 07")]
+    #[case("82 03 8208 8204 8201 03")]
+    #[case("82 03 8208 8207 6474657374")]
+    #[case(format!("82 03 8208 8206 81 {}", TX_IN))]
+    #[case("82 03 820f 1a000f4240")]
+    #[case(format!("82 03 8210 82 {} {}", TX_IN, TX_IN))]
+    fn test_round_trip_collect_error(#[case] cbor_hex: impl AsRef<str>) {
+        assert_round_trip::<CollectError<ConwayPlutusPurpose, ConwayContextError>>(
+            cbor_hex.as_ref(),
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Which rejection reasons the native decoder knows, for users hitting
+//! fallback decodes to check whether theirs is a known gap before filing a bug.
+
+use crate::haskell_types::*;
+use serde::Serialize;
+
+/// The variants of one failure type of the ledger.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct EnumCoverage {
+    pub name: &'static str,
+    pub supported: Vec<&'static str>,
+    /// Defined, but not (correctly) decoded yet
+    pub unsupported: Vec<&'static str>,
+}
+
+/// Lists the variants of every failure type, and checks at compile time that
+/// they’re all there, by matching on them exhaustively.
+macro_rules! decoder_coverage {
+    ($(
+        $name:ident $(<$arg:ty>)? {
+            $($supported:ident),* $(,)?
+        } $(unsupported {
+            $($unsupported:ident),* $(,)?
+        })?
+    ),* $(,)?) => {
+        #[allow(dead_code)]
+        fn exhaustiveness() {
+            $(
+                let _ = |failure: &$name $(<$arg>)?| match failure {
+                    $($name::$supported { .. } => {})*
+                    $($($name::$unsupported { .. } => {})*)?
+                };
+            )*
+        }
+
+        /// Every failure type of the native decoder, with its variants.
+        pub fn decoder_coverage() -> Vec<EnumCoverage> {
+            vec![$(
+                EnumCoverage {
+                    name: stringify!($name),
+                    supported: vec![$(stringify!($supported)),*],
+                    unsupported: vec![$($(stringify!($unsupported)),*)?],
+                }
+            ),*]
+        }
+    };
+}
+
+decoder_coverage! {
+    TxValidationError {
+        ByronTxValidationError,
+        ShelleyTxValidationError,
+        BabbageTxValidationError,
+        AlonzoTxValidationError,
+        AllegraTxValidationError,
+        ShelleyEraTxValidationError,
+    },
+    ApplyByronTxErr {
+        MempoolTxErr,
+        MempoolDlgErr,
+        MempoolUpdateProposalErr,
+        MempoolUpdateVoteErr,
+    },
+    ByronUtxoValidationError {
+        UTxOValidationTxValidationError,
+        UTxOValidationUTxOError,
+    },
+    ByronTxValidationError {
+        TxValidationLovelaceError,
+        TxValidationFeeTooSmall,
+        TxValidationWitnessWrongSignature,
+        TxValidationWitnessWrongKey,
+        TxValidationMissingInput,
+        TxValidationNetworkMagicMismatch,
+        TxValidationTxTooLarge,
+        TxValidationUnknownAddressAttributes,
+        TxValidationUnknownAttributes,
+    },
+    ByronUtxoError {
+        UTxOMissingInput,
+        UTxOOverlappingUnion,
+    },
+    ByronLovelaceError {
+        LovelaceOverflow,
+        LovelaceTooLarge,
+        LovelaceTooSmall,
+        LovelaceUnderflow,
+    },
+    ApplyConwayTxPredError {
+        UtxowFailure,
+        TreasuryValueMismatch,
+        TxRefScriptsSizeTooBig,
+        MempoolFailure,
+    } unsupported {
+        CertsFailure,
+        GovFailure,
+        WdrlNotDelegatedToDRep,
+    },
+    ConwayUtxoWPredFailure {
+        UtxoFailure,
+        InvalidWitnessesUTXOW,
+        MissingVKeyWitnessesUTXOW,
+        MissingScriptWitnessesUTXOW,
+        ScriptWitnessNotValidatingUTXOW,
+        MissingTxBodyMetadataHash,
+        MissingTxMetadata,
+        ConflictingMetadataHash,
+        InvalidMetadata,
+        ExtraneousScriptWitnessesUTXOW,
+        MissingRedeemers,
+        MissingRequiredDatums,
+        NotAllowedSupplementalDatums,
+        PPViewHashesDontMatch,
+        UnspendableUTxONoDatumHash,
+        ExtraRedeemers,
+        MalformedScriptWitnesses,
+        MalformedReferenceScripts,
+    },
+    ConwayUtxoPredFailure {
+        UtxosFailure,
+        BadInputsUTxO,
+        OutsideValidityIntervalUTxO,
+        MaxTxSizeUTxO,
+        InputSetEmptyUTxO,
+        FeeTooSmallUTxO,
+        ValueNotConservedUTxO,
+        WrongNetwork,
+        WrongNetworkWithdrawal,
+        OutputTooSmallUTxO,
+        OutputBootAddrAttrsTooBig,
+        OutputTooBigUTxO,
+        InsufficientCollateral,
+        ScriptsNotPaidUTxO,
+        ExUnitsTooBigUTxO,
+        CollateralContainsNonADA,
+        WrongNetworkInTxBody,
+        OutsideForecast,
+        TooManyCollateralInputs,
+        NoCollateralInputs,
+        IncorrectTotalCollateralField,
+        BabbageOutputTooSmallUTxO,
+        BabbageNonDisjointRefInputs,
+    },
+    ConwayUtxosPredFailure {
+        ValidationTagMismatch,
+        CollectErrors,
+    },
+    ShelleyLedgerPredFailure<Babbage> {
+        UtxowFailure,
+        DelegsFailure,
+    },
+    BabbageUtxowPredFailure {
+        AlonzoInBabbageUtxowPredFailure,
+        UtxoFailure,
+        MalformedScriptWitnesses,
+        MalformedReferenceScripts,
+    },
+    AlonzoUtxowPredFailure {
+        ShelleyInAlonzoUtxowPredfailure,
+        MissingRedeemers,
+        MissingRequiredDatums,
+        NotAllowedSupplementalDatums,
+        PPViewHashesDontMatch,
+        MissingRequiredSigners,
+        UnspendableUTxONoDatumHash,
+        ExtraRedeemers,
+    },
+    ShelleyUtxowPredFailure {
+        InvalidWitnessesUTXOW,
+        MissingVKeyWitnessesUTXOW,
+        MissingScriptWitnessesUTXOW,
+        ScriptWitnessNotValidatingUTXOW,
+        UtxoFailure,
+        MIRInsufficientGenesisSigsUTXOW,
+        MissingTxBodyMetadataHash,
+        MissingTxMetadata,
+        ConflictingMetadataHash,
+        InvalidMetadata,
+        ExtraneousScriptWitnessesUTXOW,
+    },
+    ShelleyUtxoPredFailure {
+        BadInputsUTxO,
+        ExpiredUTxO,
+        MaxTxSizeUTxO,
+        InputSetEmptyUTxO,
+        FeeTooSmallUTxO,
+        ValueNotConservedUTxO,
+        OutputTooSmallUTxO,
+        UpdateFailure,
+        WrongNetwork,
+        WrongNetworkWithdrawal,
+        OutputBootAddrAttrsTooBig,
+    },
+    AllegraUtxoPredFailure {
+        BadInputsUTxO,
+        OutsideValidityIntervalUTxO,
+        MaxTxSizeUTxO,
+        InputSetEmptyUTxO,
+        FeeTooSmallUTxO,
+        ValueNotConservedUTxO,
+        WrongNetwork,
+        WrongNetworkWithdrawal,
+        OutputTooSmallUTxO,
+        UpdateFailure,
+        OutputBootAddrAttrsTooBig,
+        TriesToForgeADA,
+        OutputTooBigUTxO,
+    },
+    BabbageUtxoPredFailure {
+        AlonzoInBabbageUtxoPredFailure,
+        IncorrectTotalCollateralField,
+        BabbageOutputTooSmallUTxO,
+        BabbageNonDisjointRefInputs,
+    },
+    AlonzoUtxoPredFailure {
+        BadInputsUTxO,
+        OutsideValidityIntervalUTxO,
+        MaxTxSizeUTxO,
+        InputSetEmptyUTxO,
+        FeeTooSmallUTxO,
+        ValueNotConservedUTxO,
+        OutputTooSmallUTxO,
+        UtxosFailure,
+        WrongNetwork,
+        WrongNetworkWithdrawal,
+        OutputBootAddrAttrsTooBig,
+        TriesToForgeADA,
+        OutputTooBigUTxO,
+        InsufficientCollateral,
+        ScriptsNotPaidUTxO,
+        ExUnitsTooBigUTxO,
+        CollateralContainsNonADA,
+        WrongNetworkInTxBody,
+        OutsideForecast,
+        TooManyCollateralInputs,
+        NoCollateralInputs,
+    },
+    AlonzoUtxosPredFailure {
+        ValidationTagMismatch,
+        CollectErrors,
+        UpdateFailure,
+    },
+    TagMismatchDescription {
+        PassedUnexpectedly,
+        FailedUnexpectedly,
+    },
+    FailureDescription {
+        PlutusFailure,
+    },
+    CollectError {
+        NoRedeemer,
+        NoWitness,
+        NoCostModel,
+        BadTranslation,
+    },
+    ConwayContextError {
+        BabbageContextError,
+        CertificateNotSupported,
+        PlutusPurposeNotSupported,
+        CurrentTreasuryFieldNotSupported,
+        VotingProceduresFieldNotSupported,
+        ProposalProceduresFieldNotSupported,
+        TreasuryDonationFieldNotSupported,
+        ReferenceInputsNotDisjointFromInputs,
+    },
+    BabbageContextError {
+        AlonzoContextError,
+        ByronTxOutInContext,
+        RedeemerPointerPointsToNothing,
+        InlineDatumsNotSupported,
+        ReferenceScriptsNotSupported,
+        ReferenceInputsNotSupported,
+    },
+    AlonzoContextError {
+        TranslationLogicMissingInput,
+        TimeTranslationPastHorizon,
+    },
+    ShelleyPpupPredFailure {
+        NonGenesisUpdatePPUP,
+        PPUpdateWrongEpoch,
+        PVCannotFollowPPUP,
+    },
+    ShelleyDelegsPredFailure {
+        DelegateeNotRegisteredDELEG,
+        WithdrawalsNotInRewardsDELEGS,
+        DelplFailure,
+    },
+    ShelleyDelplPredFailure {
+        PoolFailure,
+        DelegFailure,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_decoder_coverage() {
+        let coverage = decoder_coverage();
+        let conway = coverage
+            .iter()
+            .find(|e| e.name == "ApplyConwayTxPredError")
+            .unwrap();
+
+        assert!(conway.supported.contains(&"UtxowFailure"));
+        assert_eq!(
+            conway.unsupported,
+            vec!["CertsFailure", "GovFailure", "WdrlNotDelegatedToDRep"]
+        );
+        assert!(coverage.iter().all(|e| !e.supported.is_empty()));
+    }
+}
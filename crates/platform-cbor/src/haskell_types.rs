@@ -0,0 +1,2708 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+use crate::slot::SlotNo;
+use pallas::ledger::addresses::{
+    byron::{AddrAttrProperty, AddrType},
+    Address, ByronAddress, Network as AddressNetwork, ShelleyAddress, ShelleyDelegationPart,
+    ShelleyPaymentPart, StakeKeyHash, StakePayload,
+};
+use pallas_codec::minicbor;
+use pallas_codec::minicbor::{Decode, Encode};
+use pallas_codec::utils::{Bytes, Nullable};
+use pallas_crypto::hash::Hash;
+use pallas_primitives::byron::TxIn as ByronTxIn;
+use pallas_primitives::conway::{
+    AddrKeyhash, Anchor, Coin, DatumHash, DatumOption, Epoch, ExUnits, PoolKeyhash, PoolMetadata,
+    PseudoScript, RationalNumber, Relay, RewardAccount, ScriptHash, ScriptRef, TransactionInput,
+    TransactionOutput, VKeyWitness, Value, VrfKeyhash,
+};
+use pallas_traverse::ComputeHash;
+use serde::Serialize;
+use serde_with::SerializeDisplay;
+use std::fmt::Display;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// This file contains the types that are mapped from the Haskell codebase.
+/// The main reason these mappings exist is to mimick the error responses from the cardano-submit-api
+/// and generate identical responses to the [Blockfrost.io `/tx/submit` API](https://docs.blockfrost.io/#tag/cardano--transactions/POST/tx/submit).
+///
+/// To mimick, we need to:
+/// - Decode the CBOR error reasons (pallas doesn't do it) from the cardano-node
+/// - Generate the same JSON response structure as the cardano-submit-api
+///
+/// So you can expect two kind of types here:
+/// - Types that are used to decode the CBOR error reasons
+/// - Types that are used to generate the JSON response structure
+///
+/// Here is an example response from the cardano-submit-api:
+/// ```text
+/// curl --header "Content-Type: application/cbor" -X POST http://localhost:8090/api/submit/tx --data-binary @tx.bin
+/// {
+///     "contents": {
+///       "contents": {
+///         "contents": {
+///          "era": "ShelleyBasedEraConway",
+///          "error": [
+///             "ConwayUtxowFailure (UtxoFailure (ValueNotConservedUTxO (MaryValue (Coin 9498687280) (MultiAsset (fromList []))) (MaryValue (Coin 9994617117) (MultiAsset (fromList [])))))",
+///             "ConwayUtxowFailure (UtxoFailure (FeeTooSmallUTxO (Coin 166909) (Coin 173)))"
+///           ],
+///           "kind": "ShelleyTxValidationError"
+///         },
+///         "tag": "TxValidationErrorInCardanoMode"
+///       },
+///       "tag": "TxCmdTxSubmitValidationError"
+///     },
+///     "tag": "TxSubmitFail"
+///   }
+/// ```
+///
+/// Here is an example CBOR error reason from the cardano-node:
+/// ```text
+/// [2,
+///     [
+///         [6,
+///             [
+///                 [1,
+///                     [0,
+///                         [6, 9498687280, 9994617117]
+///                     ]
+///                 ],
+///                 [1,
+///                     [0,
+///                         [5, 166909, 173]
+///                     ]
+///                 ]
+///             ]
+///         ]
+///     ]
+/// ]
+/// ```
+///
+/// TxValidationError is the most outer type that is decoded from the CBOR error reason.
+/// Than, it is wrapped in TxValidationErrorInCardanoMode and TxCmdTxSubmitValidationError to generate the JSON response.
+///
+/// Type examples:
+/// * <https://github.com/IntersectMBO/ouroboros-consensus/blob/82c5ebf7c9f902b7250144445f45083c1c13929e/ouroboros-consensus-cardano/src/shelley/Ouroboros/Consensus/Shelley/Eras.hs#L334>
+/// * <https://github.com/IntersectMBO/cardano-node-emulator/blob/ba5c4910a958bbccb38399f6a871459e46701a93/cardano-node-emulator/src/Cardano/Node/Emulator/Internal/Node/Validation.hs#L255>
+/// * <https://github.com/IntersectMBO/cardano-node/blob/master/cardano-testnet/test/cardano-testnet-test/files/golden/tx.failed.response.json.golden>
+///
+/// Haskell references to the types are commented next to them.
+/// Here are some more type references:
+/// * <https://github.com/IntersectMBO/cardano-ledger/blob/78b20b6301b2703aa1fe1806ae3c129846708a10/libs/cardano-ledger-core/src/Cardano/Ledger/BaseTypes.hs#L737>
+/// * <https://github.com/IntersectMBO/cardano-ledger/blob/master/eras/mary/impl/src/Cardano/Ledger/Mary/Value.hs>
+/// * <https://github.com/IntersectMBO/cardano-ledger/blob/master/libs/cardano-ledger-core/src/Cardano/Ledger/Coin.hs>
+
+/*
+** cardano-node CBOR types
+** These types are used to decode the CBOR error reasons from the cardano-node.
+** Some of them are decoded in codec.rs and some of them using Derive(Decode) macro.
+*/
+// https://github.com/IntersectMBO/cardano-api/blob/a0df586e3a14b98ae4771a192c09391dacb44564/cardano-api/internal/Cardano/Api/InMode.hs#L289
+// https://github.com/IntersectMBO/cardano-api/blob/a0df586e3a14b98ae4771a192c09391dacb44564/cardano-api/internal/Cardano/Api/InMode.hs#L204
+// toJson https://github.com/IntersectMBO/cardano-api/blob/a0df586e3a14b98ae4771a192c09391dacb44564/cardano-api/internal/Cardano/Api/InMode.hs#L233
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind")]
+pub enum TxValidationError {
+    ByronTxValidationError {
+        error: ApplyByronTxErr,
+    },
+    ShelleyTxValidationError {
+        error: ApplyTxErr,
+        era: ShelleyBasedEra,
+    },
+    // The ones below are serialized exactly like `ShelleyTxValidationError`, only the ledger rules differ
+    #[serde(rename = "ShelleyTxValidationError")]
+    BabbageTxValidationError {
+        error: ApplyEraTxErr<Babbage>,
+        era: ShelleyBasedEra,
+    },
+    #[serde(rename = "ShelleyTxValidationError")]
+    AlonzoTxValidationError {
+        error: ApplyEraTxErr<Alonzo>,
+        era: ShelleyBasedEra,
+    },
+    /// Both Allegra and Mary, which share their ledger rules
+    #[serde(rename = "ShelleyTxValidationError")]
+    AllegraTxValidationError {
+        error: ApplyEraTxErr<Allegra>,
+        era: ShelleyBasedEra,
+    },
+    #[serde(rename = "ShelleyTxValidationError")]
+    ShelleyEraTxValidationError {
+        error: ApplyEraTxErr<Shelley>,
+        era: ShelleyBasedEra,
+    },
+}
+
+// https://github.com/IntersectMBO/ouroboros-consensus/blob/e86b921443bd6e8ea25e7190eb7cb5788e28f4cc/ouroboros-consensus-cardano/src/byron/Ouroboros/Consensus/Byron/Ledger/Mempool.hs#L97
+// The tx error of the Byron era, `ApplyMempoolPayloadErr`
+#[derive(Debug, SerializeDisplay)]
+pub enum ApplyByronTxErr {
+    MempoolTxErr(ByronUtxoValidationError),
+    MempoolDlgErr(RawCbor),
+    MempoolUpdateProposalErr(RawCbor),
+    MempoolUpdateVoteErr(RawCbor),
+}
+
+impl fmt::Display for ApplyByronTxErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ApplyByronTxErr::*;
+
+        match self {
+            MempoolTxErr(e) => write!(f, "MempoolTxErr ({})", e),
+            MempoolDlgErr(e) => write!(f, "MempoolDlgErr ({})", e),
+            MempoolUpdateProposalErr(e) => write!(f, "MempoolUpdateProposalErr ({})", e),
+            MempoolUpdateVoteErr(e) => write!(f, "MempoolUpdateVoteErr ({})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/byron/ledger/impl/src/Cardano/Chain/UTxO/Validation.hs#L361
+#[derive(Debug)]
+pub enum ByronUtxoValidationError {
+    UTxOValidationTxValidationError(ByronTxValidationError),
+    UTxOValidationUTxOError(ByronUtxoError),
+}
+
+impl fmt::Display for ByronUtxoValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ByronUtxoValidationError::*;
+
+        match self {
+            UTxOValidationTxValidationError(e) => {
+                write!(f, "UTxOValidationTxValidationError ({})", e)
+            }
+            UTxOValidationUTxOError(e) => write!(f, "UTxOValidationUTxOError ({})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/byron/ledger/impl/src/Cardano/Chain/UTxO/Validation.hs#L68
+// Transactions, witnesses and addresses aren't decoded any further yet
+#[derive(Debug)]
+pub enum ByronTxValidationError {
+    TxValidationLovelaceError(String, ByronLovelaceError),
+    TxValidationFeeTooSmall(RawCbor, ByronLovelace, ByronLovelace), // tx, minimum fee, actual fee
+    TxValidationWitnessWrongSignature(RawCbor, u32, RawCbor), // witness, protocol magic, signed data
+    TxValidationWitnessWrongKey(RawCbor, RawCbor),            // witness, address
+    TxValidationMissingInput(SerializableByronTxIn),
+    TxValidationNetworkMagicMismatch(ByronNetworkMagic, ByronNetworkMagic), // expected, actual
+    TxValidationTxTooLarge(u64, u64),                                       // max size, actual size
+    TxValidationUnknownAddressAttributes(),                                 // empty
+    TxValidationUnknownAttributes(),                                        // empty
+}
+
+impl fmt::Display for ByronTxValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ByronTxValidationError::*;
+
+        match self {
+            TxValidationLovelaceError(context, e) => {
+                write!(f, "TxValidationLovelaceError {:?} ({})", context, e)
+            }
+            TxValidationFeeTooSmall(tx, minimum, actual) => write!(
+                f,
+                "TxValidationFeeTooSmall ({}) ({}) ({})",
+                tx, minimum, actual
+            ),
+            TxValidationWitnessWrongSignature(witness, magic, data) => write!(
+                f,
+                "TxValidationWitnessWrongSignature ({}) (ProtocolMagicId {{unProtocolMagicId = {}}}) ({})",
+                witness, magic, data
+            ),
+            TxValidationWitnessWrongKey(witness, address) => {
+                write!(f, "TxValidationWitnessWrongKey ({}) ({})", witness, address)
+            }
+            TxValidationMissingInput(input) => write!(f, "TxValidationMissingInput ({})", input),
+            TxValidationNetworkMagicMismatch(expected, actual) => write!(
+                f,
+                "TxValidationNetworkMagicMismatch ({}) ({})",
+                expected, actual
+            ),
+            TxValidationTxTooLarge(max, actual) => {
+                write!(f, "TxValidationTxTooLarge {} {}", max, actual)
+            }
+            TxValidationUnknownAddressAttributes() => {
+                write!(f, "TxValidationUnknownAddressAttributes")
+            }
+            TxValidationUnknownAttributes() => write!(f, "TxValidationUnknownAttributes"),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/byron/ledger/impl/src/Cardano/Chain/UTxO/UTxO.hs#L99
+#[derive(Debug)]
+pub enum ByronUtxoError {
+    UTxOMissingInput(SerializableByronTxIn),
+    UTxOOverlappingUnion(), // empty
+}
+
+impl fmt::Display for ByronUtxoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ByronUtxoError::*;
+
+        match self {
+            UTxOMissingInput(input) => write!(f, "UTxOMissingInput ({})", input),
+            UTxOOverlappingUnion() => write!(f, "UTxOOverlappingUnion"),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/byron/ledger/impl/src/Cardano/Chain/Common/Lovelace.hs#L96
+#[derive(Debug)]
+pub enum ByronLovelaceError {
+    LovelaceOverflow(u64),
+    LovelaceTooLarge(i128),
+    LovelaceTooSmall(i128),
+    LovelaceUnderflow(u64, u64),
+}
+
+impl fmt::Display for ByronLovelaceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ByronLovelaceError::*;
+
+        match self {
+            LovelaceOverflow(e) => write!(f, "LovelaceOverflow {}", e),
+            LovelaceTooLarge(e) => write!(f, "LovelaceTooLarge {}", e),
+            LovelaceTooSmall(e) => write!(f, "LovelaceTooSmall {}", e),
+            LovelaceUnderflow(e1, e2) => write!(f, "LovelaceUnderflow {} {}", e1, e2),
+        }
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[cbor(transparent)]
+pub struct ByronLovelace(#[n(0)] pub u64);
+
+impl fmt::Display for ByronLovelace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Lovelace {{getLovelace = {}}}", self.0)
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/libs/cardano-crypto-wrapper/src/Cardano/Crypto/ProtocolMagic.hs#L142
+#[derive(Debug)]
+pub enum ByronNetworkMagic {
+    NetworkMainOrStage,
+    NetworkTestnet(u32),
+}
+
+impl fmt::Display for ByronNetworkMagic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ByronNetworkMagic::*;
+
+        match self {
+            NetworkMainOrStage => write!(f, "NetworkMainOrStage"),
+            NetworkTestnet(magic) => write!(f, "NetworkTestnet {}", magic),
+        }
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[cbor(transparent)]
+pub struct SerializableByronTxIn(#[n(0)] pub ByronTxIn);
+
+impl fmt::Display for SerializableByronTxIn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            ByronTxIn::Variant0(input) => write!(
+                f,
+                "TxInUtxo {{txInHash = {}, txInIndex = {}}}",
+                input.0 .0, input.0 .1
+            ),
+            ByronTxIn::Other(tag, bytes) => {
+                write!(f, "TxIn {} {}", tag, hex::encode(bytes.as_slice()))
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-api/blob/a0df586e3a14b98ae4771a192c09391dacb44564/cardano-api/internal/Cardano/Api/Eon/ShelleyBasedEra.hs#L271
+#[derive(Debug, Serialize, PartialEq)]
+pub enum ShelleyBasedEra {
+    ShelleyBasedEraShelley,
+    ShelleyBasedEraAllegra,
+    ShelleyBasedEraMary,
+    ShelleyBasedEraAlonzo,
+    ShelleyBasedEraBabbage,
+    ShelleyBasedEraConway,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApplyTxErr(pub Vec<ApplyConwayTxPredError>);
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/conway/impl/src/Cardano/Ledger/Conway/Rules/Ledger.hs#L146
+#[derive(Debug, SerializeDisplay)]
+pub enum ApplyConwayTxPredError {
+    UtxowFailure(ConwayUtxoWPredFailure),
+    CertsFailure(ConwayUtxoWPredFailure),
+    GovFailure(ConwayUtxoWPredFailure),
+    WdrlNotDelegatedToDRep(StakeKeyHash),
+    TreasuryValueMismatch(Coin),
+    TxRefScriptsSizeTooBig(u64),
+    MempoolFailure(String),
+}
+
+impl fmt::Display for ApplyConwayTxPredError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ApplyConwayTxPredError::*;
+
+        match self {
+            UtxowFailure(e) => write!(f, "UtxowFailure ({})", e),
+            CertsFailure(e) => write!(f, "CertsFailure ({})", e),
+            GovFailure(e) => write!(f, "GovFailure ({})", e),
+            WdrlNotDelegatedToDRep(e) => write!(f, "WdrlNotDelegatedToDRep ({})", e),
+            TreasuryValueMismatch(e) => write!(f, "TreasuryValueMismatch ({})", e),
+            TxRefScriptsSizeTooBig(e) => write!(f, "TxRefScriptsSizeTooBig ({})", e),
+            MempoolFailure(e) => write!(f, "MempoolFailure ({})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/f54489071f4faa4b6209e1ba5288507c824cca50/eras/conway/impl/src/Cardano/Ledger/Conway/Rules/Utxow.hs
+#[derive(Debug, SerializeDisplay)]
+pub enum ConwayUtxoWPredFailure {
+    UtxoFailure(ConwayUtxoPredFailure),
+    InvalidWitnessesUTXOW(DisplayVKeyWitness),
+    MissingVKeyWitnessesUTXOW(DisplayVKeyWitness),
+    MissingScriptWitnessesUTXOW(DisplayScriptHash),
+    ScriptWitnessNotValidatingUTXOW(DisplayScriptHash),
+    MissingTxBodyMetadataHash(Bytes),      // auxDataHash
+    MissingTxMetadata(Bytes),              // auxDataHash
+    ConflictingMetadataHash(Bytes, Bytes), // Mismatch auxDataHash
+    InvalidMetadata(),                     // empty
+    ExtraneousScriptWitnessesUTXOW(DisplayScriptHash),
+    MissingRedeemers(Vec<(PlutusPurpose, DisplayScriptHash)>),
+    MissingRequiredDatums(Vec<DatumHash>, Vec<DatumHash>), // set of missing data hashes, set of recieved data hashes
+    NotAllowedSupplementalDatums(Vec<DatumHash>, Vec<DatumHash>), // set of unallowed data hashes, set of acceptable data hashes
+    PPViewHashesDontMatch(Option<ScriptIntegrityHash>),
+    UnspendableUTxONoDatumHash(Vec<SerializableTxIn>), //  Set of transaction inputs that are TwoPhase scripts, and should have a DataHash but don't
+    ExtraRedeemers(Vec<PlutusPurpose>),                // List of redeemers not needed
+    MalformedScriptWitnesses(Vec<DisplayScriptHash>),
+    MalformedReferenceScripts(Vec<DisplayScriptHash>),
+}
+
+impl fmt::Display for ConwayUtxoWPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConwayUtxoWPredFailure::*;
+
+        match self {
+            UtxoFailure(e) => write!(f, "UtxoFailure ({})", e),
+            InvalidWitnessesUTXOW(e) => write!(f, "InvalidWitnessesUTXOW ({})", e),
+            MissingVKeyWitnessesUTXOW(e) => write!(f, "MissingVKeyWitnessesUTXOW ({})", e),
+            MissingScriptWitnessesUTXOW(e) => write!(f, "MissingScriptWitnessesUTXOW ({})", e),
+            ScriptWitnessNotValidatingUTXOW(e) => {
+                write!(f, "ScriptWitnessNotValidatingUTXOW ({})", e)
+            }
+            MissingTxBodyMetadataHash(e) => write!(f, "MissingTxBodyMetadataHash ({})", e),
+            MissingTxMetadata(e) => write!(f, "MissingTxMetadata ({})", e),
+            ConflictingMetadataHash(e1, e2) => {
+                write!(f, "ConflictingMetadataHash ({}, {})", e1, e2)
+            }
+            InvalidMetadata() => write!(f, "InvalidMetadata"),
+            ExtraneousScriptWitnessesUTXOW(e) => {
+                write!(f, "ExtraneousScriptWitnessesUTXOW ({})", e)
+            }
+            MissingRedeemers(e) => write!(f, "MissingRedeemers ({})", display_tuple_vec(e)),
+            MissingRequiredDatums(e1, e2) => write!(
+                f,
+                "MissingRequiredDatums ({}, {})",
+                display_vec(e1),
+                display_vec(e2)
+            ),
+            NotAllowedSupplementalDatums(e1, e2) => write!(
+                f,
+                "NotAllowedSupplementalDatums ({}, {})",
+                display_vec(e1),
+                display_vec(e2)
+            ),
+            PPViewHashesDontMatch(e) => write!(f, "PPViewHashesDontMatch ({})", display_option(e)),
+            UnspendableUTxONoDatumHash(e) => {
+                write!(f, "UnspendableUTxONoDatumHash ({})", display_vec(e))
+            }
+            ExtraRedeemers(e) => write!(f, "ExtraRedeemers ({})", display_vec(e)),
+            MalformedScriptWitnesses(e) => {
+                write!(f, "MalformedScriptWitnesses ({})", display_vec(e))
+            }
+            MalformedReferenceScripts(e) => {
+                write!(f, "MalformedReferenceScripts ({})", display_vec(e))
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/f54489071f4faa4b6209e1ba5288507c824cca50/eras/conway/impl/src/Cardano/Ledger/Conway/Rules/Utxo.hs#L315
+#[derive(Debug)]
+pub enum ConwayUtxoPredFailure {
+    UtxosFailure(ConwayUtxosPredFailure),
+    BadInputsUTxO(Vec<SerializableTxIn>),
+    OutsideValidityIntervalUTxO(ValidityInterval, SlotNo), // validity interval, current slot
+    MaxTxSizeUTxO(u64),                                    // less than or equal
+    InputSetEmptyUTxO(),                                   // empty
+    FeeTooSmallUTxO(Coin, Coin),                           // Mismatch expected, supplied
+    ValueNotConservedUTxO(DisplayValue, DisplayValue),
+    WrongNetwork(Network, Vec<Addr>), // the expected network id,  the set of addresses with incorrect network IDs
+    WrongNetworkWithdrawal(Network, Vec<RewardAccount>), // the expected network id ,  the set of reward addresses with incorrect network IDs
+    OutputTooSmallUTxO(Vec<SerializableTxOut>),
+    OutputBootAddrAttrsTooBig(Vec<SerializableTxOut>),
+    OutputTooBigUTxO(Vec<(u64, u64, SerializableTxOut)>), //  list of supplied bad transaction output triples (actualSize,PParameterMaxValue,TxOut)
+    InsufficientCollateral(Coin, Coin), // balance computed, the required collateral for the given fee
+    ScriptsNotPaidUTxO(Utxo),           // The UTxO entries which have the wrong kind of script
+    ExUnitsTooBigUTxO(DisplayExUnits),  // check: The values are serialised in reverse order
+    CollateralContainsNonADA(DisplayValue),
+    WrongNetworkInTxBody(), // take in Network, https://github.com/IntersectMBO/cardano-ledger/blob/78b20b6301b2703aa1fe1806ae3c129846708a10/libs/cardano-ledger-core/src/Cardano/Ledger/BaseTypes.hs#L779
+    OutsideForecast(SlotNo),
+    TooManyCollateralInputs(u64), // this is Haskell Natural, how many bit is it?
+    NoCollateralInputs(),         // empty
+    IncorrectTotalCollateralField(Coin, Coin), // collateral provided, collateral amount declared in transaction body
+    BabbageOutputTooSmallUTxO(Vec<(SerializableTxOut, Coin)>), // list of supplied transaction outputs that are too small, together with the minimum value for the given output
+    BabbageNonDisjointRefInputs(Vec<SerializableTxIn>), // TxIns that appear in both inputs and reference inputs
+}
+
+impl fmt::Display for ConwayUtxoPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConwayUtxoPredFailure::*;
+
+        match self {
+            UtxosFailure(e) => write!(f, "UtxosFailure ({})", e),
+            BadInputsUTxO(e) => write!(f, "BadInputsUTxO ({})", display_vec(e)),
+            OutsideValidityIntervalUTxO(vi, slot) => {
+                write!(f, "OutsideValidityIntervalUTxO ({}, {})", vi, slot)
+            }
+            MaxTxSizeUTxO(size) => write!(f, "MaxTxSizeUTxO ({})", size),
+            InputSetEmptyUTxO() => write!(f, "InputSetEmptyUTxO"),
+            FeeTooSmallUTxO(expected, supplied) => {
+                write!(f, "FeeTooSmallUTxO ({}, {})", expected, supplied)
+            }
+            ValueNotConservedUTxO(expected, supplied) => {
+                write!(f, "ValueNotConservedUTxO ({}, {})", expected, supplied)
+            }
+            WrongNetwork(network, addrs) => {
+                write!(f, "WrongNetwork ({}, {})", network, display_vec(addrs))
+            }
+            WrongNetworkWithdrawal(network, accounts) => write!(
+                f,
+                "WrongNetworkWithdrawal ({}, {})",
+                network,
+                display_vec(accounts)
+            ),
+            OutputTooSmallUTxO(outputs) => {
+                write!(f, "OutputTooSmallUTxO [{}]", display_list(outputs))
+            }
+            OutputBootAddrAttrsTooBig(outputs) => {
+                write!(f, "OutputBootAddrAttrsTooBig [{}]", display_list(outputs))
+            }
+            OutputTooBigUTxO(outputs) => {
+                let outputs = outputs
+                    .iter()
+                    .map(|(size, max, output)| format!("({},{},{})", size, max, output))
+                    .collect::<Vec<_>>();
+                write!(f, "OutputTooBigUTxO [{}]", outputs.join(","))
+            }
+            InsufficientCollateral(balance, required) => {
+                write!(f, "InsufficientCollateral ({}, {})", balance, required)
+            }
+            ScriptsNotPaidUTxO(utxo) => write!(f, "ScriptsNotPaidUTxO ({})", utxo),
+            ExUnitsTooBigUTxO(units) => write!(f, "ExUnitsTooBigUTxO ({})", units),
+            CollateralContainsNonADA(value) => write!(f, "CollateralContainsNonADA ({})", value),
+            WrongNetworkInTxBody() => write!(f, "WrongNetworkInTxBody"),
+            OutsideForecast(slot) => write!(f, "OutsideForecast ({})", slot),
+            TooManyCollateralInputs(inputs) => write!(f, "TooManyCollateralInputs ({})", inputs),
+            NoCollateralInputs() => write!(f, "NoCollateralInputs"),
+            IncorrectTotalCollateralField(provided, declared) => write!(
+                f,
+                "IncorrectTotalCollateralField ({}, {})",
+                provided, declared
+            ),
+            BabbageOutputTooSmallUTxO(outputs) => write!(
+                f,
+                "BabbageOutputTooSmallUTxO [{}]",
+                display_outputs_with_coin(outputs)
+            ),
+            BabbageNonDisjointRefInputs(inputs) => {
+                write!(f, "BabbageNonDisjointRefInputs ({})", display_vec(inputs))
+            }
+        }
+    }
+}
+
+/// The ledger rules of a Shelley-based era before Conway. They all share the
+/// LEDGER rule of Shelley, and differ in the failures of their UTXOW rule.
+pub trait EraPredFailure: fmt::Debug {
+    type UtxowFailure: fmt::Debug + Display + for<'b> Decode<'b, ()> + Encode<()>;
+}
+
+#[derive(Debug)]
+pub struct Shelley;
+
+impl EraPredFailure for Shelley {
+    type UtxowFailure = ShelleyUtxowPredFailure<ShelleyUtxoPredFailure>;
+}
+
+/// Also Mary, whose rules only differ in the values they carry.
+#[derive(Debug)]
+pub struct Allegra;
+
+impl EraPredFailure for Allegra {
+    type UtxowFailure = ShelleyUtxowPredFailure<AllegraUtxoPredFailure>;
+}
+
+#[derive(Debug)]
+pub struct Alonzo;
+
+impl EraPredFailure for Alonzo {
+    type UtxowFailure = AlonzoUtxowPredFailure<AlonzoUtxoPredFailure>;
+}
+
+#[derive(Debug)]
+pub struct Babbage;
+
+impl EraPredFailure for Babbage {
+    type UtxowFailure = BabbageUtxowPredFailure;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(bound = "")]
+pub struct ApplyEraTxErr<E: EraPredFailure>(pub Vec<ShelleyLedgerPredFailure<E>>);
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/shelley/impl/src/Cardano/Ledger/Shelley/Rules/Ledger.hs#L92
+#[derive(Debug, SerializeDisplay)]
+pub enum ShelleyLedgerPredFailure<E: EraPredFailure> {
+    UtxowFailure(E::UtxowFailure),
+    DelegsFailure(ShelleyDelegsPredFailure),
+}
+
+impl<E: EraPredFailure> fmt::Display for ShelleyLedgerPredFailure<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ShelleyLedgerPredFailure::*;
+
+        match self {
+            UtxowFailure(e) => write!(f, "UtxowFailure ({})", e),
+            DelegsFailure(e) => write!(f, "DelegsFailure ({})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/babbage/impl/src/Cardano/Ledger/Babbage/Rules/Utxow.hs#L77
+#[derive(Debug)]
+pub enum BabbageUtxowPredFailure {
+    AlonzoInBabbageUtxowPredFailure(AlonzoUtxowPredFailure),
+    UtxoFailure(BabbageUtxoPredFailure),
+    MalformedScriptWitnesses(Vec<DisplayScriptHash>),
+    MalformedReferenceScripts(Vec<DisplayScriptHash>),
+}
+
+impl fmt::Display for BabbageUtxowPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use BabbageUtxowPredFailure::*;
+
+        match self {
+            AlonzoInBabbageUtxowPredFailure(e) => {
+                write!(f, "AlonzoInBabbageUtxowPredFailure ({})", e)
+            }
+            UtxoFailure(e) => write!(f, "UtxoFailure ({})", e),
+            MalformedScriptWitnesses(e) => {
+                write!(f, "MalformedScriptWitnesses ({})", display_vec(e))
+            }
+            MalformedReferenceScripts(e) => {
+                write!(f, "MalformedReferenceScripts ({})", display_vec(e))
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Rules/Utxow.hs#L97
+// The UTXO failures are the ones of the era, i.e. Babbage's when within `BabbageUtxowPredFailure`
+#[derive(Debug)]
+pub enum AlonzoUtxowPredFailure<U = BabbageUtxoPredFailure> {
+    ShelleyInAlonzoUtxowPredfailure(ShelleyUtxowPredFailure<U>),
+    MissingRedeemers(Vec<(ScriptPurpose, DisplayScriptHash)>),
+    MissingRequiredDatums(Vec<DatumHash>, Vec<DatumHash>), // set of missing data hashes, set of recieved data hashes
+    NotAllowedSupplementalDatums(Vec<DatumHash>, Vec<DatumHash>), // set of unallowed data hashes, set of acceptable data hashes
+    PPViewHashesDontMatch(Option<ScriptIntegrityHash>, Option<ScriptIntegrityHash>), // hash in the tx body, hash computed from the protocol parameters
+    MissingRequiredSigners(Vec<AddrKeyhash>), // deprecated, but still in the encoding
+    UnspendableUTxONoDatumHash(Vec<SerializableTxIn>),
+    ExtraRedeemers(Vec<RedeemerPointer>),
+}
+
+impl<U: Display> fmt::Display for AlonzoUtxowPredFailure<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use AlonzoUtxowPredFailure::*;
+
+        match self {
+            ShelleyInAlonzoUtxowPredfailure(e) => {
+                write!(f, "ShelleyInAlonzoUtxowPredfailure ({})", e)
+            }
+            MissingRedeemers(e) => write!(f, "MissingRedeemers ({})", display_tuple_vec(e)),
+            MissingRequiredDatums(e1, e2) => write!(
+                f,
+                "MissingRequiredDatums ({}, {})",
+                display_vec(e1),
+                display_vec(e2)
+            ),
+            NotAllowedSupplementalDatums(e1, e2) => write!(
+                f,
+                "NotAllowedSupplementalDatums ({}, {})",
+                display_vec(e1),
+                display_vec(e2)
+            ),
+            PPViewHashesDontMatch(e1, e2) => write!(
+                f,
+                "PPViewHashesDontMatch ({}, {})",
+                display_option(e1),
+                display_option(e2)
+            ),
+            MissingRequiredSigners(e) => write!(f, "MissingRequiredSigners ({})", display_vec(e)),
+            UnspendableUTxONoDatumHash(e) => {
+                write!(f, "UnspendableUTxONoDatumHash ({})", display_vec(e))
+            }
+            ExtraRedeemers(e) => write!(f, "ExtraRedeemers ({})", display_vec(e)),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/shelley/impl/src/Cardano/Ledger/Shelley/Rules/Utxow.hs#L113
+#[derive(Debug)]
+pub enum ShelleyUtxowPredFailure<U = BabbageUtxoPredFailure> {
+    InvalidWitnessesUTXOW(Vec<Bytes>), // verification keys
+    MissingVKeyWitnessesUTXOW(Vec<AddrKeyhash>),
+    MissingScriptWitnessesUTXOW(Vec<DisplayScriptHash>),
+    ScriptWitnessNotValidatingUTXOW(Vec<DisplayScriptHash>),
+    UtxoFailure(Box<U>),
+    MIRInsufficientGenesisSigsUTXOW(Vec<AddrKeyhash>),
+    MissingTxBodyMetadataHash(Bytes),      // auxDataHash
+    MissingTxMetadata(Bytes),              // auxDataHash
+    ConflictingMetadataHash(Bytes, Bytes), // Mismatch auxDataHash
+    InvalidMetadata(),                     // empty
+    ExtraneousScriptWitnessesUTXOW(Vec<DisplayScriptHash>),
+}
+
+impl<U: Display> fmt::Display for ShelleyUtxowPredFailure<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ShelleyUtxowPredFailure::*;
+
+        match self {
+            InvalidWitnessesUTXOW(e) => write!(f, "InvalidWitnessesUTXOW ({})", display_vec(e)),
+            MissingVKeyWitnessesUTXOW(e) => {
+                write!(f, "MissingVKeyWitnessesUTXOW ({})", display_vec(e))
+            }
+            MissingScriptWitnessesUTXOW(e) => {
+                write!(f, "MissingScriptWitnessesUTXOW ({})", display_vec(e))
+            }
+            ScriptWitnessNotValidatingUTXOW(e) => {
+                write!(f, "ScriptWitnessNotValidatingUTXOW ({})", display_vec(e))
+            }
+            UtxoFailure(e) => write!(f, "UtxoFailure ({})", e),
+            MIRInsufficientGenesisSigsUTXOW(e) => {
+                write!(f, "MIRInsufficientGenesisSigsUTXOW ({})", display_vec(e))
+            }
+            MissingTxBodyMetadataHash(e) => write!(f, "MissingTxBodyMetadataHash ({})", e),
+            MissingTxMetadata(e) => write!(f, "MissingTxMetadata ({})", e),
+            ConflictingMetadataHash(e1, e2) => {
+                write!(f, "ConflictingMetadataHash ({}, {})", e1, e2)
+            }
+            InvalidMetadata() => write!(f, "InvalidMetadata"),
+            ExtraneousScriptWitnessesUTXOW(e) => {
+                write!(f, "ExtraneousScriptWitnessesUTXOW ({})", display_vec(e))
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/shelley/impl/src/Cardano/Ledger/Shelley/Rules/Utxo.hs#L113
+#[derive(Debug)]
+pub enum ShelleyUtxoPredFailure {
+    BadInputsUTxO(Vec<SerializableTxIn>),
+    ExpiredUTxO(SlotNo, SlotNo), // transaction's time to live, current slot
+    MaxTxSizeUTxO(u64, u64),     // actual size, max size
+    InputSetEmptyUTxO(),         // empty
+    FeeTooSmallUTxO(Coin, Coin), // min fee, supplied fee
+    ValueNotConservedUTxO(Coin, Coin), // consumed, produced
+    OutputTooSmallUTxO(Vec<SerializableTxOut>),
+    UpdateFailure(ShelleyPpupPredFailure),
+    WrongNetwork(Network, Vec<Addr>), // the expected network id, the set of addresses with incorrect network IDs
+    WrongNetworkWithdrawal(Network, Vec<RewardAccount>), // the expected network id, the set of reward addresses with incorrect network IDs
+    OutputBootAddrAttrsTooBig(Vec<SerializableTxOut>),
+}
+
+impl fmt::Display for ShelleyUtxoPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ShelleyUtxoPredFailure::*;
+
+        match self {
+            BadInputsUTxO(e) => write!(f, "BadInputsUTxO ({})", display_vec(e)),
+            ExpiredUTxO(ttl, slot) => write!(f, "ExpiredUTxO ({}, {})", ttl, slot),
+            MaxTxSizeUTxO(actual, max) => write!(f, "MaxTxSizeUTxO ({}, {})", actual, max),
+            InputSetEmptyUTxO() => write!(f, "InputSetEmptyUTxO"),
+            FeeTooSmallUTxO(expected, supplied) => {
+                write!(f, "FeeTooSmallUTxO ({}, {})", expected, supplied)
+            }
+            ValueNotConservedUTxO(consumed, produced) => {
+                write!(f, "ValueNotConservedUTxO ({}, {})", consumed, produced)
+            }
+            OutputTooSmallUTxO(outputs) => {
+                write!(f, "OutputTooSmallUTxO [{}]", display_list(outputs))
+            }
+            UpdateFailure(e) => write!(f, "UpdateFailure ({})", e),
+            WrongNetwork(network, addrs) => {
+                write!(f, "WrongNetwork ({}, {})", network, display_vec(addrs))
+            }
+            WrongNetworkWithdrawal(network, accounts) => write!(
+                f,
+                "WrongNetworkWithdrawal ({}, {})",
+                network,
+                display_vec(accounts)
+            ),
+            OutputBootAddrAttrsTooBig(outputs) => {
+                write!(f, "OutputBootAddrAttrsTooBig [{}]", display_list(outputs))
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/allegra/impl/src/Cardano/Ledger/Allegra/Rules/Utxo.hs#L104
+// Also the UTXO rule of Mary
+#[derive(Debug)]
+pub enum AllegraUtxoPredFailure {
+    BadInputsUTxO(Vec<SerializableTxIn>),
+    OutsideValidityIntervalUTxO(ValidityInterval, SlotNo), // validity interval, current slot
+    MaxTxSizeUTxO(u64, u64),                               // actual size, max size
+    InputSetEmptyUTxO(),                                   // empty
+    FeeTooSmallUTxO(Coin, Coin),                           // min fee, supplied fee
+    ValueNotConservedUTxO(DisplayValue, DisplayValue),     // consumed, produced
+    WrongNetwork(Network, Vec<Addr>), // the expected network id, the set of addresses with incorrect network IDs
+    WrongNetworkWithdrawal(Network, Vec<RewardAccount>), // the expected network id, the set of reward addresses with incorrect network IDs
+    OutputTooSmallUTxO(Vec<SerializableTxOut>),
+    UpdateFailure(ShelleyPpupPredFailure),
+    OutputBootAddrAttrsTooBig(Vec<SerializableTxOut>),
+    TriesToForgeADA(), // empty
+    OutputTooBigUTxO(Vec<SerializableTxOut>),
+}
+
+impl fmt::Display for AllegraUtxoPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use AllegraUtxoPredFailure::*;
+
+        match self {
+            BadInputsUTxO(e) => write!(f, "BadInputsUTxO ({})", display_vec(e)),
+            OutsideValidityIntervalUTxO(vi, slot) => {
+                write!(f, "OutsideValidityIntervalUTxO ({}, {})", vi, slot)
+            }
+            MaxTxSizeUTxO(actual, max) => write!(f, "MaxTxSizeUTxO ({}, {})", actual, max),
+            InputSetEmptyUTxO() => write!(f, "InputSetEmptyUTxO"),
+            FeeTooSmallUTxO(expected, supplied) => {
+                write!(f, "FeeTooSmallUTxO ({}, {})", expected, supplied)
+            }
+            ValueNotConservedUTxO(consumed, produced) => {
+                write!(f, "ValueNotConservedUTxO ({}, {})", consumed, produced)
+            }
+            WrongNetwork(network, addrs) => {
+                write!(f, "WrongNetwork ({}, {})", network, display_vec(addrs))
+            }
+            WrongNetworkWithdrawal(network, accounts) => write!(
+                f,
+                "WrongNetworkWithdrawal ({}, {})",
+                network,
+                display_vec(accounts)
+            ),
+            OutputTooSmallUTxO(outputs) => {
+                write!(f, "OutputTooSmallUTxO [{}]", display_list(outputs))
+            }
+            UpdateFailure(e) => write!(f, "UpdateFailure ({})", e),
+            OutputBootAddrAttrsTooBig(outputs) => {
+                write!(f, "OutputBootAddrAttrsTooBig [{}]", display_list(outputs))
+            }
+            TriesToForgeADA() => write!(f, "TriesToForgeADA"),
+            OutputTooBigUTxO(outputs) => {
+                write!(f, "OutputTooBigUTxO [{}]", display_list(outputs))
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/babbage/impl/src/Cardano/Ledger/Babbage/Rules/Utxo.hs#L97
+#[derive(Debug)]
+pub enum BabbageUtxoPredFailure {
+    AlonzoInBabbageUtxoPredFailure(AlonzoUtxoPredFailure),
+    IncorrectTotalCollateralField(i64, Coin), // collateral provided, collateral amount declared in transaction body
+    BabbageOutputTooSmallUTxO(Vec<(SerializableTxOut, Coin)>), // list of supplied transaction outputs that are too small, together with the minimum value for the given output
+    BabbageNonDisjointRefInputs(Vec<SerializableTxIn>), // TxIns that appear in both inputs and reference inputs
+}
+
+impl fmt::Display for BabbageUtxoPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use BabbageUtxoPredFailure::*;
+
+        match self {
+            AlonzoInBabbageUtxoPredFailure(e) => {
+                write!(f, "AlonzoInBabbageUtxoPredFailure ({})", e)
+            }
+            IncorrectTotalCollateralField(provided, declared) => write!(
+                f,
+                "IncorrectTotalCollateralField ({}, {})",
+                provided, declared
+            ),
+            BabbageOutputTooSmallUTxO(outputs) => write!(
+                f,
+                "BabbageOutputTooSmallUTxO [{}]",
+                display_outputs_with_coin(outputs)
+            ),
+            BabbageNonDisjointRefInputs(inputs) => {
+                write!(f, "BabbageNonDisjointRefInputs ({})", display_vec(inputs))
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Rules/Utxo.hs#L126
+#[derive(Debug)]
+pub enum AlonzoUtxoPredFailure {
+    BadInputsUTxO(Vec<SerializableTxIn>),
+    OutsideValidityIntervalUTxO(ValidityInterval, SlotNo), // validity interval, current slot
+    MaxTxSizeUTxO(u64, u64),                               // actual size, max size
+    InputSetEmptyUTxO(),                                   // empty
+    FeeTooSmallUTxO(Coin, Coin),                           // min fee, supplied fee
+    ValueNotConservedUTxO(DisplayValue, DisplayValue),     // consumed, produced
+    OutputTooSmallUTxO(Vec<SerializableTxOut>),
+    UtxosFailure(AlonzoUtxosPredFailure),
+    WrongNetwork(Network, Vec<Addr>), // the expected network id, the set of addresses with incorrect network IDs
+    WrongNetworkWithdrawal(Network, Vec<RewardAccount>), // the expected network id, the set of reward addresses with incorrect network IDs
+    OutputBootAddrAttrsTooBig(Vec<SerializableTxOut>),
+    TriesToForgeADA(),                                    // empty
+    OutputTooBigUTxO(Vec<(u64, u64, SerializableTxOut)>), // list of supplied bad transaction output triples (actualSize,PParameterMaxValue,TxOut)
+    InsufficientCollateral(i64, Coin), // balance computed, the required collateral for the given fee
+    ScriptsNotPaidUTxO(Utxo),          // The UTxO entries which have the wrong kind of script
+    ExUnitsTooBigUTxO(DisplayExUnits, DisplayExUnits), // max, supplied
+    CollateralContainsNonADA(DisplayValue),
+    WrongNetworkInTxBody(Network, Network), // expected, supplied
+    OutsideForecast(SlotNo),
+    TooManyCollateralInputs(u64, u64), // max, supplied
+    NoCollateralInputs(),              // empty
+}
+
+impl fmt::Display for AlonzoUtxoPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use AlonzoUtxoPredFailure::*;
+
+        match self {
+            BadInputsUTxO(e) => write!(f, "BadInputsUTxO ({})", display_vec(e)),
+            OutsideValidityIntervalUTxO(vi, slot) => {
+                write!(f, "OutsideValidityIntervalUTxO ({}, {})", vi, slot)
+            }
+            MaxTxSizeUTxO(actual, max) => write!(f, "MaxTxSizeUTxO ({}, {})", actual, max),
+            InputSetEmptyUTxO() => write!(f, "InputSetEmptyUTxO"),
+            FeeTooSmallUTxO(expected, supplied) => {
+                write!(f, "FeeTooSmallUTxO ({}, {})", expected, supplied)
+            }
+            ValueNotConservedUTxO(consumed, produced) => {
+                write!(f, "ValueNotConservedUTxO ({}, {})", consumed, produced)
+            }
+            OutputTooSmallUTxO(outputs) => {
+                write!(f, "OutputTooSmallUTxO [{}]", display_list(outputs))
+            }
+            UtxosFailure(e) => write!(f, "UtxosFailure ({})", e),
+            WrongNetwork(network, addrs) => {
+                write!(f, "WrongNetwork ({}, {})", network, display_vec(addrs))
+            }
+            WrongNetworkWithdrawal(network, accounts) => write!(
+                f,
+                "WrongNetworkWithdrawal ({}, {})",
+                network,
+                display_vec(accounts)
+            ),
+            OutputBootAddrAttrsTooBig(outputs) => {
+                write!(f, "OutputBootAddrAttrsTooBig [{}]", display_list(outputs))
+            }
+            TriesToForgeADA() => write!(f, "TriesToForgeADA"),
+            OutputTooBigUTxO(outputs) => {
+                let outputs = outputs
+                    .iter()
+                    .map(|(size, max, output)| format!("({},{},{})", size, max, output))
+                    .collect::<Vec<_>>();
+                write!(f, "OutputTooBigUTxO [{}]", outputs.join(","))
+            }
+            InsufficientCollateral(balance, required) => {
+                write!(f, "InsufficientCollateral ({}, {})", balance, required)
+            }
+            ScriptsNotPaidUTxO(utxo) => write!(f, "ScriptsNotPaidUTxO ({})", utxo),
+            ExUnitsTooBigUTxO(max, supplied) => {
+                write!(f, "ExUnitsTooBigUTxO ({}, {})", max, supplied)
+            }
+            CollateralContainsNonADA(value) => write!(f, "CollateralContainsNonADA ({})", value),
+            WrongNetworkInTxBody(expected, supplied) => {
+                write!(f, "WrongNetworkInTxBody ({}, {})", expected, supplied)
+            }
+            OutsideForecast(slot) => write!(f, "OutsideForecast ({})", slot),
+            TooManyCollateralInputs(max, supplied) => {
+                write!(f, "TooManyCollateralInputs ({}, {})", max, supplied)
+            }
+            NoCollateralInputs() => write!(f, "NoCollateralInputs"),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Rules/Utxos.hs#L362
+#[derive(Debug)]
+pub enum AlonzoUtxosPredFailure {
+    ValidationTagMismatch(bool, TagMismatchDescription), // the IsValid flag of the transaction, what happened instead
+    CollectErrors(Vec<CollectError>),
+    UpdateFailure(ShelleyPpupPredFailure),
+}
+
+impl fmt::Display for AlonzoUtxosPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use AlonzoUtxosPredFailure::*;
+
+        match self {
+            ValidationTagMismatch(is_valid, description) => write!(
+                f,
+                "ValidationTagMismatch (IsValid {}, {})",
+                if *is_valid { "True" } else { "False" },
+                description
+            ),
+            CollectErrors(e) => write!(f, "CollectErrors ({})", display_vec(e)),
+            UpdateFailure(e) => write!(f, "UpdateFailure ({})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Rules/Utxos.hs#L299
+#[derive(Debug)]
+pub enum TagMismatchDescription {
+    PassedUnexpectedly(),
+    FailedUnexpectedly(Vec<FailureDescription>),
+}
+
+impl fmt::Display for TagMismatchDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use TagMismatchDescription::*;
+
+        match self {
+            PassedUnexpectedly() => write!(f, "PassedUnexpectedly"),
+            FailedUnexpectedly(e) => write!(f, "FailedUnexpectedly ({})", display_vec(e)),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Rules/Utxos.hs#L277
+#[derive(Debug)]
+pub enum FailureDescription {
+    PlutusFailure(String, Bytes), // the error message, the re-serialised script context for debugging
+}
+
+impl fmt::Display for FailureDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use FailureDescription::*;
+
+        match self {
+            PlutusFailure(message, context) => {
+                write!(f, "PlutusFailure {:?} {}", message, context)
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/conway/impl/src/Cardano/Ledger/Conway/Rules/Utxos.hs#L108
+#[derive(Debug)]
+pub enum ConwayUtxosPredFailure {
+    ValidationTagMismatch(bool, TagMismatchDescription), // the IsValid flag of the transaction, what happened instead
+    CollectErrors(Vec<CollectError<ConwayPlutusPurpose, ConwayContextError>>),
+}
+
+impl fmt::Display for ConwayUtxosPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConwayUtxosPredFailure::*;
+
+        match self {
+            ValidationTagMismatch(is_valid, description) => write!(
+                f,
+                "ValidationTagMismatch (IsValid {}) ({})",
+                if *is_valid { "True" } else { "False" },
+                description
+            ),
+            CollectErrors(e) => write!(
+                f,
+                "CollectErrors [{}]",
+                e.iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/conway/impl/src/Cardano/Ledger/Conway/Scripts.hs#L112
+// Voters and proposals are shown as their CBOR hex
+#[derive(Debug)]
+pub enum ConwayPlutusPurpose {
+    ConwaySpending(SerializableTxIn),
+    ConwayMinting(DisplayPolicyId),
+    ConwayCertifying(ConwayTxCert),
+    ConwayRewarding(RewardAccount),
+    ConwayVoting(RawCbor),
+    ConwayProposing(RawCbor),
+}
+
+impl fmt::Display for ConwayPlutusPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConwayPlutusPurpose::*;
+
+        let (constructor, item) = match self {
+            ConwaySpending(e) => ("ConwaySpending", e.to_string()),
+            ConwayMinting(e) => ("ConwayMinting", e.to_string()),
+            ConwayCertifying(e) => ("ConwayCertifying", e.to_string()),
+            ConwayRewarding(e) => ("ConwayRewarding", e.to_string()),
+            ConwayVoting(e) => ("ConwayVoting", e.to_string()),
+            ConwayProposing(e) => ("ConwayProposing", e.to_string()),
+        };
+
+        write!(f, "{} (AsItem {{unAsItem = {}}})", constructor, item)
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[cbor(transparent)]
+pub struct DisplayPolicyId(#[n(0)] pub ScriptHash);
+
+impl fmt::Display for DisplayPolicyId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PolicyID {{policyID = ScriptHash \"{}\"}}", self.0)
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Plutus/Evaluate.hs#L75
+// The script purpose and the context error differ between Babbage and Conway
+#[derive(Debug)]
+pub enum CollectError<P = ScriptPurpose, C = BabbageContextError> {
+    NoRedeemer(P),
+    NoWitness(DisplayScriptHash),
+    NoCostModel(Language),
+    BadTranslation(C),
+}
+
+impl<P: Display, C: Display> fmt::Display for CollectError<P, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use CollectError::*;
+
+        match self {
+            NoRedeemer(e) => write!(f, "NoRedeemer ({})", e),
+            NoWitness(e) => write!(f, "NoWitness ({})", e),
+            NoCostModel(e) => write!(f, "NoCostModel {}", e),
+            BadTranslation(e) => write!(f, "BadTranslation ({})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/conway/impl/src/Cardano/Ledger/Conway/TxInfo.hs#L105
+// Voting and proposal procedures are shown as their CBOR hex
+#[derive(Debug)]
+pub enum ConwayContextError {
+    BabbageContextError(BabbageContextError<ConwayPurposeAsIx>),
+    CertificateNotSupported(ConwayTxCert),
+    PlutusPurposeNotSupported(ConwayPlutusPurpose),
+    CurrentTreasuryFieldNotSupported(Coin),
+    VotingProceduresFieldNotSupported(RawCbor),
+    ProposalProceduresFieldNotSupported(RawCbor),
+    TreasuryDonationFieldNotSupported(Coin),
+    ReferenceInputsNotDisjointFromInputs(Vec<SerializableTxIn>), // non-empty
+}
+
+impl fmt::Display for ConwayContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConwayContextError::*;
+
+        match self {
+            BabbageContextError(e) => write!(f, "BabbageContextError ({})", e),
+            CertificateNotSupported(e) => write!(f, "CertificateNotSupported ({})", e),
+            PlutusPurposeNotSupported(e) => write!(f, "PlutusPurposeNotSupported ({})", e),
+            CurrentTreasuryFieldNotSupported(e) => {
+                write!(f, "CurrentTreasuryFieldNotSupported (Coin {})", e)
+            }
+            VotingProceduresFieldNotSupported(e) => {
+                write!(f, "VotingProceduresFieldNotSupported ({})", e)
+            }
+            ProposalProceduresFieldNotSupported(e) => {
+                write!(f, "ProposalProceduresFieldNotSupported ({})", e)
+            }
+            TreasuryDonationFieldNotSupported(e) => {
+                write!(f, "TreasuryDonationFieldNotSupported (Coin {})", e)
+            }
+            ReferenceInputsNotDisjointFromInputs(e) => {
+                write!(
+                    f,
+                    "ReferenceInputsNotDisjointFromInputs ({})",
+                    display_non_empty(e)
+                )
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/babbage/impl/src/Cardano/Ledger/Babbage/TxInfo.hs#L118
+// The redeemer pointer differs between Babbage and Conway
+#[derive(Debug)]
+pub enum BabbageContextError<I = AlonzoPurposeAsIx> {
+    AlonzoContextError(AlonzoContextError),
+    ByronTxOutInContext(TxOutSource),
+    RedeemerPointerPointsToNothing(I),
+    InlineDatumsNotSupported(TxOutSource),
+    ReferenceScriptsNotSupported(TxOutSource),
+    ReferenceInputsNotSupported(Vec<SerializableTxIn>),
+}
+
+impl<I: Display> fmt::Display for BabbageContextError<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use BabbageContextError::*;
+
+        match self {
+            AlonzoContextError(e) => write!(f, "AlonzoContextError ({})", e),
+            ByronTxOutInContext(e) => write!(f, "ByronTxOutInContext ({})", e),
+            RedeemerPointerPointsToNothing(e) => {
+                write!(f, "RedeemerPointerPointsToNothing ({})", e)
+            }
+            InlineDatumsNotSupported(e) => write!(f, "InlineDatumsNotSupported ({})", e),
+            ReferenceScriptsNotSupported(e) => write!(f, "ReferenceScriptsNotSupported ({})", e),
+            ReferenceInputsNotSupported(e) => {
+                write!(
+                    f,
+                    "ReferenceInputsNotSupported (fromList [{}])",
+                    display_list(e)
+                )
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Plutus/TxInfo.hs#L97
+#[derive(Debug)]
+pub enum AlonzoContextError {
+    TranslationLogicMissingInput(SerializableTxIn),
+    TimeTranslationPastHorizon(String),
+}
+
+impl fmt::Display for AlonzoContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use AlonzoContextError::*;
+
+        match self {
+            TranslationLogicMissingInput(e) => write!(f, "TranslationLogicMissingInput ({})", e),
+            TimeTranslationPastHorizon(e) => write!(f, "TimeTranslationPastHorizon {:?}", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/libs/cardano-ledger-core/src/Cardano/Ledger/Plutus/TxInfo.hs#L105
+#[derive(Debug)]
+pub enum TxOutSource {
+    TxOutFromInput(SerializableTxIn),
+    TxOutFromOutput(u64), // index of the output in the transaction
+}
+
+impl fmt::Display for TxOutSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use TxOutSource::*;
+
+        match self {
+            TxOutFromInput(e) => write!(f, "TxOutFromInput ({})", e),
+            TxOutFromOutput(e) => write!(f, "TxOutFromOutput (TxIx {{unTxIx = {}}})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Scripts.hs#L219
+// A script purpose by its redeemer's tag and index, as in Babbage
+#[derive(Debug)]
+pub struct AlonzoPurposeAsIx {
+    pub tag: PlutusPurpose,
+    pub index: u32,
+}
+
+impl fmt::Display for AlonzoPurposeAsIx {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Alonzo{} (AsIx {{unAsIx = {}}})", self.tag, self.index)
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/conway/impl/src/Cardano/Ledger/Conway/Scripts.hs#L112
+// A script purpose by its redeemer's tag and index, as in Conway
+#[derive(Debug)]
+pub struct ConwayPurposeAsIx {
+    pub tag: ConwayPurposeTag,
+    pub index: u32,
+}
+
+impl fmt::Display for ConwayPurposeAsIx {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Conway{:?} (AsIx {{unAsIx = {}}})", self.tag, self.index)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConwayPurposeTag {
+    Spending,   // 0
+    Minting,    // 1
+    Certifying, // 2
+    Rewarding,  // 3
+    Voting,     // 4
+    Proposing,  // 5
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/libs/cardano-ledger-core/src/Cardano/Ledger/Plutus/Language.hs#L171
+#[derive(Debug)]
+pub enum Language {
+    PlutusV1,
+    PlutusV2,
+    PlutusV3,
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Scripts.hs#L497
+// Pre-Conway script purposes carry the item they refer to
+#[derive(Debug)]
+pub enum ScriptPurpose {
+    Spending(SerializableTxIn),
+    Minting(DisplayScriptHash), // policy id
+    Certifying(RawCbor),        // the certificate
+    Rewarding(RewardAccount),
+}
+
+impl fmt::Display for ScriptPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ScriptPurpose::*;
+
+        match self {
+            Spending(e) => write!(f, "Spending ({})", e),
+            Minting(e) => write!(f, "Minting ({})", e),
+            Certifying(e) => write!(f, "Certifying ({})", e),
+            Rewarding(e) => write!(f, "Rewarding ({})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/TxWits.hs#L169
+// The tag and the index of a redeemer
+#[derive(Debug)]
+pub struct RedeemerPointer {
+    pub tag: PlutusPurpose,
+    pub index: u64,
+}
+
+impl fmt::Display for RedeemerPointer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RdmrPtr {} {}", self.tag, self.index)
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/shelley/impl/src/Cardano/Ledger/Shelley/Rules/Ppup.hs#L69
+#[derive(Debug)]
+pub enum ShelleyPpupPredFailure {
+    NonGenesisUpdatePPUP(Vec<AddrKeyhash>, Vec<AddrKeyhash>), // keys of the update, genesis keys
+    PPUpdateWrongEpoch(u64, u64, VotingPeriod),               // current epoch, epoch of the update
+    PVCannotFollowPPUP(u64, u64),                             // major, minor
+}
+
+impl fmt::Display for ShelleyPpupPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ShelleyPpupPredFailure::*;
+
+        match self {
+            NonGenesisUpdatePPUP(e1, e2) => write!(
+                f,
+                "NonGenesisUpdatePPUP ({}, {})",
+                display_vec(e1),
+                display_vec(e2)
+            ),
+            PPUpdateWrongEpoch(current, update, period) => write!(
+                f,
+                "PPUpdateWrongEpoch (EpochNo {}, EpochNo {}, {})",
+                current, update, period
+            ),
+            PVCannotFollowPPUP(major, minor) => write!(
+                f,
+                "PVCannotFollowPPUP (ProtVer {{pvMajor = Version {}, pvMinor = {}}})",
+                major, minor
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum VotingPeriod {
+    VoteForThisEpoch,
+    VoteForNextEpoch,
+}
+
+impl fmt::Display for VotingPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/shelley/impl/src/Cardano/Ledger/Shelley/Rules/Delegs.hs#L80
+#[derive(Debug)]
+pub enum ShelleyDelegsPredFailure {
+    DelegateeNotRegisteredDELEG(PoolKeyhash),
+    WithdrawalsNotInRewardsDELEGS(Vec<(RewardAccount, Coin)>),
+    DelplFailure(ShelleyDelplPredFailure),
+}
+
+impl fmt::Display for ShelleyDelegsPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ShelleyDelegsPredFailure::*;
+
+        match self {
+            DelegateeNotRegisteredDELEG(e) => write!(f, "DelegateeNotRegisteredDELEG ({})", e),
+            WithdrawalsNotInRewardsDELEGS(e) => {
+                write!(
+                    f,
+                    "WithdrawalsNotInRewardsDELEGS ({})",
+                    display_tuple_vec(e)
+                )
+            }
+            DelplFailure(e) => write!(f, "DelplFailure ({})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/shelley/impl/src/Cardano/Ledger/Shelley/Rules/Delpl.hs#L72
+// The POOL and DELEG failures aren't decoded any further yet
+#[derive(Debug)]
+pub enum ShelleyDelplPredFailure {
+    PoolFailure(RawCbor),
+    DelegFailure(RawCbor),
+}
+
+impl fmt::Display for ShelleyDelplPredFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ShelleyDelplPredFailure::*;
+
+        match self {
+            PoolFailure(e) => write!(f, "PoolFailure ({})", e),
+            DelegFailure(e) => write!(f, "DelegFailure ({})", e),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/conway/impl/src/Cardano/Ledger/Conway/TxCert.hs#L336
+// Not encoded on its own, but inline in the delegation certificates, see `codec::decode_delegatee`
+#[derive(Debug, PartialEq)]
+pub enum Delegatee {
+    DelegStake(DisplayKeyHash),
+    DelegVote(DRep),
+    DelegStakeVote(DisplayKeyHash, DRep),
+}
+
+impl fmt::Display for Delegatee {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Delegatee::*;
+
+        match self {
+            DelegStake(pool) => write!(f, "DelegStake ({})", pool),
+            DelegVote(drep) => write!(f, "DelegVote {}", drep.show_arg()),
+            DelegStakeVote(pool, drep) => {
+                write!(f, "DelegStakeVote ({}) {}", pool, drep.show_arg())
+            }
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/libs/cardano-ledger-core/src/Cardano/Ledger/DRep.hs#L56
+#[derive(Debug, PartialEq)]
+pub enum DRep {
+    DRepCredential(Credential),
+    DRepAlwaysAbstain,
+    DRepAlwaysNoConfidence,
+}
+
+impl DRep {
+    /// Haskell only parenthesizes constructors with arguments.
+    fn show_arg(&self) -> String {
+        match self {
+            DRep::DRepCredential(_) => format!("({})", self),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for DRep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use DRep::*;
+
+        match self {
+            DRepCredential(credential) => write!(f, "DRepCredential ({})", credential),
+            DRepAlwaysAbstain => write!(f, "DRepAlwaysAbstain"),
+            DRepAlwaysNoConfidence => write!(f, "DRepAlwaysNoConfidence"),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/libs/cardano-ledger-core/src/Cardano/Ledger/Credential.hs#L82
+#[derive(Debug, PartialEq, Clone)]
+pub enum Credential {
+    KeyHashObj(DisplayKeyHash),
+    ScriptHashObj(DisplayScriptHash),
+}
+
+impl From<&StakePayload> for Credential {
+    fn from(payload: &StakePayload) -> Self {
+        match payload {
+            StakePayload::Stake(hash) => Credential::KeyHashObj(DisplayKeyHash(*hash)),
+            StakePayload::Script(hash) => Credential::ScriptHashObj(DisplayScriptHash(*hash)),
+        }
+    }
+}
+
+impl fmt::Display for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Credential::*;
+
+        match self {
+            KeyHashObj(hash) => write!(f, "KeyHashObj ({})", hash),
+            ScriptHashObj(hash) => write!(f, "ScriptHashObj ({})", hash),
+        }
+    }
+}
+
+#[derive(Debug, Decode, Encode, PartialEq, Clone)]
+#[cbor(transparent)]
+pub struct DisplayKeyHash(#[n(0)] pub AddrKeyhash);
+
+impl fmt::Display for DisplayKeyHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KeyHash {{unKeyHash = \"{}\"}}", self.0)
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/conway/impl/src/Cardano/Ledger/Conway/TxCert.hs#L484
+#[derive(Debug, PartialEq)]
+pub enum ConwayTxCert {
+    ConwayTxCertDeleg(ConwayDelegCert),
+    ConwayTxCertPool(PoolCert),
+    ConwayTxCertGov(ConwayGovCert),
+}
+
+impl fmt::Display for ConwayTxCert {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConwayTxCert::*;
+
+        match self {
+            ConwayTxCertDeleg(cert) => write!(f, "ConwayTxCertDeleg ({})", cert),
+            ConwayTxCertPool(cert) => write!(f, "ConwayTxCertPool ({})", cert),
+            ConwayTxCertGov(cert) => write!(f, "ConwayTxCertGov ({})", cert),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/conway/impl/src/Cardano/Ledger/Conway/TxCert.hs#L366
+#[derive(Debug, PartialEq)]
+pub enum ConwayDelegCert {
+    ConwayRegCert(Credential, Option<Coin>), // deposit, only in the new-style certificate
+    ConwayUnRegCert(Credential, Option<Coin>), // refund, only in the new-style certificate
+    ConwayDelegCert(Credential, Delegatee),
+    ConwayRegDelegCert(Credential, Delegatee, Coin),
+}
+
+impl fmt::Display for ConwayDelegCert {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ConwayRegCert(credential, deposit) => write!(
+                f,
+                "ConwayRegCert ({}) {}",
+                credential,
+                display_strict_maybe_coin(deposit)
+            ),
+            Self::ConwayUnRegCert(credential, refund) => write!(
+                f,
+                "ConwayUnRegCert ({}) {}",
+                credential,
+                display_strict_maybe_coin(refund)
+            ),
+            Self::ConwayDelegCert(credential, delegatee) => {
+                write!(f, "ConwayDelegCert ({}) ({})", credential, delegatee)
+            }
+            Self::ConwayRegDelegCert(credential, delegatee, deposit) => write!(
+                f,
+                "ConwayRegDelegCert ({}) ({}) (Coin {})",
+                credential, delegatee, deposit
+            ),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/libs/cardano-ledger-core/src/Cardano/Ledger/PoolParams.hs
+#[derive(Debug, PartialEq)]
+pub enum PoolCert {
+    RegPool(PoolParams),
+    RetirePool(DisplayKeyHash, Epoch),
+}
+
+impl fmt::Display for PoolCert {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use PoolCert::*;
+
+        match self {
+            RegPool(params) => write!(f, "RegPool ({})", params),
+            RetirePool(pool, epoch) => write!(f, "RetirePool ({}) (EpochNo {})", pool, epoch),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PoolParams {
+    pub id: PoolKeyhash,
+    pub vrf: VrfKeyhash,
+    pub pledge: Coin,
+    pub cost: Coin,
+    pub margin: RationalNumber,
+    pub reward_account: RewardAccount,
+    pub owners: Vec<AddrKeyhash>,
+    pub relays: Vec<Relay>,
+    pub metadata: Option<PoolMetadata>,
+}
+
+impl fmt::Display for PoolParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The ledger keeps the owners in a `Set`, which shows them sorted
+        let mut owners = self.owners.clone();
+        owners.sort();
+        let owners = owners
+            .iter()
+            .map(|owner| format!("KeyHash {{unKeyHash = \"{}\"}}", owner))
+            .collect::<Vec<_>>();
+        let relays = self.relays.iter().map(display_relay).collect::<Vec<_>>();
+        let metadata = match &self.metadata {
+            Some(metadata) => format!(
+                "SJust (PoolMetadata {{pmUrl = Url {{urlToText = {:?}}}, pmHash = {}}})",
+                metadata.url,
+                haskell_show_bytes(metadata.hash.as_ref())
+            ),
+            None => "SNothing".to_string(),
+        };
+
+        write!(
+            f,
+            "PoolParams {{ppId = KeyHash {{unKeyHash = \"{}\"}}, ppVrf = VRFVerKeyHash {{unVRFVerKeyHash = \"{}\"}}, ppPledge = Coin {}, ppCost = Coin {}, ppMargin = {}, ppRewardAccount = {}, ppOwners = fromList [{}], ppRelays = StrictSeq {{fromStrict = fromList [{}]}}, ppMetadata = {}}}",
+            self.id,
+            self.vrf,
+            self.pledge,
+            self.cost,
+            display_ratio(&self.margin),
+            DisplayRewardAccount(&self.reward_account),
+            owners.join(","),
+            relays.join(","),
+            metadata
+        )
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/conway/impl/src/Cardano/Ledger/Conway/TxCert.hs#L418
+#[derive(Debug, PartialEq)]
+pub enum ConwayGovCert {
+    ConwayRegDRep(Credential, Coin, Option<Anchor>),
+    ConwayUnRegDRep(Credential, Coin),
+    ConwayUpdateDRep(Credential, Option<Anchor>),
+    ConwayAuthCommitteeHotKey(Credential, Credential), // cold, hot
+    ConwayResignCommitteeColdKey(Credential, Option<Anchor>),
+}
+
+impl fmt::Display for ConwayGovCert {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConwayGovCert::*;
+
+        match self {
+            ConwayRegDRep(credential, deposit, anchor) => write!(
+                f,
+                "ConwayRegDRep ({}) (Coin {}) {}",
+                credential,
+                deposit,
+                display_strict_maybe_anchor(anchor)
+            ),
+            ConwayUnRegDRep(credential, refund) => {
+                write!(f, "ConwayUnRegDRep ({}) (Coin {})", credential, refund)
+            }
+            ConwayUpdateDRep(credential, anchor) => write!(
+                f,
+                "ConwayUpdateDRep ({}) {}",
+                credential,
+                display_strict_maybe_anchor(anchor)
+            ),
+            ConwayAuthCommitteeHotKey(cold, hot) => {
+                write!(f, "ConwayAuthCommitteeHotKey ({}) ({})", cold, hot)
+            }
+            ConwayResignCommitteeColdKey(cold, anchor) => write!(
+                f,
+                "ConwayResignCommitteeColdKey ({}) {}",
+                cold,
+                display_strict_maybe_anchor(anchor)
+            ),
+        }
+    }
+}
+
+pub struct DisplayRewardAccount<'a>(pub &'a [u8]);
+
+impl fmt::Display for DisplayRewardAccount<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (header, hash) = match self.0 {
+            [header, hash @ ..] if hash.len() == 28 => (header, hex::encode(hash)),
+            _ => return write!(f, "{}", hex::encode(self.0)),
+        };
+        let network = match header & 0x0f {
+            1 => "Mainnet",
+            _ => "Testnet",
+        };
+        let credential = if header & 0x10 == 0 {
+            format!("KeyHashObj (KeyHash {{unKeyHash = \"{}\"}})", hash)
+        } else {
+            format!("ScriptHashObj (ScriptHash \"{}\")", hash)
+        };
+
+        write!(
+            f,
+            "RewardAccount {{raNetwork = {}, raCredential = {}}}",
+            network, credential
+        )
+    }
+}
+
+/// CBOR that we don't decode (yet), displayed as hex.
+#[derive(Debug)]
+pub struct RawCbor(pub Vec<u8>);
+
+impl fmt::Display for RawCbor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.0))
+    }
+}
+
+#[derive(Debug, Decode, Encode, PartialEq, Clone)]
+#[cbor(transparent)]
+pub struct DisplayScriptHash(#[n(0)] pub ScriptHash);
+
+impl fmt::Display for DisplayScriptHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ScriptHash \"{}\"", self.0)
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[cbor(transparent)]
+pub struct DisplayVKeyWitness(#[n(0)] pub VKeyWitness);
+
+impl fmt::Display for DisplayVKeyWitness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "VKeyWitness {{ vkey: {}, signature: {} }}",
+            (self.0).vkey,
+            (self.0).signature
+        )
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/f54489071f4faa4b6209e1ba5288507c824cca50/libs/cardano-ledger-core/src/Cardano/Ledger/Address.hs
+// the bytes are not decoded
+pub type Addr = Bytes;
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/78b20b6301b2703aa1fe1806ae3c129846708a10/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/Scripts.hs#L497
+// not tested yet
+#[derive(Debug, Serialize)]
+pub enum PlutusPurpose {
+    Spending,   // 0
+    Minting,    // 1
+    Certifying, // 2
+    Rewarding,  // 3
+}
+
+impl fmt::Display for PlutusPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use PlutusPurpose::*;
+
+        match self {
+            Spending => write!(f, "Spending"),
+            Minting => write!(f, "Minting"),
+            Certifying => write!(f, "Certifying"),
+            Rewarding => write!(f, "Rewarding"),
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/78b20b6301b2703aa1fe1806ae3c129846708a10/libs/cardano-ledger-core/src/Cardano/Ledger/BaseTypes.hs#L779
+#[derive(Debug, Decode, Encode, Serialize)]
+pub enum Network {
+    #[n(0)]
+    Mainnet,
+    #[n(1)]
+    Testnet,
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Network::*;
+
+        match self {
+            Mainnet => write!(f, "Mainnet"),
+            Testnet => write!(f, "Testnet"),
+        }
+    }
+}
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/alonzo/impl/src/Cardano/Ledger/Alonzo/TxBody/Internal.hs#L162
+// not tested yet
+#[derive(Debug, Decode, Encode)]
+pub struct ScriptIntegrityHash(#[n(0)] Hash<32>);
+
+impl fmt::Display for ScriptIntegrityHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ScriptIntegrityHash({:?})", self.0)
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/allegra/impl/src/Cardano/Ledger/Allegra/Scripts.hs#L109
+#[derive(Debug, Decode, Encode, Serialize)]
+
+pub struct ValidityInterval {
+    #[n(0)]
+    pub invalid_before: Option<SlotNo>, // SlotNo
+    #[n(1)]
+    pub invalid_hereafter: Option<SlotNo>, // SlotNo
+}
+
+impl fmt::Display for ValidityInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ValidityInterval {{ invalid_before: {}, invalid_hereafter: {} }}",
+            display_option(&self.invalid_before),
+            display_option(&self.invalid_hereafter)
+        )
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/libs/cardano-ledger-core/src/Cardano/Ledger/UTxO.hs#L83
+#[derive(Debug)]
+pub struct Utxo(pub Vec<(SerializableTxIn, SerializableTxOut)>);
+
+impl fmt::Display for Utxo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = self
+            .0
+            .iter()
+            .map(|(input, output)| format!("({},{})", input, output))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        write!(f, "UTxO (fromList [{}])", entries)
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[cbor(transparent)]
+pub struct SerializableTxIn(#[n(0)] pub TransactionInput);
+
+impl fmt::Display for SerializableTxIn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TxIn (TxId {{unTxId = SafeHash \"{}\"}}) (TxIx {{unTxIx = {}}})",
+            self.0.transaction_id, self.0.index
+        )
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[cbor(transparent)]
+pub struct SerializableTxOut(#[n(0)] pub TransactionOutput);
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/eras/babbage/impl/src/Cardano/Ledger/Babbage/TxOut.hs#L121
+// Inline datums and reference scripts are shown as their CBOR hex
+/// Shown like the ledger does, as the tuple of its fields, whichever of the
+/// (compact) layouts it was encoded in.
+impl fmt::Display for SerializableTxOut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            TransactionOutput::Legacy(output) => {
+                let (coin, assets) = match &output.amount {
+                    pallas_primitives::alonzo::Value::Coin(coin) => (*coin, vec![]),
+                    pallas_primitives::alonzo::Value::Multiasset(coin, assets) => (
+                        *coin,
+                        assets
+                            .iter()
+                            .map(|(policy, names)| {
+                                (*policy, names.iter().map(|(n, q)| (n, *q)).collect())
+                            })
+                            .collect(),
+                    ),
+                };
+                let datum = match &output.datum_hash {
+                    Some(hash) => format!("DatumHash (SafeHash \"{}\")", hash),
+                    None => "NoDatum".to_string(),
+                };
+
+                write!(
+                    f,
+                    "({},{},{},SNothing)",
+                    DisplayAddress(&output.address),
+                    display_mary_value(coin, &assets),
+                    datum
+                )
+            }
+            TransactionOutput::PostAlonzo(output) => {
+                let datum = match &output.datum_option {
+                    Some(DatumOption::Hash(hash)) => {
+                        format!("DatumHash (SafeHash \"{}\")", hash)
+                    }
+                    Some(DatumOption::Data(data)) => format!(
+                        "Datum {}",
+                        haskell_show_bytes(&minicbor::to_vec(&data.0).unwrap_or_default())
+                    ),
+                    None => "NoDatum".to_string(),
+                };
+                let script = match &output.script_ref {
+                    Some(script) => format!("SJust {}", display_script(&script.0)),
+                    None => "SNothing".to_string(),
+                };
+
+                write!(
+                    f,
+                    "({},{},{},{})",
+                    DisplayAddress(&output.address),
+                    DisplayValue(output.value.clone()),
+                    datum,
+                    script
+                )
+            }
+        }
+    }
+}
+
+/// Plutus scripts are shown by their language and hash.
+fn display_script(script: &ScriptRef) -> String {
+    match script {
+        PseudoScript::NativeScript(script) => format!(
+            "TimelockScript {}",
+            hex::encode(minicbor::to_vec(script).unwrap_or_default())
+        ),
+        PseudoScript::PlutusV1Script(script) => {
+            format!(
+                "PlutusScript PlutusV1 ScriptHash \"{}\"",
+                script.compute_hash()
+            )
+        }
+        PseudoScript::PlutusV2Script(script) => {
+            format!(
+                "PlutusScript PlutusV2 ScriptHash \"{}\"",
+                script.compute_hash()
+            )
+        }
+        PseudoScript::PlutusV3Script(script) => {
+            format!(
+                "PlutusScript PlutusV3 ScriptHash \"{}\"",
+                script.compute_hash()
+            )
+        }
+    }
+}
+
+// https://github.com/IntersectMBO/cardano-ledger/blob/aed1dc28b98c25ea73bc692e7e6c6d3a22381ff5/libs/cardano-ledger-core/src/Cardano/Ledger/Address.hs#L178
+pub struct DisplayAddress<'a>(pub &'a [u8]);
+
+impl fmt::Display for DisplayAddress<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match Address::from_bytes(self.0) {
+            Ok(Address::Byron(address)) => display_bootstrap_address(f, &address),
+            Ok(Address::Shelley(address)) => display_shelley_address(f, &address),
+            _ => write!(f, "{}", hex::encode(self.0)),
+        }
+    }
+}
+
+fn display_shelley_address(f: &mut fmt::Formatter, address: &ShelleyAddress) -> fmt::Result {
+    let network = match address.network() {
+        AddressNetwork::Mainnet => "Mainnet",
+        _ => "Testnet",
+    };
+    let payment = match address.payment() {
+        ShelleyPaymentPart::Key(hash) => {
+            format!("KeyHashObj (KeyHash {{unKeyHash = \"{}\"}})", hash)
+        }
+        ShelleyPaymentPart::Script(hash) => format!("ScriptHashObj (ScriptHash \"{}\")", hash),
+    };
+    let delegation = match address.delegation() {
+        ShelleyDelegationPart::Key(hash) => format!(
+            "(StakeRefBase (KeyHashObj (KeyHash {{unKeyHash = \"{}\"}})))",
+            hash
+        ),
+        ShelleyDelegationPart::Script(hash) => {
+            format!("(StakeRefBase (ScriptHashObj (ScriptHash \"{}\")))", hash)
+        }
+        ShelleyDelegationPart::Pointer(pointer) => format!(
+            "(StakeRefPtr (Ptr (SlotNo {}) (TxIx {}) (CertIx {})))",
+            pointer.slot(),
+            pointer.tx_idx(),
+            pointer.cert_idx()
+        ),
+        ShelleyDelegationPart::Null => "StakeRefNull".to_string(),
+    };
+
+    write!(f, "Addr {} ({}) {}", network, payment, delegation)
+}
+
+fn display_bootstrap_address(f: &mut fmt::Formatter, address: &ByronAddress) -> fmt::Result {
+    let Ok(payload) = address.decode() else {
+        return write!(f, "{}", hex::encode(address.to_vec()));
+    };
+
+    let mut derivation_path = "Nothing".to_string();
+    let mut network_magic = ByronNetworkMagic::NetworkMainOrStage;
+
+    // Both attributes are CBOR wrapped in a byte string
+    for attribute in payload.attributes.iter() {
+        match attribute {
+            AddrAttrProperty::DerivationPath(bytes) => {
+                if let Ok(path) = minicbor::decode::<Bytes>(bytes) {
+                    derivation_path = format!(
+                        "Just (HDAddressPayload {{getHDAddressPayload = {}}})",
+                        haskell_show_bytes(&path)
+                    );
+                }
+            }
+            AddrAttrProperty::NetworkTag(bytes) => {
+                if let Ok(magic) = minicbor::decode::<u32>(bytes) {
+                    network_magic = ByronNetworkMagic::NetworkTestnet(magic);
+                }
+            }
+            AddrAttrProperty::AddrDistr(_) => {}
+        }
+    }
+
+    let addr_type = match payload.addrtype {
+        AddrType::Redeem => "ATRedeem",
+        _ => "ATVerKey",
+    };
+
+    write!(
+        f,
+        "AddrBootstrap (BootstrapAddress (Address {{addrRoot = {}, addrAttributes = Attributes {{ data_ = AddrAttributes {{aaVKDerivationPath = {}, aaNetworkMagic = {}}} }}, addrType = {}}}))",
+        payload.root, derivation_path, network_magic, addr_type
+    )
+}
+
+// https://github.com/IntersectMBO/ouroboros-consensus/blob/e86b921443bd6e8ea25e7190eb7cb5788e28f4cc/ouroboros-consensus/src/ouroboros-consensus/Ouroboros/Consensus/HardFork/Combinator/AcrossEras.hs#L208
+#[derive(Debug, Serialize)]
+pub struct EraMismatch {
+    ledger: String, //  Name of the era of the ledger ("Byron" or "Shelley").
+    other: String,  // Era of the block, header, transaction, or query.
+}
+
+/*
+** cardano-submit-api types
+** These types are used to mimick cardano-submit-api error responses.
+*/
+
+// https://github.com/IntersectMBO/cardano-node/blob/9dbf0b141e67ec2dfd677c77c63b1673cf9c5f3e/cardano-submit-api/src/Cardano/TxSubmit/Types.hs#L54
+#[derive(Debug, Serialize)]
+#[serde(tag = "tag", content = "contents")]
+pub enum TxSubmitFail {
+    TxSubmitDecodeHex,
+    TxSubmitEmpty,
+    TxSubmitDecodeFail(DecoderError),
+    TxSubmitBadTx(String),
+    TxSubmitFail(TxCmdError),
+}
+
+// https://github.com/IntersectMBO/cardano-node/blob/9dbf0b141e67ec2dfd677c77c63b1673cf9c5f3e/cardano-submit-api/src/Cardano/TxSubmit/Types.hs#L92
+#[derive(Debug, Serialize)]
+#[serde(tag = "tag", content = "contents")]
+pub enum TxCmdError {
+    SocketEnvError(String),
+    TxReadError(Vec<DecoderError>),
+    TxCmdTxSubmitValidationError(TxValidationErrorInCardanoMode),
+}
+
+// https://github.com/IntersectMBO/cardano-base/blob/391a2c5cfd30d2234097e000dbd8d9db21ef94d7/cardano-binary/src/Cardano/Binary/FromCBOR.hs#L90
+// The first field is always the name of what was being decoded.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "tag", content = "contents")]
+pub enum DecoderError {
+    DecoderErrorCanonicityViolation(String),
+    DecoderErrorCustom(String, String),
+    DecoderErrorDeserialiseFailure(String, DeserialiseFailure),
+    DecoderErrorEmptyList(String),
+    DecoderErrorLeftover(String, Bytes),
+    DecoderErrorSizeMismatch(String, i64, i64),
+    DecoderErrorUnknownTag(String, u8),
+    DecoderErrorVoid,
+}
+
+// https://github.com/well-typed/cborg/blob/4bdc818a1f0b35f38bc118a87944630043b58384/cborg/src/Codec/CBOR/Read.hs#L100
+/// Byte offset of the failure, and what went wrong there.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeserialiseFailure(pub u64, pub String);
+
+// https://github.com/IntersectMBO/cardano-api/blob/d7c62a04ebf18d194a6ea70e6765eb7691d57668/cardano-api/internal/Cardano/Api/InMode.hs#L259
+#[derive(Debug, Serialize)]
+#[serde(tag = "tag", content = "contents")]
+pub enum TxValidationErrorInCardanoMode {
+    TxValidationErrorInCardanoMode(TxValidationError),
+    EraMismatch(EraMismatch),
+}
+
+/// Mimicks the data structure of the error response from the cardano-submit-api
+impl From<TxValidationError> for TxSubmitFail {
+    fn from(error: TxValidationError) -> Self {
+        TxSubmitFail::TxSubmitFail(TxCmdError::TxCmdTxSubmitValidationError(
+            TxValidationErrorInCardanoMode::TxValidationErrorInCardanoMode(error),
+        ))
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[cbor(transparent)]
+pub struct DisplayExUnits(#[n(0)] pub ExUnits);
+
+impl fmt::Display for DisplayExUnits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ExUnits {{ mem: {}, steps: {} }}",
+            self.0.mem, self.0.steps
+        )
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[cbor(transparent)]
+pub struct DisplayValue(#[n(0)] pub Value);
+
+impl fmt::Display for DisplayValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (coin, assets) = match &self.0 {
+            Value::Coin(coin) => (*coin, vec![]),
+            Value::Multiasset(coin, assets) => (
+                *coin,
+                assets
+                    .iter()
+                    .map(|(policy, names)| {
+                        (*policy, names.iter().map(|(n, q)| (n, q.into())).collect())
+                    })
+                    .collect(),
+            ),
+        };
+
+        write!(f, "{}", display_mary_value(coin, &assets))
+    }
+}
+
+/*
+**Helper functions for Display'ing the types.
+*/
+fn display_tuple<T: Display, U: Display>(t: &(T, U)) -> String {
+    format!("({} {})", t.0, t.1)
+}
+
+fn display_tuple_vec<T: Display, U: Display>(vec: &[(T, U)]) -> String {
+    vec.iter()
+        .map(|x| display_tuple(x))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn display_vec<T: Display>(vec: &[T]) -> String {
+    vec.iter()
+        .map(|x| format!("{}", x))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Mimicks Haskell's `show` of a `ByteString`, i.e. a Latin-1 string literal.
+fn haskell_show_bytes(bytes: &[u8]) -> String {
+    const CONTROL: [&str; 32] = [
+        "NUL", "SOH", "STX", "ETX", "EOT", "ENQ", "ACK", "a", "b", "t", "n", "v", "f", "r", "SO",
+        "SI", "DLE", "DC1", "DC2", "DC3", "DC4", "NAK", "SYN", "ETB", "CAN", "EM", "SUB", "ESC",
+        "FS", "GS", "RS", "US",
+    ];
+
+    let mut shown = String::from("\"");
+    let mut previous: Option<u8> = None;
+
+    for &byte in bytes {
+        // `\&` separates escapes from characters that would otherwise extend them
+        match previous {
+            Some(128..) if byte.is_ascii_digit() => shown.push_str("\\&"),
+            Some(14) if byte == b'H' => shown.push_str("\\&"),
+            _ => {}
+        }
+
+        match byte {
+            b'"' => shown.push_str("\\\""),
+            b'\\' => shown.push_str("\\\\"),
+            0..=31 => {
+                shown.push('\\');
+                shown.push_str(CONTROL.get(byte as usize).copied().unwrap_or_default());
+            }
+            32..=126 => shown.push(byte as char),
+            127 => shown.push_str("\\DEL"),
+            _ => shown.push_str(&format!("\\{}", byte)),
+        }
+
+        previous = Some(byte);
+    }
+
+    shown.push('"');
+    shown
+}
+
+fn display_mary_value(coin: u64, assets: &[(Hash<28>, Vec<(&Bytes, u64)>)]) -> String {
+    let assets = assets
+        .iter()
+        .map(|(policy, names)| {
+            let names = names
+                .iter()
+                .map(|(name, quantity)| format!("(\"{}\",{})", name, quantity))
+                .collect::<Vec<String>>()
+                .join(",");
+
+            format!(
+                "(PolicyID {{policyID = ScriptHash \"{}\"}},fromList [{}])",
+                policy, names
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+
+    format!(
+        "MaryValue (Coin {}) (MultiAsset (fromList [{}]))",
+        coin, assets
+    )
+}
+
+fn display_outputs_with_coin(outputs: &[(SerializableTxOut, Coin)]) -> String {
+    outputs
+        .iter()
+        .map(|(output, coin)| format!("({},Coin {})", output, coin))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Haskell's list, e.g. `[a,b,c]` without the brackets.
+fn display_list<T: Display>(vec: &[T]) -> String {
+    vec.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Haskell's `NonEmpty`, e.g. `a :| [b,c]`.
+fn display_non_empty<T: Display>(vec: &[T]) -> String {
+    match vec.split_first() {
+        Some((head, tail)) => format!("{} :| [{}]", head, display_list(tail)),
+        None => "[]".to_string(),
+    }
+}
+
+fn display_strict_maybe_coin(coin: &Option<Coin>) -> String {
+    match coin {
+        Some(coin) => format!("(SJust (Coin {}))", coin),
+        None => "SNothing".to_string(),
+    }
+}
+
+fn display_strict_maybe_anchor(anchor: &Option<Anchor>) -> String {
+    match anchor {
+        Some(anchor) => format!(
+            "(SJust (Anchor {{anchorUrl = Url {{urlToText = {:?}}}, anchorDataHash = SafeHash \"{}\"}}))",
+            anchor.url, anchor.content_hash
+        ),
+        None => "SNothing".to_string(),
+    }
+}
+
+/// Haskell's `Ratio` is always shown reduced.
+fn display_ratio(ratio: &RationalNumber) -> String {
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    let divisor = gcd(ratio.numerator, ratio.denominator).max(1);
+
+    format!(
+        "{} % {}",
+        ratio.numerator / divisor,
+        ratio.denominator / divisor
+    )
+}
+
+fn display_relay(relay: &Relay) -> String {
+    let port = |port: &Nullable<u32>| match port {
+        Nullable::Some(port) => format!("(SJust (Port {{portToWord16 = {}}}))", port),
+        _ => "SNothing".to_string(),
+    };
+
+    match relay {
+        Relay::SingleHostAddr(p, ipv4, ipv6) => {
+            let ipv4 = match ipv4 {
+                Nullable::Some(ip) => match <[u8; 4]>::try_from(ip.as_slice()) {
+                    Ok(octets) => format!("(SJust {})", Ipv4Addr::from(octets)),
+                    Err(_) => "SNothing".to_string(),
+                },
+                _ => "SNothing".to_string(),
+            };
+            // Encoded as four little-endian 32-bit words
+            let ipv6 = match ipv6 {
+                Nullable::Some(ip) => match <[u8; 16]>::try_from(ip.as_slice()) {
+                    Ok(mut octets) => {
+                        octets.chunks_mut(4).for_each(|word| word.reverse());
+                        format!("(SJust {})", Ipv6Addr::from(octets))
+                    }
+                    Err(_) => "SNothing".to_string(),
+                },
+                _ => "SNothing".to_string(),
+            };
+
+            format!("SingleHostAddr {} {} {}", port(p), ipv4, ipv6)
+        }
+        Relay::SingleHostName(p, dns) => format!(
+            "SingleHostName {} (DnsName {{dnsToText = {:?}}})",
+            port(p),
+            dns
+        ),
+        Relay::MultiHostName(dns) => format!("MultiHostName (DnsName {{dnsToText = {:?}}})", dns),
+    }
+}
+
+fn display_option<T: Display>(opt: &Option<T>) -> String {
+    match opt {
+        Some(x) => format!("{}", x),
+        None => "None".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pallas::ledger::addresses::byron::{AddrAttrs, AddressPayload};
+    use pallas_codec::minicbor::bytes::ByteVec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_decode_delegatee() {
+        let pool = "aa".repeat(28);
+        let drep = "bb".repeat(28);
+        let decode = |cert_tag: u16, hex: String| {
+            let bytes = hex::decode(hex).unwrap();
+            crate::codec::decode_delegatee(
+                &mut pallas_codec::minicbor::Decoder::new(&bytes),
+                cert_tag,
+            )
+            .unwrap()
+            .to_string()
+        };
+
+        assert_eq!(
+            decode(2, format!("581c{}", pool)),
+            format!("DelegStake (KeyHash {{unKeyHash = \"{}\"}})", pool)
+        );
+        assert_eq!(
+            decode(9, format!("8200581c{}", drep)),
+            format!(
+                "DelegVote (DRepCredential (KeyHashObj (KeyHash {{unKeyHash = \"{}\"}})))",
+                drep
+            )
+        );
+        assert_eq!(
+            decode(12, "8102".to_string()),
+            "DelegVote DRepAlwaysAbstain".to_string()
+        );
+        assert_eq!(
+            decode(10, format!("581c{}8103", pool)),
+            format!(
+                "DelegStakeVote (KeyHash {{unKeyHash = \"{}\"}}) DRepAlwaysNoConfidence",
+                pool
+            )
+        );
+        assert_eq!(
+            decode(13, format!("581c{}8201581c{}", pool, drep)),
+            format!(
+                "DelegStakeVote (KeyHash {{unKeyHash = \"{}\"}}) (DRepCredential (ScriptHashObj (ScriptHash \"{}\")))",
+                pool, drep
+            )
+        );
+    }
+
+    fn decode_cert(hex: &str) -> String {
+        let bytes = hex::decode(hex.replace(' ', "")).unwrap();
+        pallas_codec::minicbor::decode::<ConwayTxCert>(&bytes)
+            .unwrap()
+            .to_string()
+    }
+
+    const STAKE: &str = "11111111111111111111111111111111111111111111111111111111";
+    const POOL: &str = "22222222222222222222222222222222222222222222222222222222";
+    const DREP: &str = "33333333333333333333333333333333333333333333333333333333";
+
+    #[test]
+    fn test_decode_deleg_certs() {
+        let stake = format!("KeyHashObj (KeyHash {{unKeyHash = \"{}\"}})", STAKE);
+
+        assert_eq!(
+            decode_cert(&format!("82 00 8200581c{}", STAKE)),
+            format!("ConwayTxCertDeleg (ConwayRegCert ({}) SNothing)", stake)
+        );
+        assert_eq!(
+            decode_cert(&format!("83 07 8200581c{} 1a001e8480", STAKE)),
+            format!(
+                "ConwayTxCertDeleg (ConwayRegCert ({}) (SJust (Coin 2000000)))",
+                stake
+            )
+        );
+        assert_eq!(
+            decode_cert(&format!("83 08 8200581c{} 1a001e8480", STAKE)),
+            format!(
+                "ConwayTxCertDeleg (ConwayUnRegCert ({}) (SJust (Coin 2000000)))",
+                stake
+            )
+        );
+        assert_eq!(
+            decode_cert(&format!("83 02 8200581c{} 581c{}", STAKE, POOL)),
+            format!(
+                "ConwayTxCertDeleg (ConwayDelegCert ({}) (DelegStake (KeyHash {{unKeyHash = \"{}\"}})))",
+                stake, POOL
+            )
+        );
+        assert_eq!(
+            decode_cert(&format!(
+                "85 0d 8200581c{} 581c{} 8102 1a001e8480",
+                STAKE, POOL
+            )),
+            format!(
+                "ConwayTxCertDeleg (ConwayRegDelegCert ({}) (DelegStakeVote (KeyHash {{unKeyHash = \"{}\"}}) DRepAlwaysAbstain) (Coin 2000000))",
+                stake, POOL
+            )
+        );
+    }
+
+    #[test]
+    fn test_decode_pool_certs() {
+        assert_eq!(
+            decode_cert(&format!("83 04 581c{} 05", POOL)),
+            format!(
+                "ConwayTxCertPool (RetirePool (KeyHash {{unKeyHash = \"{}\"}}) (EpochNo 5))",
+                POOL
+            )
+        );
+
+        let vrf = "44".repeat(32);
+        let metadata_hash = "61".repeat(32);
+        assert_eq!(
+            decode_cert(&format!(
+                concat!(
+                    "8a 03 581c{} 5820{} 1a000f4240 1a1443fd00 d81e8202 1828 581de0{} d9010281581c{} ",
+                    "83 8400190bb94401020304f6 8301f668 72656c61792e696f 8202 67 706f6f6c2e696f ",
+                    "82 6c 68747470733a2f2f782e696f 5820{}"
+                ),
+                POOL, vrf, STAKE, STAKE, metadata_hash
+            )),
+            format!(
+                concat!(
+                    "ConwayTxCertPool (RegPool (PoolParams {{ppId = KeyHash {{unKeyHash = \"{}\"}}, ",
+                    "ppVrf = VRFVerKeyHash {{unVRFVerKeyHash = \"{}\"}}, ppPledge = Coin 1000000, ppCost = Coin 340000000, ",
+                    "ppMargin = 1 % 20, ",
+                    "ppRewardAccount = RewardAccount {{raNetwork = Testnet, raCredential = KeyHashObj (KeyHash {{unKeyHash = \"{}\"}})}}, ",
+                    "ppOwners = fromList [KeyHash {{unKeyHash = \"{}\"}}], ",
+                    "ppRelays = StrictSeq {{fromStrict = fromList [",
+                    "SingleHostAddr (SJust (Port {{portToWord16 = 3001}})) (SJust 1.2.3.4) SNothing,",
+                    "SingleHostName SNothing (DnsName {{dnsToText = \"relay.io\"}}),",
+                    "MultiHostName (DnsName {{dnsToText = \"pool.io\"}})]}}, ",
+                    "ppMetadata = SJust (PoolMetadata {{pmUrl = Url {{urlToText = \"https://x.io\"}}, pmHash = \"{}\"}})}}))"
+                ),
+                POOL,
+                vrf,
+                STAKE,
+                STAKE,
+                "a".repeat(32)
+            )
+        );
+    }
+
+    #[test]
+    fn test_decode_gov_certs() {
+        let drep = format!("KeyHashObj (KeyHash {{unKeyHash = \"{}\"}})", DREP);
+        let script = format!("ScriptHashObj (ScriptHash \"{}\")", DREP);
+
+        assert_eq!(
+            decode_cert(&format!(
+                "84 10 8200581c{} 1a1dcd6500 82 6c 68747470733a2f2f642e696f 5820{}",
+                DREP,
+                "dd".repeat(32)
+            )),
+            format!(
+                "ConwayTxCertGov (ConwayRegDRep ({}) (Coin 500000000) (SJust (Anchor {{anchorUrl = Url {{urlToText = \"https://d.io\"}}, anchorDataHash = SafeHash \"{}\"}})))",
+                drep,
+                "dd".repeat(32)
+            )
+        );
+        assert_eq!(
+            decode_cert(&format!("83 11 8201581c{} 1a1dcd6500", DREP)),
+            format!(
+                "ConwayTxCertGov (ConwayUnRegDRep ({}) (Coin 500000000))",
+                script
+            )
+        );
+        assert_eq!(
+            decode_cert(&format!("83 12 8200581c{} f6", DREP)),
+            format!("ConwayTxCertGov (ConwayUpdateDRep ({}) SNothing)", drep)
+        );
+        assert_eq!(
+            decode_cert(&format!("83 0e 8200581c{} 8201581c{}", DREP, DREP)),
+            format!(
+                "ConwayTxCertGov (ConwayAuthCommitteeHotKey ({}) ({}))",
+                drep, script
+            )
+        );
+        assert_eq!(
+            decode_cert(&format!("83 0f 8200581c{} f6", DREP)),
+            format!(
+                "ConwayTxCertGov (ConwayResignCommitteeColdKey ({}) SNothing)",
+                drep
+            )
+        );
+    }
+
+    fn decode_collect_error(hex: &str) -> String {
+        let bytes = hex::decode(hex.replace(' ', "")).unwrap();
+        pallas_codec::minicbor::decode::<CollectError<ConwayPlutusPurpose, ConwayContextError>>(
+            &bytes,
+        )
+        .unwrap()
+        .to_string()
+    }
+
+    const TX_ID: &str = "5555555555555555555555555555555555555555555555555555555555555555";
+
+    #[test]
+    fn test_decode_collect_error() {
+        let tx_in = format!(
+            "TxIn (TxId {{unTxId = SafeHash \"{}\"}}) (TxIx {{unTxIx = 2}})",
+            TX_ID
+        );
+
+        assert_eq!(
+            decode_collect_error(&format!("82 01 581c{}", POOL)),
+            format!("NoWitness (ScriptHash \"{}\")", POOL)
+        );
+        assert_eq!(
+            decode_collect_error("82 02 02"),
+            "NoCostModel PlutusV3".to_string()
+        );
+        assert_eq!(
+            decode_collect_error(&format!("82 03 8208 8201 825820{}02", TX_ID)),
+            format!(
+                "BadTranslation (BabbageContextError (AlonzoContextError (TranslationLogicMissingInput ({}))))",
+                tx_in
+            )
+        );
+        assert_eq!(
+            decode_collect_error(&format!("82 03 8208 8200 8200 825820{}02", TX_ID)),
+            format!(
+                "BadTranslation (BabbageContextError (ByronTxOutInContext (TxOutFromInput ({}))))",
+                tx_in
+            )
+        );
+        assert_eq!(
+            decode_collect_error("82 03 8208 8204 8201 03"),
+            "BadTranslation (BabbageContextError (InlineDatumsNotSupported (TxOutFromOutput (TxIx {unTxIx = 3}))))".to_string()
+        );
+        assert_eq!(
+            decode_collect_error("82 03 8208 8207 6474657374"),
+            "BadTranslation (BabbageContextError (AlonzoContextError (TimeTranslationPastHorizon \"test\")))".to_string()
+        );
+        assert_eq!(
+            decode_collect_error(&format!("82 03 8208 8206 d9010281 825820{}02", TX_ID)),
+            format!(
+                "BadTranslation (BabbageContextError (ReferenceInputsNotSupported (fromList [{}])))",
+                tx_in
+            )
+        );
+        assert_eq!(
+            decode_collect_error(&format!("82 03 820a 8304 581c{} 05", POOL)),
+            format!(
+                "BadTranslation (CertificateNotSupported (ConwayTxCertPool (RetirePool (KeyHash {{unKeyHash = \"{}\"}}) (EpochNo 5))))",
+                POOL
+            )
+        );
+        assert_eq!(
+            decode_collect_error("82 03 820f 1a000f4240"),
+            "BadTranslation (TreasuryDonationFieldNotSupported (Coin 1000000))".to_string()
+        );
+        assert_eq!(
+            decode_collect_error(&format!(
+                "82 03 8210 82 825820{}02 825820{}02",
+                TX_ID, TX_ID
+            )),
+            format!(
+                "BadTranslation (ReferenceInputsNotDisjointFromInputs ({} :| [{}]))",
+                tx_in, tx_in
+            )
+        );
+    }
+
+    #[test]
+    fn test_redeemer_pointer_points_to_nothing() {
+        use pallas_codec::minicbor::Encoder;
+
+        let tags = [
+            "Spending",
+            "Minting",
+            "Certifying",
+            "Rewarding",
+            "Voting",
+            "Proposing",
+        ];
+
+        for (tag, name) in tags.iter().enumerate() {
+            for index in [0, 1, 23, 24, 255, 256, 65535, 65536, u32::MAX] {
+                let mut e = Encoder::new(Vec::new());
+                e.array(2).unwrap().u16(3).unwrap();
+                e.array(2).unwrap().u16(8).unwrap();
+                e.array(2).unwrap().u16(2).unwrap();
+                e.array(2)
+                    .unwrap()
+                    .u16(tag as u16)
+                    .unwrap()
+                    .u32(index)
+                    .unwrap();
+
+                assert_eq!(
+                    decode_collect_error(&hex::encode(e.into_writer())),
+                    format!(
+                        "BadTranslation (BabbageContextError (RedeemerPointerPointsToNothing (Conway{} (AsIx {{unAsIx = {}}}))))",
+                        name, index
+                    )
+                );
+
+                // Babbage only knows the first four
+                if tag < 4 {
+                    let mut e = Encoder::new(Vec::new());
+                    e.array(2).unwrap().u16(3).unwrap();
+                    e.array(2).unwrap().u16(2).unwrap();
+                    e.array(2)
+                        .unwrap()
+                        .u16(tag as u16)
+                        .unwrap()
+                        .u32(index)
+                        .unwrap();
+                    let bytes = e.into_writer();
+
+                    assert_eq!(
+                        pallas_codec::minicbor::decode::<CollectError>(&bytes)
+                            .unwrap()
+                            .to_string(),
+                        format!(
+                            "BadTranslation (RedeemerPointerPointsToNothing (Alonzo{} (AsIx {{unAsIx = {}}})))",
+                            name, index
+                        )
+                    );
+                }
+            }
+        }
+    }
+
+    #[rstest::rstest]
+    // TxOutCompact: [address, coin]
+    #[case("82 581d60{ADDR} 1a000f4240", "NoDatum,SNothing")]
+    // TxOutCompactDH: [address, value, datum hash]
+    #[case(
+        "83 581d60{ADDR} 1a000f4240 5820{HASH}",
+        "DatumHash (SafeHash \"{HASH}\"),SNothing"
+    )]
+    // A map with only the address and the value
+    #[case("a2 00 581d60{ADDR} 01 1a000f4240", "NoDatum,SNothing")]
+    // TxOutCompactDatum: an inline datum (the integer 42)
+    #[case(
+        "a3 00 581d60{ADDR} 01 1a000f4240 02 8201 d818 42 182a",
+        "Datum \"\\CAN*\",SNothing"
+    )]
+    // And with a reference script
+    #[case(
+        "a4 00 581d60{ADDR} 01 1a000f4240 02 8200 5820{HASH} 03 d818 46 82 02 43 010203",
+        "DatumHash (SafeHash \"{HASH}\"),SJust PlutusScript PlutusV2 ScriptHash \"{SCRIPT}\""
+    )]
+    // An indefinite-length map
+    #[case("bf 00 581d60{ADDR} 01 1a000f4240 ff", "NoDatum,SNothing")]
+    fn test_display_tx_out(#[case] cbor_hex: &str, #[case] expected_rest: &str) {
+        let addr = "11".repeat(28);
+        let hash = "66".repeat(32);
+        let script = pallas_primitives::PlutusScript::<2>(vec![1, 2, 3].into()).compute_hash();
+        let fill = |s: &str| {
+            s.replace("{ADDR}", &addr)
+                .replace("{HASH}", &hash)
+                .replace("{SCRIPT}", &script.to_string())
+        };
+
+        let bytes = hex::decode(fill(cbor_hex).replace(' ', "")).unwrap();
+        let output: SerializableTxOut = pallas_codec::minicbor::decode(&bytes).unwrap();
+
+        assert_eq!(
+            output.to_string(),
+            format!(
+                "(Addr Testnet (KeyHashObj (KeyHash {{unKeyHash = \"{}\"}})) StakeRefNull,MaryValue (Coin 1000000) (MultiAsset (fromList [])),{})",
+                addr,
+                fill(expected_rest)
+            )
+        );
+    }
+
+    #[test]
+    fn test_haskell_show_bytes() {
+        assert_eq!(
+            haskell_show_bytes(&[202, 14, b'H', 200, b'1', b'"', 127, 7]),
+            r#""\202\SO\&H\200\&1\"\DEL\a""#
+        );
+    }
+
+    #[test]
+    fn test_display_bootstrap_address() {
+        let attributes: AddrAttrs = vec![
+            AddrAttrProperty::DerivationPath(ByteVec::from(
+                minicbor::to_vec(Bytes::from(vec![202, 14, b'H'])).unwrap(),
+            )),
+            AddrAttrProperty::NetworkTag(ByteVec::from(minicbor::to_vec(1097911063u32).unwrap())),
+        ]
+        .into();
+        let address = ByronAddress::from_decoded(AddressPayload {
+            root: Hash::new([0x4b; 28]),
+            attributes,
+            addrtype: AddrType::PubKey,
+        });
+
+        assert_eq!(
+            DisplayAddress(&address.to_vec()).to_string(),
+            format!(
+                "AddrBootstrap (BootstrapAddress (Address {{addrRoot = {}, addrAttributes = Attributes {{ data_ = AddrAttributes {{aaVKDerivationPath = Just (HDAddressPayload {{getHDAddressPayload = \"\\202\\SO\\&H\"}}), aaNetworkMagic = NetworkTestnet 1097911063}} }}, addrType = ATVerKey}}))",
+                "4b".repeat(28)
+            )
+        );
+
+        let mainnet = ByronAddress::from_decoded(AddressPayload {
+            root: Hash::new([0x4b; 28]),
+            attributes: vec![].into(),
+            addrtype: AddrType::Redeem,
+        });
+
+        assert!(DisplayAddress(&mainnet.to_vec())
+            .to_string()
+            .ends_with("aaVKDerivationPath = Nothing, aaNetworkMagic = NetworkMainOrStage} }, addrType = ATRedeem}))"));
+    }
+}
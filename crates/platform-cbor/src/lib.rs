@@ -0,0 +1,100 @@
+//! Native decoding of the reasons cardano-node rejects transactions with,
+//! into the JSON cardano-submit-api responds with.
+//!
+//! It doesn’t depend on the platform server, so wallets and indexers can
+//! reuse it through [`decode_apply_tx_err`], e.g.:
+//!
+//! ```
+//! use platform_cbor::decode_apply_tx_err;
+//!
+//! let reason = hex::decode("818206828201820083051a000151351a00074b8582076162").unwrap();
+//! let json = serde_json::to_value(decode_apply_tx_err(&reason).unwrap()).unwrap();
+//!
+//! assert_eq!(json["tag"], "TxSubmitFail");
+//! ```
+
+// Decoding runs on whatever bytes the node sends, so a malformed reason must
+// end up a decode error, never a panic taking the request down with it.
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::indexing_slicing
+    )
+)]
+
+pub mod codec;
+pub mod coverage;
+pub mod haskell_types;
+pub mod slot;
+
+use haskell_types::{TxSubmitFail, TxValidationError};
+use pallas_codec::minicbor;
+
+pub use minicbor::decode::Error as DecodeError;
+
+/// Decodes the reason of a `MsgRejectTx` of the local tx-submission protocol,
+/// i.e. the message without its `[2, …]` envelope, into what
+/// cardano-submit-api would respond with.
+///
+/// Failing to decode a valid reason means a type is missing or
+/// mis-implemented here. The platform then falls back to `testgen-hs`.
+pub fn decode_apply_tx_err(bytes: &[u8]) -> Result<TxSubmitFail, DecodeError> {
+    minicbor::decode::<TxValidationError>(bytes).map(TxSubmitFail::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[test]
+    fn test_decode_apply_tx_err() {
+        let reason = hex::decode(
+            "818206828201820083061b00000002362a77301b0000000253b9c11d8201820083051a00028bfd18ad",
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(decode_apply_tx_err(&reason).unwrap()).unwrap();
+        let error = &json["contents"]["contents"]["contents"];
+
+        assert_eq!(error["kind"], "ShelleyTxValidationError");
+        assert_eq!(error["era"], "ShelleyBasedEraConway");
+        assert_eq!(error["error"].as_array().unwrap().len(), 2);
+
+        assert!(decode_apply_tx_err(&reason[..10]).is_err());
+    }
+
+    /// Every truncation and single-byte corruption of a valid reason decodes
+    /// to an error or to something displayable, without panicking.
+    #[rstest]
+    #[case("818206828201820083061b00000002362a77301b0000000253b9c11d8201820083051a00028bfd18ad")]
+    #[case(concat!(
+        "818206818201820082 0d",
+        "a1825820222222222222222222222222222222222222222222222222222222222222222200",
+        "a200581d6011111111111111111111111111111111111111111111111111111111011a000f4240",
+    ))]
+    #[case("8182048282008200820481038201820058 1c00000000000000000000000000000000000000000000000000000001")]
+    #[case("81820082018200 8200d8185824825820 0f00000000000000000000000000000000000000000000000000000000000001 00")]
+    fn test_malformed_reasons_dont_panic(#[case] cbor_hex: &str) {
+        let reason = hex::decode(cbor_hex.replace(' ', "")).unwrap();
+
+        let truncated = (0..reason.len()).map(|len| reason[..len].to_vec());
+        let corrupted = (0..reason.len()).flat_map(|i| {
+            [0x00, 0x01, 0x18, 0x40, 0x9f, 0xff].map(|byte| {
+                let mut reason = reason.clone();
+                reason[i] = byte;
+                reason
+            })
+        });
+
+        for reason in truncated.chain(corrupted) {
+            if let Ok(fail) = decode_apply_tx_err(&reason) {
+                serde_json::to_value(fail).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use pallas_codec::minicbor::{self, Decode, Encode};
+use pallas_traverse::wellknown::GenesisValues;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// https://github.com/IntersectMBO/cardano-base/blob/ad6b5b1e0d3ff1bd0ef3aa78da42ab40c8eb3b25/cardano-slotting/src/Cardano/Slotting/Slot.hs#L50
+/// An absolute slot number. Encoded and shown like a plain number, both in
+/// CBOR and in JSON.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Decode,
+    Encode,
+    Serialize,
+    Deserialize,
+)]
+#[cbor(transparent)]
+#[serde(transparent)]
+pub struct SlotNo(#[n(0)] pub u64);
+
+impl SlotNo {
+    pub fn checked_add(self, slots: u64) -> Option<Self> {
+        self.0.checked_add(slots).map(Self)
+    }
+
+    /// Slots between the two, in whichever order.
+    pub fn distance(self, other: Self) -> u64 {
+        self.0.abs_diff(other.0)
+    }
+
+    /// The epoch of the slot, and the slot within that epoch. Unlike
+    /// [`GenesisValues::absolute_slot_to_relative`], right in Byron too, whose
+    /// epoch length is in seconds rather than slots.
+    pub fn epoch(self, genesis: &GenesisValues) -> (u64, u64) {
+        let epoch_slots = |epoch_length: u32, slot_length: u32| {
+            u64::from(epoch_length / slot_length.max(1)).max(1)
+        };
+        let byron_epoch_slots = epoch_slots(genesis.byron_epoch_length, genesis.byron_slot_length);
+
+        let (era_slot, era_epoch, era_epoch_slots) = if self.0 < genesis.shelley_known_slot {
+            (self.0, 0, byron_epoch_slots)
+        } else {
+            (
+                self.0 - genesis.shelley_known_slot,
+                genesis.shelley_known_slot / byron_epoch_slots,
+                epoch_slots(genesis.shelley_epoch_length, genesis.shelley_slot_length),
+            )
+        };
+
+        (
+            era_epoch + era_slot / era_epoch_slots,
+            era_slot % era_epoch_slots,
+        )
+    }
+
+    /// UNIX time of the beginning of the slot. Like
+    /// [`GenesisValues::slot_to_wallclock`], but never panics on user input.
+    pub fn to_time(self, genesis: &GenesisValues) -> Option<u64> {
+        let (known_slot, known_time, slot_length) = if self.0 < genesis.shelley_known_slot {
+            (
+                genesis.byron_known_slot,
+                genesis.byron_known_time,
+                genesis.byron_slot_length,
+            )
+        } else {
+            (
+                genesis.shelley_known_slot,
+                genesis.shelley_known_time,
+                genesis.shelley_slot_length,
+            )
+        };
+
+        self.0
+            .checked_sub(known_slot)?
+            .checked_mul(slot_length.into())?
+            .checked_add(known_time)
+    }
+
+    /// The inverse of [`SlotNo::to_time`]: the slot containing the given UNIX
+    /// time.
+    pub fn from_time(genesis: &GenesisValues, time: u64) -> Option<Self> {
+        let (known_slot, known_time, slot_length) = if time < genesis.shelley_known_time {
+            (
+                genesis.byron_known_slot,
+                genesis.byron_known_time,
+                genesis.byron_slot_length,
+            )
+        } else {
+            (
+                genesis.shelley_known_slot,
+                genesis.shelley_known_time,
+                genesis.shelley_slot_length,
+            )
+        };
+
+        let elapsed = time.checked_sub(known_time)?;
+
+        known_slot
+            .checked_add(elapsed.checked_div(slot_length.into())?)
+            .map(Self)
+    }
+}
+
+impl From<u64> for SlotNo {
+    fn from(slot: u64) -> Self {
+        Self(slot)
+    }
+}
+
+impl From<SlotNo> for u64 {
+    fn from(slot: SlotNo) -> Self {
+        slot.0
+    }
+}
+
+impl fmt::Display for SlotNo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    // Byron
+    #[case(0, 1506203091)]
+    #[case(4492799, 1596059071)]
+    // Shelley
+    #[case(4492800, 1596059091)]
+    #[case(139000000, 1730566291)]
+    fn test_slot_time_conversion(#[case] slot: u64, #[case] time: u64) {
+        let genesis = GenesisValues::mainnet();
+
+        assert_eq!(SlotNo(slot).to_time(&genesis), Some(time));
+        assert_eq!(SlotNo::from_time(&genesis, time), Some(SlotNo(slot)));
+    }
+
+    #[test]
+    fn test_slot_time_conversion_out_of_range() {
+        let genesis = GenesisValues::mainnet();
+
+        assert_eq!(SlotNo::from_time(&genesis, 0), None);
+        assert_eq!(SlotNo(u64::MAX).to_time(&genesis), None);
+        // In the middle of a Byron slot
+        assert_eq!(
+            SlotNo::from_time(&genesis, 1506203091 + 25),
+            Some(SlotNo(1))
+        );
+    }
+
+    #[rstest]
+    // Byron
+    #[case(0, (0, 0))]
+    #[case(4492799, (207, 21599))]
+    // Shelley
+    #[case(4492800, (208, 0))]
+    #[case(139000000, (519, 155200))]
+    fn test_epoch(#[case] slot: u64, #[case] epoch: (u64, u64)) {
+        assert_eq!(SlotNo(slot).epoch(&GenesisValues::mainnet()), epoch);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(SlotNo(100).checked_add(20), Some(SlotNo(120)));
+        assert_eq!(SlotNo(u64::MAX).checked_add(1), None);
+        assert_eq!(SlotNo(100).distance(SlotNo(120)), 20);
+        assert_eq!(SlotNo(120).distance(SlotNo(100)), 20);
+    }
+
+    #[test]
+    fn test_encoding() {
+        let slot = SlotNo(139000000);
+
+        assert_eq!(
+            minicbor::to_vec(slot).unwrap(),
+            minicbor::to_vec(139000000u64).unwrap()
+        );
+        assert_eq!(
+            minicbor::decode::<SlotNo>(&[0x18, 0x64]).unwrap(),
+            SlotNo(100)
+        );
+        assert_eq!(serde_json::to_string(&slot).unwrap(), "139000000");
+        assert_eq!(slot.to_string(), "139000000");
+    }
+}
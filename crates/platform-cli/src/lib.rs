@@ -0,0 +1,1536 @@
+use clap::{arg, command, ArgAction, Parser, ValueEnum};
+use pallas::ledger::addresses::{Address, Network as AddressNetwork};
+use pallas_crypto::hash::Hasher;
+use platform_node::{genesis::genesis_values, node::chainsync::DEFAULT_WINDOW, AppError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt::{self, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::Level;
+
+pub use platform_node::config::{Network, NodeConfig};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None, args_override_self = true)]
+pub struct Args {
+    /// Print what the binary expects to find at runtime, and exit
+    #[arg(long, exclusive = true)]
+    print_runtime_deps: bool,
+
+    /// A TOML file setting flags by name, e.g. `network = "mainnet"`, below the environment
+    /// and the command line
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Directory of drop-in `*.toml` files setting flags by name, e.g.
+    /// `node-socket-path = "/run/cardano-node/node.socket"`, merged in lexicographic order.
+    /// They override `--config`, while the environment and the command line override them
+    #[arg(long)]
+    config_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Check the configuration, node and Icebreakers credentials, print a report, and exit 0 or 1
+    #[arg(long)]
+    dry_run: bool,
+
+    #[command(flatten)]
+    server: ServerArgs,
+
+    #[command(flatten)]
+    node: NodeArgs,
+
+    #[command(flatten)]
+    icebreakers: IcebreakersArgs,
+
+    #[command(flatten)]
+    limits: LimitsArgs,
+
+    #[command(flatten)]
+    observability: ObservabilityArgs,
+
+    #[command(flatten)]
+    cors: CorsArgs,
+
+    #[command(flatten)]
+    tls: TlsArgs,
+}
+
+#[derive(clap::Subcommand, Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(clap::Subcommand, Debug, Clone, Copy, PartialEq)]
+pub enum ConfigAction {
+    /// Print the effective configuration, once files, environment and flags are merged, and exit
+    Dump,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Server")]
+struct ServerArgs {
+    #[arg(long, default_value = "0.0.0.0")]
+    server_address: String,
+
+    #[arg(long, default_value = "3000")]
+    server_port: u16,
+
+    #[arg(long, default_value = "compact")]
+    mode: Mode,
+
+    /// URL to POST a JSON notification to whenever the node crosses an epoch boundary
+    #[arg(long)]
+    epoch_webhook_url: Option<String>,
+
+    /// Directory for the platform's on-disk state
+    /// [default: `$STATE_DIRECTORY` under systemd, or `$XDG_STATE_HOME/blockfrost-platform` if set]
+    #[arg(long, env = "STATE_DIRECTORY")]
+    data_dir: Option<PathBuf>,
+
+    /// Add CIP-14 fingerprints and decoded asset names wherever assets appear in responses
+    #[arg(long)]
+    enrich_responses: bool,
+
+    /// Blockfrost project id; read endpoints not served locally are proxied to Blockfrost with it
+    #[arg(long)]
+    blockfrost_project_id: Option<String>,
+
+    /// Directory to record sanitized request/response pairs to, for debugging integrations
+    #[arg(long)]
+    record_traffic: Option<PathBuf>,
+
+    /// Record transaction CBOR as is, instead of only its hash
+    #[arg(long, requires("record_traffic"))]
+    record_bodies: bool,
+
+    /// Serve only the query endpoints, never submitting transactions
+    #[arg(long)]
+    read_only: bool,
+
+    /// Bearer token required by the operator routes, `/metrics`, `/stats`, `/stats/slo` and
+    /// `/admin/storage`, which are public without it
+    #[arg(long)]
+    operator_token: Option<String>,
+
+    /// Verify CIP-30 signatures sent along with submissions, passing the stake address
+    /// that signed on to the submission hooks
+    #[arg(long)]
+    verify_signatures: bool,
+
+    /// Check the ledger rules that need no node first: inputs, fee, validity interval and
+    /// output networks, rejecting like the node would without submitting
+    #[arg(long)]
+    pre_validate: bool,
+
+    /// Hold submissions in the data directory while the node is unreachable, and submit
+    /// them in order once it’s back. They may then reach it long after clients expected
+    #[arg(long, requires("data_dir"))]
+    spool_offline: bool,
+
+    /// Follow accepted transactions through the node’s mempool for `/tx/{txid}/status`
+    /// and onto the chain for `/tx/{txid}/confirmations`
+    /// [default by mode: false in compact, true otherwise]
+    #[arg(long)]
+    track_mempool: Option<bool>,
+
+    /// Sync progress of the node, in percent, below which `/ready` answers 503
+    #[arg(long, default_value = "99.0", value_parser = parse_percentage)]
+    ready_min_sync_progress: f64,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Node")]
+struct NodeArgs {
+    #[arg(long, required = true)]
+    network: Network,
+
+    /// The network magic of `--network custom`
+    #[arg(long, required_if_eq("network", "custom"))]
+    network_magic: Option<u64>,
+
+    /// The `shelley-genesis.json` of `--network custom`, which slots are converted to time by
+    #[arg(long, required_if_eq("network", "custom"))]
+    shelley_genesis: Option<PathBuf>,
+
+    /// Not required with `--node-relay`, for running apart from any node
+    #[arg(long, required_unless_present("node_relay"))]
+    node_socket_path: Option<String>,
+
+    /// A relay’s `HOST:PORT`, to submit transactions to over node-to-node
+    /// TxSubmission whenever the local node socket can’t be reached
+    #[arg(long)]
+    node_relay: Option<String>,
+
+    /// Close pooled node connections unused for this long, in seconds, re-opening them on demand
+    #[arg(long, default_value = "60")]
+    node_idle_timeout_secs: u64,
+
+    /// Replace pooled node connections older than this, in seconds, even if they’re healthy
+    #[arg(long, default_value = "3600")]
+    node_max_connection_age_secs: u64,
+
+    /// Give up opening a node connection, including the handshake, after this long, in seconds
+    #[arg(long, env = "NODE_CONNECT_TIMEOUT_SECS", default_value = "10")]
+    node_connect_timeout_secs: u64,
+
+    /// Give up waiting for the node to accept or reject a transaction after this long, in seconds
+    #[arg(long, env = "NODE_SUBMIT_TIMEOUT_SECS", default_value = "30")]
+    node_submit_timeout_secs: u64,
+
+    /// Give up a local state query, e.g. for protocol parameters or pools, after this long, in seconds
+    #[arg(long, env = "NODE_STATEQUERY_TIMEOUT_SECS", default_value = "60")]
+    node_statequery_timeout_secs: u64,
+
+    /// Retry submissions failing on the way to the node, e.g. on a broken
+    /// connection or a timeout, up to this many times. Ledger rejections are never retried
+    #[arg(long, default_value = "0")]
+    node_submit_retries: u32,
+
+    /// Wait at least this long between retries of a submission, in milliseconds, backing off
+    /// with jitter
+    #[arg(long, default_value = "250")]
+    node_submit_retry_backoff_ms: u64,
+
+    /// Maximum number of pooled node connections [default by mode: 2 in compact, 5 in light, 10 in full]
+    #[arg(long)]
+    node_pool_size: Option<usize>,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Icebreakers")]
+struct IcebreakersArgs {
+    /// Whether to run in solitary mode, without registering with the Icebreakers API
+    #[arg(long)]
+    solitary: bool,
+
+    #[arg(
+        long,
+        required_unless_present("solitary"),
+        conflicts_with("solitary"),
+        requires("reward_address")
+    )]
+    secret: Option<String>,
+
+    /// A stake address of the --network: stake1… on mainnet, stake_test1… elsewhere
+    #[arg(
+        long,
+        required_unless_present("solitary"),
+        conflicts_with("solitary"),
+        requires("secret")
+    )]
+    reward_address: Option<String>,
+
+    /// Don't periodically send the Icebreakers API anonymized load reports: request counts,
+    /// success rate and sync progress
+    #[arg(long, conflicts_with("solitary"))]
+    no_telemetry: bool,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Limits")]
+struct LimitsArgs {
+    /// Size quota of every data directory component, in megabytes
+    #[arg(long, default_value = "512")]
+    data_dir_quota_mb: u64,
+
+    /// How long successful proxied Blockfrost responses are cached, in seconds
+    /// [default by mode: 60 in full, 20 otherwise]
+    #[arg(long)]
+    blockfrost_cache_secs: Option<u64>,
+
+    /// How many submission outcomes `/tx/{txid}/status` remembers
+    /// [default by mode: 1000 in compact, 10000 in light, 100000 in full]
+    #[arg(long)]
+    submission_statuses: Option<usize>,
+
+    /// How many of the most recent blocks the chain follower keeps for `/blocks/{hash}`
+    #[arg(long, default_value_t = DEFAULT_WINDOW)]
+    recent_blocks: usize,
+
+    /// How many asynchronous submissions may wait for a node connection
+    #[arg(long, default_value = "1000")]
+    submission_queue: usize,
+
+    /// How many submissions `--spool-offline` holds while the node is unreachable
+    #[arg(long, default_value = "100")]
+    spool_max_txs: usize,
+
+    /// Drop spooled submissions not submitted after this long, in seconds, or past their TTL
+    #[arg(long, default_value = "900")]
+    spool_max_age_secs: u64,
+
+    /// Largest transaction `/tx/submit` takes, in bytes of CBOR, answering 413 beyond. Above the
+    /// maxTxSize protocol parameter of the public networks, which the node enforces
+    #[arg(long, default_value = "65536")]
+    max_tx_size: usize,
+
+    /// Queries per second allowed from each client IP, expensive ones (e.g. `/pools`) counting
+    /// as 10, answering 429 beyond
+    #[arg(long, value_parser = parse_rate)]
+    rate_limit_query_per_ip: Option<f64>,
+
+    /// Queries per second allowed from all clients together
+    #[arg(long, value_parser = parse_rate)]
+    rate_limit_query_global: Option<f64>,
+
+    /// Transaction submissions per second allowed from each client IP
+    #[arg(long, value_parser = parse_rate)]
+    rate_limit_submit_per_ip: Option<f64>,
+
+    /// Transaction submissions per second allowed from all clients together
+    #[arg(long, value_parser = parse_rate)]
+    rate_limit_submit_global: Option<f64>,
+
+    /// Take the client IP from the last `X-Forwarded-For` entry, as appended by a reverse proxy in
+    /// front of us
+    #[arg(long)]
+    rate_limit_forwarded_for: bool,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Observability")]
+struct ObservabilityArgs {
+    #[arg(long, default_value = "info")]
+    log_level: LogLevel,
+
+    /// Serve Prometheus metrics at `/metrics`; `--metrics=false` turns them off
+    #[arg(
+        long,
+        action = ArgAction::Set,
+        num_args = 0..=1,
+        default_value = "true",
+        default_missing_value = "true"
+    )]
+    metrics: bool,
+
+    /// Fraction of requests (0.0 to 1.0) that get a tracing span
+    #[arg(long, default_value = "1.0", value_parser = parse_ratio)]
+    trace_sample_rate: f64,
+
+    /// Per-route sample rate override, e.g. `/tx/submit=0.01` (can be repeated)
+    #[arg(long, value_parser = parse_route_sample_rate)]
+    trace_sample_route: Vec<(String, f64)>,
+
+    /// Export the spans of sampled requests over OTLP/HTTP, e.g. to `http://localhost:4318/v1/traces`
+    #[arg(long)]
+    otlp_endpoint: Option<String>,
+
+    /// Availability objective of transaction submission, used for error budgets under `/stats/slo`
+    #[arg(long, default_value = "0.999", value_parser = parse_ratio)]
+    slo_target: f64,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "CORS")]
+struct CorsArgs {
+    /// Let browser pages from this origin, e.g. `https://app.example.com`, call the API, or
+    /// from any with `*` (can be repeated)
+    #[arg(long, value_parser = parse_origin)]
+    cors_allow_origin: Vec<String>,
+
+    /// How long browsers may cache the answer to a CORS preflight request, in seconds
+    #[arg(long, default_value = "3600")]
+    cors_max_age_secs: u64,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "TLS")]
+struct TlsArgs {
+    /// Serve HTTPS with this PEM certificate chain instead of plain HTTP
+    #[arg(long, requires("tls_key"))]
+    tls_cert: Option<PathBuf>,
+
+    /// PEM private key of `--tls-cert`
+    #[arg(long, requires("tls_cert"))]
+    tls_key: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    Compact,
+    Light,
+    Full,
+}
+
+/// The resources a [`Mode`] implies, unless the corresponding flags say
+/// otherwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModeDefaults {
+    pub node_pool_size: usize,
+    pub blockfrost_cache_secs: u64,
+    pub submission_statuses: usize,
+    pub track_mempool: bool,
+}
+
+impl Mode {
+    /// Whether the node’s chain is followed, for `/ws`, confirmations and the
+    /// recent blocks, which compact does without.
+    pub fn follows_chain(&self) -> bool {
+        !matches!(self, Mode::Compact)
+    }
+
+    pub fn defaults(&self) -> ModeDefaults {
+        match self {
+            Mode::Compact => ModeDefaults {
+                node_pool_size: 2,
+                blockfrost_cache_secs: 20,
+                submission_statuses: 1_000,
+                track_mempool: false,
+            },
+            Mode::Light => ModeDefaults {
+                node_pool_size: 5,
+                blockfrost_cache_secs: 20,
+                submission_statuses: 10_000,
+                track_mempool: true,
+            },
+            Mode::Full => ModeDefaults {
+                node_pool_size: 10,
+                blockfrost_cache_secs: 60,
+                submission_statuses: 100_000,
+                track_mempool: true,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Trace,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub node: NodeConfig,
+    /// [`None`] in solitary mode.
+    pub icebreakers: Option<IcebreakersConfig>,
+    pub limits: LimitsConfig,
+    pub observability: ObservabilityConfig,
+    pub cors: CorsConfig,
+    /// [`None`] when serving plain HTTP.
+    pub tls: Option<TlsConfig>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ServerConfig {
+    pub address: String,
+    pub port: u16,
+    pub mode: Mode,
+    pub epoch_webhook_url: Option<String>,
+    pub data_dir: Option<PathBuf>,
+    pub enrich_responses: bool,
+    pub blockfrost_project_id: Option<String>,
+    pub record_traffic: Option<PathBuf>,
+    pub record_bodies: bool,
+    pub read_only: bool,
+    pub operator_token: Option<String>,
+    pub verify_signatures: bool,
+    pub pre_validate: bool,
+    pub spool_offline: bool,
+    pub track_mempool: bool,
+    pub ready_min_sync_progress: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IcebreakersConfig {
+    pub reward_address: String,
+    pub secret: String,
+    /// Whether to send load reports, see `LoadReport` of the Icebreakers API
+    pub telemetry: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LimitsConfig {
+    pub data_dir_quota_mb: u64,
+    pub blockfrost_cache_secs: u64,
+    pub submission_statuses: usize,
+    pub recent_blocks: usize,
+    pub submission_queue: usize,
+    pub spool_max_txs: usize,
+    pub spool_max_age_secs: u64,
+    pub max_tx_size: usize,
+    pub rate_limit_query_per_ip: Option<f64>,
+    pub rate_limit_query_global: Option<f64>,
+    pub rate_limit_submit_per_ip: Option<f64>,
+    pub rate_limit_submit_global: Option<f64>,
+    pub rate_limit_forwarded_for: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObservabilityConfig {
+    pub log_level: LogLevel,
+    pub metrics: bool,
+    pub trace_sample_rate: f64,
+    pub trace_sample_routes: Vec<(String, f64)>,
+    pub otlp_endpoint: Option<String>,
+    pub slo_target: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CorsConfig {
+    /// Empty when CORS is off, `["*"]` for any origin.
+    pub allow_origins: Vec<String>,
+    pub max_age_secs: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+impl Config {
+    pub fn from_args(args: Args) -> Result<Self, AppError> {
+        let icebreakers = match (
+            args.icebreakers.solitary,
+            args.icebreakers.reward_address,
+            args.icebreakers.secret,
+        ) {
+            (false, Some(reward_address), Some(secret)) => Some(IcebreakersConfig {
+                reward_address,
+                secret,
+                telemetry: !args.icebreakers.no_telemetry,
+            }),
+            _ => None,
+        };
+
+        let defaults = args.server.mode.defaults();
+        let genesis = genesis_values(
+            &args.node.network,
+            args.node.network_magic,
+            args.node.shelley_genesis.as_deref(),
+        )?;
+
+        if matches!(args.node.network, Network::Custom) {
+            if icebreakers.is_some() {
+                return Err(AppError::Server(
+                    "Custom networks can only run --solitary".to_string(),
+                ));
+            }
+            if args.server.blockfrost_project_id.is_some() {
+                return Err(AppError::Server(
+                    "--blockfrost-project-id isn't available on custom networks".to_string(),
+                ));
+            }
+        }
+
+        if let Some(icebreakers) = &icebreakers {
+            check_reward_address(&icebreakers.reward_address, &args.node.network)?;
+        }
+
+        if args.cors.cors_allow_origin.len() > 1
+            && args
+                .cors
+                .cors_allow_origin
+                .iter()
+                .any(|origin| origin == "*")
+        {
+            return Err(AppError::Server(
+                "--cors-allow-origin '*' already allows every origin".to_string(),
+            ));
+        }
+
+        Ok(Config {
+            server: ServerConfig {
+                address: args.server.server_address,
+                port: args.server.server_port,
+                mode: args.server.mode,
+                epoch_webhook_url: args.server.epoch_webhook_url,
+                data_dir: args.server.data_dir.or_else(xdg_data_dir),
+                enrich_responses: args.server.enrich_responses,
+                blockfrost_project_id: args.server.blockfrost_project_id,
+                record_traffic: args.server.record_traffic,
+                record_bodies: args.server.record_bodies,
+                read_only: args.server.read_only,
+                operator_token: args.server.operator_token,
+                verify_signatures: args.server.verify_signatures,
+                pre_validate: args.server.pre_validate,
+                spool_offline: args.server.spool_offline,
+                track_mempool: args.server.track_mempool.unwrap_or(defaults.track_mempool),
+                ready_min_sync_progress: args.server.ready_min_sync_progress,
+            },
+            node: NodeConfig {
+                network: args.node.network,
+                genesis,
+                socket_path: args.node.node_socket_path.unwrap_or_default(),
+                relay: args.node.node_relay,
+                max_pool_connections: args.node.node_pool_size.unwrap_or(defaults.node_pool_size),
+                idle_timeout_secs: args.node.node_idle_timeout_secs,
+                max_connection_age_secs: args.node.node_max_connection_age_secs,
+                connect_timeout_secs: args.node.node_connect_timeout_secs,
+                submit_timeout_secs: args.node.node_submit_timeout_secs,
+                statequery_timeout_secs: args.node.node_statequery_timeout_secs,
+                submit_retries: args.node.node_submit_retries,
+                submit_retry_backoff_ms: args.node.node_submit_retry_backoff_ms,
+            },
+            icebreakers,
+            limits: LimitsConfig {
+                data_dir_quota_mb: args.limits.data_dir_quota_mb,
+                blockfrost_cache_secs: args
+                    .limits
+                    .blockfrost_cache_secs
+                    .unwrap_or(defaults.blockfrost_cache_secs),
+                submission_statuses: args
+                    .limits
+                    .submission_statuses
+                    .unwrap_or(defaults.submission_statuses),
+                recent_blocks: args.limits.recent_blocks,
+                submission_queue: args.limits.submission_queue,
+                spool_max_txs: args.limits.spool_max_txs,
+                spool_max_age_secs: args.limits.spool_max_age_secs,
+                max_tx_size: args.limits.max_tx_size,
+                rate_limit_query_per_ip: args.limits.rate_limit_query_per_ip,
+                rate_limit_query_global: args.limits.rate_limit_query_global,
+                rate_limit_submit_per_ip: args.limits.rate_limit_submit_per_ip,
+                rate_limit_submit_global: args.limits.rate_limit_submit_global,
+                rate_limit_forwarded_for: args.limits.rate_limit_forwarded_for,
+            },
+            observability: ObservabilityConfig {
+                log_level: args.observability.log_level,
+                metrics: args.observability.metrics,
+                trace_sample_rate: args.observability.trace_sample_rate,
+                trace_sample_routes: args.observability.trace_sample_route,
+                otlp_endpoint: args.observability.otlp_endpoint,
+                slo_target: args.observability.slo_target,
+            },
+            cors: CorsConfig {
+                allow_origins: args.cors.cors_allow_origin,
+                max_age_secs: args.cors.cors_max_age_secs,
+            },
+            tls: args
+                .tls
+                .tls_cert
+                .zip(args.tls.tls_key)
+                .map(|(cert, key)| TlsConfig { cert, key }),
+        })
+    }
+}
+
+/// `$XDG_STATE_HOME/blockfrost-platform`, only if the environment sets it.
+fn xdg_data_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_STATE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(dir).join("blockfrost-platform"))
+}
+
+/// Where a setting comes from, lowest precedence first.
+type Settings = BTreeMap<String, (toml::Value, PathBuf)>;
+
+/// Expands `--config` and `--config-dir` into the flags their files set, then
+/// `BLOCKFROST_<FLAG>` environment variables into theirs, e.g.
+/// `BLOCKFROST_SERVER_PORT` for `--server-port`, all placed before the command
+/// line ones, so that clap validates them alike and the command line wins.
+/// Files may group flags into tables, which are only for show. Flags set from
+/// the environment, including by their own variable, e.g. `STATE_DIRECTORY`,
+/// ignore what files set.
+pub fn args_with_config_files(
+    args: impl IntoIterator<Item = OsString>,
+) -> Result<Vec<OsString>, AppError> {
+    args_with_layers(args, |name| std::env::var_os(name))
+}
+
+fn args_with_layers(
+    args: impl IntoIterator<Item = OsString>,
+    env: impl Fn(&str) -> Option<OsString>,
+) -> Result<Vec<OsString>, AppError> {
+    let mut args: Vec<OsString> = args.into_iter().collect();
+
+    let mut settings = Settings::new();
+    if let Some(file) = flag_value(&args, "--config") {
+        read_config_file(&file, &mut settings)?;
+    }
+    if let Some(dir) = flag_value(&args, "--config-dir") {
+        read_config_dir(&dir, &mut settings)?;
+    }
+
+    let command = <Args as clap::CommandFactory>::command();
+    let settable = || {
+        command.get_arguments().filter(|arg| {
+            arg.get_long()
+                .is_some_and(|long| !["config", "config-dir", "print-runtime-deps"].contains(&long))
+        })
+    };
+    let mut config_args = vec![];
+    let mut env_args = vec![];
+
+    for (name, (value, file)) in settings {
+        let invalid =
+            |reason: String| AppError::Server(format!("{}: {}: {}", file.display(), name, reason));
+        let arg = settable()
+            .find(|arg| arg.get_long() == Some(name.as_str()))
+            .ok_or_else(|| invalid("not a known setting".to_string()))?;
+
+        if arg
+            .get_env()
+            .is_some_and(|var| env(&var.to_string_lossy()).is_some())
+            || env(&env_var(&name)).is_some()
+        {
+            continue;
+        }
+
+        config_args.extend(setting_args(arg, &name, value).map_err(invalid)?);
+    }
+
+    for arg in settable() {
+        let name = arg.get_long().unwrap_or_default();
+        let var = env_var(name);
+        let Some(value) = env(&var) else {
+            continue;
+        };
+        let value = value
+            .into_string()
+            .map_err(|_| "not valid UTF-8".to_string())
+            .and_then(|value| {
+                let value = if arg.get_action().takes_values() {
+                    toml::Value::String(value)
+                } else {
+                    toml::Value::Boolean(
+                        value
+                            .parse()
+                            .map_err(|_| format!("must be true or false, got '{}'", value))?,
+                    )
+                };
+                setting_args(arg, name, value)
+            })
+            .map_err(|reason| AppError::Server(format!("{}: {}", var, reason)))?;
+
+        env_args.extend(value);
+    }
+
+    let program = if args.is_empty() {
+        vec![]
+    } else {
+        vec![args.remove(0)]
+    };
+
+    Ok(program
+        .into_iter()
+        .chain(config_args)
+        .chain(env_args)
+        .chain(args)
+        .collect())
+}
+
+/// The environment variable setting `--name`.
+fn env_var(name: &str) -> String {
+    format!("BLOCKFROST_{}", name.to_uppercase().replace('-', "_"))
+}
+
+/// The command line flags setting `--name` to `value`, once validated by
+/// `arg`, which tells apart switches, e.g. `--solitary`, only present if
+/// `true`.
+fn setting_args(arg: &clap::Arg, name: &str, value: toml::Value) -> Result<Vec<OsString>, String> {
+    let values = match value {
+        toml::Value::Array(values) => values,
+        value => vec![value],
+    };
+    let mut args = vec![];
+
+    for value in values {
+        let value = match value {
+            // Switches take no value and are off by default, which `false` leaves
+            // them; those on by default, like `--metrics`, take `true` or `false`
+            toml::Value::Boolean(set) if !arg.get_action().takes_values() => {
+                if set {
+                    args.push(format!("--{}", name).into());
+                }
+                continue;
+            }
+            toml::Value::String(value) => value,
+            toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+                value.to_string()
+            }
+            _ => return Err("must be a string, number, boolean or array".to_string()),
+        };
+
+        // Alone, so that an invalid value is told apart from the rest
+        clap::Command::new("config")
+            .no_binary_name(true)
+            .arg(
+                clap::Arg::new(arg.get_id().clone())
+                    .action(arg.get_action().clone())
+                    .value_parser(arg.get_value_parser().clone()),
+            )
+            .try_get_matches_from([&value])
+            .map_err(|e| clap_error_reason(&e))?;
+        args.push(format!("--{}={}", name, value).into());
+    }
+
+    Ok(args)
+}
+
+/// The value of `flag` on the command line, as `--flag value` or `--flag=value`.
+fn flag_value(args: &[OsString], flag: &str) -> Option<PathBuf> {
+    args.iter()
+        .enumerate()
+        .find_map(|(i, arg)| match arg.to_str()?.strip_prefix(flag)? {
+            "" => args.get(i + 1).map(PathBuf::from),
+            value => value.strip_prefix('=').map(PathBuf::from),
+        })
+}
+
+/// Why clap refused a value, without naming the argument or usage hints,
+/// e.g. `invalid value 'abc': invalid digit found in string`.
+fn clap_error_reason(error: &clap::Error) -> String {
+    let rendered = error.render().to_string();
+    let reason = rendered.lines().next().unwrap_or_default();
+    let reason = reason.strip_prefix("error: ").unwrap_or(reason);
+
+    match reason.split_once(" for '") {
+        Some((value, rest)) => match rest.split_once("': ") {
+            Some((_, why)) => format!("{}: {}", value, why),
+            None => value.to_string(),
+        },
+        None => reason.to_string(),
+    }
+}
+
+/// The settings of all `*.toml` files in `dir`, in lexicographic order.
+fn read_config_dir(dir: &Path, settings: &mut Settings) -> Result<(), AppError> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| AppError::Server(format!("Cannot read {}: {}", dir.display(), e)))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml") && path.is_file())
+        .collect();
+    files.sort();
+
+    files
+        .iter()
+        .try_for_each(|file| read_config_file(file, settings))
+}
+
+/// Adds the settings of `file`, by flag name, overriding those read before.
+fn read_config_file(file: &Path, settings: &mut Settings) -> Result<(), AppError> {
+    let table: toml::Table = fs::read_to_string(file)
+        .map_err(|e| e.to_string())
+        .and_then(|content| content.parse().map_err(|e: toml::de::Error| e.to_string()))
+        .map_err(|e| AppError::Server(format!("{}: {}", file.display(), e)))?;
+
+    for (key, value) in table {
+        let entries = match value {
+            toml::Value::Table(section) => section.into_iter().collect(),
+            value => vec![(key, value)],
+        };
+        for (key, value) in entries {
+            settings.insert(key.replace('_', "-"), (value, file.to_path_buf()));
+        }
+    }
+
+    Ok(())
+}
+
+impl Args {
+    /// The flags `matches` ended up with, from files, the environment or the
+    /// command line, or by default, as TOML that `--config` reads back: by
+    /// name, in tables by section, with the Icebreakers secret and the operator
+    /// token redacted. Flags left to their mode default aren’t set, so they’re
+    /// left out.
+    pub fn dump(matches: &clap::ArgMatches) -> Result<String, AppError> {
+        let command = <Args as clap::CommandFactory>::command();
+        let mut dump = toml::Table::new();
+
+        for arg in command.get_arguments() {
+            let (Some(name), Some(section)) = (arg.get_long(), arg.get_help_heading()) else {
+                continue;
+            };
+            let Ok(Some(raw)) = matches.try_get_raw(arg.get_id().as_str()) else {
+                continue;
+            };
+            let mut values: Vec<toml::Value> = raw
+                .map(|value| match name {
+                    "secret" | "operator-token" => toml::Value::String("<redacted>".to_string()),
+                    _ => dump_value(&value.to_string_lossy()),
+                })
+                .collect();
+            let value = match arg.get_action() {
+                ArgAction::Append => toml::Value::Array(values),
+                _ => match values.pop() {
+                    Some(value) => value,
+                    None => continue,
+                },
+            };
+
+            if let toml::Value::Table(section) = dump
+                .entry(section.to_lowercase())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            {
+                section.insert(name.to_string(), value);
+            }
+        }
+
+        toml::to_string(&dump)
+            .map_err(|e| AppError::Server(format!("Failed to dump the configuration: {}", e)))
+    }
+}
+
+/// A flag’s value, typed as it would be written by hand.
+fn dump_value(value: &str) -> toml::Value {
+    if let Ok(value) = value.parse() {
+        toml::Value::Boolean(value)
+    } else if let Ok(value) = value.parse() {
+        toml::Value::Integer(value)
+    } else if let Some(value) = value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite() && value.contains('.'))
+    {
+        toml::Value::Float(value)
+    } else {
+        toml::Value::String(value.to_string())
+    }
+}
+
+impl IcebreakersConfig {
+    /// Identifies the operator in metrics and recorded traffic, so that their
+    /// instances can be aggregated without exposing the reward address itself.
+    pub fn reward_address_hash(&self) -> String {
+        hex::encode(Hasher::<256>::hash(self.reward_address.as_bytes()))
+    }
+}
+
+fn parse_ratio(s: &str) -> Result<f64, String> {
+    let rate: f64 = s.parse().map_err(|_| format!("invalid ratio: {}", s))?;
+
+    if (0.0..=1.0).contains(&rate) {
+        Ok(rate)
+    } else {
+        Err(format!("must be between 0.0 and 1.0, got {}", rate))
+    }
+}
+
+fn parse_rate(s: &str) -> Result<f64, String> {
+    let rate: f64 = s.parse().map_err(|_| format!("invalid rate: {}", s))?;
+
+    if rate > 0.0 && rate.is_finite() {
+        Ok(rate)
+    } else {
+        Err(format!(
+            "must be a positive number of requests per second, got {}",
+            rate
+        ))
+    }
+}
+
+fn parse_percentage(s: &str) -> Result<f64, String> {
+    let percentage: f64 = s
+        .parse()
+        .map_err(|_| format!("invalid percentage: {}", s))?;
+
+    if (0.0..=100.0).contains(&percentage) {
+        Ok(percentage)
+    } else {
+        Err(format!("must be between 0 and 100, got {}", percentage))
+    }
+}
+
+fn parse_origin(s: &str) -> Result<String, String> {
+    if s == "*" {
+        return Ok(s.to_string());
+    }
+
+    let host = s
+        .strip_prefix("https://")
+        .or_else(|| s.strip_prefix("http://"))
+        .ok_or_else(|| format!("expected http(s)://HOST[:PORT] or *, got {}", s))?;
+
+    if host.is_empty() || host.contains(['/', '?', '#']) || !s.is_ascii() {
+        return Err(format!("expected http(s)://HOST[:PORT] or *, got {}", s));
+    }
+
+    Ok(s.to_string())
+}
+
+fn parse_route_sample_rate(s: &str) -> Result<(String, f64), String> {
+    let (route, rate) = s
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected ROUTE=RATE, got {}", s))?;
+
+    Ok((route.to_string(), parse_ratio(rate)?))
+}
+
+// Implement conversion from LogLevel enum to tracing::Level
+impl From<LogLevel> for Level {
+    fn from(log_level: LogLevel) -> Self {
+        match log_level {
+            LogLevel::Debug => Level::DEBUG,
+            LogLevel::Info => Level::INFO,
+            LogLevel::Warn => Level::WARN,
+            LogLevel::Error => Level::ERROR,
+            LogLevel::Trace => Level::TRACE,
+        }
+    }
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Mode::Compact => write!(f, "compact"),
+            Mode::Light => write!(f, "light"),
+            Mode::Full => write!(f, "full"),
+        }
+    }
+}
+
+/// Checks that `reward_address` is a stake address of `network`, as the
+/// Icebreakers API would only fail on it later.
+fn check_reward_address(reward_address: &str, network: &Network) -> Result<(), AppError> {
+    let address = Address::from_bech32(reward_address).map_err(|e| {
+        AppError::Server(format!(
+            "Invalid --reward-address {}: {}",
+            reward_address, e
+        ))
+    })?;
+
+    let kind = match &address {
+        Address::Stake(_) => None,
+        Address::Shelley(_) => Some("a payment address"),
+        Address::Byron(_) => Some("a Byron address"),
+    };
+    if let Some(kind) = kind {
+        return Err(AppError::Server(format!(
+            "--reward-address must be a stake address, i.e. stake1… or stake_test1…, not {}",
+            kind
+        )));
+    }
+
+    let (expected, actual) = match (network, address.network()) {
+        (Network::Mainnet, Some(AddressNetwork::Mainnet)) => return Ok(()),
+        (Network::Mainnet, _) => ("mainnet", "testnet"),
+        (_, Some(AddressNetwork::Testnet)) => return Ok(()),
+        (_, _) => ("testnet", "mainnet"),
+    };
+
+    Err(AppError::Server(format!(
+        "--reward-address is a {} stake address, but --network {} needs a {} one",
+        actual, network, expected
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use pallas_network::miniprotocols::{MAINNET_MAGIC, PREVIEW_MAGIC};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[test]
+    fn test_config_sections_roundtrip() {
+        let args = Args::parse_from([
+            "blockfrost-platform",
+            "--network",
+            "preview",
+            "--node-socket-path",
+            "/run/cardano-node/node.socket",
+            "--secret",
+            "s3cr3t",
+            "--reward-address",
+            "stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn",
+            "--trace-sample-route",
+            "/tx/submit=0.01",
+            "--log-level",
+            "debug",
+        ]);
+        let config = Config::from_args(args).unwrap();
+
+        assert_eq!(config.node.network_magic(), PREVIEW_MAGIC);
+        assert_eq!(config.server.port, 3000);
+        assert_eq!(config.limits.data_dir_quota_mb, 512);
+
+        let serialized = toml::to_string(&config).unwrap();
+        let deserialized: Config = toml::from_str(&serialized).unwrap();
+
+        assert_eq!(format!("{:?}", deserialized), format!("{:?}", config));
+        assert_eq!(
+            deserialized.icebreakers.map(|i| i.reward_address),
+            Some("stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn".to_string())
+        );
+    }
+
+    #[test]
+    fn test_custom_network() {
+        let genesis = std::env::temp_dir().join(format!(
+            "bf-cli-shelley-genesis-{}.json",
+            std::process::id()
+        ));
+        fs::write(
+            &genesis,
+            r#"{"networkMagic": 42, "networkId": "Testnet", "epochLength": 500,
+                "slotLength": 1, "systemStart": "2024-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let args = |extra: &[&str]| {
+            Args::try_parse_from(
+                [
+                    "blockfrost-platform",
+                    "--network",
+                    "custom",
+                    "--node-socket-path",
+                    "/run/cardano-node/node.socket",
+                    "--shelley-genesis",
+                    genesis.to_str().unwrap(),
+                ]
+                .iter()
+                .chain(extra),
+            )
+        };
+
+        assert!(args(&["--solitary"]).is_err());
+
+        let config = Config::from_args(args(&["--solitary", "--network-magic", "42"]).unwrap());
+        assert_eq!(config.unwrap().node.network_magic(), 42);
+
+        let err = Config::from_args(
+            args(&[
+                "--network-magic",
+                "42",
+                "--secret",
+                "s3cr3t",
+                "--reward-address",
+                "stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn",
+            ])
+            .unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("can only run --solitary"));
+
+        fs::remove_file(&genesis).unwrap();
+    }
+
+    #[test]
+    fn test_reward_address_network() {
+        let err = Config::from_args(Args::parse_from([
+            "blockfrost-platform",
+            "--network",
+            "mainnet",
+            "--node-socket-path",
+            "/run/cardano-node/node.socket",
+            "--secret",
+            "s3cr3t",
+            "--reward-address",
+            "stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn",
+        ]))
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("is a testnet stake address, but --network mainnet needs a mainnet one"));
+    }
+
+    #[rstest]
+    #[case(&[], 2, false)]
+    #[case(&["--mode", "full"], 10, true)]
+    #[case(&["--node-pool-size", "4"], 4, false)]
+    #[case(&["--mode", "full", "--track-mempool", "false"], 10, false)]
+    fn test_mode_defaults(
+        #[case] flags: &[&str],
+        #[case] node_pool_size: usize,
+        #[case] track_mempool: bool,
+    ) {
+        let args = Args::parse_from(
+            [
+                "blockfrost-platform",
+                "--network",
+                "preview",
+                "--node-socket-path",
+                "/run/cardano-node/node.socket",
+                "--solitary",
+            ]
+            .iter()
+            .chain(flags),
+        );
+        let config = Config::from_args(args).unwrap();
+
+        assert_eq!(config.node.max_pool_connections, node_pool_size);
+        assert_eq!(config.server.track_mempool, track_mempool);
+    }
+
+    /// Allowed origins, and TLS certificate and key.
+    type CorsTls<'a> = (Vec<&'a str>, Option<(&'a str, &'a str)>);
+
+    #[rstest]
+    #[case(&[], Ok((vec![], None)))]
+    #[case(
+        &["--cors-allow-origin", "https://app.example.com", "--cors-allow-origin", "http://localhost:5173"],
+        Ok((vec!["https://app.example.com", "http://localhost:5173"], None))
+    )]
+    #[case(&["--cors-allow-origin", "*"], Ok((vec!["*"], None)))]
+    #[case(&["--cors-allow-origin", "app.example.com"], Err("expected http(s)://HOST[:PORT] or *"))]
+    #[case(&["--cors-allow-origin", "https://app.example.com/"], Err("expected http(s)://HOST[:PORT] or *"))]
+    #[case(
+        &["--cors-allow-origin", "*", "--cors-allow-origin", "https://app.example.com"],
+        Err("already allows every origin")
+    )]
+    #[case(
+        &["--tls-cert", "/etc/tls/cert.pem", "--tls-key", "/etc/tls/key.pem"],
+        Ok((vec![], Some(("/etc/tls/cert.pem", "/etc/tls/key.pem"))))
+    )]
+    #[case(&["--tls-cert", "/etc/tls/cert.pem"], Err("--tls-key"))]
+    #[case(&["--tls-key", "/etc/tls/key.pem"], Err("--tls-cert"))]
+    fn test_cors_tls(#[case] flags: &[&str], #[case] expected: Result<CorsTls, &str>) {
+        let config = Args::try_parse_from(
+            [
+                "blockfrost-platform",
+                "--network",
+                "preview",
+                "--node-socket-path",
+                "/run/cardano-node/node.socket",
+                "--solitary",
+            ]
+            .iter()
+            .chain(flags),
+        )
+        .map_err(|e| e.to_string())
+        .and_then(|args| Config::from_args(args).map_err(|e| e.to_string()));
+
+        match (config, expected) {
+            (Ok(config), Ok((allow_origins, tls))) => {
+                assert_eq!(config.cors.allow_origins, allow_origins);
+                assert_eq!(config.cors.max_age_secs, 3600);
+                assert_eq!(
+                    config
+                        .tls
+                        .as_ref()
+                        .map(|tls| (tls.cert.to_str().unwrap(), tls.key.to_str().unwrap())),
+                    tls
+                );
+            }
+            (Err(err), Err(expected)) => assert!(err.contains(expected), "{}", err),
+            (config, expected) => panic!("got {:?}, expected {:?}", config, expected),
+        }
+    }
+
+    #[test]
+    fn test_reward_address_hash() {
+        let icebreakers = |reward_address: &str| IcebreakersConfig {
+            reward_address: reward_address.to_string(),
+            secret: "s3cr3t".to_string(),
+            telemetry: true,
+        };
+        let hash = icebreakers("stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn")
+            .reward_address_hash();
+
+        assert_eq!(hash.len(), 64);
+        assert_eq!(
+            hash,
+            icebreakers("stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn")
+                .reward_address_hash()
+        );
+        assert_ne!(hash, icebreakers("addr_test2").reward_address_hash());
+    }
+
+    #[test]
+    fn test_config_dir() {
+        let dir = std::env::temp_dir().join(format!("bf-config-dir-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("00-base.toml"),
+            r#"
+                network = "preview"
+                solitary = true
+
+                [node]
+                node-socket-path = "/run/cardano-node/node.socket"
+                node_pool_size = 3
+
+                [observability]
+                trace-sample-route = ["/tx/submit=0.01", "/metrics=0"]
+            "#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("50-site.toml"),
+            "network = \"mainnet\"\nmode = \"full\"",
+        )
+        .unwrap();
+        fs::write(dir.join("README"), "not a drop-in").unwrap();
+
+        let args = args_with_config_files(
+            [
+                "blockfrost-platform",
+                "--config-dir",
+                dir.to_str().unwrap(),
+                "--mode",
+                "light",
+            ]
+            .map(OsString::from),
+        )
+        .unwrap();
+        let config = Config::from_args(Args::parse_from(args)).unwrap();
+
+        // The later file, and then the command line, win
+        assert_eq!(config.node.network_magic(), MAINNET_MAGIC);
+        assert_eq!(config.server.mode, Mode::Light);
+        assert_eq!(config.node.socket_path, "/run/cardano-node/node.socket");
+        assert_eq!(config.node.max_pool_connections, 3);
+        assert!(config.icebreakers.is_none());
+        assert_eq!(config.observability.trace_sample_routes.len(), 2);
+
+        fs::write(dir.join("99-typo.toml"), "node-sokcet-path = \"/tmp\"").unwrap();
+        let err = args_with_config_files(
+            [
+                "blockfrost-platform",
+                &format!("--config-dir={}", dir.display()),
+            ]
+            .map(OsString::from),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("node-sokcet-path: not a known setting"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_config_file() {
+        let dir = std::env::temp_dir().join(format!("bf-config-file-{}", std::process::id()));
+        let drop_ins = dir.join("conf.d");
+        fs::create_dir_all(&drop_ins).unwrap();
+        let file = dir.join("config.toml");
+        fs::write(
+            &file,
+            r#"
+                network = "preview"
+                mode = "full"
+                secret = "hunter2"
+                reward-address = "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw"
+
+                [node]
+                node-socket-path = "/run/cardano-node/node.socket"
+                node-pool-size = 3
+            "#,
+        )
+        .unwrap();
+        fs::write(drop_ins.join("50-site.toml"), "network = \"mainnet\"").unwrap();
+
+        let args = args_with_config_files(
+            [
+                "blockfrost-platform",
+                "--config",
+                file.to_str().unwrap(),
+                "--config-dir",
+                drop_ins.to_str().unwrap(),
+                "--mode",
+                "light",
+                "config",
+                "dump",
+            ]
+            .map(OsString::from),
+        )
+        .unwrap();
+        let dump = Args::dump(&Args::command().get_matches_from(args.clone())).unwrap();
+        let args = Args::parse_from(args);
+        assert_eq!(
+            args.command,
+            Some(Command::Config {
+                action: ConfigAction::Dump
+            })
+        );
+        let config = Config::from_args(args).unwrap();
+
+        // The file, then the drop-ins, then the command line
+        assert_eq!(config.node.network_magic(), MAINNET_MAGIC);
+        assert_eq!(config.server.mode, Mode::Light);
+        assert_eq!(config.node.max_pool_connections, 3);
+
+        assert!(dump.contains("stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw"));
+        assert!(dump.contains("secret = \"<redacted>\""));
+        assert!(!dump.contains("hunter2"));
+
+        fs::write(
+            &file,
+            "network = \"preview\"\nsolitary = true\nnode-socket-path = \"/tmp/node.socket\"\nmetrics = false",
+        )
+        .unwrap();
+        let config_file = |extra: &[&str]| {
+            let args = args_with_config_files(
+                ["blockfrost-platform", "--config", file.to_str().unwrap()]
+                    .iter()
+                    .chain(extra)
+                    .map(OsString::from),
+            )
+            .unwrap();
+            Config::from_args(Args::parse_from(args)).unwrap()
+        };
+        assert!(!config_file(&[]).observability.metrics);
+        assert!(config_file(&["--metrics"]).observability.metrics);
+
+        fs::write(&file, "network = \"preview\"\nnode-pool-size = \"abc\"").unwrap();
+        let err = args_with_config_files(
+            ["blockfrost-platform", "--config", file.to_str().unwrap()].map(OsString::from),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("node-pool-size: invalid value 'abc'"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_dump_roundtrip() {
+        let file = std::env::temp_dir().join(format!("bf-config-dump-{}.toml", std::process::id()));
+        let args = args_with_layers(
+            [
+                "blockfrost-platform",
+                "--network",
+                "preview",
+                "--node-socket-path",
+                "/run/cardano-node/node.socket",
+                "--solitary",
+                "--mode",
+                "light",
+                "--metrics=false",
+                "--slo-target",
+                "0.99",
+                "--trace-sample-route",
+                "/tx/submit=0.01",
+                "--trace-sample-route",
+                "/metrics=0",
+            ]
+            .map(OsString::from),
+            |_| None,
+        )
+        .unwrap();
+        let dump = Args::dump(&Args::command().get_matches_from(args.clone())).unwrap();
+        assert!(dump.contains("[server]\n"));
+        assert!(dump.contains("\nserver-port = 3000\n"));
+        assert!(dump.contains("\nmetrics = false\n"));
+        assert!(dump.contains("node-socket-path = \"/run/cardano-node/node.socket\""));
+
+        fs::write(&file, &dump).unwrap();
+        let reread = args_with_layers(
+            ["blockfrost-platform", "--config", file.to_str().unwrap()].map(OsString::from),
+            |_| None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Args::dump(&Args::command().get_matches_from(reread.clone())).unwrap(),
+            dump
+        );
+        assert_eq!(
+            format!("{:?}", Config::from_args(Args::parse_from(reread)).unwrap()),
+            format!("{:?}", Config::from_args(Args::parse_from(args)).unwrap())
+        );
+
+        fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_env_layer() {
+        let file = std::env::temp_dir().join(format!("bf-config-env-{}.toml", std::process::id()));
+        fs::write(
+            &file,
+            r#"
+                network = "preview"
+                node-socket-path = "/run/cardano-node/node.socket"
+                server-port = 3001
+                mode = "full"
+            "#,
+        )
+        .unwrap();
+        let config = |env: &[(&str, &str)], flags: &[&str]| {
+            let env: BTreeMap<String, OsString> = env
+                .iter()
+                .map(|(name, value)| (name.to_string(), OsString::from(value)))
+                .collect();
+            args_with_layers(
+                ["blockfrost-platform", "--config", file.to_str().unwrap()]
+                    .iter()
+                    .chain(flags)
+                    .map(OsString::from),
+                |name| env.get(name).cloned(),
+            )
+            .map(|args| Config::from_args(Args::parse_from(args)).unwrap())
+        };
+        let env = [
+            ("BLOCKFROST_NETWORK", "mainnet"),
+            ("BLOCKFROST_SERVER_PORT", "4000"),
+            ("BLOCKFROST_SOLITARY", "true"),
+        ];
+
+        // The environment overrides the file, and the command line both
+        let config_env = config(&env, &[]).unwrap();
+        assert_eq!(config_env.node.network_magic(), MAINNET_MAGIC);
+        assert_eq!(config_env.server.port, 4000);
+        assert_eq!(config_env.server.mode, Mode::Full);
+        assert!(config_env.icebreakers.is_none());
+        assert_eq!(
+            config(&env, &["--server-port", "5000"])
+                .unwrap()
+                .server
+                .port,
+            5000
+        );
+
+        let err = config(&[("BLOCKFROST_SERVER_PORT", "abc")], &["--solitary"]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("BLOCKFROST_SERVER_PORT: invalid value 'abc'"));
+        let err = config(&[("BLOCKFROST_SOLITARY", "yes")], &[]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("BLOCKFROST_SOLITARY: must be true or false"));
+
+        fs::remove_file(&file).unwrap();
+    }
+
+    #[rstest]
+    #[case(
+        "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw",
+        Network::Mainnet,
+        None
+    )]
+    #[case(
+        "stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn",
+        Network::Preprod,
+        None
+    )]
+    #[case(
+        "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw",
+        Network::Preview,
+        Some("is a mainnet stake address, but --network preview needs a testnet one")
+    )]
+    #[case(
+        "stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn",
+        Network::Mainnet,
+        Some("is a testnet stake address, but --network mainnet needs a mainnet one")
+    )]
+    #[case(
+        "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x",
+        Network::Mainnet,
+        Some("must be a stake address, i.e. stake1… or stake_test1…, not a payment address")
+    )]
+    #[case(
+        "not-an-address",
+        Network::Mainnet,
+        Some("Invalid --reward-address not-an-address")
+    )]
+    fn test_check_reward_address(
+        #[case] reward_address: &str,
+        #[case] network: Network,
+        #[case] error: Option<&str>,
+    ) {
+        match (check_reward_address(reward_address, &network), error) {
+            (Ok(()), None) => {}
+            (Err(e), Some(error)) => assert!(e.to_string().contains(error), "{}", e),
+            (result, _) => panic!("unexpected {:?}", result),
+        }
+    }
+}
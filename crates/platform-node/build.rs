@@ -56,12 +56,12 @@ fn main() {
 
     println!("Fetching {}", file_name);
 
-    // Use the project’s target directory instead of a system cache location.
+    // Use the workspace’s target directory instead of a system cache location.
     let cargo_manifest_dir =
         env::var("CARGO_MANIFEST_DIR").expect("Unable to find CARGO_MANIFEST_DIR");
 
     let testgen_dir = PathBuf::from(&cargo_manifest_dir)
-        .join("target")
+        .join("../../target")
         .join("testgen-hs");
 
     create_dir_all(&testgen_dir).expect("Unable to create testgen directory");
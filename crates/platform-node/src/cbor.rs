@@ -0,0 +1,6 @@
+//! The native decoding lives in the `platform-cbor` crate, so that it can be
+//! used without the server. It's re-exported here under its old paths.
+
+pub use platform_cbor::{codec, coverage, decode_apply_tx_err, haskell_types, slot, DecodeError};
+
+pub mod fallback_decoder;
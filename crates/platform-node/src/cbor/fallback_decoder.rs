@@ -1,15 +1,22 @@
-use crate::AppError;
+use crate::{retry::Backoff, AppError};
+use std::ffi::OsString;
 use std::io::{BufRead, BufReader, Write};
-use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::path::{Path, PathBuf};
 use std::process::{self as proc, Command};
 use std::sync::{
     atomic::{self, AtomicU32},
     Arc,
 };
+use std::time::{Duration, Instant};
 use std::{env, thread};
 use tokio::sync::{mpsc, oneshot};
 use tracing::{error, info};
-use walkdir::WalkDir;
+
+const RESTART_BACKOFF: Backoff = Backoff::new(
+    "fallback_decoder",
+    Duration::from_secs(1),
+    Duration::from_secs(30),
+);
 
 #[derive(Clone)]
 pub struct FallbackDecoder {
@@ -25,20 +32,10 @@ struct FDRequest {
 impl FallbackDecoder {
     /// Starts a new child process.
     pub fn spawn() -> Result<Self, AppError> {
-        let mut search_roots = vec![
-            PathBuf::from("/usr/bin"),
-            PathBuf::from("/bin"),
-            PathBuf::from("target/testgen-hs/extracted/testgen-hs"),
-            PathBuf::from("/app/testgen-hs"),
-        ];
-
-        if let Ok(path_var) = env::var("PATH") {
-            for dir in path_var.split(MAIN_SEPARATOR) {
-                search_roots.push(PathBuf::from(dir));
-            }
-        }
-
-        let testgen_hs_lib = Self::find_testgen_hs(&search_roots).map_err(AppError::Server)?;
+        let testgen_hs_lib = Self::locate_child_binary()
+            .map_err(AppError::Server)?
+            .to_string_lossy()
+            .to_string();
 
         info!("Using {} as a fallback CBOR error decoder", &testgen_hs_lib);
 
@@ -52,15 +49,22 @@ impl FallbackDecoder {
         thread::spawn(move || {
             // For retries:
             let mut last_unfulfilled_request: Option<FDRequest> = None;
+            let mut restarts = RESTART_BACKOFF.start();
 
             loop {
+                let started_at = Instant::now();
                 let single_run = Self::spawn_child(
                     &testgen_hs_path_for_thread,
                     &mut receiver,
                     &mut last_unfulfilled_request,
                     &current_child_pid_clone,
                 );
-                let restart_delay = std::time::Duration::from_secs(1);
+
+                // Only crashing right away again is backed off
+                if started_at.elapsed() > RESTART_BACKOFF.max {
+                    restarts.reset();
+                }
+                let restart_delay = restarts.next_delay().unwrap_or(RESTART_BACKOFF.max);
                 error!(
                     "FallbackDecoder: will restart in {:?} because of a subprocess error: {:?}",
                     restart_delay, single_run
@@ -75,6 +79,17 @@ impl FallbackDecoder {
         })
     }
 
+    /// A decoder without a child process, whose requests all fail.
+    #[cfg(test)]
+    pub fn disconnected() -> Self {
+        let (sender, _) = mpsc::channel::<FDRequest>(1);
+
+        Self {
+            sender,
+            current_child_pid: Arc::new(AtomicU32::new(u32::MAX)),
+        }
+    }
+
     /// Decodes a CBOR error using the child process.
     pub async fn decode(&self, cbor: &[u8]) -> Result<serde_json::Value, String> {
         let (response_tx, response_rx) = oneshot::channel();
@@ -94,50 +109,63 @@ impl FallbackDecoder {
         })?
     }
 
-    /// Searches for `testgen-hs` in multiple directories.
-    pub fn find_testgen_hs(roots: &[PathBuf]) -> Result<String, String> {
-        // 1. Check project root
-        let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    /// Where we look for `testgen-hs`, in order:
+    /// 1. the `TESTGEN_HS_PATH` environment variable,
+    /// 2. alongside our own binary, also inside a `testgen-hs/` directory as in release archives,
+    /// 3. the system `PATH`,
+    /// 4. the one downloaded by `build.rs`, for development builds.
+    pub fn search_order() -> Vec<(&'static str, PathBuf)> {
+        Self::search_order_from(
+            env::var_os("TESTGEN_HS_PATH"),
+            env::current_exe().ok(),
+            env::var_os("PATH"),
+            option_env!("TESTGEN_HS_PATH"),
+        )
+    }
 
-        // 2. Check the TESTGEN_HS_PATH environment variable
-        if let Ok(val) = env::var("TESTGEN_HS_PATH") {
-            let candidate = PathBuf::from(val);
-            if candidate.is_file() && candidate.exists() {
-                return Ok(candidate.to_string_lossy().into_owned());
-            }
+    fn search_order_from(
+        env_path: Option<OsString>,
+        current_exe: Option<PathBuf>,
+        path_var: Option<OsString>,
+        built_path: Option<&str>,
+    ) -> Vec<(&'static str, PathBuf)> {
+        let file_name = format!("testgen-hs{}", env::consts::EXE_SUFFIX);
+        let mut candidates = vec![];
+
+        if let Some(env_path) = env_path {
+            candidates.push(("TESTGEN_HS_PATH", PathBuf::from(env_path)));
         }
 
-        // 3. Construct a potential path in the project root
-        #[cfg_attr(not(target_os = "windows"), allow(unused_mut))]
-        let mut root_candidate = PathBuf::from(&manifest_dir).join("testgen-hs");
-
-        // On Windows, add .exe suffix
-        #[cfg(target_os = "windows")]
-        {
-            root_candidate.set_file_name("testgen-hs.exe");
+        if let Some(exe_dir) = current_exe.as_deref().and_then(Path::parent) {
+            candidates.push(("alongside the binary", exe_dir.join(&file_name)));
+            candidates.push((
+                "alongside the binary",
+                exe_dir.join("testgen-hs").join(&file_name),
+            ));
         }
 
-        // 4. If that file exists and responds to --version, use it
-        if Self::is_executable(root_candidate.as_path()) {
-            return Ok(root_candidate.to_string_lossy().to_string());
+        if let Some(path_var) = path_var {
+            for dir in env::split_paths(&path_var) {
+                candidates.push(("PATH", dir.join(&file_name)));
+            }
         }
 
-        // 5. Recursively walk each directory in `roots` to find a matching file
-        for root in roots {
-            for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
-                let path = entry.path();
-                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
-
-                if file_name.starts_with("testgen-hs")
-                    && path.is_file()
-                    && Self::is_executable(path)
-                {
-                    return Ok(path.to_string_lossy().to_string());
-                }
-            }
+        if let Some(built_path) = built_path {
+            candidates.push(("build time", PathBuf::from(built_path)));
         }
 
-        Err("No valid `testgen-hs` binary found in subdomains.".to_string())
+        candidates
+    }
+
+    /// Returns the first runnable `testgen-hs` in [`Self::search_order`].
+    pub fn locate_child_binary() -> Result<PathBuf, String> {
+        Self::search_order()
+            .into_iter()
+            .map(|(_, path)| path)
+            .find(|path| path.is_file() && Self::is_executable(path))
+            .ok_or_else(|| {
+                "No runnable `testgen-hs` found, set TESTGEN_HS_PATH or run with --print-runtime-deps to see where we looked".to_string()
+            })
     }
 
     /// Checks if the path is runnable. Adjust for platform specifics if needed.
@@ -308,6 +336,36 @@ fn partition_result<A, E>(ae: Result<A, E>) -> (Result<A, ()>, Result<(), E>) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_search_order() {
+        let exe = format!("testgen-hs{}", env::consts::EXE_SUFFIX);
+        let path_var = env::join_paths(["/usr/local/bin", "/usr/bin"]).unwrap();
+
+        let order = FallbackDecoder::search_order_from(
+            Some(OsString::from("/opt/testgen-hs")),
+            Some(PathBuf::from("/srv/bin/blockfrost-platform")),
+            Some(path_var),
+            Some("/build/testgen-hs"),
+        );
+
+        assert_eq!(
+            order,
+            vec![
+                ("TESTGEN_HS_PATH", PathBuf::from("/opt/testgen-hs")),
+                ("alongside the binary", Path::new("/srv/bin").join(&exe)),
+                (
+                    "alongside the binary",
+                    Path::new("/srv/bin/testgen-hs").join(&exe)
+                ),
+                ("PATH", Path::new("/usr/local/bin").join(&exe)),
+                ("PATH", Path::new("/usr/bin").join(&exe)),
+                ("build time", PathBuf::from("/build/testgen-hs")),
+            ]
+        );
+        assert!(FallbackDecoder::search_order_from(None, None, None, None).is_empty());
+    }
     #[tokio::test]
     #[tracing_test::traced_test]
     async fn test_fallback_decoder() {
@@ -0,0 +1,61 @@
+//! The node’s part of the configuration, which the CLI fills in.
+
+use crate::{
+    genesis::preset,
+    node::transactions::{era_index, LATEST_ERA},
+};
+use clap::ValueEnum;
+use pallas_traverse::wellknown::GenesisValues;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+#[derive(Debug, Clone, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    Mainnet,
+    Preprod,
+    Preview,
+    Sanchonet,
+    /// A private testnet or devnet, see `--network-magic` and `--shelley-genesis`
+    Custom,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeConfig {
+    pub network: Network,
+    /// Slots, epochs and time of the network
+    pub genesis: GenesisValues,
+    pub socket_path: String,
+    pub relay: Option<String>,
+    pub max_pool_connections: usize,
+    pub idle_timeout_secs: u64,
+    pub max_connection_age_secs: u64,
+    pub connect_timeout_secs: u64,
+    pub submit_timeout_secs: u64,
+    pub statequery_timeout_secs: u64,
+    pub submit_retries: u32,
+    pub submit_retry_backoff_ms: u64,
+}
+
+impl NodeConfig {
+    pub fn network_magic(&self) -> u64 {
+        self.genesis.magic
+    }
+
+    /// The era index of `EraTx`, where the node can’t be asked for its current one.
+    pub fn submission_era(&self) -> u16 {
+        preset(&self.network).map_or(LATEST_ERA, |preset| era_index(preset.era))
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Network::Mainnet => write!(f, "mainnet"),
+            Network::Preprod => write!(f, "preprod"),
+            Network::Preview => write!(f, "preview"),
+            Network::Sanchonet => write!(f, "sanchonet"),
+            Network::Custom => write!(f, "custom"),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::node::timeouts::NodeTimeout;
 use axum::{
     response::{IntoResponse, Response},
     {http, Json},
@@ -15,6 +16,9 @@ pub enum AppError {
     #[error("Node connection error: {0}")]
     Node(String),
 
+    #[error("{0}")]
+    NodeTimeout(NodeTimeout),
+
     #[error("Icebreakers registration error: {0}")]
     Registration(String),
 
@@ -51,12 +55,19 @@ impl From<AppError> for BlockfrostError {
     fn from(err: AppError) -> Self {
         match err {
             AppError::Node(e) => Self::internal_server_error(e),
+            AppError::NodeTimeout(e) => e.into(),
             AppError::Registration(e) => Self::internal_server_error(e),
             AppError::Server(e) => Self::internal_server_error(e),
         }
     }
 }
 
+impl From<NodeTimeout> for BlockfrostError {
+    fn from(err: NodeTimeout) -> Self {
+        Self::node_timeout(err)
+    }
+}
+
 impl From<VarError> for AppError {
     fn from(err: VarError) -> Self {
         AppError::Server(err.to_string())
@@ -82,12 +93,24 @@ impl From<pallas_network::miniprotocols::handshake::Error> for BlockfrostError {
     }
 }
 
+impl From<pallas_network::miniprotocols::chainsync::ClientError> for BlockfrostError {
+    fn from(err: pallas_network::miniprotocols::chainsync::ClientError) -> Self {
+        BlockfrostError::internal_server_error(format!("chainsync::ClientError: {}", err))
+    }
+}
+
 impl From<pallas_network::miniprotocols::localstate::ClientError> for BlockfrostError {
     fn from(err: pallas_network::miniprotocols::localstate::ClientError) -> Self {
         BlockfrostError::internal_server_error(format!("localstate::ClientError: {}", err))
     }
 }
 
+impl From<pallas_network::miniprotocols::txmonitor::Error> for BlockfrostError {
+    fn from(err: pallas_network::miniprotocols::txmonitor::Error) -> Self {
+        BlockfrostError::internal_server_error(format!("txmonitor::Error: {}", err))
+    }
+}
+
 impl From<TryFromSliceError> for BlockfrostError {
     fn from(err: TryFromSliceError) -> Self {
         BlockfrostError::internal_server_error(format!("Hash conversion failed: {}", err))
@@ -117,6 +140,16 @@ impl BlockfrostError {
         }
     }
 
+    /// Our custom 404 error
+    pub fn custom_404(message: String) -> Self {
+        Self {
+            error: "Not Found".to_string(),
+            message,
+            status_code: 404,
+            details: None,
+        }
+    }
+
     /// Our custom 400 error
     pub fn custom_400(message: String) -> Self {
         Self {
@@ -137,6 +170,60 @@ impl BlockfrostError {
         }
     }
 
+    /// Our custom 413 error
+    pub fn payload_too_large(message: String) -> Self {
+        Self {
+            error: "Payload Too Large".to_string(),
+            message,
+            status_code: 413,
+            details: None,
+        }
+    }
+
+    /// Our custom 401 error, for operator routes requested without the token
+    pub fn unauthorized(message: String) -> Self {
+        Self {
+            error: "Unauthorized".to_string(),
+            message,
+            status_code: 401,
+            details: None,
+        }
+    }
+
+    /// Our custom 429 error, for clients over their rate limit
+    pub fn too_many_requests(message: String) -> Self {
+        Self {
+            error: "Too Many Requests".to_string(),
+            message,
+            status_code: 429,
+            details: None,
+        }
+    }
+
+    /// Our custom 503 error, for a node that stopped answering. It’s passed on
+    /// as is, so that clients can tell it apart from a failure to retry later.
+    pub fn node_timeout(timeout: NodeTimeout) -> Self {
+        Self {
+            error: "Service Unavailable".to_string(),
+            message: timeout.to_string(),
+            status_code: 503,
+            details: Some(serde_json::json!({
+                "timeout": timeout.operation,
+                "after_secs": timeout.after.as_secs(),
+            })),
+        }
+    }
+
+    /// Our custom 503 error, for when we’re too busy to take the request.
+    pub fn service_unavailable(message: String) -> Self {
+        Self {
+            error: "Service Unavailable".to_string(),
+            message,
+            status_code: 503,
+            details: None,
+        }
+    }
+
     /// This error is converted in middleware to internal_server_error_user
     pub fn internal_server_error(error: String) -> Self {
         Self {
@@ -174,7 +261,10 @@ impl IntoResponse for BlockfrostError {
             400 => StatusCode::BAD_REQUEST,
             404 => StatusCode::NOT_FOUND,
             405 => StatusCode::METHOD_NOT_ALLOWED,
+            413 => StatusCode::PAYLOAD_TOO_LARGE,
+            429 => StatusCode::TOO_MANY_REQUESTS,
             500 => StatusCode::INTERNAL_SERVER_ERROR,
+            503 => StatusCode::SERVICE_UNAVAILABLE,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
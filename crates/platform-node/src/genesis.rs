@@ -0,0 +1,231 @@
+use crate::{config::Network, AppError};
+use chrono::DateTime;
+use pallas_traverse::{wellknown::GenesisValues, Era};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+/// What’s known of a named network without asking its node. Future named
+/// testnets only need one of these, and a [`Network`] variant.
+#[derive(Debug, Clone, Copy)]
+pub struct Preset {
+    pub genesis: fn() -> GenesisValues,
+    /// The era its nodes are in, to tag transactions with where they can’t
+    /// be asked, as over a relay
+    pub era: Era,
+}
+
+/// [`None`] for a custom network, see [`genesis_values`].
+pub fn preset(network: &Network) -> Option<Preset> {
+    let (genesis, era): (fn() -> GenesisValues, _) = match network {
+        Network::Mainnet => (GenesisValues::mainnet, Era::Conway),
+        Network::Preprod => (GenesisValues::preprod, Era::Conway),
+        Network::Preview => (GenesisValues::preview, Era::Conway),
+        Network::Sanchonet => (sanchonet, Era::Conway),
+        Network::Custom => return None,
+    };
+
+    Some(Preset { genesis, era })
+}
+
+/// Sanchonet hard forks right into Conway at epoch 0.
+fn sanchonet() -> GenesisValues {
+    GenesisValues {
+        magic: 4,
+        network_id: 0,
+        byron_epoch_length: 86400,
+        byron_slot_length: 1,
+        byron_known_slot: 0,
+        byron_known_hash: String::new(),
+        byron_known_time: 1686789000,
+        shelley_epoch_length: 86400,
+        shelley_slot_length: 1,
+        shelley_known_slot: 0,
+        shelley_known_hash: String::new(),
+        shelley_known_time: 1686789000,
+    }
+}
+
+/// The parts of a `shelley-genesis.json` that slots and epochs are counted by.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ShelleyGenesis {
+    network_magic: u64,
+    network_id: String,
+    epoch_length: u32,
+    /// In seconds, possibly fractional
+    slot_length: f64,
+    system_start: String,
+}
+
+/// The slot and time arithmetic of `network`: hardcoded for the well-known
+/// ones, and read from `shelley_genesis` for a custom one.
+pub fn genesis_values(
+    network: &Network,
+    network_magic: Option<u64>,
+    shelley_genesis: Option<&Path>,
+) -> Result<GenesisValues, AppError> {
+    let genesis = match (preset(network), network_magic, shelley_genesis) {
+        (Some(preset), None, None) => (preset.genesis)(),
+        (None, Some(network_magic), Some(path)) => {
+            let genesis = read_shelley_genesis(path)?;
+
+            if genesis.magic != network_magic {
+                return Err(AppError::Server(format!(
+                    "{}: networkMagic is {}, but --network-magic is {}",
+                    path.display(),
+                    genesis.magic,
+                    network_magic
+                )));
+            }
+
+            genesis
+        }
+        (None, _, _) => {
+            return Err(AppError::Server(
+                "--network custom needs both --network-magic and --shelley-genesis".to_string(),
+            ))
+        }
+        (Some(_), _, _) => {
+            return Err(AppError::Server(format!(
+                "--network-magic and --shelley-genesis are only for --network custom, not {}",
+                network
+            )))
+        }
+    };
+
+    Ok(genesis)
+}
+
+/// A network starting right in Shelley, as private testnets and devnets do,
+/// with no Byron epochs before slot 0.
+fn read_shelley_genesis(path: &Path) -> Result<GenesisValues, AppError> {
+    let invalid = |reason: String| AppError::Server(format!("{}: {}", path.display(), reason));
+
+    let json = fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+    let genesis: ShelleyGenesis =
+        serde_json::from_str(&json).map_err(|e| invalid(format!("Invalid genesis: {}", e)))?;
+
+    // `GenesisValues` counts in whole seconds
+    if genesis.slot_length < 1.0 || genesis.slot_length.fract() != 0.0 {
+        return Err(invalid(format!(
+            "slotLength must be a whole number of seconds, got {}",
+            genesis.slot_length
+        )));
+    }
+    if genesis.network_id != "Testnet" {
+        return Err(invalid(format!(
+            "networkId must be Testnet for a custom network, got {}",
+            genesis.network_id
+        )));
+    }
+
+    let system_start = DateTime::parse_from_rfc3339(&genesis.system_start)
+        .map_err(|e| invalid(format!("Invalid systemStart: {}", e)))?
+        .timestamp()
+        .try_into()
+        .map_err(|_| invalid("systemStart is before 1970".to_string()))?;
+    let slot_length = genesis.slot_length as u32;
+    // In seconds, as `SlotNo::epoch` takes it
+    let epoch_length = genesis
+        .epoch_length
+        .checked_mul(slot_length)
+        .ok_or_else(|| invalid("epochLength is too long".to_string()))?;
+
+    Ok(GenesisValues {
+        magic: genesis.network_magic,
+        network_id: 0,
+        byron_epoch_length: epoch_length,
+        byron_slot_length: slot_length,
+        byron_known_slot: 0,
+        byron_known_hash: String::new(),
+        byron_known_time: system_start,
+        shelley_epoch_length: epoch_length,
+        shelley_slot_length: slot_length,
+        shelley_known_slot: 0,
+        shelley_known_hash: String::new(),
+        shelley_known_time: system_start,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbor::slot::SlotNo;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn write_genesis(name: &str, slot_length: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "bf-shelley-genesis-{}-{}.json",
+            name,
+            std::process::id()
+        ));
+        fs::write(
+            &path,
+            format!(
+                r#"{{
+                    "activeSlotsCoeff": 0.05,
+                    "epochLength": 500,
+                    "networkId": "Testnet",
+                    "networkMagic": 42,
+                    "slotLength": {},
+                    "systemStart": "2024-01-01T00:00:00Z"
+                }}"#,
+                slot_length
+            ),
+        )
+        .unwrap();
+
+        path
+    }
+
+    #[test]
+    fn test_custom_network() {
+        let path = write_genesis("custom", "2");
+        let genesis = genesis_values(&Network::Custom, Some(42), Some(&path)).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(genesis.magic, 42);
+        assert_eq!(SlotNo(0).to_time(&genesis), Some(1704067200));
+        assert_eq!(SlotNo(1250).to_time(&genesis), Some(1704067200 + 2500));
+        assert_eq!(SlotNo::from_time(&genesis, 1704067201), Some(SlotNo(0)));
+        assert_eq!(SlotNo(1250).epoch(&genesis), (2, 250));
+    }
+
+    #[rstest]
+    #[case(Network::Custom, Some(43), "but --network-magic is 43")]
+    #[case(Network::Custom, None, "needs both")]
+    #[case(Network::Preview, Some(42), "only for --network custom, not preview")]
+    fn test_custom_network_invalid(
+        #[case] network: Network,
+        #[case] network_magic: Option<u64>,
+        #[case] error: &str,
+    ) {
+        let path = write_genesis(&format!("invalid-{}", error.len()), "1");
+        let err = genesis_values(&network, network_magic, Some(&path)).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert!(err.to_string().contains(error), "{}", err);
+    }
+
+    #[test]
+    fn test_sanchonet() {
+        let genesis = genesis_values(&Network::Sanchonet, None, None).unwrap();
+
+        assert_eq!(genesis.magic, 4);
+        // The first block of epoch 1
+        assert_eq!(SlotNo(86400).to_time(&genesis), Some(1686875400));
+        assert_eq!(SlotNo(86400).epoch(&genesis), (1, 0));
+    }
+
+    #[test]
+    fn test_sub_second_slots() {
+        let path = write_genesis("sub-second", "0.1");
+        let err = read_shelley_genesis(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert!(err
+            .to_string()
+            .contains("slotLength must be a whole number"));
+    }
+}
@@ -0,0 +1,14 @@
+//! The node client of the platform: a pool of connections to the local
+//! `cardano-node`, the queries and submissions over them, and what they
+//! need besides, i.e. their configuration, errors and on-disk state.
+
+pub mod cbor;
+pub mod config;
+pub mod errors;
+pub mod genesis;
+pub mod node;
+pub mod retry;
+pub mod storage;
+
+pub use errors::{AppError, BlockfrostError};
+pub use node::pool::NodePool;
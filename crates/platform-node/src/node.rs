@@ -0,0 +1,18 @@
+pub mod api;
+pub mod chain;
+pub mod chainsync;
+pub mod connection;
+pub mod era_cache;
+pub mod era_history;
+pub mod governance;
+pub mod health;
+pub mod mempool;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod pool;
+pub mod pool_manager;
+pub mod protocol_params;
+pub mod relay;
+pub mod sync_progress;
+pub mod timeouts;
+pub mod transactions;
@@ -0,0 +1,190 @@
+use super::{
+    chain::{AccountState, ChainTip, NetworkState, StakePool},
+    connection::NodeClient,
+    governance::{CommitteeState, DRepState, GovernanceParams, Proposal},
+    protocol_params::ProtocolParams,
+    sync_progress::NodeInfo,
+    transactions::SubmitTimings,
+};
+use crate::{cbor::haskell_types::Credential, BlockfrostError};
+use pallas_crypto::hash::Hash;
+use pallas_primitives::conway::DRep;
+use std::{collections::BTreeSet, future::Future, time::Duration};
+
+/// What the HTTP handlers need from a node, so that they can be tested
+/// against a [`super::mock::MockNode`] instead of a live socket.
+pub trait NodeApi: Send {
+    fn submit_transaction_timed(
+        &mut self,
+        tx: Vec<u8>,
+        timings: &mut SubmitTimings,
+    ) -> impl Future<Output = Result<String, BlockfrostError>> + Send;
+
+    fn sync_progress(&mut self) -> impl Future<Output = Result<NodeInfo, BlockfrostError>> + Send;
+
+    fn protocol_params(
+        &mut self,
+    ) -> impl Future<Output = Result<ProtocolParams, BlockfrostError>> + Send;
+
+    fn chain_tip(&mut self) -> impl Future<Output = Result<ChainTip, BlockfrostError>> + Send;
+
+    fn network_state(
+        &mut self,
+    ) -> impl Future<Output = Result<NetworkState, BlockfrostError>> + Send;
+
+    fn stake_pools(&mut self)
+        -> impl Future<Output = Result<Vec<Vec<u8>>, BlockfrostError>> + Send;
+
+    fn stake_pool(
+        &mut self,
+        pool: Vec<u8>,
+    ) -> impl Future<Output = Result<Option<StakePool>, BlockfrostError>> + Send;
+
+    fn account_state(
+        &mut self,
+        credential: Credential,
+    ) -> impl Future<Output = Result<Option<AccountState>, BlockfrostError>> + Send;
+
+    fn mempool_tx_hashes(
+        &mut self,
+    ) -> impl Future<Output = Result<BTreeSet<Hash<32>>, BlockfrostError>> + Send;
+
+    fn unspent_tx_hashes(
+        &mut self,
+        txs: Vec<(Hash<32>, u64)>,
+    ) -> impl Future<Output = Result<BTreeSet<Hash<32>>, BlockfrostError>> + Send;
+
+    fn drep_states(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<DRepState>, BlockfrostError>> + Send;
+
+    fn committee_state(
+        &mut self,
+    ) -> impl Future<Output = Result<CommitteeState, BlockfrostError>> + Send;
+
+    fn proposals(&mut self) -> impl Future<Output = Result<Vec<Proposal>, BlockfrostError>> + Send;
+
+    fn governance_params(
+        &mut self,
+    ) -> impl Future<Output = Result<GovernanceParams, BlockfrostError>> + Send;
+
+    fn drep_stake_distribution(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<(DRep, u64)>, BlockfrostError>> + Send;
+
+    fn pool_stake_distribution(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<(Hash<28>, u64)>, BlockfrostError>> + Send;
+
+    fn ping(&mut self) -> impl Future<Output = Result<(), BlockfrostError>> + Send;
+
+    /// How long opening the connection took, if it was opened for this borrow.
+    fn take_connect_duration(&mut self) -> Option<Duration> {
+        None
+    }
+}
+
+impl NodeApi for NodeClient {
+    fn submit_transaction_timed(
+        &mut self,
+        tx: Vec<u8>,
+        timings: &mut SubmitTimings,
+    ) -> impl Future<Output = Result<String, BlockfrostError>> + Send {
+        NodeClient::submit_transaction_timed(self, tx, timings)
+    }
+
+    fn sync_progress(&mut self) -> impl Future<Output = Result<NodeInfo, BlockfrostError>> + Send {
+        NodeClient::sync_progress(self)
+    }
+
+    fn protocol_params(
+        &mut self,
+    ) -> impl Future<Output = Result<ProtocolParams, BlockfrostError>> + Send {
+        NodeClient::protocol_params(self)
+    }
+
+    fn chain_tip(&mut self) -> impl Future<Output = Result<ChainTip, BlockfrostError>> + Send {
+        NodeClient::chain_tip(self)
+    }
+
+    fn network_state(
+        &mut self,
+    ) -> impl Future<Output = Result<NetworkState, BlockfrostError>> + Send {
+        NodeClient::network_state(self)
+    }
+
+    fn stake_pools(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<Vec<u8>>, BlockfrostError>> + Send {
+        NodeClient::stake_pools(self)
+    }
+
+    fn stake_pool(
+        &mut self,
+        pool: Vec<u8>,
+    ) -> impl Future<Output = Result<Option<StakePool>, BlockfrostError>> + Send {
+        NodeClient::stake_pool(self, pool)
+    }
+
+    fn account_state(
+        &mut self,
+        credential: Credential,
+    ) -> impl Future<Output = Result<Option<AccountState>, BlockfrostError>> + Send {
+        NodeClient::account_state(self, credential)
+    }
+
+    fn mempool_tx_hashes(
+        &mut self,
+    ) -> impl Future<Output = Result<BTreeSet<Hash<32>>, BlockfrostError>> + Send {
+        NodeClient::mempool_tx_hashes(self)
+    }
+
+    fn unspent_tx_hashes(
+        &mut self,
+        txs: Vec<(Hash<32>, u64)>,
+    ) -> impl Future<Output = Result<BTreeSet<Hash<32>>, BlockfrostError>> + Send {
+        NodeClient::unspent_tx_hashes(self, txs)
+    }
+
+    fn drep_states(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<DRepState>, BlockfrostError>> + Send {
+        NodeClient::drep_states(self)
+    }
+
+    fn committee_state(
+        &mut self,
+    ) -> impl Future<Output = Result<CommitteeState, BlockfrostError>> + Send {
+        NodeClient::committee_state(self)
+    }
+
+    fn proposals(&mut self) -> impl Future<Output = Result<Vec<Proposal>, BlockfrostError>> + Send {
+        NodeClient::proposals(self)
+    }
+
+    fn governance_params(
+        &mut self,
+    ) -> impl Future<Output = Result<GovernanceParams, BlockfrostError>> + Send {
+        NodeClient::governance_params(self)
+    }
+
+    fn drep_stake_distribution(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<(DRep, u64)>, BlockfrostError>> + Send {
+        NodeClient::drep_stake_distribution(self)
+    }
+
+    fn pool_stake_distribution(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<(Hash<28>, u64)>, BlockfrostError>> + Send {
+        NodeClient::pool_stake_distribution(self)
+    }
+
+    fn ping(&mut self) -> impl Future<Output = Result<(), BlockfrostError>> + Send {
+        NodeClient::ping(self)
+    }
+
+    fn take_connect_duration(&mut self) -> Option<Duration> {
+        NodeClient::take_connect_duration(self)
+    }
+}
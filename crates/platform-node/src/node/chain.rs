@@ -0,0 +1,214 @@
+use super::connection::NodeClient;
+use crate::{
+    cbor::{haskell_types::Credential, slot::SlotNo},
+    BlockfrostError,
+};
+use pallas_codec::utils::{Bytes, Set};
+use pallas_network::miniprotocols::{
+    localstate,
+    localstate::queries_v16::{self, BlockQuery, LedgerQuery, Request, StakeAddr},
+    Point,
+};
+use std::{boxed::Box, collections::BTreeSet};
+
+/// The block at the tip of the node’s chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainTip {
+    pub slot: SlotNo,
+    /// Empty at the origin
+    pub hash: String,
+    pub height: u64,
+    pub epoch: u32,
+    pub era: u16,
+}
+
+/// The supply and stake figures the node’s ledger state gives away.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkState {
+    pub max_supply: u64,
+    /// Stake of the snapshot used for leader election in the current epoch
+    pub active_stake: u64,
+}
+
+/// A registered stake pool, as its registration certificate has it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakePool {
+    pub vrf_key_hash: Vec<u8>,
+    pub pledge: u64,
+    pub cost: u64,
+    /// As a fraction
+    pub margin: (u64, u64),
+    pub reward_account: Vec<u8>,
+    pub owners: Vec<Vec<u8>>,
+    /// Share of the stake distribution, as a fraction, unless the pool has none
+    pub active_size: Option<(u64, u64)>,
+}
+
+/// A registered stake address, as the ledger state has it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountState {
+    /// Rewards available for withdrawal
+    pub rewards: u64,
+    /// Id of the pool it delegates to
+    pub pool: Option<Vec<u8>>,
+}
+
+impl NodeClient {
+    /// Queries the chain point, block height and epoch at once, so that they
+    /// all describe the same block.
+    pub async fn chain_tip(&mut self) -> Result<ChainTip, BlockfrostError> {
+        self.with_statequery(|generic_client: &mut localstate::GenericClient| {
+            Box::pin(async {
+                let era = queries_v16::get_current_era(generic_client).await?;
+                let epoch = queries_v16::get_block_epoch_number(generic_client, era).await?;
+                let chain_point = queries_v16::get_chain_point(generic_client).await?;
+                let block_no = queries_v16::get_chain_block_no(generic_client).await?;
+
+                let hash = match &chain_point {
+                    Point::Origin => String::new(),
+                    Point::Specific(_, hash) => hex::encode(hash),
+                };
+
+                Ok(ChainTip {
+                    slot: SlotNo(chain_point.slot_or_default()),
+                    hash,
+                    height: block_no.block_number.into(),
+                    epoch,
+                    era,
+                })
+            })
+        })
+        .await
+    }
+
+    pub async fn network_state(&mut self) -> Result<NetworkState, BlockfrostError> {
+        self.with_statequery(|generic_client: &mut localstate::GenericClient| {
+            Box::pin(async {
+                let era = queries_v16::get_current_era(generic_client).await?;
+
+                let geneses = queries_v16::get_genesis_config(generic_client, era).await?;
+                let genesis = geneses.first().ok_or_else(|| {
+                    BlockfrostError::internal_server_error(
+                        "Expected at least one genesis".to_string(),
+                    )
+                })?;
+
+                // With no pools given, the totals are over all of them
+                let snapshots =
+                    queries_v16::get_stake_snapshots(generic_client, era, BTreeSet::new()).await?;
+
+                Ok(NetworkState {
+                    max_supply: u64::from(&genesis.max_lovelace_supply),
+                    active_stake: snapshots.snapshots.snapshot_stake_set_total,
+                })
+            })
+        })
+        .await
+    }
+
+    /// Ids, i.e. cold key hashes, of all the registered stake pools.
+    pub async fn stake_pools(&mut self) -> Result<Vec<Vec<u8>>, BlockfrostError> {
+        self.with_statequery(|generic_client: &mut localstate::GenericClient| {
+            Box::pin(async {
+                let era = queries_v16::get_current_era(generic_client).await?;
+
+                // There's no helper for this one in pallas
+                let query =
+                    Request::LedgerQuery(LedgerQuery::BlockQuery(era, BlockQuery::GetStakePools));
+                let (pools,): (Set<Bytes>,) = generic_client.query(query).await?;
+
+                Ok(pools.iter().map(|pool| pool.to_vec()).collect())
+            })
+        })
+        .await
+    }
+
+    /// The registration of the pool with the id `pool`, with its share of the
+    /// stake distribution, or `None` if it’s not registered.
+    pub async fn stake_pool(
+        &mut self,
+        pool: Vec<u8>,
+    ) -> Result<Option<StakePool>, BlockfrostError> {
+        self.with_statequery(move |generic_client: &mut localstate::GenericClient| {
+            Box::pin(async move {
+                let era = queries_v16::get_current_era(generic_client).await?;
+
+                let pool = Bytes::from(pool);
+                let pool_ids = BTreeSet::from([pool.clone()]).into();
+                let params = queries_v16::get_stake_pool_params(generic_client, era, pool_ids)
+                    .await?
+                    .remove(&pool);
+                let Some(params) = params else {
+                    return Ok(None);
+                };
+
+                let distribution = queries_v16::get_stake_distribution(generic_client, era).await?;
+                let active_size = distribution
+                    .pools
+                    .iter()
+                    .find(|(id, _)| *id == pool)
+                    .map(|(_, stake)| (stake.stakes.numerator, stake.stakes.denominator));
+
+                Ok(Some(StakePool {
+                    vrf_key_hash: params.vrf_keyhash.to_vec(),
+                    pledge: u64::from(&params.pledge),
+                    cost: u64::from(&params.cost),
+                    margin: (params.margin.numerator, params.margin.denominator),
+                    reward_account: params.reward_account.to_vec(),
+                    owners: params
+                        .pool_owners
+                        .hashes
+                        .iter()
+                        .map(|owner| owner.to_vec())
+                        .collect(),
+                    active_size,
+                }))
+            })
+        })
+        .await
+    }
+
+    /// The state of the stake address with the `credential`, or `None` if it’s
+    /// not registered.
+    pub async fn account_state(
+        &mut self,
+        credential: Credential,
+    ) -> Result<Option<AccountState>, BlockfrostError> {
+        self.with_statequery(move |generic_client: &mut localstate::GenericClient| {
+            Box::pin(async move {
+                let era = queries_v16::get_current_era(generic_client).await?;
+
+                let stake_addr = stake_addr(&credential);
+                let addrs = BTreeSet::from([stake_addr.clone()]);
+                let filtered =
+                    queries_v16::get_filtered_delegations_rewards(generic_client, era, addrs)
+                        .await?;
+
+                let Some(rewards) = filtered
+                    .rewards
+                    .iter()
+                    .find(|(addr, _)| *addr == stake_addr)
+                    .map(|(_, rewards)| *rewards)
+                else {
+                    return Ok(None);
+                };
+                let pool = filtered
+                    .delegs
+                    .iter()
+                    .find(|(addr, _)| *addr == stake_addr)
+                    .map(|(_, pool)| pool.to_vec());
+
+                Ok(Some(AccountState { rewards, pool }))
+            })
+        })
+        .await
+    }
+}
+
+/// The ledger’s encoding of a stake credential, as in the queries.
+fn stake_addr(credential: &Credential) -> StakeAddr {
+    match credential {
+        Credential::KeyHashObj(hash) => (0, Bytes::from(hash.0.to_vec())).into(),
+        Credential::ScriptHashObj(hash) => (1, Bytes::from(hash.0.to_vec())).into(),
+    }
+}
@@ -0,0 +1,658 @@
+use super::timeouts::{NodeOperation, NodeTimeouts};
+use crate::{
+    cbor::slot::SlotNo,
+    config::NodeConfig,
+    retry::{Attempts, Backoff},
+    storage::{Component, DataDir},
+    AppError, BlockfrostError,
+};
+use pallas_crypto::hash::Hash;
+use pallas_network::{
+    facades::NodeClient as NodeClientFacade,
+    miniprotocols::{
+        chainsync::{N2CClient, NextResponse, Tip},
+        Point,
+    },
+};
+use pallas_traverse::{wellknown::GenesisValues, MultiEraBlock};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// How many of the most recent blocks we keep by default, about an hour’s
+/// worth.
+pub const DEFAULT_WINDOW: usize = 180;
+
+/// How many events a slow subscriber may fall behind before missing some.
+const EVENTS_CAPACITY: usize = 64;
+
+/// Where the follower’s cursor is kept under the data directory.
+const CURSOR_FILE: &str = "cursor.json";
+
+/// How many of the newest points of the window the cursor keeps, so that a
+/// restart still finds an intersection if the newest ones were rolled back
+/// meanwhile.
+const CURSOR_POINTS: usize = 10;
+
+const RECONNECT_BACKOFF: Backoff = Backoff::new(
+    "chain_follower",
+    Duration::from_secs(1),
+    Duration::from_secs(60),
+);
+
+/// A block we followed, with only the hashes of its transactions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockSummary {
+    pub slot: SlotNo,
+    pub hash: Hash<32>,
+    pub height: u64,
+    pub tx_hashes: Vec<Hash<32>>,
+}
+
+impl BlockSummary {
+    pub fn decode(cbor: &[u8]) -> Result<Self, BlockfrostError> {
+        let block = MultiEraBlock::decode(cbor).map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Failed to decode a block: {}", e))
+        })?;
+
+        Ok(Self {
+            slot: SlotNo(block.slot()),
+            hash: block.hash(),
+            height: block.number(),
+            tx_hashes: block.txs().iter().map(|tx| tx.hash()).collect(),
+        })
+    }
+
+    pub fn point(&self) -> Point {
+        Point::Specific(self.slot.into(), self.hash.to_vec())
+    }
+}
+
+/// A block of a [`RecentChain`], with its neighbours in the window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowBlock {
+    pub block: BlockSummary,
+    pub previous: Option<Hash<32>>,
+    pub next: Option<Hash<32>>,
+}
+
+/// What subscribers of a [`RecentChain`] are told, in the node’s order, so
+/// that caches, trackers and streams react to the chain instead of polling.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainEvent {
+    NewBlock(BlockSummary),
+    /// Every block after this point is gone
+    Rollback(Point),
+    /// Right after the [`ChainEvent::NewBlock`] of the first block of a new
+    /// epoch, which may still be rolled back
+    EpochBoundary {
+        epoch: u64,
+        slot: SlotNo,
+    },
+}
+
+/// A rolling window of the most recent blocks of the node’s chain, kept up
+/// to date by a [`ChainFollower`], rollbacks included.
+#[derive(Clone)]
+pub struct RecentChain {
+    window: Arc<RwLock<Window>>,
+    events: broadcast::Sender<ChainEvent>,
+    /// To tell epochs apart, without which there are no epoch boundaries
+    genesis: Option<Arc<GenesisValues>>,
+}
+
+struct Window {
+    /// Oldest first
+    blocks: VecDeque<BlockSummary>,
+    capacity: usize,
+    /// The node’s own tip, as of its last message
+    tip: Option<Tip>,
+}
+
+impl RecentChain {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            window: Arc::new(RwLock::new(Window {
+                blocks: VecDeque::new(),
+                capacity: capacity.max(1),
+                tip: None,
+            })),
+            events: broadcast::channel(EVENTS_CAPACITY).0,
+            genesis: None,
+        }
+    }
+
+    pub fn with_genesis(mut self, genesis: GenesisValues) -> Self {
+        self.genesis = Some(Arc::new(genesis));
+        self
+    }
+
+    /// Gets told about every block rolled forward or back from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ChainEvent> {
+        self.events.subscribe()
+    }
+
+    /// The blocks in the window, oldest first.
+    pub fn blocks(&self) -> Vec<BlockSummary> {
+        self.window
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .blocks
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    pub fn latest(&self) -> Option<BlockSummary> {
+        self.window
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .blocks
+            .back()
+            .cloned()
+    }
+
+    /// Height of the node’s tip, which the window lags behind while catching up.
+    pub fn tip_height(&self) -> Option<u64> {
+        self.window
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .tip
+            .as_ref()
+            .map(|tip| tip.1)
+    }
+
+    /// The block of the window with the transaction of `tx_hash` in it.
+    pub fn find_tx(&self, tx_hash: &Hash<32>) -> Option<BlockSummary> {
+        self.window
+            .read()
+            .unwrap()
+            .blocks
+            .iter()
+            .find(|block| block.tx_hashes.contains(tx_hash))
+            .cloned()
+    }
+
+    /// The block of the window with `hash`.
+    pub fn find_block(&self, hash: &Hash<32>) -> Option<WindowBlock> {
+        let window = self.window.read().unwrap_or_else(|e| e.into_inner());
+        let index = window.blocks.iter().position(|block| block.hash == *hash)?;
+        let hash_at = |index: usize| window.blocks.get(index).map(|block| block.hash);
+
+        Some(WindowBlock {
+            block: window.blocks[index].clone(),
+            previous: index.checked_sub(1).and_then(hash_at),
+            next: hash_at(index + 1),
+        })
+    }
+
+    /// The points of the window, newest first, to pick up from after a
+    /// reconnect.
+    pub fn points(&self) -> Vec<Point> {
+        let window = self.window.read().unwrap_or_else(|e| e.into_inner());
+        window
+            .blocks
+            .iter()
+            .rev()
+            .map(BlockSummary::point)
+            .collect()
+    }
+
+    pub fn roll_forward(&self, block: BlockSummary, tip: Tip) {
+        let boundary = {
+            let mut window = self.window.write().unwrap_or_else(|e| e.into_inner());
+            let boundary = self.genesis.as_ref().and_then(|genesis| {
+                let (epoch, _) = block.slot.epoch(genesis);
+                let previous = window.blocks.back()?;
+                (previous.slot.epoch(genesis).0 != epoch).then_some(ChainEvent::EpochBoundary {
+                    epoch,
+                    slot: block.slot,
+                })
+            });
+
+            window.blocks.push_back(block.clone());
+            while window.blocks.len() > window.capacity {
+                window.blocks.pop_front();
+            }
+            window.tip = Some(tip);
+            boundary
+        };
+
+        // Nobody listening is fine
+        let _ = self.events.send(ChainEvent::NewBlock(block));
+        if let Some(boundary) = boundary {
+            let _ = self.events.send(boundary);
+        }
+    }
+
+    /// Drops the blocks after `point`, or all of them if it’s older than the
+    /// window, as we can’t tell which are still on the chain then.
+    pub fn roll_backward(&self, point: Point, tip: Tip) {
+        {
+            let mut window = self.window.write().unwrap_or_else(|e| e.into_inner());
+            let slot = SlotNo(point.slot_or_default());
+            while window
+                .blocks
+                .back()
+                .is_some_and(|block| block.slot > slot || point == Point::Origin)
+            {
+                window.blocks.pop_back();
+            }
+            if window
+                .blocks
+                .back()
+                .is_some_and(|block| block.point() != point)
+            {
+                window.blocks.clear();
+            }
+            window.tip = Some(tip);
+        }
+
+        let _ = self.events.send(ChainEvent::Rollback(point));
+    }
+}
+
+/// Follows the chain of the local node over a connection of its own, as
+/// ChainSync would otherwise hold a pooled one forever.
+pub struct ChainFollower {
+    socket_path: String,
+    network_magic: u64,
+    timeouts: NodeTimeouts,
+    chain: RecentChain,
+    /// To keep the cursor in, if there’s one
+    data_dir: Option<DataDir>,
+}
+
+impl ChainFollower {
+    pub fn new(config: &NodeConfig, chain: RecentChain, data_dir: Option<DataDir>) -> Self {
+        Self {
+            socket_path: config.socket_path.clone(),
+            network_magic: config.network_magic(),
+            timeouts: NodeTimeouts::from_config(config),
+            chain,
+            data_dir,
+        }
+    }
+
+    /// Starts following the chain in the background, into a window of
+    /// `capacity` blocks, from where a previous run left off in `data_dir`.
+    pub fn spawn(config: &NodeConfig, capacity: usize, data_dir: Option<DataDir>) -> RecentChain {
+        let chain = RecentChain::new(capacity).with_genesis(config.genesis.clone());
+        tokio::spawn(Self::new(config, chain.clone(), data_dir).run());
+
+        chain
+    }
+
+    /// Follows the chain for as long as we run, reconnecting whenever the
+    /// connection fails, e.g. on a node restart.
+    pub async fn run(self) {
+        let mut reconnects = RECONNECT_BACKOFF.start();
+
+        loop {
+            let result = self.connect_and_follow(&mut reconnects).await;
+
+            // Never out of retries, for lack of limits
+            let delay = reconnects.next_delay().unwrap_or(RECONNECT_BACKOFF.max);
+            if let Err(e) = result {
+                warn!("Chain follower: {}, reconnecting in {:?}", e.message, delay);
+            }
+
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Starts the `reconnects` over once connected.
+    async fn connect_and_follow(&self, reconnects: &mut Attempts) -> Result<(), BlockfrostError> {
+        let mut client = self
+            .timeouts
+            .run(
+                NodeOperation::Connect,
+                NodeClientFacade::connect(&self.socket_path, self.network_magic),
+            )
+            .await?
+            .map_err(AppError::from)?;
+
+        info!("Chain follower connected to {}", self.socket_path);
+        reconnects.reset();
+
+        let result = follow(client.chainsync(), &self.chain, self.data_dir.as_ref()).await;
+        client.abort().await;
+
+        result
+    }
+}
+
+/// Picks up from the newest block of the window the node still has, or of
+/// the cursor in `data_dir` with an empty window, or else from its tip, and
+/// applies whatever comes next until the connection fails.
+///
+/// The node starts by rolling back to the intersection, which trims anything
+/// in the window it no longer has.
+async fn follow(
+    client: &mut N2CClient,
+    chain: &RecentChain,
+    data_dir: Option<&DataDir>,
+) -> Result<(), BlockfrostError> {
+    let mut points = chain.points();
+    if points.is_empty() {
+        points = data_dir.map(load_cursor).unwrap_or_default();
+    }
+    let intersection = if points.is_empty() {
+        None
+    } else {
+        client.find_intersect(points).await?.0
+    };
+
+    if intersection.is_none() {
+        client.intersect_tip().await?;
+    }
+
+    loop {
+        match client.request_or_await_next().await? {
+            NextResponse::RollForward(content, tip) => {
+                chain.roll_forward(BlockSummary::decode(&content.0)?, tip)
+            }
+            NextResponse::RollBackward(point, tip) => chain.roll_backward(point, tip),
+            NextResponse::Await => continue,
+        }
+
+        if let Some(data_dir) = data_dir {
+            save_cursor(data_dir, &chain.points());
+        }
+    }
+}
+
+/// A point of the cursor, as kept in the data directory.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct CursorPoint {
+    slot: u64,
+    hash: String,
+}
+
+/// The newest points of the window a previous run saved in `data_dir`, newest
+/// first.
+fn load_cursor(data_dir: &DataDir) -> Vec<Point> {
+    let path = data_dir.path(Component::ChainSync).join(CURSOR_FILE);
+    let Ok(json) = fs::read(path) else {
+        return Vec::new();
+    };
+
+    match serde_json::from_slice::<Vec<CursorPoint>>(&json) {
+        Ok(cursor) => cursor
+            .into_iter()
+            .filter_map(|point| Some(Point::Specific(point.slot, hex::decode(point.hash).ok()?)))
+            .collect(),
+        Err(e) => {
+            warn!("Ignoring unreadable {}: {}", CURSOR_FILE, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Keeps the newest of the window’s `points` in `data_dir`, unless there are
+/// none, as after a rollback past the window the previous ones are still the
+/// best guess.
+fn save_cursor(data_dir: &DataDir, points: &[Point]) {
+    let cursor: Vec<CursorPoint> = points
+        .iter()
+        .take(CURSOR_POINTS)
+        .filter_map(|point| match point {
+            Point::Specific(slot, hash) => Some(CursorPoint {
+                slot: *slot,
+                hash: hex::encode(hash),
+            }),
+            Point::Origin => None,
+        })
+        .collect();
+    if cursor.is_empty() {
+        return;
+    }
+
+    let result = serde_json::to_vec(&cursor)
+        .map_err(|e| e.to_string())
+        .and_then(|json| {
+            data_dir
+                .write(Component::ChainSync, CURSOR_FILE, &json)
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        warn!("Failed to persist the chain follower's cursor: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pallas_network::{
+        miniprotocols::{
+            chainsync::{ClientRequest, N2CServer},
+            PROTOCOL_N2C_CHAIN_SYNC,
+        },
+        multiplexer::{Bearer, Plexer, RunningPlexer},
+    };
+    use pretty_assertions::assert_eq;
+
+    fn block(height: u64) -> BlockSummary {
+        BlockSummary {
+            slot: SlotNo(height * 20),
+            hash: Hash::new([height as u8; 32]),
+            height,
+            tx_hashes: vec![Hash::new([0xf0 + height as u8; 32])],
+        }
+    }
+
+    fn tip(height: u64) -> Tip {
+        Tip(block(height).point(), height)
+    }
+
+    fn chain_of(heights: impl IntoIterator<Item = u64>) -> RecentChain {
+        let chain = RecentChain::new(3);
+        for height in heights {
+            chain.roll_forward(block(height), tip(height));
+        }
+        chain
+    }
+
+    fn heights(chain: &RecentChain) -> Vec<u64> {
+        chain.blocks().iter().map(|block| block.height).collect()
+    }
+
+    #[test]
+    fn test_roll_forward() {
+        let chain = chain_of(1..=4);
+
+        assert_eq!(heights(&chain), vec![2, 3, 4]);
+        assert_eq!(chain.tip_height(), Some(4));
+        assert_eq!(chain.latest(), Some(block(4)));
+        assert_eq!(chain.points()[0], block(4).point());
+        assert_eq!(chain.find_tx(&Hash::new([0xf3; 32])), Some(block(3)));
+        assert_eq!(chain.find_tx(&Hash::new([0xf1; 32])), None);
+        assert_eq!(
+            chain.find_block(&block(3).hash),
+            Some(WindowBlock {
+                block: block(3),
+                previous: Some(block(2).hash),
+                next: Some(block(4).hash),
+            })
+        );
+        assert_eq!(
+            chain.find_block(&block(2).hash).map(|found| found.previous),
+            Some(None)
+        );
+        assert_eq!(chain.find_block(&block(1).hash), None);
+    }
+
+    #[test]
+    fn test_epoch_boundary() {
+        let chain = RecentChain::new(3).with_genesis(GenesisValues::preview());
+        let mut events = chain.subscribe();
+        let at_slot = |height: u64, slot: u64| BlockSummary {
+            slot: SlotNo(slot),
+            ..block(height)
+        };
+
+        // None for the first block seen, as we can't tell
+        chain.roll_forward(at_slot(1, 86_390), tip(1));
+        chain.roll_forward(at_slot(2, 86_399), tip(2));
+        chain.roll_forward(at_slot(3, 86_410), tip(3));
+        chain.roll_forward(at_slot(4, 86_420), tip(4));
+
+        let events: Vec<_> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+        assert_eq!(
+            events,
+            vec![
+                ChainEvent::NewBlock(at_slot(1, 86_390)),
+                ChainEvent::NewBlock(at_slot(2, 86_399)),
+                ChainEvent::NewBlock(at_slot(3, 86_410)),
+                ChainEvent::EpochBoundary {
+                    epoch: 1,
+                    slot: SlotNo(86_410)
+                },
+                ChainEvent::NewBlock(at_slot(4, 86_420)),
+            ]
+        );
+
+        // Nor without a genesis
+        let chain = RecentChain::new(3);
+        let mut events = chain.subscribe();
+        chain.roll_forward(at_slot(1, 86_390), tip(1));
+        chain.roll_forward(at_slot(2, 86_410), tip(2));
+        assert!(std::iter::from_fn(|| events.try_recv().ok())
+            .all(|event| matches!(event, ChainEvent::NewBlock(_))));
+    }
+
+    #[test]
+    fn test_roll_backward() {
+        let chain = chain_of(1..=3);
+        let mut events = chain.subscribe();
+
+        chain.roll_backward(block(2).point(), tip(2));
+        assert_eq!(heights(&chain), vec![1, 2]);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ChainEvent::Rollback(block(2).point())
+        );
+
+        // A fork
+        chain.roll_forward(block(3), tip(3));
+        chain.roll_backward(Point::Specific(40, vec![0xff; 32]), tip(2));
+        assert_eq!(heights(&chain), Vec::<u64>::new());
+
+        let chain = chain_of(5..=7);
+        chain.roll_backward(block(1).point(), tip(1));
+        assert_eq!(heights(&chain), Vec::<u64>::new());
+
+        let chain = chain_of(1..=3);
+        chain.roll_backward(Point::Origin, tip(0));
+        assert_eq!(heights(&chain), Vec::<u64>::new());
+    }
+
+    /// A ChainSync client connected to a node played by the test.
+    fn connected() -> (N2CClient, RunningPlexer, N2CServer, RunningPlexer) {
+        let (ours, theirs) = tokio::net::UnixStream::pair().unwrap();
+
+        let mut plexer = Plexer::new(Bearer::Unix(ours));
+        let client = N2CClient::new(plexer.subscribe_client(PROTOCOL_N2C_CHAIN_SYNC));
+        let ours = plexer.spawn();
+
+        let mut plexer = Plexer::new(Bearer::Unix(theirs));
+        let node = N2CServer::new(plexer.subscribe_server(PROTOCOL_N2C_CHAIN_SYNC));
+        let theirs = plexer.spawn();
+
+        (client, ours, node, theirs)
+    }
+
+    #[tokio::test]
+    async fn test_follow_picks_up() {
+        let (mut client, ours, mut node, theirs) = connected();
+
+        // The node forked off after block 2
+        let node = tokio::spawn(async move {
+            let Some(ClientRequest::Intersect(points)) = node.recv_while_idle().await.unwrap()
+            else {
+                panic!("expected an intersection request");
+            };
+            node.send_intersect_found(block(2).point(), tip(2))
+                .await
+                .unwrap();
+            let request = node.recv_while_idle().await.unwrap();
+            node.send_roll_backward(block(2).point(), tip(2))
+                .await
+                .unwrap();
+            // Asking for more once it rolled back
+            node.recv_while_idle().await.unwrap();
+
+            theirs.abort().await;
+            (points, request)
+        });
+
+        let chain = chain_of(1..=3);
+        let mut events = chain.subscribe();
+        assert!(follow(&mut client, &chain, None).await.is_err());
+        let (points, request) = node.await.unwrap();
+
+        assert_eq!(
+            points,
+            vec![block(3).point(), block(2).point(), block(1).point()]
+        );
+        assert!(matches!(request, Some(ClientRequest::RequestNext)));
+        assert_eq!(heights(&chain), vec![1, 2]);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ChainEvent::Rollback(block(2).point())
+        );
+
+        ours.abort().await;
+    }
+
+    #[tokio::test]
+    async fn test_follow_resumes_from_cursor() {
+        let root = std::env::temp_dir().join(format!("bf-chainsync-{}", std::process::id()));
+        let data_dir = DataDir::new(&root, 1024 * 1024).unwrap();
+        save_cursor(&data_dir, &chain_of(1..=3).points());
+        let (mut client, ours, mut node, theirs) = connected();
+
+        let node = tokio::spawn(async move {
+            let Some(ClientRequest::Intersect(points)) = node.recv_while_idle().await.unwrap()
+            else {
+                panic!("expected an intersection request");
+            };
+            node.send_intersect_found(block(3).point(), tip(4))
+                .await
+                .unwrap();
+            node.recv_while_idle().await.unwrap();
+            node.send_roll_backward(block(3).point(), tip(4))
+                .await
+                .unwrap();
+            node.recv_while_idle().await.unwrap();
+
+            theirs.abort().await;
+            points
+        });
+
+        // A restart, with nothing in the window yet
+        let chain = RecentChain::new(3);
+        assert!(follow(&mut client, &chain, Some(&data_dir)).await.is_err());
+
+        assert_eq!(
+            node.await.unwrap(),
+            vec![block(3).point(), block(2).point(), block(1).point()]
+        );
+        // Still there for the next restart
+        assert_eq!(load_cursor(&data_dir), chain_of(1..=3).points());
+
+        ours.abort().await;
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_decode_rejects() {
+        assert!(BlockSummary::decode(&[0x82, 0x06]).is_err());
+    }
+}
@@ -0,0 +1,519 @@
+use super::{
+    era_cache::EraCache,
+    timeouts::{NodeOperation, NodeTimeouts},
+};
+use crate::{
+    cbor::fallback_decoder::FallbackDecoder, cbor::haskell_types::TxValidationError,
+    storage::DataDir, BlockfrostError,
+};
+use metrics::gauge;
+use pallas_codec::minicbor::{display, Decoder};
+use pallas_network::{
+    facades::NodeClient as NodeClientFacade, miniprotocols::localstate, multiplexer::Error,
+};
+use std::{
+    boxed::Box,
+    collections::BTreeSet,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+
+/// Node-to-client protocol version numbers have this bit set on the wire, to
+/// tell them apart from node-to-node ones.
+const N2C_VERSION_BIT: u64 = 0x8000;
+
+/// Our wrapper around [`pallas_network::facades::NodeClient`]. If you only use
+/// this, you won’t get any deadlocks, inconsistencies, etc.
+pub struct NodeClient {
+    /// Note: this is an [`Option`] *only* to satisfy the borrow checker. It’s
+    /// *always* [`Some`]. See [`<super::pool_manager::NodePoolManager as
+    /// deadpool::managed::Manager>>::recycle`] for an explanation.
+    pub(in crate::node) client: Option<NodeClientFacade>,
+    pub(in crate::node) fallback_decoder: FallbackDecoder,
+    /// Shared by the whole pool
+    pub(in crate::node) era_cache: EraCache,
+    /// Where to keep rejection reasons we failed to decode, if configured.
+    pub(in crate::node) data_dir: Option<DataDir>,
+    /// The node-to-client protocol version negotiated in the handshake, as sent on the wire.
+    pub(in crate::node) n2c_version: u64,
+    /// How long opening this connection took, until it’s first borrowed.
+    pub(in crate::node) connect_duration: Option<Duration>,
+    /// Labels the metrics of this connection, once it’s established.
+    pub(in crate::node) connection_id: Option<ConnectionId>,
+    pub(in crate::node) timeouts: NodeTimeouts,
+    /// Set while a mini-protocol is mid-exchange. If it’s still set when the
+    /// connection is returned to the pool, the operation was cancelled, and
+    /// the node may be waiting on us, so the connection is thrown away.
+    pub(in crate::node) in_flight: Option<NodeOperation>,
+}
+
+/// Hands out the smallest free [`ConnectionId`], so that the number of
+/// per-connection metric labels stays bounded by the pool size.
+#[derive(Clone, Default)]
+pub struct ConnectionIds(Arc<Mutex<BTreeSet<usize>>>);
+
+impl ConnectionIds {
+    pub fn acquire(&self) -> ConnectionId {
+        let mut in_use = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let id = (0..).find(|id| !in_use.contains(id)).unwrap_or_default();
+        in_use.insert(id);
+
+        ConnectionId {
+            id,
+            in_use: self.0.clone(),
+        }
+    }
+}
+
+/// Released for reuse when dropped together with its connection.
+pub struct ConnectionId {
+    id: usize,
+    in_use: Arc<Mutex<BTreeSet<usize>>>,
+}
+
+impl ConnectionId {
+    pub fn label(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl Drop for ConnectionId {
+    fn drop(&mut self) {
+        let mut in_use = self.in_use.lock().unwrap_or_else(|e| e.into_inner());
+        in_use.remove(&self.id);
+    }
+}
+
+impl NodeClient {
+    /// We always have to release the [`localstate::GenericClient`], even on errors,
+    /// otherwise `cardano-node` stalls. If you use this function, it’s handled for you.
+    pub async fn with_statequery<A, F>(&mut self, action: F) -> Result<A, BlockfrostError>
+    where
+        F: for<'a> FnOnce(
+            &'a mut localstate::GenericClient,
+        ) -> Pin<
+            Box<dyn std::future::Future<Output = Result<A, BlockfrostError>> + 'a + Sync + Send>,
+        >,
+    {
+        let timeouts = self.timeouts;
+        self.in_flight = Some(NodeOperation::Statequery);
+
+        // Acquire the client, and run the action within the timeout
+        let client = self.client.as_mut().unwrap().statequery();
+        let result = timeouts
+            .run(NodeOperation::Statequery, async {
+                client.acquire(None).await?;
+                Ok::<_, BlockfrostError>(action(client).await)
+            })
+            .await;
+
+        let result = match result {
+            // Always release the client, even if action fails
+            Ok(Ok(result)) => {
+                match timeouts
+                    .run(NodeOperation::Statequery, client.send_release())
+                    .await
+                {
+                    Ok(Ok(())) => self.in_flight = None,
+                    Ok(Err(e)) => warn!("Failed to release client: {:?}", e),
+                    Err(e) => warn!("Failed to release client: {}", e),
+                }
+                result
+            }
+            Ok(Err(e)) => Err(e),
+            // Releasing needs the node’s reply to the query in flight first.
+            // Closing the connection releases the acquired state on its side.
+            Err(timeout) => {
+                warn!("{}, closing the connection", timeout);
+                self.close();
+                Err(timeout.into())
+            }
+        };
+
+        if result.is_ok() {
+            self.record_success();
+        }
+
+        result
+    }
+
+    /// Closes the connection, e.g. after a timeout left a mini-protocol in an
+    /// unknown state. The pool replaces it on the next borrow.
+    pub(in crate::node) fn close(&mut self) {
+        if let Some(client) = self.client.take() {
+            // `abort` doesn’t really wait for anything, but it’s `async`. With
+            // no runtime left, the multiplexer tasks are already gone anyway.
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                runtime.spawn(client.abort());
+            }
+        }
+    }
+
+    /// Whether the connection is still fit to be lent out again.
+    pub fn is_reusable(&self) -> bool {
+        self.client.is_some() && self.in_flight.is_none()
+    }
+
+    /// Marks the connection as alive now, for telling a flapping node socket
+    /// apart from an idle one.
+    pub(in crate::node) fn record_success(&self) {
+        if let Some(id) = &self.connection_id {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            gauge!("cardano_node_last_success_timestamp_seconds", "connection" => id.label())
+                .set(now.as_secs_f64());
+        }
+    }
+
+    /// The negotiated node-to-client protocol version, e.g. `16` for `NodeToClientV_16`.
+    pub fn n2c_version(&self) -> u64 {
+        self.n2c_version & !N2C_VERSION_BIT
+    }
+
+    /// How long opening the connection took, if it was opened for this borrow.
+    pub fn take_connect_duration(&mut self) -> Option<Duration> {
+        self.connect_duration.take()
+    }
+
+    /// Queries that the node doesn’t know make it kill the whole connection
+    /// with a protocol violation. Call this before sending any query newer than
+    /// `NodeToClientV_10`, the oldest version we negotiate.
+    pub fn require_n2c_version(&self, minimum: u64, query: &str) -> Result<(), BlockfrostError> {
+        if self.n2c_version() >= minimum {
+            Ok(())
+        } else {
+            Err(BlockfrostError::custom_400(format!(
+                "{} requires a node supporting NodeToClientV_{} or newer, but the connected node only supports NodeToClientV_{}",
+                query,
+                minimum,
+                self.n2c_version()
+            )))
+        }
+    }
+
+    /// Pings the node, e.g. to see if the connection is still alive.
+    pub async fn ping(&mut self) -> Result<(), BlockfrostError> {
+        // FIXME: we should be able to use `miniprotocols::keepalive`
+        // (cardano-cli does), but for some reason it’s not added to
+        // `NodeClient`? Let’s try to acquire a local state client instead:
+
+        self.with_statequery(|_| Box::pin(async { Ok(()) })).await
+    }
+
+    pub fn try_decode_error(buffer: &[u8]) -> Result<TxValidationError, Error> {
+        let maybe_error = Decoder::new(buffer[2..].as_ref()).decode();
+
+        match maybe_error {
+            Ok(error) => Ok(error),
+            Err(err) => {
+                let buffer_display = display(buffer);
+                warn!(
+                    "Failed to decode error: {:?}, buffer: {}",
+                    err, buffer_display
+                );
+
+                // Decoding failures are not errors, but some missing implementation or mis-implementations on our side.
+                // A decoding failure is a bug in our code, not a bug in the node.
+                // It should not effect the program flow, but should be logged and reported.
+                Err(Error::Decoding(err.to_string()))
+            }
+        }
+    }
+}
+
+/// Without this, a [`NodeClient`] abandoned mid-operation (a panic, or a
+/// cancelled request future) would leave its multiplexer tasks running, and
+/// with them the socket, until the node hangs up.
+impl Drop for NodeClient {
+    fn drop(&mut self) {
+        gauge!("cardano_node_connections").decrement(1);
+
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::cbor::haskell_types::{ApplyEraTxErr, ApplyTxErr, ShelleyBasedEra};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drop_closes_connection() {
+        use pallas_network::multiplexer::Bearer;
+        use tokio::io::AsyncReadExt;
+
+        let (ours, mut node_side) = tokio::net::UnixStream::pair().unwrap();
+
+        let client = NodeClient {
+            client: Some(NodeClientFacade::new(Bearer::Unix(ours))),
+            fallback_decoder: FallbackDecoder::disconnected(),
+            era_cache: EraCache::default(),
+            data_dir: None,
+            n2c_version: 0,
+            connect_duration: None,
+            connection_id: None,
+            timeouts: NodeTimeouts::default(),
+            in_flight: None,
+        };
+        drop(client);
+
+        // Only once both multiplexer tasks are gone is the socket closed
+        let mut buf = [0u8; 1];
+        let read =
+            tokio::time::timeout(std::time::Duration::from_secs(5), node_side.read(&mut buf))
+                .await
+                .expect("the connection should be closed on drop");
+
+        assert_eq!(read.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_connection_ids_are_reused() {
+        let ids = ConnectionIds::default();
+
+        let first = ids.acquire();
+        let second = ids.acquire();
+        assert_eq!((first.label(), second.label()), ("0".into(), "1".into()));
+
+        drop(first);
+        let third = ids.acquire();
+        let fourth = ids.acquire();
+        assert_eq!((third.label(), fourth.label()), ("0".into(), "2".into()));
+    }
+
+    #[test]
+    fn test_try_decode_error() {
+        assert_decoding(
+            "8202818206828201820083061b00000002362a77301b0000000253b9c11d8201820083051a00028bfd18ad", 2
+        );
+        assert_decoding("8202818206828201820083051a000151351a00074b8582076162", 2);
+    }
+    #[test]
+    fn test_try_decode_scripts_not_paid() {
+        let buffer = hex::decode(
+            concat!(
+                "8202818206818201820082 0d",
+                "a1825820222222222222222222222222222222222222222222222222222222222222222200",
+                "a200581d6011111111111111111111111111111111111111111111111111111111011a000f4240",
+            )
+            .replace(' ', ""),
+        )
+        .unwrap();
+
+        match NodeClient::try_decode_error(&buffer) {
+            Ok(TxValidationError::ShelleyTxValidationError {
+                error: ApplyTxErr(errors),
+                ..
+            }) => assert_eq!(
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+                vec![concat!(
+                    "UtxowFailure (UtxoFailure (ScriptsNotPaidUTxO (UTxO (fromList [(",
+                    "TxIn (TxId {unTxId = SafeHash \"2222222222222222222222222222222222222222222222222222222222222222\"}) (TxIx {unTxIx = 0}),",
+                    "(Addr Testnet (KeyHashObj (KeyHash {unKeyHash = \"11111111111111111111111111111111111111111111111111111111\"})) StakeRefNull,",
+                    "MaryValue (Coin 1000000) (MultiAsset (fromList [])),NoDatum,SNothing)",
+                    ")]))))"
+                )]
+            ),
+            other => panic!("Expected ShelleyTxValidationError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_try_decode_output_too_small() {
+        let buffer = hex::decode(
+            concat!(
+                "8202818206818201820082 15",
+                "81 82 82581d6011111111111111111111111111111111111111111111111111111111 01 1a000d1658",
+            )
+            .replace(' ', ""),
+        )
+        .unwrap();
+
+        match NodeClient::try_decode_error(&buffer) {
+            Ok(TxValidationError::ShelleyTxValidationError {
+                error: ApplyTxErr(errors),
+                ..
+            }) => assert_eq!(
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+                vec![concat!(
+                    "UtxowFailure (UtxoFailure (BabbageOutputTooSmallUTxO [(",
+                    "(Addr Testnet (KeyHashObj (KeyHash {unKeyHash = \"11111111111111111111111111111111111111111111111111111111\"})) StakeRefNull,",
+                    "MaryValue (Coin 1) (MultiAsset (fromList [])),NoDatum,SNothing),",
+                    "Coin 857688)]))"
+                )]
+            ),
+            other => panic!("Expected ShelleyTxValidationError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_try_decode_collect_errors() {
+        let buffer = hex::decode(
+            concat!(
+                "8202818206818201820082008201 82",
+                "82008200825820333333333333333333333333333333333333333333333333333333333333333301",
+                "8201581c44444444444444444444444444444444444444444444444444444444",
+            )
+            .replace(' ', ""),
+        )
+        .unwrap();
+
+        match NodeClient::try_decode_error(&buffer) {
+            Ok(TxValidationError::ShelleyTxValidationError {
+                error: ApplyTxErr(errors),
+                ..
+            }) => assert_eq!(
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+                vec![concat!(
+                    "UtxowFailure (UtxoFailure (UtxosFailure (CollectErrors [",
+                    "NoRedeemer (ConwaySpending (AsItem {unAsItem = TxIn (TxId {unTxId = SafeHash \"3333333333333333333333333333333333333333333333333333333333333333\"}) (TxIx {unTxIx = 1})})),",
+                    "NoWitness (ScriptHash \"44444444444444444444444444444444444444444444444444444444\")",
+                    "])))"
+                )]
+            ),
+            other => panic!("Expected ShelleyTxValidationError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_try_decode_babbage_error() {
+        // ValueNotConservedUTxO
+        assert_babbage_decoding(
+            "8202818205818200820282018305 1a00989680 1a004c4b40",
+            &["UtxowFailure (UtxoFailure (AlonzoInBabbageUtxoPredFailure (ValueNotConservedUTxO"],
+        );
+        // BadInputsUTxO and MissingVKeyWitnessesUTXOW
+        assert_babbage_decoding(
+            "8202818205828200820282018200818258200f00000000000000000000000000000000000000000000000000000000000001 03\
+             8200820182008201 81581c00000000000000000000000000000000000000000000000000000001",
+            &[
+                "UtxowFailure (UtxoFailure (AlonzoInBabbageUtxoPredFailure (BadInputsUTxO (TxIn (TxId {unTxId = SafeHash \"0f00000000000000000000000000000000000000000000000000000000000001\"}) (TxIx {unTxIx = 3})))))",
+                "UtxowFailure (AlonzoInBabbageUtxowPredFailure (ShelleyInAlonzoUtxowPredfailure (MissingVKeyWitnessesUTXOW (00000000000000000000000000000000000000000000000000000001))))",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_try_decode_pre_babbage_errors() {
+        // ExpiredUTxO
+        assert_era_decoding(
+            "820281820181820082048301 1903e8 1907d0",
+            "ShelleyBasedEraShelley",
+            &["UtxowFailure (UtxoFailure (ExpiredUTxO (1000, 2000)))"],
+        );
+        // TriesToForgeADA, with the rules of Allegra
+        assert_era_decoding(
+            "82028182038182008204810b",
+            "ShelleyBasedEraMary",
+            &["UtxowFailure (UtxoFailure (TriesToForgeADA))"],
+        );
+        // InputSetEmptyUTxO and DelegateeNotRegisteredDELEG
+        assert_era_decoding(
+            "820281820482 820082008204 8103 \
+             82018200 581c00000000000000000000000000000000000000000000000000000001",
+            "ShelleyBasedEraAlonzo",
+            &[
+                "UtxowFailure (ShelleyInAlonzoUtxowPredfailure (UtxoFailure (InputSetEmptyUTxO)))",
+                "DelegsFailure (DelegateeNotRegisteredDELEG (00000000000000000000000000000000000000000000000000000001))",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_try_decode_byron_error() {
+        // UTxOMissingInput
+        assert_byron_decoding(
+            "8202818200820082018200 8200d8185824825820\
+             0f00000000000000000000000000000000000000000000000000000000000001 00",
+            "MempoolTxErr (UTxOValidationUTxOError (UTxOMissingInput (TxInUtxo {txInHash = 0f00000000000000000000000000000000000000000000000000000000000001, txInIndex = 0})))",
+        );
+        // TxValidationFeeTooSmall
+        assert_byron_decoding(
+            "820281820082008200840180 1a000f4240 1a00030d40",
+            "MempoolTxErr (UTxOValidationTxValidationError (TxValidationFeeTooSmall (80) (Lovelace {getLovelace = 1000000}) (Lovelace {getLovelace = 200000})))",
+        );
+    }
+
+    fn assert_byron_decoding(cbor_hex: &str, expected: &str) {
+        let buffer = hex::decode(cbor_hex.replace([' ', '\n'], "")).unwrap();
+
+        match NodeClient::try_decode_error(&buffer) {
+            Ok(error @ TxValidationError::ByronTxValidationError { .. }) => assert_eq!(
+                serde_json::to_value(&error).unwrap(),
+                serde_json::json!({
+                    "kind": "ByronTxValidationError",
+                    "error": expected,
+                })
+            ),
+            Err(error) => panic!("Failed to decode cbor: {:?}, error: {:?}", cbor_hex, error),
+            _ => panic!("Expected ByronTxValidationError"),
+        }
+    }
+
+    fn assert_era_decoding(cbor_hex: &str, era: &str, expected: &[&str]) {
+        let buffer = hex::decode(cbor_hex.replace([' ', '\n'], "")).unwrap();
+
+        match NodeClient::try_decode_error(&buffer) {
+            Ok(error) => assert_eq!(
+                serde_json::to_value(&error).unwrap(),
+                serde_json::json!({
+                    "kind": "ShelleyTxValidationError",
+                    "error": expected,
+                    "era": era,
+                })
+            ),
+            Err(error) => panic!("Failed to decode cbor: {:?}, error: {:?}", cbor_hex, error),
+        }
+    }
+
+    fn assert_babbage_decoding(cbor_hex: &str, expected: &[&str]) {
+        let buffer = hex::decode(cbor_hex.replace([' ', '\n'], "")).unwrap();
+
+        match NodeClient::try_decode_error(&buffer) {
+            Ok(TxValidationError::BabbageTxValidationError {
+                error: ApplyEraTxErr(errors),
+                era,
+            }) => {
+                assert_eq!(era, ShelleyBasedEra::ShelleyBasedEraBabbage);
+                assert_eq!(errors.len(), expected.len(), "Errors count mismatch");
+
+                for (error, expected) in errors.iter().zip(expected) {
+                    assert!(
+                        error.to_string().starts_with(expected),
+                        "{} doesn't start with {}",
+                        error,
+                        expected
+                    );
+                }
+            }
+            Err(error) => panic!("Failed to decode cbor: {:?}, error: {:?}", cbor_hex, error),
+            _ => panic!("Expected BabbageTxValidationError"),
+        }
+    }
+
+    fn assert_decoding(cbor_hex: &str, error_count: usize) {
+        let buffer = hex::decode(cbor_hex).unwrap();
+
+        let error = NodeClient::try_decode_error(&buffer);
+
+        match error {
+            Ok(TxValidationError::ShelleyTxValidationError {
+                error: ApplyTxErr(errors),
+                era,
+            }) => {
+                assert!(
+                    era == ShelleyBasedEra::ShelleyBasedEraConway,
+                    "Expected ShelleyBasedEraConway"
+                );
+                assert_eq!(errors.len(), error_count, "Errors count mismatch",);
+            }
+            Err(error) => panic!("Failed to decode cbor: {:?}, error: {:?}", cbor_hex, error),
+            _ => panic!("Expected ShelleyTxValidationError"),
+        }
+    }
+}
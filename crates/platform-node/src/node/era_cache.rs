@@ -0,0 +1,119 @@
+use super::chainsync::{ChainEvent, RecentChain};
+use pallas_codec::minicbor::Decoder;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::RecvError;
+
+/// The node’s current era, as its hard fork combinator index, so that not
+/// every submission has to ask for it first. It only changes at an epoch
+/// boundary, and the node tells us when we got it wrong, see
+/// [`is_era_mismatch`].
+#[derive(Clone, Default)]
+pub struct EraCache(Arc<Mutex<Option<u16>>>);
+
+impl EraCache {
+    pub fn get(&self) -> Option<u16> {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set(&self, era: u16) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(era);
+    }
+
+    pub fn invalidate(&self) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Forgets the era whenever `chain` enters a new epoch, which a hard fork
+    /// may come with.
+    pub fn invalidate_on(&self, chain: &RecentChain) {
+        let mut events = chain.subscribe();
+        let cache = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(ChainEvent::NewBlock(_) | ChainEvent::Rollback(_)) => {}
+                    Ok(ChainEvent::EpochBoundary { .. }) | Err(RecvError::Lagged(_)) => {
+                        cache.invalidate()
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+    }
+}
+
+/// Whether a rejection `reason` (without Pallas’ extra 2 bytes) is the hard
+/// fork combinator’s `HardForkApplyTxErrWrongEra`, i.e. the transaction was
+/// tagged with another era than the node’s. Other rejections are a single
+/// `[era, error]` pair, this one is the two mismatched eras.
+pub fn is_era_mismatch(reason: &[u8]) -> bool {
+    Decoder::new(reason).array().ok() == Some(Some(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cbor::slot::SlotNo, node::chainsync::BlockSummary};
+    use pallas_crypto::hash::Hash;
+    use pallas_network::miniprotocols::{chainsync::Tip, Point};
+    use pallas_traverse::wellknown::GenesisValues;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[test]
+    fn test_cache() {
+        let cache = EraCache::default();
+        assert_eq!(cache.get(), None);
+
+        cache.clone().set(6);
+        assert_eq!(cache.get(), Some(6));
+
+        cache.invalidate();
+        assert_eq!(cache.get(), None);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_on_epoch_boundary() {
+        let chain = RecentChain::new(3).with_genesis(GenesisValues::preview());
+        let cache = EraCache::default();
+        cache.invalidate_on(&chain);
+        let roll_forward = |slot: u64| {
+            chain.roll_forward(
+                BlockSummary {
+                    slot: SlotNo(slot),
+                    hash: Hash::new([0; 32]),
+                    height: slot,
+                    tx_hashes: vec![],
+                },
+                Tip(Point::Origin, slot),
+            )
+        };
+
+        cache.set(6);
+        roll_forward(86_390);
+        roll_forward(86_399);
+        tokio::task::yield_now().await;
+        assert_eq!(cache.get(), Some(6));
+
+        roll_forward(86_410);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while cache.get().is_some() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("the era should be forgotten at the epoch boundary");
+    }
+
+    #[rstest]
+    // `[[6, "Conway"], [5, "Babbage"]]`
+    #[case("82820666436f6e77617982056742616262616765", true)]
+    // `[[6, …]]`, an ordinary Conway rejection
+    #[case("81820681820080", false)]
+    #[case("", false)]
+    fn test_is_era_mismatch(#[case] reason: &str, #[case] mismatch: bool) {
+        assert_eq!(is_era_mismatch(&hex::decode(reason).unwrap()), mismatch);
+    }
+}
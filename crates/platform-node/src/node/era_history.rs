@@ -0,0 +1,250 @@
+use crate::cbor::slot::SlotNo;
+use pallas_codec::minicbor::{
+    data::{IanaTag, Tag, Type},
+    decode, Decoder,
+};
+use pallas_network::miniprotocols::localstate::queries_v16::{HardForkQuery, LedgerQuery, Request};
+
+/// `GetInterpreter`, the query for the era history.
+pub fn request() -> Request {
+    Request::LedgerQuery(LedgerQuery::HardForkQuery(HardForkQuery::GetInterpreter))
+}
+
+/// Where an era starts or ends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bound {
+    /// Since the system start
+    pub time_picos: u128,
+    pub slot: SlotNo,
+    pub epoch: u64,
+}
+
+/// One era of the hard fork history, as the node’s `EraSummary`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EraSummary {
+    pub start: Bound,
+    /// [`None`] for the current era, until its end is known
+    pub end: Option<Bound>,
+    pub epoch_size: u64,
+    pub slot_length_ms: u64,
+}
+
+/// The eras of the chain so far, which slot lengths and epoch sizes change
+/// with, as the node’s hard fork `Interpreter` has them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EraHistory(pub Vec<EraSummary>);
+
+impl EraHistory {
+    /// Like `slotToWallclock`: milliseconds from the system start to the
+    /// beginning of `slot`, or [`None`] past the horizon of what’s known.
+    pub fn slot_to_relative_time(&self, slot: SlotNo) -> Option<u64> {
+        let era = self
+            .0
+            .iter()
+            .find(|era| era.start.slot <= slot && era.end.is_none_or(|end| slot < end.slot))?;
+        let start_ms = u64::try_from(era.start.time_picos / 1_000_000_000).ok()?;
+
+        (slot.0 - era.start.slot.0)
+            .checked_mul(era.slot_length_ms)?
+            .checked_add(start_ms)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, decode::Error> {
+        let mut d = Decoder::new(bytes);
+        let mut eras = vec![];
+
+        // Haskell’s lists come indefinite-length
+        match d.array()? {
+            Some(len) => {
+                for _ in 0..len {
+                    eras.push(decode_era_summary(&mut d)?);
+                }
+            }
+            None => {
+                while d.datatype()? != Type::Break {
+                    eras.push(decode_era_summary(&mut d)?);
+                }
+                d.skip()?;
+            }
+        }
+
+        Ok(Self(eras))
+    }
+}
+
+fn decode_era_summary(d: &mut Decoder) -> Result<EraSummary, decode::Error> {
+    d.array()?;
+    let start = decode_bound(d)?;
+    let end = match d.datatype()? {
+        Type::Null => {
+            d.skip()?;
+            None
+        }
+        _ => Some(decode_bound(d)?),
+    };
+
+    // Also the safe zone and, lately, the genesis window
+    let params = d.array()?;
+    let epoch_size = d.u64()?;
+    let slot_length_ms = d.u64()?;
+    match params {
+        Some(len) => {
+            for _ in 2..len {
+                d.skip()?;
+            }
+        }
+        None => {
+            while d.datatype()? != Type::Break {
+                d.skip()?;
+            }
+            d.skip()?;
+        }
+    }
+
+    Ok(EraSummary {
+        start,
+        end,
+        epoch_size,
+        slot_length_ms,
+    })
+}
+
+fn decode_bound(d: &mut Decoder) -> Result<Bound, decode::Error> {
+    d.array()?;
+
+    Ok(Bound {
+        time_picos: decode_integer(d)?,
+        slot: SlotNo(d.u64()?),
+        epoch: d.u64()?,
+    })
+}
+
+/// Picoseconds outgrow a `u64` in a few months, and come as bignums then.
+fn decode_integer(d: &mut Decoder) -> Result<u128, decode::Error> {
+    match d.datatype()? {
+        Type::Tag => {
+            if d.tag()? != Tag::from(IanaTag::PosBignum) {
+                return Err(decode::Error::message("expected a positive bignum"));
+            }
+            let bytes = d.bytes()?;
+            if bytes.len() > 16 {
+                return Err(decode::Error::message("bignum too large"));
+            }
+
+            Ok(bytes
+                .iter()
+                .fold(0u128, |n, byte| (n << 8) | u128::from(*byte)))
+        }
+        _ => Ok(d.u64()?.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pallas_codec::minicbor::Encoder;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const PICOS_PER_SEC: u128 = 1_000_000_000_000;
+
+    /// Mainnet’s Byron and Shelley, the latter still open-ended.
+    fn mainnet() -> EraHistory {
+        let shelley_start = Bound {
+            time_picos: 89_856_000 * PICOS_PER_SEC,
+            slot: SlotNo(4_492_800),
+            epoch: 208,
+        };
+
+        EraHistory(vec![
+            EraSummary {
+                start: Bound {
+                    time_picos: 0,
+                    slot: SlotNo(0),
+                    epoch: 0,
+                },
+                end: Some(shelley_start),
+                epoch_size: 21_600,
+                slot_length_ms: 20_000,
+            },
+            EraSummary {
+                start: shelley_start,
+                end: None,
+                epoch_size: 432_000,
+                slot_length_ms: 1_000,
+            },
+        ])
+    }
+
+    fn encode_bound(e: &mut Encoder<&mut Vec<u8>>, bound: &Bound) {
+        e.array(3).unwrap();
+        match u64::try_from(bound.time_picos) {
+            Ok(time) => e.u64(time).unwrap(),
+            Err(_) => e
+                .tag(IanaTag::PosBignum)
+                .unwrap()
+                .bytes(&bound.time_picos.to_be_bytes()[3..])
+                .unwrap(),
+        };
+        e.u64(bound.slot.0).unwrap().u64(bound.epoch).unwrap();
+    }
+
+    #[test]
+    fn test_decode() {
+        let history = mainnet();
+        let mut bytes = vec![];
+        let mut e = Encoder::new(&mut bytes);
+
+        e.begin_array().unwrap();
+        for era in &history.0 {
+            e.array(3).unwrap();
+            encode_bound(&mut e, &era.start);
+            match &era.end {
+                Some(end) => encode_bound(&mut e, end),
+                None => {
+                    e.null().unwrap();
+                }
+            }
+            // With a standard safe zone, and a genesis window
+            e.array(4).unwrap();
+            e.u64(era.epoch_size)
+                .unwrap()
+                .u64(era.slot_length_ms)
+                .unwrap();
+            e.array(3).unwrap().u8(0).unwrap().u64(4320).unwrap();
+            e.array(1).unwrap().u8(0).unwrap();
+            e.u64(36_000).unwrap();
+        }
+        e.end().unwrap();
+
+        assert_eq!(EraHistory::decode(&bytes).unwrap(), history);
+        assert!(EraHistory::decode(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[rstest]
+    // Byron
+    #[case(0, Some(0))]
+    #[case(4_492_799, Some(89_855_980_000))]
+    // Shelley
+    #[case(4_492_800, Some(89_856_000_000))]
+    #[case(139_000_000, Some(224_363_200_000))]
+    fn test_slot_to_relative_time(#[case] slot: u64, #[case] time: Option<u64>) {
+        assert_eq!(mainnet().slot_to_relative_time(SlotNo(slot)), time);
+    }
+
+    #[test]
+    fn test_past_horizon() {
+        let mut history = mainnet();
+        history.0[1].end = Some(Bound {
+            time_picos: 89_942_400 * PICOS_PER_SEC,
+            slot: SlotNo(4_579_200),
+            epoch: 209,
+        });
+
+        assert_eq!(
+            history.slot_to_relative_time(SlotNo(4_579_199)),
+            Some(89_942_399_000)
+        );
+        assert_eq!(history.slot_to_relative_time(SlotNo(4_579_200)), None);
+    }
+}
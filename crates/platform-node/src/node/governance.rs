@@ -0,0 +1,663 @@
+use super::connection::NodeClient;
+use crate::{cbor::haskell_types::Credential, BlockfrostError};
+use pallas_codec::{
+    minicbor::{
+        data::{Tag, Type},
+        decode, encode, Decoder, Encoder,
+    },
+    utils::AnyCbor,
+};
+use pallas_crypto::hash::Hash;
+use pallas_network::miniprotocols::{localstate, localstate::queries_v16};
+use pallas_primitives::conway::{
+    Anchor, DRep, DRepVotingThresholds, GovActionId, PoolVotingThresholds, ProposalProcedure, Vote,
+};
+use std::boxed::Box;
+
+/// A registered DRep, as the ledger state has it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DRepState {
+    pub credential: Credential,
+    /// Last epoch it's active in, unless it votes or updates itself
+    pub expiry: u64,
+    pub anchor: Option<Anchor>,
+    pub deposit: u64,
+}
+
+/// The constitutional committee, with the members the ledger knows of,
+/// including those just elected but not yet enacted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitteeState {
+    pub members: Vec<CommitteeMember>,
+    /// As a fraction, if there's a committee at all
+    pub threshold: Option<(u64, u64)>,
+    pub epoch: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitteeMember {
+    pub cold_credential: Credential,
+    pub hot_credential: Option<Credential>,
+    pub authorization: HotCredAuthStatus,
+    pub status: MemberStatus,
+    pub expiration: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HotCredAuthStatus {
+    Authorized,
+    NotAuthorized,
+    Resigned,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MemberStatus {
+    Active,
+    Expired,
+    /// Elected, but not enacted yet
+    Unrecognized,
+}
+
+/// A governance action being voted on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proposal {
+    pub id: GovActionId,
+    /// By hot credential
+    pub committee_votes: Vec<(Credential, Vote)>,
+    pub drep_votes: Vec<(Credential, Vote)>,
+    pub pool_votes: Vec<(Hash<28>, Vote)>,
+    pub procedure: ProposalProcedure,
+    pub proposed_in: u64,
+    pub expires_after: u64,
+}
+
+/// The current protocol parameters that ratification depends on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GovernanceParams {
+    pub protocol_major: u64,
+    pub pool_voting_thresholds: PoolVotingThresholds,
+    pub drep_voting_thresholds: DRepVotingThresholds,
+    pub min_committee_size: u64,
+}
+
+/// The Conway queries pallas doesn’t know yet, all of them without filters,
+/// i.e. asking for everything, and the Conway protocol parameters, which
+/// pallas decodes only up to Babbage’s.
+#[derive(Debug, Clone, Copy)]
+enum GovernanceQuery {
+    CurrentPParams,
+    DRepState,
+    DRepStakeDistr,
+    CommitteeMembersState,
+    SPOStakeDistr,
+    Proposals,
+}
+
+impl GovernanceQuery {
+    fn tag(self) -> u16 {
+        match self {
+            GovernanceQuery::CurrentPParams => 3,
+            GovernanceQuery::DRepState => 25,
+            GovernanceQuery::DRepStakeDistr => 26,
+            GovernanceQuery::CommitteeMembersState => 27,
+            GovernanceQuery::SPOStakeDistr => 30,
+            GovernanceQuery::Proposals => 31,
+        }
+    }
+
+    fn min_n2c_version(self) -> u64 {
+        match self {
+            GovernanceQuery::CurrentPParams
+            | GovernanceQuery::DRepState
+            | GovernanceQuery::DRepStakeDistr
+            | GovernanceQuery::CommitteeMembersState => 16,
+            GovernanceQuery::SPOStakeDistr | GovernanceQuery::Proposals => 17,
+        }
+    }
+}
+
+/// Encodes like [`queries_v16::Request::LedgerQuery`] of a block query would.
+struct GovernanceRequest {
+    era: u16,
+    query: GovernanceQuery,
+}
+
+impl encode::Encode<()> for GovernanceRequest {
+    fn encode<W: encode::Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.array(2)?.u16(0)?;
+        e.array(2)?.u16(0)?;
+        e.array(2)?.u16(self.era)?;
+
+        match self.query {
+            GovernanceQuery::CurrentPParams => {
+                e.array(1)?.u16(self.query.tag())?;
+            }
+            GovernanceQuery::DRepState
+            | GovernanceQuery::DRepStakeDistr
+            | GovernanceQuery::SPOStakeDistr
+            | GovernanceQuery::Proposals => {
+                e.array(2)?.u16(self.query.tag())?;
+                e.array(0)?;
+            }
+            GovernanceQuery::CommitteeMembersState => {
+                e.array(4)?.u16(self.query.tag())?;
+                e.array(0)?.array(0)?.array(0)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NodeClient {
+    pub async fn drep_states(&mut self) -> Result<Vec<DRepState>, BlockfrostError> {
+        self.governance_query(GovernanceQuery::DRepState, |d| {
+            let mut dreps = vec![];
+            for _ in 0..expect_len(d.map()?)? {
+                let credential = d.decode()?;
+                dreps.push(decode_drep_state(d, credential)?);
+            }
+            Ok(dreps)
+        })
+        .await
+    }
+
+    pub async fn committee_state(&mut self) -> Result<CommitteeState, BlockfrostError> {
+        self.governance_query(
+            GovernanceQuery::CommitteeMembersState,
+            decode_committee_state,
+        )
+        .await
+    }
+
+    pub async fn proposals(&mut self) -> Result<Vec<Proposal>, BlockfrostError> {
+        self.governance_query(GovernanceQuery::Proposals, |d| {
+            let mut proposals = vec![];
+            for _ in 0..expect_len(d.array()?)? {
+                proposals.push(decode_proposal(d)?);
+            }
+            Ok(proposals)
+        })
+        .await
+    }
+
+    pub async fn governance_params(&mut self) -> Result<GovernanceParams, BlockfrostError> {
+        self.governance_query(GovernanceQuery::CurrentPParams, decode_governance_params)
+            .await
+    }
+
+    /// Active stake delegated to each DRep, the predefined ones included.
+    pub async fn drep_stake_distribution(&mut self) -> Result<Vec<(DRep, u64)>, BlockfrostError> {
+        self.governance_query(GovernanceQuery::DRepStakeDistr, decode_stake_map)
+            .await
+    }
+
+    /// Active stake of each stake pool.
+    pub async fn pool_stake_distribution(
+        &mut self,
+    ) -> Result<Vec<(Hash<28>, u64)>, BlockfrostError> {
+        self.governance_query(GovernanceQuery::SPOStakeDistr, decode_stake_map)
+            .await
+    }
+
+    async fn governance_query<A: Send + 'static>(
+        &mut self,
+        query: GovernanceQuery,
+        decode_result: fn(&mut Decoder) -> Result<A, decode::Error>,
+    ) -> Result<A, BlockfrostError> {
+        self.require_n2c_version(query.min_n2c_version(), &format!("{:?}", query))?;
+
+        self.with_statequery(move |generic_client: &mut localstate::GenericClient| {
+            Box::pin(async move {
+                let era = queries_v16::get_current_era(generic_client).await?;
+                let request = GovernanceRequest { era, query };
+                let response = generic_client
+                    .query_any(AnyCbor::from_encode(request))
+                    .await?;
+
+                decode_wrapped(response.raw_bytes(), decode_result).map_err(|e| {
+                    BlockfrostError::internal_server_error(format!(
+                        "Failed to decode the {:?} result: {}",
+                        query, e
+                    ))
+                })
+            })
+        })
+        .await
+    }
+}
+
+/// Query results come in a one-element array, as the era matched.
+fn decode_wrapped<A>(
+    bytes: &[u8],
+    decode_result: fn(&mut Decoder) -> Result<A, decode::Error>,
+) -> Result<A, decode::Error> {
+    let mut d = Decoder::new(bytes);
+    d.array()?;
+    decode_result(&mut d)
+}
+
+fn expect_len(len: Option<u64>) -> Result<u64, decode::Error> {
+    len.ok_or_else(|| decode::Error::message("indefinite-length collections aren’t expected"))
+}
+
+/// Decodes a Haskell `Maybe` or `StrictMaybe`, however it’s encoded: as
+/// `null`, as a list of zero or one elements, or as the bare value. Values
+/// that are themselves arrays are told apart by their `bare_len`.
+fn decode_maybe<'b, T>(
+    d: &mut Decoder<'b>,
+    bare_len: Option<u64>,
+    decode_value: impl FnOnce(&mut Decoder<'b>) -> Result<T, decode::Error>,
+) -> Result<Option<T>, decode::Error> {
+    match d.datatype()? {
+        Type::Null => {
+            d.null()?;
+            Ok(None)
+        }
+        Type::Array => {
+            let len = d.probe().array()?;
+            if bare_len.is_some() && len == bare_len {
+                return decode_value(d).map(Some);
+            }
+
+            match d.array()? {
+                Some(0) => Ok(None),
+                Some(1) => decode_value(d).map(Some),
+                _ => Err(decode::Error::message("unexpected Maybe encoding")),
+            }
+        }
+        _ => decode_value(d).map(Some),
+    }
+}
+
+fn decode_anchor(d: &mut Decoder) -> Result<Option<Anchor>, decode::Error> {
+    decode_maybe(d, Some(2), |d| d.decode())
+}
+
+fn decode_rational(d: &mut Decoder) -> Result<(u64, u64), decode::Error> {
+    if d.datatype()? == Type::Tag {
+        let tag = d.tag()?;
+        if tag != Tag::new(30) {
+            return Err(decode::Error::message(format!(
+                "unexpected tag for a rational: {:?}",
+                tag
+            )));
+        }
+    }
+    d.array()?;
+
+    Ok((d.u64()?, d.u64()?))
+}
+
+fn decode_stake_map<K: for<'b> decode::Decode<'b, ()>>(
+    d: &mut Decoder,
+) -> Result<Vec<(K, u64)>, decode::Error> {
+    let mut stake = vec![];
+    for _ in 0..expect_len(d.map()?)? {
+        stake.push((d.decode()?, d.u64()?));
+    }
+    Ok(stake)
+}
+
+/// Picks what ratification needs out of the Conway protocol parameters.
+fn decode_governance_params(d: &mut Decoder) -> Result<GovernanceParams, decode::Error> {
+    d.array()?;
+    // Fees, sizes, deposits, and rewards
+    for _ in 0..12 {
+        d.skip()?;
+    }
+    d.array()?;
+    let protocol_major = d.u64()?;
+    d.skip()?;
+    // Costs and limits of scripts and values
+    for _ in 13..22 {
+        d.skip()?;
+    }
+    let pool_voting_thresholds = d.decode()?;
+    let drep_voting_thresholds = d.decode()?;
+    let min_committee_size = d.u64()?;
+
+    Ok(GovernanceParams {
+        protocol_major,
+        pool_voting_thresholds,
+        drep_voting_thresholds,
+        min_committee_size,
+    })
+}
+
+fn decode_votes<K: for<'b> decode::Decode<'b, ()>>(
+    d: &mut Decoder,
+) -> Result<Vec<(K, Vote)>, decode::Error> {
+    let mut votes = vec![];
+    for _ in 0..expect_len(d.map()?)? {
+        votes.push((d.decode()?, d.decode()?));
+    }
+    Ok(votes)
+}
+
+fn decode_drep_state(d: &mut Decoder, credential: Credential) -> Result<DRepState, decode::Error> {
+    let len = expect_len(d.array()?)?;
+    let expiry = d.u64()?;
+    let anchor = decode_anchor(d)?;
+    let deposit = d.u64()?;
+    // Newer nodes also list the delegators
+    for _ in 3..len {
+        d.skip()?;
+    }
+
+    Ok(DRepState {
+        credential,
+        expiry,
+        anchor,
+        deposit,
+    })
+}
+
+fn decode_committee_state(d: &mut Decoder) -> Result<CommitteeState, decode::Error> {
+    d.array()?;
+
+    let mut members = vec![];
+    for _ in 0..expect_len(d.map()?)? {
+        let cold_credential = d.decode()?;
+
+        d.array()?;
+        d.array()?;
+        let (authorization, hot_credential) = match d.u16()? {
+            0 => (HotCredAuthStatus::Authorized, Some(d.decode()?)),
+            1 => (HotCredAuthStatus::NotAuthorized, None),
+            2 => (HotCredAuthStatus::Resigned, None),
+            tag => {
+                return Err(decode::Error::message(format!(
+                    "unknown HotCredAuthStatus: {}",
+                    tag
+                )))
+            }
+        };
+        if hot_credential.is_none() {
+            decode_anchor(d)?;
+        }
+        let status = match d.u16()? {
+            0 => MemberStatus::Active,
+            1 => MemberStatus::Expired,
+            2 => MemberStatus::Unrecognized,
+            tag => {
+                return Err(decode::Error::message(format!(
+                    "unknown MemberStatus: {}",
+                    tag
+                )))
+            }
+        };
+        let expiration = decode_maybe(d, None, |d| d.u64())?;
+        // The next epoch change
+        d.skip()?;
+
+        members.push(CommitteeMember {
+            cold_credential,
+            hot_credential,
+            authorization,
+            status,
+            expiration,
+        });
+    }
+
+    let threshold = decode_maybe(d, None, decode_rational)?;
+    let epoch = d.u64()?;
+
+    Ok(CommitteeState {
+        members,
+        threshold,
+        epoch,
+    })
+}
+
+fn decode_proposal(d: &mut Decoder) -> Result<Proposal, decode::Error> {
+    d.array()?;
+    let id = d.decode()?;
+    let committee_votes = decode_votes(d)?;
+    let drep_votes = decode_votes(d)?;
+    let pool_votes = decode_votes(d)?;
+    let procedure = d.decode()?;
+    let proposed_in = d.u64()?;
+    let expires_after = d.u64()?;
+
+    Ok(Proposal {
+        id,
+        committee_votes,
+        drep_votes,
+        pool_votes,
+        procedure,
+        proposed_in,
+        expires_after,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbor::haskell_types::DisplayKeyHash;
+    use pallas_codec::minicbor;
+    use pretty_assertions::assert_eq;
+
+    fn key_hash(byte: u8) -> Credential {
+        Credential::KeyHashObj(DisplayKeyHash([byte; 28].into()))
+    }
+
+    #[test]
+    fn test_encode_request() {
+        let request = GovernanceRequest {
+            era: 6,
+            query: GovernanceQuery::CommitteeMembersState,
+        };
+
+        assert_eq!(
+            hex::encode(minicbor::to_vec(request).unwrap()),
+            "82008200820684181b808080"
+        );
+
+        let request = GovernanceRequest {
+            era: 6,
+            query: GovernanceQuery::CurrentPParams,
+        };
+        assert_eq!(
+            hex::encode(minicbor::to_vec(request).unwrap()),
+            "8200820082068103"
+        );
+    }
+
+    #[test]
+    fn test_decode_governance_params() {
+        let mut e = Encoder::new(vec![]);
+        let ratio = |e: &mut Encoder<Vec<u8>>, numerator: u64| {
+            e.tag(Tag::new(30)).unwrap();
+            e.array(2)
+                .unwrap()
+                .u64(numerator)
+                .unwrap()
+                .u64(100)
+                .unwrap();
+        };
+        e.array(1).unwrap().array(31).unwrap();
+        for _ in 0..12 {
+            e.u64(0).unwrap();
+        }
+        e.array(2).unwrap().u64(10).unwrap().u64(0).unwrap();
+        for _ in 13..22 {
+            e.array(0).unwrap();
+        }
+        e.array(5).unwrap();
+        for numerator in 51..56 {
+            ratio(&mut e, numerator);
+        }
+        e.array(10).unwrap();
+        for numerator in 60..70 {
+            ratio(&mut e, numerator);
+        }
+        e.u64(7).unwrap();
+        for _ in 25..31 {
+            e.u64(0).unwrap();
+        }
+
+        let params = decode_wrapped(&e.into_writer(), decode_governance_params).unwrap();
+
+        assert_eq!(params.protocol_major, 10);
+        assert_eq!(
+            params
+                .pool_voting_thresholds
+                .security_voting_threshold
+                .numerator,
+            55
+        );
+        assert_eq!(
+            params.drep_voting_thresholds.treasury_withdrawal.numerator,
+            69
+        );
+        assert_eq!(params.min_committee_size, 7);
+    }
+
+    #[test]
+    fn test_decode_proposal() {
+        let mut e = Encoder::new(vec![]);
+        e.array(1).unwrap().array(7).unwrap();
+        e.array(2).unwrap().bytes(&[1; 32]).unwrap().u32(0).unwrap();
+        e.map(1)
+            .unwrap()
+            .encode(key_hash(1))
+            .unwrap()
+            .u8(1)
+            .unwrap();
+        e.map(1)
+            .unwrap()
+            .encode(key_hash(2))
+            .unwrap()
+            .u8(0)
+            .unwrap();
+        e.map(1).unwrap().bytes(&[3; 28]).unwrap().u8(2).unwrap();
+        e.array(4)
+            .unwrap()
+            .u64(100)
+            .unwrap()
+            .bytes(&[0xe1; 29])
+            .unwrap();
+        e.array(1).unwrap().u16(6).unwrap();
+        e.array(2)
+            .unwrap()
+            .str("https://example.com")
+            .unwrap()
+            .bytes(&[7; 32])
+            .unwrap();
+        e.u64(500).unwrap().u64(506).unwrap();
+
+        let proposal = decode_wrapped(&e.into_writer(), decode_proposal).unwrap();
+
+        assert_eq!(proposal.committee_votes, vec![(key_hash(1), Vote::Yes)]);
+        assert_eq!(proposal.drep_votes, vec![(key_hash(2), Vote::No)]);
+        assert_eq!(proposal.pool_votes, vec![([3; 28].into(), Vote::Abstain)]);
+        assert_eq!(proposal.expires_after, 506);
+    }
+
+    #[test]
+    fn test_decode_drep_states() {
+        let mut e = Encoder::new(vec![]);
+        e.array(1).unwrap().map(2).unwrap();
+        // An older node, with a null anchor
+        e.encode(key_hash(1)).unwrap();
+        e.array(3).unwrap().u64(600).unwrap().null().unwrap();
+        e.u64(500_000_000).unwrap();
+        // A newer one, with delegators
+        e.encode(key_hash(2)).unwrap();
+        e.array(4).unwrap().u64(601).unwrap();
+        e.array(1)
+            .unwrap()
+            .array(2)
+            .unwrap()
+            .str("https://example.com")
+            .unwrap();
+        e.bytes(&[7; 32]).unwrap();
+        e.u64(500_000_000).unwrap().array(0).unwrap();
+
+        let dreps = decode_wrapped(&e.into_writer(), |d| {
+            let mut dreps = vec![];
+            for _ in 0..expect_len(d.map()?)? {
+                let credential = d.decode()?;
+                dreps.push(decode_drep_state(d, credential)?);
+            }
+            Ok(dreps)
+        })
+        .unwrap();
+
+        assert_eq!(
+            dreps,
+            vec![
+                DRepState {
+                    credential: key_hash(1),
+                    expiry: 600,
+                    anchor: None,
+                    deposit: 500_000_000,
+                },
+                DRepState {
+                    credential: key_hash(2),
+                    expiry: 601,
+                    anchor: Some(Anchor {
+                        url: "https://example.com".to_string(),
+                        content_hash: [7; 32].into(),
+                    }),
+                    deposit: 500_000_000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_committee_state() {
+        let mut e = Encoder::new(vec![]);
+        e.array(1).unwrap().array(3).unwrap().map(2).unwrap();
+        e.encode(key_hash(1)).unwrap();
+        e.array(4).unwrap();
+        e.array(2)
+            .unwrap()
+            .u16(0)
+            .unwrap()
+            .encode(key_hash(2))
+            .unwrap();
+        e.u16(0).unwrap().array(1).unwrap().u64(700).unwrap();
+        e.array(1).unwrap().u16(2).unwrap();
+        e.encode(key_hash(3)).unwrap();
+        e.array(4).unwrap();
+        e.array(2).unwrap().u16(2).unwrap().array(0).unwrap();
+        e.u16(1).unwrap().array(0).unwrap();
+        e.array(1).unwrap().u16(2).unwrap();
+        e.array(1).unwrap().tag(Tag::new(30)).unwrap();
+        e.array(2).unwrap().u64(2).unwrap().u64(3).unwrap();
+        e.u64(550).unwrap();
+
+        let state = decode_wrapped(&e.into_writer(), decode_committee_state).unwrap();
+
+        assert_eq!(
+            state,
+            CommitteeState {
+                members: vec![
+                    CommitteeMember {
+                        cold_credential: key_hash(1),
+                        hot_credential: Some(key_hash(2)),
+                        authorization: HotCredAuthStatus::Authorized,
+                        status: MemberStatus::Active,
+                        expiration: Some(700),
+                    },
+                    CommitteeMember {
+                        cold_credential: key_hash(3),
+                        hot_credential: None,
+                        authorization: HotCredAuthStatus::Resigned,
+                        status: MemberStatus::Expired,
+                        expiration: None,
+                    },
+                ],
+                threshold: Some((2, 3)),
+                epoch: 550,
+            }
+        );
+    }
+}
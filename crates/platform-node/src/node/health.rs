@@ -0,0 +1,149 @@
+use crate::retry::{Attempts, Backoff};
+use metrics::gauge;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const RECONNECT_BACKOFF: Backoff = Backoff::new(
+    "node_reconnect",
+    Duration::from_secs(1),
+    Duration::from_secs(60),
+);
+
+/// Whether the node is reachable, as last seen by the server’s node
+/// supervisor task.
+#[derive(Clone, Default)]
+pub struct NodeHealth(Arc<Mutex<Inner>>);
+
+#[derive(Default)]
+struct Inner {
+    connected: bool,
+    /// UNIX time of the last change between connected and not
+    since: Option<u64>,
+    failed_attempts: u32,
+    reconnects: Option<Attempts>,
+    next_attempt_at: Option<u64>,
+    last_error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NodeHealthReport {
+    pub connected: bool,
+    pub since: Option<u64>,
+    /// Failed attempts to reconnect since the node was last reachable
+    pub failed_attempts: u32,
+    pub next_attempt_at: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+impl NodeHealth {
+    pub fn record_connected(&self) {
+        let mut inner = self.0.lock().unwrap_or_else(|e| e.into_inner());
+
+        if !inner.connected {
+            inner.since = Some(unix_now());
+        }
+        inner.connected = true;
+        inner.failed_attempts = 0;
+        inner.reconnects = None;
+        inner.next_attempt_at = None;
+        inner.last_error = None;
+
+        gauge!("cardano_node_connected").set(1);
+    }
+
+    /// Returns how long to wait before the next attempt.
+    pub fn record_failure(&self, error: String) -> Duration {
+        let mut inner = self.0.lock().unwrap_or_else(|e| e.into_inner());
+
+        if inner.connected || inner.since.is_none() {
+            inner.since = Some(unix_now());
+        }
+        inner.connected = false;
+        inner.failed_attempts = inner.failed_attempts.saturating_add(1);
+        inner.last_error = Some(error);
+
+        // Never out of retries, for lack of limits
+        let delay = inner
+            .reconnects
+            .get_or_insert_with(|| RECONNECT_BACKOFF.start())
+            .next_delay()
+            .unwrap_or(RECONNECT_BACKOFF.max);
+        inner.next_attempt_at = Some(unix_now() + delay.as_secs());
+
+        gauge!("cardano_node_connected").set(0);
+
+        delay
+    }
+
+    /// Whether the node was unreachable when last checked, as opposed to
+    /// connected or not checked yet.
+    pub fn is_down(&self) -> bool {
+        let inner = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        !inner.connected && inner.since.is_some()
+    }
+
+    pub fn report(&self) -> NodeHealthReport {
+        let inner = self.0.lock().unwrap_or_else(|e| e.into_inner());
+
+        NodeHealthReport {
+            connected: inner.connected,
+            since: inner.since,
+            failed_attempts: inner.failed_attempts,
+            next_attempt_at: inner.next_attempt_at,
+            last_error: inner.last_error.clone(),
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_backoff() {
+        let health = NodeHealth::default();
+
+        let mut previous = RECONNECT_BACKOFF.base;
+        for _ in 0..100 {
+            let delay = health.record_failure("No such file or directory".to_string());
+
+            assert!(delay >= RECONNECT_BACKOFF.base);
+            assert!(delay <= (previous * 3).min(RECONNECT_BACKOFF.max));
+            previous = delay;
+        }
+
+        // Starting over once connected
+        health.record_connected();
+        let delay = health.record_failure("No such file or directory".to_string());
+        assert!(delay <= RECONNECT_BACKOFF.base * 3);
+    }
+
+    #[test]
+    fn test_health() {
+        let health = NodeHealth::default();
+        assert!(!health.report().connected);
+
+        health.record_failure("No such file or directory".to_string());
+        health.record_failure("No such file or directory".to_string());
+        let report = health.report();
+        assert_eq!(report.failed_attempts, 2);
+        assert!(report.next_attempt_at.is_some());
+
+        health.record_connected();
+        let report = health.report();
+        assert!(report.connected);
+        assert_eq!(report.failed_attempts, 0);
+        assert_eq!(report.last_error, None);
+    }
+}
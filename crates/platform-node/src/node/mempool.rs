@@ -0,0 +1,76 @@
+use super::connection::NodeClient;
+use crate::BlockfrostError;
+use pallas_crypto::hash::Hash;
+use pallas_network::miniprotocols::{
+    localstate,
+    localstate::queries_v16::{self, TransactionInput},
+};
+use pallas_traverse::MultiEraTx;
+use std::{boxed::Box, collections::BTreeSet};
+use tracing::warn;
+
+impl NodeClient {
+    /// Hashes of all the transactions in the node’s mempool, from a
+    /// LocalTxMonitor snapshot of it.
+    pub async fn mempool_tx_hashes(&mut self) -> Result<BTreeSet<Hash<32>>, BlockfrostError> {
+        let monitor = self.client.as_mut().unwrap().monitor();
+        monitor.acquire().await?;
+
+        // Like the state query client, it has to be released even on errors
+        let mut hashes = BTreeSet::new();
+        let result = loop {
+            match monitor.query_next_tx().await {
+                Ok(Some((_era, tx))) => match MultiEraTx::decode(&tx.0) {
+                    Ok(tx) => {
+                        hashes.insert(tx.hash());
+                    }
+                    Err(e) => warn!("Failed to decode a mempool transaction: {}", e),
+                },
+                Ok(None) => break Ok(hashes),
+                Err(e) => break Err(e.into()),
+            }
+        };
+
+        if let Err(e) = monitor.release().await {
+            warn!("Failed to release the mempool monitor: {:?}", e);
+        }
+
+        if result.is_ok() {
+            self.record_success();
+        }
+
+        result
+    }
+
+    /// Which of `txs` have any of their outputs, given by count, unspent. That’s
+    /// how we tell a transaction made it into a block, as long as it wasn’t
+    /// spent from in full since.
+    pub async fn unspent_tx_hashes(
+        &mut self,
+        txs: Vec<(Hash<32>, u64)>,
+    ) -> Result<BTreeSet<Hash<32>>, BlockfrostError> {
+        self.with_statequery(move |generic_client: &mut localstate::GenericClient| {
+            Box::pin(async move {
+                let era = queries_v16::get_current_era(generic_client).await?;
+
+                let txins = txs
+                    .iter()
+                    .flat_map(|(hash, outputs)| {
+                        (0..*outputs).map(|index| TransactionInput {
+                            transaction_id: *hash,
+                            index,
+                        })
+                    })
+                    .collect();
+                let utxo = queries_v16::get_utxo_by_txin(generic_client, era, txins).await?;
+
+                Ok(utxo
+                    .utxo
+                    .iter()
+                    .map(|(utxo, _)| utxo.transaction_id)
+                    .collect())
+            })
+        })
+        .await
+    }
+}
@@ -0,0 +1,345 @@
+use super::{
+    api::NodeApi,
+    chain::{AccountState, ChainTip, NetworkState, StakePool},
+    governance::{CommitteeState, DRepState, GovernanceParams, Proposal},
+    protocol_params::ProtocolParams,
+    sync_progress::NodeInfo,
+    transactions::SubmitTimings,
+};
+use crate::{cbor::haskell_types::Credential, BlockfrostError};
+use pallas_crypto::hash::Hash;
+use pallas_primitives::conway::DRep;
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    future::Future,
+};
+
+/// A signed Conway transaction, as submitted in tests.
+pub const CONWAY_TX: &str = "84a300d90102818258205176274bef11d575edd6aa72392aaf993a07f736e70239c1fb22d4b1426b22bc01018282583900ddf1eb9ce2a1561e8f156991486b97873fb6969190cbc99ddcb3816621dcb03574152623414ed354d2d8f50e310f3f2e7d167cb20e5754271a003d09008258390099a5cb0fa8f19aba38cacf8a243d632149129f882df3a8e67f6bd512bcb0cde66a545e9fbc7ca4492f39bca1f4f265cc1503b4f7d6ff205c1b000000024f127a7c021a0002a2ada100d90102818258208b83e59abc9d7a66a77be5e0825525546a595174f8b929f164fcf5052d7aab7b5840709c64556c946abf267edd90b8027343d065193ef816529d8fa7aa2243f1fd2ec27036a677974199e2264cb582d01925134b9a20997d5a734da298df957eb002f5f6";
+
+/// [`CONWAY_TX`] with `invalid_hereafter` at slot 100000, long gone on
+/// every network.
+pub const CONWAY_TX_EXPIRED: &str = "84a400d90102818258205176274bef11d575edd6aa72392aaf993a07f736e70239c1fb22d4b1426b22bc01018282583900ddf1eb9ce2a1561e8f156991486b97873fb6969190cbc99ddcb3816621dcb03574152623414ed354d2d8f50e310f3f2e7d167cb20e5754271a003d09008258390099a5cb0fa8f19aba38cacf8a243d632149129f882df3a8e67f6bd512bcb0cde66a545e9fbc7ca4492f39bca1f4f265cc1503b4f7d6ff205c1b000000024f127a7c021a0002a2ad031a000186a0a100d90102818258208b83e59abc9d7a66a77be5e0825525546a595174f8b929f164fcf5052d7aab7b5840709c64556c946abf267edd90b8027343d065193ef816529d8fa7aa2243f1fd2ec27036a677974199e2264cb582d01925134b9a20997d5a734da298df957eb002f5f6";
+
+/// A [`NodeApi`] answering with what it was scripted to, for unit tests.
+/// Calls that weren’t scripted fail with a 500.
+#[derive(Default)]
+pub struct MockNode {
+    /// Answers to the consecutive submissions
+    pub submit_results: VecDeque<Result<String, BlockfrostError>>,
+    pub node_info: Option<NodeInfo>,
+    pub protocol_params: Option<ProtocolParams>,
+    pub chain_tip: Option<ChainTip>,
+    pub network_state: Option<NetworkState>,
+    /// Registered pools, by id
+    pub stake_pools: Option<BTreeMap<Vec<u8>, StakePool>>,
+    /// Registered stake addresses
+    pub accounts: Option<Vec<(Credential, AccountState)>>,
+    pub mempool: Option<BTreeSet<Hash<32>>>,
+    /// Transactions with unspent outputs
+    pub unspent: Option<BTreeSet<Hash<32>>>,
+    pub dreps: Option<Vec<DRepState>>,
+    pub committee: Option<CommitteeState>,
+    pub proposals: Option<Vec<Proposal>>,
+    pub governance_params: Option<GovernanceParams>,
+    pub drep_stake: Option<Vec<(DRep, u64)>>,
+    pub pool_stake: Option<Vec<(Hash<28>, u64)>>,
+    pub ping_result: Option<Result<(), BlockfrostError>>,
+    /// Transactions submitted so far
+    pub submitted: Vec<Vec<u8>>,
+}
+
+impl MockNode {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_submit_result(mut self, result: Result<String, BlockfrostError>) -> Self {
+        self.submit_results.push_back(result);
+        self
+    }
+
+    pub fn with_node_info(mut self, node_info: NodeInfo) -> Self {
+        self.node_info = Some(node_info);
+        self
+    }
+
+    pub fn with_protocol_params(mut self, protocol_params: ProtocolParams) -> Self {
+        self.protocol_params = Some(protocol_params);
+        self
+    }
+
+    pub fn with_chain_tip(mut self, chain_tip: ChainTip) -> Self {
+        self.chain_tip = Some(chain_tip);
+        self
+    }
+
+    pub fn with_network_state(mut self, network_state: NetworkState) -> Self {
+        self.network_state = Some(network_state);
+        self
+    }
+
+    pub fn with_stake_pool(mut self, pool: Vec<u8>, stake_pool: StakePool) -> Self {
+        self.stake_pools
+            .get_or_insert_with(BTreeMap::new)
+            .insert(pool, stake_pool);
+        self
+    }
+
+    pub fn with_account(mut self, credential: Credential, account: AccountState) -> Self {
+        self.accounts
+            .get_or_insert_with(Vec::new)
+            .push((credential, account));
+        self
+    }
+
+    pub fn with_mempool(mut self, mempool: BTreeSet<Hash<32>>) -> Self {
+        self.mempool = Some(mempool);
+        self
+    }
+
+    pub fn with_unspent(mut self, unspent: BTreeSet<Hash<32>>) -> Self {
+        self.unspent = Some(unspent);
+        self
+    }
+
+    pub fn with_dreps(mut self, dreps: Vec<DRepState>) -> Self {
+        self.dreps = Some(dreps);
+        self
+    }
+
+    pub fn with_committee(mut self, committee: CommitteeState) -> Self {
+        self.committee = Some(committee);
+        self
+    }
+
+    pub fn with_proposals(mut self, proposals: Vec<Proposal>) -> Self {
+        self.proposals = Some(proposals);
+        self
+    }
+
+    pub fn with_governance_params(mut self, governance_params: GovernanceParams) -> Self {
+        self.governance_params = Some(governance_params);
+        self
+    }
+
+    pub fn with_drep_stake(mut self, drep_stake: Vec<(DRep, u64)>) -> Self {
+        self.drep_stake = Some(drep_stake);
+        self
+    }
+
+    pub fn with_pool_stake(mut self, pool_stake: Vec<(Hash<28>, u64)>) -> Self {
+        self.pool_stake = Some(pool_stake);
+        self
+    }
+
+    pub fn with_ping_result(mut self, result: Result<(), BlockfrostError>) -> Self {
+        self.ping_result = Some(result);
+        self
+    }
+}
+
+fn not_scripted<A>(call: &str) -> Result<A, BlockfrostError> {
+    Err(BlockfrostError::internal_server_error(format!(
+        "MockNode: {} wasn't scripted",
+        call
+    )))
+}
+
+impl NodeApi for MockNode {
+    fn submit_transaction_timed(
+        &mut self,
+        tx: Vec<u8>,
+        _timings: &mut SubmitTimings,
+    ) -> impl Future<Output = Result<String, BlockfrostError>> + Send {
+        self.submitted.push(tx);
+        let result = self
+            .submit_results
+            .pop_front()
+            .unwrap_or_else(|| not_scripted("submit_transaction"));
+
+        async { result }
+    }
+
+    fn sync_progress(&mut self) -> impl Future<Output = Result<NodeInfo, BlockfrostError>> + Send {
+        let result = self
+            .node_info
+            .clone()
+            .map_or_else(|| not_scripted("sync_progress"), Ok);
+
+        async { result }
+    }
+
+    fn protocol_params(
+        &mut self,
+    ) -> impl Future<Output = Result<ProtocolParams, BlockfrostError>> + Send {
+        let result = self
+            .protocol_params
+            .clone()
+            .map_or_else(|| not_scripted("protocol_params"), Ok);
+
+        async { result }
+    }
+
+    fn chain_tip(&mut self) -> impl Future<Output = Result<ChainTip, BlockfrostError>> + Send {
+        let result = self
+            .chain_tip
+            .clone()
+            .map_or_else(|| not_scripted("chain_tip"), Ok);
+
+        async { result }
+    }
+
+    fn network_state(
+        &mut self,
+    ) -> impl Future<Output = Result<NetworkState, BlockfrostError>> + Send {
+        let result = self
+            .network_state
+            .clone()
+            .map_or_else(|| not_scripted("network_state"), Ok);
+
+        async { result }
+    }
+
+    fn stake_pools(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<Vec<u8>>, BlockfrostError>> + Send {
+        let result = self.stake_pools.as_ref().map_or_else(
+            || not_scripted("stake_pools"),
+            |pools| Ok(pools.keys().cloned().collect()),
+        );
+
+        async { result }
+    }
+
+    fn stake_pool(
+        &mut self,
+        pool: Vec<u8>,
+    ) -> impl Future<Output = Result<Option<StakePool>, BlockfrostError>> + Send {
+        let result = self.stake_pools.as_ref().map_or_else(
+            || not_scripted("stake_pool"),
+            |pools| Ok(pools.get(&pool).cloned()),
+        );
+
+        async { result }
+    }
+
+    fn account_state(
+        &mut self,
+        credential: Credential,
+    ) -> impl Future<Output = Result<Option<AccountState>, BlockfrostError>> + Send {
+        let result = self.accounts.as_ref().map_or_else(
+            || not_scripted("account_state"),
+            |accounts| {
+                Ok(accounts
+                    .iter()
+                    .find(|(c, _)| *c == credential)
+                    .map(|(_, account)| account.clone()))
+            },
+        );
+
+        async { result }
+    }
+
+    fn mempool_tx_hashes(
+        &mut self,
+    ) -> impl Future<Output = Result<BTreeSet<Hash<32>>, BlockfrostError>> + Send {
+        let result = self
+            .mempool
+            .clone()
+            .map_or_else(|| not_scripted("mempool_tx_hashes"), Ok);
+
+        async { result }
+    }
+
+    fn unspent_tx_hashes(
+        &mut self,
+        txs: Vec<(Hash<32>, u64)>,
+    ) -> impl Future<Output = Result<BTreeSet<Hash<32>>, BlockfrostError>> + Send {
+        let result = self.unspent.as_ref().map_or_else(
+            || not_scripted("unspent_tx_hashes"),
+            |unspent| {
+                Ok(txs
+                    .iter()
+                    .map(|(hash, _)| *hash)
+                    .filter(|hash| unspent.contains(hash))
+                    .collect())
+            },
+        );
+
+        async { result }
+    }
+
+    fn drep_states(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<DRepState>, BlockfrostError>> + Send {
+        let result = self
+            .dreps
+            .clone()
+            .map_or_else(|| not_scripted("drep_states"), Ok);
+
+        async { result }
+    }
+
+    fn committee_state(
+        &mut self,
+    ) -> impl Future<Output = Result<CommitteeState, BlockfrostError>> + Send {
+        let result = self
+            .committee
+            .clone()
+            .map_or_else(|| not_scripted("committee_state"), Ok);
+
+        async { result }
+    }
+
+    fn proposals(&mut self) -> impl Future<Output = Result<Vec<Proposal>, BlockfrostError>> + Send {
+        let result = self
+            .proposals
+            .clone()
+            .map_or_else(|| not_scripted("proposals"), Ok);
+
+        async { result }
+    }
+
+    fn governance_params(
+        &mut self,
+    ) -> impl Future<Output = Result<GovernanceParams, BlockfrostError>> + Send {
+        let result = self
+            .governance_params
+            .clone()
+            .map_or_else(|| not_scripted("governance_params"), Ok);
+
+        async { result }
+    }
+
+    fn drep_stake_distribution(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<(DRep, u64)>, BlockfrostError>> + Send {
+        let result = self
+            .drep_stake
+            .clone()
+            .map_or_else(|| not_scripted("drep_stake_distribution"), Ok);
+
+        async { result }
+    }
+
+    fn pool_stake_distribution(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<(Hash<28>, u64)>, BlockfrostError>> + Send {
+        let result = self
+            .pool_stake
+            .clone()
+            .map_or_else(|| not_scripted("pool_stake_distribution"), Ok);
+
+        async { result }
+    }
+
+    fn ping(&mut self) -> impl Future<Output = Result<(), BlockfrostError>> + Send {
+        let result = self
+            .ping_result
+            .clone()
+            .unwrap_or_else(|| not_scripted("ping"));
+
+        async { result }
+    }
+}
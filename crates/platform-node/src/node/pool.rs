@@ -0,0 +1,110 @@
+use super::{
+    era_cache::EraCache, health::NodeHealth, pool_manager::NodePoolManager, relay::Relay,
+    timeouts::NodeTimeouts,
+};
+use crate::{
+    cbor::fallback_decoder::FallbackDecoder, config::NodeConfig, storage::DataDir, AppError,
+};
+use deadpool::managed::{Object, Pool, PoolError};
+use metrics::gauge;
+use std::time::Duration;
+
+/// This represents a pool of `NodeToClient` connections to a single `cardano-node`.
+///
+/// It can be safely cloned to multiple threads, while still sharing the same
+/// set of underlying connections to the node.
+#[derive(Clone)]
+pub struct NodePool {
+    pool_manager: Pool<NodePoolManager>,
+    health: NodeHealth,
+    relay: Option<Relay>,
+}
+
+impl NodePool {
+    /// Creates a new pool of [`super::connection::NodeClient`] connections.
+    pub fn new(
+        config: &NodeConfig,
+        fallback_decoder: FallbackDecoder,
+        data_dir: Option<DataDir>,
+    ) -> Result<Self, AppError> {
+        let timeouts = NodeTimeouts::from_config(config);
+        let relay = config.relay.clone().map(|address| {
+            Relay::new(
+                address,
+                config.network_magic(),
+                config.submission_era(),
+                timeouts,
+            )
+        });
+        let manager = NodePoolManager {
+            network_magic: config.network_magic(),
+            socket_path: config.socket_path.to_string(),
+            fallback_decoder,
+            era_cache: EraCache::default(),
+            data_dir,
+            idle_timeout: Duration::from_secs(config.idle_timeout_secs),
+            max_age: Duration::from_secs(config.max_connection_age_secs),
+            timeouts,
+            connection_ids: Default::default(),
+        };
+        let pool_manager = deadpool::managed::Pool::builder(manager)
+            .max_size(config.max_pool_connections)
+            .build()
+            .map_err(|err| AppError::Node(err.to_string()))?;
+
+        Ok(Self {
+            pool_manager,
+            health: NodeHealth::default(),
+            relay,
+        })
+    }
+
+    /// Borrows a single [`super::connection::NodeClient`] connection from the pool.
+    pub async fn get(&self) -> Result<Object<NodePoolManager>, AppError> {
+        self.pool_manager.get().await.map_err(|err| match err {
+            PoolError::Backend(AppError::NodeTimeout(timeout)) => AppError::NodeTimeout(timeout),
+            err => AppError::Node(format!("NodeConnPool: {}", err)),
+        })
+    }
+
+    /// Where to submit transactions while the node socket can’t be reached.
+    pub fn relay(&self) -> Option<&Relay> {
+        self.relay.as_ref()
+    }
+
+    /// The node’s current era, as last seen by a submission.
+    pub fn era_cache(&self) -> &EraCache {
+        &self.pool_manager.manager().era_cache
+    }
+
+    pub fn health(&self) -> &NodeHealth {
+        &self.health
+    }
+
+    pub fn idle_timeout(&self) -> Duration {
+        self.pool_manager.manager().idle_timeout
+    }
+
+    /// Closes the pooled connections that haven’t been borrowed for longer
+    /// than the idle timeout, or are past their maximum age, returning how
+    /// many there were. New ones are opened lazily on the next [`Self::get`].
+    pub fn reap_stale(&self) -> usize {
+        let manager = self.pool_manager.manager();
+        let before = self.pool_manager.status().size;
+
+        self.pool_manager
+            .retain(|_, metrics| !manager.is_stale(&metrics));
+
+        before.saturating_sub(self.pool_manager.status().size)
+    }
+
+    /// Sets the gauges of borrowed and idle connections, e.g. before metrics
+    /// are rendered.
+    pub fn record_gauges(&self) {
+        let status = self.pool_manager.status();
+        let busy = status.size.saturating_sub(status.available);
+
+        gauge!("cardano_node_pool_connections", "state" => "busy").set(busy as f64);
+        gauge!("cardano_node_pool_connections", "state" => "idle").set(status.available as f64);
+    }
+}
@@ -0,0 +1,312 @@
+use super::{
+    connection::{ConnectionIds, NodeClient},
+    era_cache::EraCache,
+    timeouts::{NodeOperation, NodeTimeouts},
+};
+use crate::{cbor::fallback_decoder::FallbackDecoder, storage::DataDir, AppError};
+use deadpool::managed::{Manager, Metrics, RecycleError, RecycleResult};
+use metrics::{counter, gauge};
+use pallas_network::{
+    facades::{Error as FacadeError, NodeClient as NodeClientFacade},
+    miniprotocols::handshake,
+    multiplexer::Bearer,
+};
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+pub struct NodePoolManager {
+    pub network_magic: u64,
+    pub socket_path: String,
+    pub fallback_decoder: FallbackDecoder,
+    pub era_cache: EraCache,
+    pub data_dir: Option<DataDir>,
+    pub idle_timeout: Duration,
+    /// Connections are replaced after this long, even if healthy
+    pub max_age: Duration,
+    pub timeouts: NodeTimeouts,
+    pub connection_ids: ConnectionIds,
+}
+
+impl Manager for NodePoolManager {
+    type Type = NodeClient;
+    type Error = AppError;
+
+    async fn create(&self) -> Result<NodeClient, AppError> {
+        // TODO: maybe use `ExponentialBackoff` from `tokio-retry`, to have at
+        // least _some_ debouncing between requests, if the node is down?
+        let started_at = Instant::now();
+
+        let connected = match self
+            .timeouts
+            .run(NodeOperation::Connect, self.connect())
+            .await
+        {
+            Ok(connected) => connected,
+            Err(timeout) => {
+                counter!("cardano_node_connects_total", "result" => "failure").increment(1);
+                error!(
+                    "Failed to connect a node socket: {}: {}",
+                    self.socket_path, timeout
+                );
+                return Err(AppError::NodeTimeout(timeout));
+            }
+        };
+
+        match connected {
+            Ok(mut node) => {
+                let connect_duration = started_at.elapsed();
+                let connection_id = self.connection_ids.acquire();
+
+                counter!("cardano_node_connects_total", "result" => "success").increment(1);
+                gauge!("cardano_node_handshake_seconds", "connection" => connection_id.label())
+                    .set(connect_duration.as_secs_f64());
+
+                node.connect_duration = Some(connect_duration);
+                node.connection_id = Some(connection_id);
+                node.record_success();
+
+                info!(
+                    "N2C connection to node was successfully established at socket: {} (NodeToClientV_{})",
+                    self.socket_path,
+                    node.n2c_version()
+                );
+
+                Ok(node)
+            }
+            Err(err) => {
+                counter!("cardano_node_connects_total", "result" => "failure").increment(1);
+                error!(
+                    "Failed to connect a node socket: {}: {:?}",
+                    self.socket_path,
+                    err.to_string()
+                );
+                Err(AppError::Node(err.to_string()))
+            }
+        }
+    }
+
+    /// Pallas decided to make the
+    /// [`pallas_network::facades::NodeClient::abort`] take ownership of `self`.
+    /// That’s why we need our `NodeClient::client` to be an [`Option`], because
+    /// in here we only get a mutable reference. If the connection is broken, we
+    /// have to call [`pallas_network::facades::NodeClient::abort`], because it
+    /// joins certain multiplexer threads. Otherwise, it’s a resource leak.
+    async fn recycle(&self, node: &mut NodeClient, metrics: &Metrics) -> RecycleResult<AppError> {
+        // A borrower gave up on it mid-operation, e.g. on a timeout, or when the
+        // HTTP client went away. Dropping it is enough to clean up.
+        if !node.is_reusable() {
+            info!(
+                "N2C connection returned with an unfinished {:?} operation, reconnecting: {}",
+                node.in_flight, self.socket_path
+            );
+            return Err(RecycleError::message("operation unfinished"));
+        }
+
+        // The node may have half-closed it in the meantime, and a ping doesn’t
+        // always notice. Dropping it is enough to clean up, see `NodeClient`.
+        if metrics.last_used() > self.idle_timeout {
+            info!(
+                "N2C connection idle for {:?}, reconnecting: {}",
+                metrics.last_used(),
+                self.socket_path
+            );
+            return Err(RecycleError::message("idle for too long"));
+        }
+
+        if metrics.age() > self.max_age {
+            info!(
+                "N2C connection open for {:?}, reconnecting: {}",
+                metrics.age(),
+                self.socket_path
+            );
+            return Err(RecycleError::message("open for too long"));
+        }
+
+        // Check if the connection is still viable
+        match node.ping().await {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                error!(
+                    "N2C connection no longer viable: {}, {}, {:?}",
+                    self.socket_path, err, metrics
+                );
+
+                // Take ownership of the `NodeClient` from Pallas
+                // This is the only moment when `client` becomes `None`.
+                // I should not be used again.
+                let owned = node.client.take().unwrap();
+
+                // Now call `abort` to clean up their resources:
+                owned.abort().await;
+
+                // And scrap the connection from the pool:
+                Err(RecycleError::Backend(AppError::Node(err.to_string())))
+            }
+        }
+    }
+}
+
+impl NodePoolManager {
+    /// Whether a pooled connection is due to be closed, rather than lent out.
+    pub fn is_stale(&self, metrics: &Metrics) -> bool {
+        metrics.last_used() > self.idle_timeout || metrics.age() > self.max_age
+    }
+
+    /// Like [`pallas_network::facades::NodeClient::connect`], but also keeps
+    /// the negotiated protocol version, which Pallas throws away.
+    ///
+    /// The connection is wrapped in our [`NodeClient`] before the handshake, so
+    /// that it’s cleaned up on `Drop` if the handshake fails or this future is
+    /// cancelled.
+    async fn connect(&self) -> Result<NodeClient, FacadeError> {
+        #[cfg(unix)]
+        let bearer = Bearer::connect_unix(&self.socket_path)
+            .await
+            .map_err(FacadeError::ConnectFailure)?;
+
+        #[cfg(windows)]
+        let bearer = {
+            let pipe_name = self.socket_path.clone();
+            tokio::task::spawn_blocking(move || Bearer::connect_named_pipe(pipe_name))
+                .await
+                .expect("can't join tokio thread")
+                .map_err(FacadeError::ConnectFailure)?
+        };
+
+        gauge!("cardano_node_connections").increment(1);
+
+        let mut node = NodeClient {
+            client: Some(NodeClientFacade::new(bearer)),
+            fallback_decoder: self.fallback_decoder.clone(),
+            era_cache: self.era_cache.clone(),
+            data_dir: self.data_dir.clone(),
+            n2c_version: 0,
+            connect_duration: None,
+            connection_id: None,
+            timeouts: self.timeouts,
+            in_flight: None,
+        };
+
+        let versions = handshake::n2c::VersionTable::v10_and_above(self.network_magic);
+
+        let confirmation = node
+            .client
+            .as_mut()
+            .unwrap()
+            .handshake()
+            .handshake(versions)
+            .await
+            .map_err(FacadeError::HandshakeProtocol)?;
+
+        match confirmation {
+            handshake::Confirmation::Accepted(version, _) => {
+                node.n2c_version = version;
+                Ok(node)
+            }
+            handshake::Confirmation::Rejected(reason) => {
+                error!("N2C handshake refused: {:?}", reason);
+                Err(FacadeError::IncompatibleVersion)
+            }
+            handshake::Confirmation::QueryReply(_) => Err(FacadeError::HandshakeProtocol(
+                handshake::Error::InvalidInbound,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use tokio::io::AsyncReadExt;
+
+    #[rstest]
+    // Idle
+    #[case(61, None)]
+    // Busy, but old
+    #[case(3601, Some(1))]
+    #[tokio::test]
+    async fn test_recycle_drops_stale_connection(
+        #[case] created_secs_ago: u64,
+        #[case] recycled_secs_ago: Option<u64>,
+    ) {
+        let (ours, mut node_side) = tokio::net::UnixStream::pair().unwrap();
+
+        let manager = NodePoolManager {
+            network_magic: 0,
+            socket_path: "test".to_string(),
+            fallback_decoder: FallbackDecoder::disconnected(),
+            era_cache: EraCache::default(),
+            data_dir: None,
+            idle_timeout: Duration::from_secs(60),
+            max_age: Duration::from_secs(3600),
+            timeouts: NodeTimeouts::default(),
+            connection_ids: ConnectionIds::default(),
+        };
+        let mut node = NodeClient {
+            client: Some(NodeClientFacade::new(Bearer::Unix(ours))),
+            fallback_decoder: FallbackDecoder::disconnected(),
+            era_cache: EraCache::default(),
+            data_dir: None,
+            n2c_version: 0,
+            connect_duration: None,
+            connection_id: None,
+            timeouts: NodeTimeouts::default(),
+            in_flight: None,
+        };
+        let metrics = Metrics {
+            created: Instant::now() - Duration::from_secs(created_secs_ago),
+            recycled: recycled_secs_ago.map(|secs| Instant::now() - Duration::from_secs(secs)),
+            recycle_count: 0,
+        };
+
+        assert!(manager.is_stale(&metrics));
+        assert!(manager.recycle(&mut node, &metrics).await.is_err());
+        drop(node);
+
+        // It’s not even pinged, only closed
+        let mut buf = [0u8; 1];
+        let read = tokio::time::timeout(Duration::from_secs(5), node_side.read(&mut buf))
+            .await
+            .expect("the connection should be closed");
+
+        assert_eq!(read.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_recycle_drops_unfinished_connection() {
+        let (ours, _node_side) = tokio::net::UnixStream::pair().unwrap();
+
+        let manager = NodePoolManager {
+            network_magic: 0,
+            socket_path: "test".to_string(),
+            fallback_decoder: FallbackDecoder::disconnected(),
+            era_cache: EraCache::default(),
+            data_dir: None,
+            idle_timeout: Duration::from_secs(60),
+            max_age: Duration::from_secs(3600),
+            timeouts: NodeTimeouts::default(),
+            connection_ids: ConnectionIds::default(),
+        };
+        let mut node = NodeClient {
+            client: Some(NodeClientFacade::new(Bearer::Unix(ours))),
+            fallback_decoder: FallbackDecoder::disconnected(),
+            era_cache: EraCache::default(),
+            data_dir: None,
+            n2c_version: 0,
+            connect_duration: None,
+            connection_id: None,
+            timeouts: NodeTimeouts::default(),
+            in_flight: Some(NodeOperation::Statequery),
+        };
+        let metrics = Metrics {
+            created: Instant::now(),
+            recycled: None,
+            recycle_count: 0,
+        };
+
+        // Not pinged either, the node would never answer
+        assert!(!manager.is_stale(&metrics));
+        assert!(manager.recycle(&mut node, &metrics).await.is_err());
+    }
+}
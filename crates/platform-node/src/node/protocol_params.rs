@@ -0,0 +1,225 @@
+use super::connection::NodeClient;
+use crate::BlockfrostError;
+use pallas_network::miniprotocols::localstate::{
+    self,
+    queries_v16::{ProtocolParam, RationalNumber},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+/// How many epochs worth of protocol parameters we keep in memory.
+const HISTORY_EPOCHS: usize = 128;
+
+/// Protocol parameters, named and shaped like in the Blockfrost API
+/// `/epochs/latest/parameters` response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ProtocolParams {
+    pub min_fee_a: Option<u32>,
+    pub min_fee_b: Option<u32>,
+    pub max_block_size: Option<u32>,
+    pub max_tx_size: Option<u32>,
+    pub max_block_header_size: Option<u32>,
+    pub key_deposit: Option<String>,
+    pub pool_deposit: Option<String>,
+    pub e_max: Option<u64>,
+    pub n_opt: Option<u32>,
+    pub a0: Option<f64>,
+    pub rho: Option<f64>,
+    pub tau: Option<f64>,
+    pub protocol_major_ver: Option<u64>,
+    pub protocol_minor_ver: Option<u64>,
+    pub min_pool_cost: Option<String>,
+    pub coins_per_utxo_size: Option<String>,
+    pub price_mem: Option<f64>,
+    pub price_step: Option<f64>,
+    pub max_tx_ex_mem: Option<String>,
+    pub max_tx_ex_steps: Option<String>,
+    pub max_block_ex_mem: Option<String>,
+    pub max_block_ex_steps: Option<String>,
+    pub max_val_size: Option<String>,
+    pub collateral_percent: Option<u32>,
+    pub max_collateral_inputs: Option<u32>,
+}
+
+/// A single changed field between two sets of protocol parameters.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ParamChange {
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+impl ProtocolParams {
+    /// Returns the fields that differ between `self` and `newer`, keyed by field name.
+    pub fn diff(&self, newer: &ProtocolParams) -> BTreeMap<String, ParamChange> {
+        let (old, new) = match (serde_json::to_value(self), serde_json::to_value(newer)) {
+            (Ok(serde_json::Value::Object(old)), Ok(serde_json::Value::Object(new))) => (old, new),
+            _ => return BTreeMap::new(),
+        };
+
+        old.into_iter()
+            .filter_map(|(field, from)| {
+                let to = new.get(&field).cloned().unwrap_or(serde_json::Value::Null);
+                (from != to).then_some((field, ParamChange { from, to }))
+            })
+            .collect()
+    }
+}
+
+/// Protocol parameters observed per epoch since the process started.
+///
+/// It can be safely cloned to multiple threads, while still sharing the same
+/// underlying history.
+#[derive(Clone, Default)]
+pub struct ProtocolParamsHistory {
+    epochs: Arc<RwLock<BTreeMap<u32, ProtocolParams>>>,
+}
+
+impl ProtocolParamsHistory {
+    /// Records the parameters for `epoch`, and returns the last known epoch
+    /// before it together with its parameters, if any.
+    pub fn record(&self, epoch: u32, params: ProtocolParams) -> Option<(u32, ProtocolParams)> {
+        let mut epochs = self.epochs.write().unwrap_or_else(|e| e.into_inner());
+
+        let previous = epochs
+            .range(..epoch)
+            .next_back()
+            .map(|(epoch, params)| (*epoch, params.clone()));
+
+        epochs.insert(epoch, params);
+
+        while epochs.len() > HISTORY_EPOCHS {
+            epochs.pop_first();
+        }
+
+        previous
+    }
+
+    pub fn get(&self, epoch: u32) -> Option<ProtocolParams> {
+        let epochs = self.epochs.read().unwrap_or_else(|e| e.into_inner());
+
+        epochs.get(&epoch).cloned()
+    }
+
+    /// Parameters of the latest epoch seen.
+    pub fn latest(&self) -> Option<ProtocolParams> {
+        let epochs = self.epochs.read().unwrap_or_else(|e| e.into_inner());
+
+        epochs.values().next_back().cloned()
+    }
+
+    pub fn latest_epoch(&self) -> Option<u32> {
+        let epochs = self.epochs.read().unwrap_or_else(|e| e.into_inner());
+
+        epochs.keys().next_back().copied()
+    }
+}
+
+impl From<ProtocolParam> for ProtocolParams {
+    fn from(pp: ProtocolParam) -> Self {
+        let coin = |c: Option<localstate::queries_v16::Coin>| c.map(|c| u64::from(c).to_string());
+        let ratio = |r: Option<RationalNumber>| {
+            r.and_then(|r| (r.denominator != 0).then(|| r.numerator as f64 / r.denominator as f64))
+        };
+
+        Self {
+            min_fee_a: pp.minfee_a,
+            min_fee_b: pp.minfee_b,
+            max_block_size: pp.max_block_body_size,
+            max_tx_size: pp.max_transaction_size,
+            max_block_header_size: pp.max_block_header_size,
+            key_deposit: coin(pp.key_deposit),
+            pool_deposit: coin(pp.pool_deposit),
+            e_max: pp.maximum_epoch,
+            n_opt: pp.desired_number_of_stake_pools,
+            a0: ratio(pp.pool_pledge_influence),
+            rho: ratio(pp.expansion_rate),
+            tau: ratio(pp.treasury_growth_rate),
+            protocol_major_ver: pp.protocol_version.map(|(major, _)| major),
+            protocol_minor_ver: pp.protocol_version.map(|(_, minor)| minor),
+            min_pool_cost: coin(pp.min_pool_cost),
+            coins_per_utxo_size: coin(pp.ada_per_utxo_byte),
+            price_mem: ratio(pp.execution_costs.as_ref().map(|p| p.mem_price.clone())),
+            price_step: ratio(pp.execution_costs.map(|p| p.step_price)),
+            max_tx_ex_mem: pp.max_tx_ex_units.as_ref().map(|u| u.mem.to_string()),
+            max_tx_ex_steps: pp.max_tx_ex_units.map(|u| u.steps.to_string()),
+            max_block_ex_mem: pp.max_block_ex_units.as_ref().map(|u| u.mem.to_string()),
+            max_block_ex_steps: pp.max_block_ex_units.map(|u| u.steps.to_string()),
+            max_val_size: pp.max_value_size.map(|s| s.to_string()),
+            collateral_percent: pp.collateral_percentage,
+            max_collateral_inputs: pp.max_collateral_inputs,
+        }
+    }
+}
+
+impl NodeClient {
+    /// Fetches the current protocol parameters from the node.
+    pub async fn protocol_params(&mut self) -> Result<ProtocolParams, BlockfrostError> {
+        self.with_statequery(|generic_client: &mut localstate::GenericClient| {
+            Box::pin(async {
+                let current_era = localstate::queries_v16::get_current_era(generic_client).await?;
+
+                let params =
+                    localstate::queries_v16::get_current_pparams(generic_client, current_era)
+                        .await?;
+
+                let params = params.into_iter().next().ok_or_else(|| {
+                    BlockfrostError::internal_server_error(
+                        "Expected at least one set of protocol parameters".to_string(),
+                    )
+                })?;
+
+                Ok(params.into())
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_protocol_params_diff() {
+        let old = ProtocolParams {
+            min_fee_a: Some(44),
+            max_tx_size: Some(16384),
+            ..Default::default()
+        };
+        let new = ProtocolParams {
+            max_tx_size: Some(32768),
+            ..old.clone()
+        };
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.len(), 1);
+        assert_eq!(
+            diff.get("max_tx_size"),
+            Some(&ParamChange {
+                from: serde_json::json!(16384),
+                to: serde_json::json!(32768),
+            })
+        );
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn test_protocol_params_history() {
+        let history = ProtocolParamsHistory::default();
+        let params = |min_fee_a| ProtocolParams {
+            min_fee_a: Some(min_fee_a),
+            ..Default::default()
+        };
+
+        assert_eq!(history.record(500, params(44)), None);
+        assert_eq!(history.record(502, params(45)), Some((500, params(44))));
+        assert_eq!(history.get(502), Some(params(45)));
+        assert_eq!(history.get(501), None);
+        assert_eq!(history.latest_epoch(), Some(502));
+    }
+}
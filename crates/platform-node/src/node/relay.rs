@@ -0,0 +1,192 @@
+use super::{
+    timeouts::{NodeOperation, NodeTimeouts},
+    transactions::{check_submittable, tx_era},
+};
+use crate::{AppError, BlockfrostError};
+use pallas_network::{
+    facades::PeerClient,
+    miniprotocols::txsubmission::{self, EraTxBody, EraTxId, Request, TxIdAndSize},
+};
+use pallas_traverse::MultiEraTx;
+use tracing::{info, warn};
+
+/// Submits transactions to a remote relay over the node-to-node TxSubmission
+/// protocol, for when there’s no local node socket to use.
+///
+/// Unlike node-to-client submission, the relay never tells us whether it
+/// accepted the transaction into its mempool, only that it fetched it.
+#[derive(Clone)]
+pub struct Relay {
+    address: String,
+    network_magic: u64,
+    /// Of `EraTx`, as the relay can’t be asked for its era
+    era: u16,
+    timeouts: NodeTimeouts,
+}
+
+impl Relay {
+    pub fn new(address: String, network_magic: u64, era: u16, timeouts: NodeTimeouts) -> Self {
+        Self {
+            address,
+            network_magic,
+            era,
+            timeouts,
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Offers `tx` to the relay, returning its id once the relay either
+    /// fetched it or told us it already has it.
+    pub async fn submit_transaction(&self, tx: Vec<u8>) -> Result<String, BlockfrostError> {
+        check_submittable(&tx)?;
+
+        let decoded = MultiEraTx::decode(&tx)
+            .map_err(|e| BlockfrostError::custom_400(format!("Invalid transaction: {}", e)))?;
+        let txid = decoded.hash().to_vec();
+        let era = tx_era(&tx, self.era);
+
+        let mut peer = self
+            .timeouts
+            .run(
+                NodeOperation::Connect,
+                PeerClient::connect(self.address.as_str(), self.network_magic),
+            )
+            .await?
+            .map_err(AppError::from)?;
+
+        let result = self
+            .timeouts
+            .run(
+                NodeOperation::Submit,
+                offer(peer.txsubmission(), era, &txid, &tx),
+            )
+            .await;
+
+        peer.abort().await;
+
+        let fetched = result??;
+        let txid = hex::encode(txid);
+        if fetched {
+            info!("Transaction {} fetched by relay {}", txid, self.address);
+        } else {
+            warn!(
+                "Transaction {} not fetched by relay {}, it already had it",
+                txid, self.address
+            );
+        }
+
+        Ok(txid)
+    }
+}
+
+/// Plays the outbound side of TxSubmission with a single transaction to
+/// give, until the relay asks for more. Returns whether it fetched the body.
+async fn offer(
+    client: &mut txsubmission::Client,
+    era: u16,
+    txid: &[u8],
+    tx: &[u8],
+) -> Result<bool, txsubmission::Error> {
+    let mut announced = false;
+    let mut fetched = false;
+
+    client.send_init().await?;
+
+    loop {
+        match client.next_request().await? {
+            Request::TxIds(..) | Request::TxIdsNonBlocking(..) if !announced => {
+                client
+                    .reply_tx_ids(vec![TxIdAndSize(
+                        EraTxId(era, txid.to_vec()),
+                        tx.len() as u32,
+                    )])
+                    .await?;
+                announced = true;
+            }
+            Request::TxIdsNonBlocking(..) => client.reply_tx_ids(vec![]).await?,
+            // Blocking until we have more, i.e. it’s done with ours
+            Request::TxIds(..) => {
+                client.send_done().await?;
+                return Ok(fetched);
+            }
+            Request::Txs(ids) => {
+                let bodies: Vec<_> = ids
+                    .iter()
+                    .filter(|id| id.1 == txid)
+                    .map(|_| EraTxBody(era, tx.to_vec()))
+                    .collect();
+                fetched |= !bodies.is_empty();
+                client.reply_txs(bodies).await?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pallas_network::{
+        miniprotocols::{txsubmission::Reply, PROTOCOL_N2N_TX_SUBMISSION},
+        multiplexer::{Bearer, Plexer},
+    };
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn test_offer() {
+        let (ours, theirs) = tokio::net::UnixStream::pair().unwrap();
+
+        let mut plexer = Plexer::new(Bearer::Unix(ours));
+        let mut client =
+            txsubmission::Client::new(plexer.subscribe_client(PROTOCOL_N2N_TX_SUBMISSION));
+        let ours = plexer.spawn();
+
+        let mut plexer = Plexer::new(Bearer::Unix(theirs));
+        let mut relay =
+            txsubmission::Server::new(plexer.subscribe_server(PROTOCOL_N2N_TX_SUBMISSION));
+        let theirs = plexer.spawn();
+
+        let relay = tokio::spawn(async move {
+            relay.wait_for_init().await.unwrap();
+            relay
+                .acknowledge_and_request_tx_ids(true, 0, 3)
+                .await
+                .unwrap();
+            let Reply::TxIds(ids) = relay.receive_next_reply().await.unwrap() else {
+                panic!("expected tx ids");
+            };
+            relay
+                .request_txs(ids.iter().map(|id| id.0.clone()).collect())
+                .await
+                .unwrap();
+            let Reply::Txs(txs) = relay.receive_next_reply().await.unwrap() else {
+                panic!("expected txs");
+            };
+            relay
+                .acknowledge_and_request_tx_ids(true, 1, 3)
+                .await
+                .unwrap();
+            let done = relay.receive_next_reply().await.unwrap();
+
+            (ids, txs, matches!(done, Reply::Done))
+        });
+
+        let fetched = offer(&mut client, 6, &[1; 32], &[2; 10]).await.unwrap();
+        let (ids, txs, done) = relay.await.unwrap();
+
+        assert!(fetched);
+        assert!(done);
+        assert_eq!(
+            ids.into_iter()
+                .map(|TxIdAndSize(EraTxId(era, id), size)| (era, id, size))
+                .collect::<Vec<_>>(),
+            vec![(6, vec![1; 32], 10)]
+        );
+        assert_eq!(txs, vec![EraTxBody(6, vec![2; 10])]);
+
+        ours.abort().await;
+        theirs.abort().await;
+    }
+}
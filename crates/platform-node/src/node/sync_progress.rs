@@ -1,24 +1,37 @@
-use super::connection::NodeClient;
-use crate::BlockfrostError;
+use super::{
+    connection::NodeClient,
+    era_history::{self, EraHistory},
+};
+use crate::{cbor::slot::SlotNo, BlockfrostError};
 use chrono::{Duration, TimeZone, Utc};
+use pallas_codec::utils::AnyCbor;
 use pallas_network::{miniprotocols, miniprotocols::localstate};
-use pallas_traverse::wellknown;
 use serde::{Deserialize, Serialize};
 use std::boxed::Box;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct NodeInfo {
     pub block: String,
     pub epoch: u32,
     pub era: String,
-    pub slot: u64,
+    pub slot: SlotNo,
     #[serde(rename = "syncProgress")]
     pub sync_progress: f64,
+    /// Negotiated node-to-client protocol version
+    #[serde(rename = "n2cVersion", default)]
+    pub n2c_version: u64,
 }
 
 impl NodeClient {
     /// Reports the sync progress of the node.
     pub async fn sync_progress(&mut self) -> Result<NodeInfo, BlockfrostError> {
+        let mut info = self.query_sync_progress().await?;
+        info.n2c_version = self.n2c_version();
+        Ok(info)
+    }
+
+    /// All of [`NodeInfo`] but `n2c_version`, which isn’t a state query.
+    async fn query_sync_progress(&mut self) -> Result<NodeInfo, BlockfrostError> {
         self.with_statequery(|generic_client: &mut localstate::GenericClient| {
             Box::pin(async {
                 let current_era = localstate::queries_v16::get_current_era(generic_client).await?;
@@ -27,31 +40,20 @@ impl NodeClient {
                     localstate::queries_v16::get_block_epoch_number(generic_client, current_era)
                         .await?;
 
-                let geneses =
-                    localstate::queries_v16::get_genesis_config(generic_client, current_era)
-                        .await?;
-                let genesis = geneses.first().ok_or_else(|| {
-                    BlockfrostError::internal_server_error(
-                        "Expected at least one genesis".to_string(),
-                    )
-                })?;
-
                 let system_start =
                     localstate::queries_v16::get_system_start(generic_client).await?;
                 let chain_point = localstate::queries_v16::get_chain_point(generic_client).await?;
-                let slot = chain_point.slot_or_default();
-
-                // FIXME: this is debatable, because it won’t work for custom networks; we should rather
-                // get this information by calling `Ouroboros.Consensus.HardFork.History.Qry.slotToWallclock`
-                // like both cardano-cli (through cardano-api) and Ogmios do, but it’s not implemented
-                // in pallas_network yet.
-                let wellknown_genesis = wellknown::GenesisValues::from_magic(
-                    genesis.network_magic.into(),
-                )
-                .ok_or_else(|| {
+                let slot = SlotNo(chain_point.slot_or_default());
+
+                // Like `Ouroboros.Consensus.HardFork.History.Qry.slotToWallclock`, which both
+                // cardano-cli (through cardano-api) and Ogmios use, so custom networks work too
+                let era_history = generic_client
+                    .query_any(AnyCbor::from_encode(era_history::request()))
+                    .await?;
+                let era_history = EraHistory::decode(era_history.raw_bytes()).map_err(|e| {
                     BlockfrostError::internal_server_error(format!(
-                        "Only well-known networks are supported (unsupported network magic: {})",
-                        genesis.network_magic
+                        "Failed to decode the era history: {}",
+                        e
                     ))
                 })?;
 
@@ -81,22 +83,17 @@ impl NodeClient {
 
                 let utc_start = base_date + days + duration_ns;
 
-                let slot_time_secs: i64 = wellknown_genesis
-                    .slot_to_wallclock(slot)
-                    .try_into()
-                    .map_err(|e| {
+                let slot_time_ms: i64 = era_history
+                    .slot_to_relative_time(slot)
+                    .and_then(|time| time.try_into().ok())
+                    .ok_or_else(|| {
                         BlockfrostError::internal_server_error(format!(
                             "Failed to convert slot time: {}",
-                            e
+                            slot
                         ))
                     })?;
 
-                let utc_slot = Utc
-                    .timestamp_opt(slot_time_secs, 0)
-                    .single()
-                    .ok_or_else(|| {
-                        BlockfrostError::internal_server_error("Invalid slot timestamp".to_string())
-                    })?;
+                let utc_slot = utc_start + Duration::milliseconds(slot_time_ms);
 
                 let utc_now = Utc::now();
 
@@ -124,6 +121,7 @@ impl NodeClient {
                     epoch,
                     slot,
                     block,
+                    n2c_version: 0,
                 })
             })
         })
@@ -0,0 +1,116 @@
+use crate::config::NodeConfig;
+use serde::{Deserialize, Serialize};
+use std::{fmt, future::Future, time::Duration};
+
+/// What we were waiting for the node to do when it took too long.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeOperation {
+    Connect,
+    Submit,
+    Statequery,
+}
+
+impl fmt::Display for NodeOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NodeOperation::Connect => "connect",
+            NodeOperation::Submit => "submit",
+            NodeOperation::Statequery => "statequery",
+        })
+    }
+}
+
+/// How long a hung node may stall each kind of operation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeTimeouts {
+    pub connect: Duration,
+    pub submit: Duration,
+    pub statequery: Duration,
+}
+
+impl NodeTimeouts {
+    pub fn from_config(config: &NodeConfig) -> Self {
+        Self {
+            connect: Duration::from_secs(config.connect_timeout_secs),
+            submit: Duration::from_secs(config.submit_timeout_secs),
+            statequery: Duration::from_secs(config.statequery_timeout_secs),
+        }
+    }
+
+    pub fn of(&self, operation: NodeOperation) -> Duration {
+        match operation {
+            NodeOperation::Connect => self.connect,
+            NodeOperation::Submit => self.submit,
+            NodeOperation::Statequery => self.statequery,
+        }
+    }
+
+    /// Runs `future`, giving up on it after the timeout of `operation`.
+    pub async fn run<F: Future>(
+        &self,
+        operation: NodeOperation,
+        future: F,
+    ) -> Result<F::Output, NodeTimeout> {
+        let after = self.of(operation);
+
+        tokio::time::timeout(after, future)
+            .await
+            .map_err(|_| NodeTimeout { operation, after })
+    }
+}
+
+impl Default for NodeTimeouts {
+    fn default() -> Self {
+        Self {
+            connect: Duration::from_secs(10),
+            submit: Duration::from_secs(30),
+            statequery: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeTimeout {
+    pub operation: NodeOperation,
+    pub after: Duration,
+}
+
+impl fmt::Display for NodeTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The node didn’t finish a {} operation within {} s",
+            self.operation,
+            self.after.as_secs()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn test_run() {
+        let timeouts = NodeTimeouts {
+            statequery: Duration::from_millis(10),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            timeouts.run(NodeOperation::Submit, async { 42 }).await,
+            Ok(42)
+        );
+        assert_eq!(
+            timeouts
+                .run(NodeOperation::Statequery, std::future::pending::<()>())
+                .await,
+            Err(NodeTimeout {
+                operation: NodeOperation::Statequery,
+                after: Duration::from_millis(10),
+            })
+        );
+    }
+}
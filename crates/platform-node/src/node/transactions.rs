@@ -0,0 +1,570 @@
+use super::{connection::NodeClient, era_cache::is_era_mismatch, timeouts::NodeOperation};
+use crate::{
+    cbor::haskell_types::{DecoderError, DeserialiseFailure, TxSubmitFail},
+    storage::Component,
+    BlockfrostError,
+};
+use metrics::{counter, histogram};
+use pallas_codec::minicbor::{data::Type, decode, Decoder};
+use pallas_crypto::hash::Hasher;
+use pallas_network::miniprotocols::{
+    localstate,
+    localtxsubmission::{EraTx, Response},
+};
+use pallas_traverse::{Era, MultiEraTx};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Where the time of a single `/tx/submit` went, to tell apart the platform’s
+/// share of the latency from the node’s.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SubmitTimings {
+    /// Waiting for a pooled connection, not counting opening a new one
+    pub queue_wait: Duration,
+    pub node_connect: Duration,
+    /// Zero if the era was cached
+    pub era_query: Duration,
+    /// From sending the transaction until the node replied
+    pub submit_rtt: Duration,
+    /// Decoding the rejection reason, if any
+    pub decode: Duration,
+}
+
+impl SubmitTimings {
+    /// In the `Server-Timing` syntax, e.g. `queue_wait;dur=0.12, node_connect;dur=0.00, …`,
+    /// with durations in milliseconds.
+    pub fn header_value(&self) -> String {
+        [
+            ("queue_wait", self.queue_wait),
+            ("node_connect", self.node_connect),
+            ("era_query", self.era_query),
+            ("submit_rtt", self.submit_rtt),
+            ("decode", self.decode),
+        ]
+        .iter()
+        .map(|(name, duration)| format!("{};dur={:.2}", name, duration.as_secs_f64() * 1000.0))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+impl NodeClient {
+    /// Submits a transaction to the connected Cardano node.
+    /// This API meant to be fully compatible with cardano-submit-api.
+    /// Should return HTTP 200 if the transaction was accepted by the node.
+    /// If the transaction was rejected, should return HTTP 400 with a JSON body:
+    /// * Swagger: <https://github.com/IntersectMBO/cardano-node/blob/6e969c6bcc0f07bd1a69f4d76b85d6fa9371a90b/cardano-submit-api/swagger.yaml#L52>
+    /// * Haskell code: <https://github.com/IntersectMBO/cardano-node/blob/6e969c6bcc0f07bd1a69f4d76b85d6fa9371a90b/cardano-submit-api/src/Cardano/TxSubmit/Web.hs#L158>
+    pub async fn submit_transaction(&mut self, tx: Vec<u8>) -> Result<String, BlockfrostError> {
+        self.submit_transaction_timed(tx, &mut SubmitTimings::default())
+            .await
+    }
+
+    /// Like [`Self::submit_transaction`], but also records the node-side
+    /// segments of its latency into `timings`.
+    #[tracing::instrument(name = "node_submit", skip_all, fields(txid = %tx_id(&tx)))]
+    pub async fn submit_transaction_timed(
+        &mut self,
+        tx: Vec<u8>,
+        timings: &mut SubmitTimings,
+    ) -> Result<String, BlockfrostError> {
+        let txid = tx_id(&tx);
+        let submit_started_at = Instant::now();
+        check_submittable(&tx).inspect_err(|_| record_outcome("rejected", submit_started_at))?;
+
+        let current_era = match self.era_cache.get() {
+            Some(era) => era,
+            None => {
+                let started_at = Instant::now();
+                let era = self
+                    .with_statequery(|generic_client: &mut localstate::GenericClient| {
+                        Box::pin(async {
+                            Ok(localstate::queries_v16::get_current_era(generic_client).await?)
+                        })
+                    })
+                    .await
+                    .map_err(|e| {
+                        record_outcome("transport_error", submit_started_at);
+                        transport_error(e)
+                    })?;
+                timings.era_query = started_at.elapsed();
+                self.era_cache.set(era);
+                era
+            }
+        };
+
+        let era = tx_era(&tx, current_era);
+        if era != current_era {
+            info!(
+                "Transaction {} is in era {}, the node in {}",
+                txid, era, current_era
+            );
+        }
+        let era_tx = EraTx(era, tx);
+
+        // Connect to the node
+        let timeouts = self.timeouts;
+        self.in_flight = Some(NodeOperation::Submit);
+        let submission_client = self.client.as_mut().unwrap().submission();
+
+        // Submit the transaction
+        let started_at = Instant::now();
+        let response = timeouts
+            .run(NodeOperation::Submit, submission_client.submit_tx(era_tx))
+            .await;
+        timings.submit_rtt = started_at.elapsed();
+
+        // The node may still answer, and the next submission would read that
+        // answer as its own
+        let response = match response {
+            Ok(response) => response,
+            Err(timeout) => {
+                warn!("Transaction {}: {}, closing the connection", txid, timeout);
+                record_outcome("transport_error", submit_started_at);
+                self.close();
+                return Err(timeout.into());
+            }
+        };
+        self.in_flight = None;
+
+        if response.is_ok() {
+            self.record_success();
+        }
+
+        match response {
+            Ok(Response::Accepted) => {
+                info!("Transaction accepted by the node {}", txid);
+                record_outcome("accepted", submit_started_at);
+                Ok(txid)
+            }
+            Ok(Response::Rejected(reason)) => {
+                let started_at = Instant::now();
+                let decoded = self.decode_error_reason(&reason.0).await;
+                timings.decode = started_at.elapsed();
+                record_outcome("rejected", submit_started_at);
+
+                // The [2..] is a Pallas bug, cf. <https://github.com/txpipe/pallas/pull/548>.
+                let reason = &reason.0[2..];
+
+                // Most likely a hard fork since we last asked
+                if is_era_mismatch(reason) {
+                    warn!(
+                        "Transaction {} was tagged with the wrong era, asking the node again next time",
+                        txid
+                    );
+                    self.era_cache.invalidate();
+                }
+
+                match decoded {
+                    Ok(submit_api_json) => {
+                        for failure in predicate_failures(&submit_api_json) {
+                            counter!("tx_submit_rejections_total", "failure" => failure.to_string())
+                                .increment(1);
+                        }
+
+                        let error_message = "TxSubmitFail".to_string();
+                        warn!(
+                            "{}: {} ~ {:?}",
+                            error_message,
+                            hex::encode(reason),
+                            submit_api_json
+                        );
+
+                        Err(BlockfrostError::custom_400_details(
+                            error_message,
+                            submit_api_json,
+                        ))
+                    }
+
+                    Err(e) => {
+                        warn!("Failed to decode error reason: {:?}", e);
+
+                        // Keep the reason around, so that the decoder can be fixed later
+                        if let Some(data_dir) = &self.data_dir {
+                            let file_name = format!("{}.cbor", txid);
+                            if let Err(e) =
+                                data_dir.write(Component::DecodeFailures, &file_name, reason)
+                            {
+                                warn!("Failed to store the undecoded error reason: {}", e);
+                            }
+                        }
+
+                        Err(BlockfrostError::custom_400(format!(
+                            "Failed to decode error reason: {:?}",
+                            e
+                        )))
+                    }
+                }
+            }
+            // Not the transaction’s fault, and the connection is likely broken,
+            // so that a retry gets a new one from the pool
+            Err(e) => {
+                warn!("Transaction {}: {:?}, closing the connection", txid, e);
+                record_outcome("transport_error", submit_started_at);
+                self.close();
+
+                Err(transport_error(e))
+            }
+        }
+    }
+
+    /// Decodes a rejection reason into cardano-submit-api JSON, natively if we
+    /// can, falling back to the Haskell decoder otherwise.
+    #[tracing::instrument(name = "decode_rejection", skip_all)]
+    async fn decode_error_reason(&self, reason: &[u8]) -> Result<serde_json::Value, String> {
+        let native = Self::try_decode_error(reason).and_then(|error| {
+            serde_json::to_value(TxSubmitFail::from(error))
+                .map_err(|e| pallas_network::multiplexer::Error::Decoding(e.to_string()))
+        });
+
+        let (decoder, result) = match native {
+            Ok(json) => ("native", Ok(json)),
+            Err(_) => match self.fallback_decoder.decode(&reason[2..]).await {
+                Ok(json) => ("fallback", Ok(json)),
+                Err(e) => ("failed", Err(e)),
+            },
+        };
+
+        counter!("tx_submit_error_decoding_total", "decoder" => decoder).increment(1);
+
+        result
+    }
+}
+
+/// Counts a submission by `outcome`: accepted, rejected, or transport_error
+/// if the node never answered, and how long it took.
+fn record_outcome(outcome: &'static str, started_at: Instant) {
+    counter!("tx_submit_total", "outcome" => outcome).increment(1);
+    histogram!("tx_submit_duration_seconds", "outcome" => outcome)
+        .record(started_at.elapsed().as_secs_f64());
+}
+
+/// The ledger rules that failed in a rejection decoded into cardano-submit-api
+/// JSON, by their innermost constructor, e.g. `BadInputsUTxO` for
+/// `UtxowFailure (UtxoFailure (BadInputsUTxO …))`.
+fn predicate_failures(submit_api_json: &serde_json::Value) -> Vec<&str> {
+    match submit_api_json {
+        serde_json::Value::Object(map) => match map.get("error") {
+            Some(serde_json::Value::Array(errors)) => errors
+                .iter()
+                .filter_map(|error| predicate_failure(error.as_str()?))
+                .collect(),
+            _ => map.values().flat_map(predicate_failures).collect(),
+        },
+        _ => vec![],
+    }
+}
+
+/// Unwraps the `…Failure (…)` constructors of a shown predicate failure.
+fn predicate_failure(shown: &str) -> Option<&str> {
+    let starts_constructor = |s: &str| s.starts_with(|c: char| c.is_ascii_uppercase());
+
+    let mut rest = shown;
+    loop {
+        let end = rest
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        let name = &rest[..end];
+        if !starts_constructor(name) {
+            return None;
+        }
+
+        match rest[end..].strip_prefix(" (") {
+            Some(inner) if name.ends_with("Failure") && starts_constructor(inner) => rest = inner,
+            _ => return Some(name),
+        }
+    }
+}
+
+/// Eras in the order of their hard fork combinator index, as used by `EraTx`
+/// and `get_current_era`.
+const ERAS: [Era; 7] = [
+    Era::Byron,
+    Era::Shelley,
+    Era::Allegra,
+    Era::Mary,
+    Era::Alonzo,
+    Era::Babbage,
+    Era::Conway,
+];
+
+/// The newest era we know of, for when the node can’t tell us its current one.
+pub const LATEST_ERA: u16 = ERAS.len() as u16 - 1;
+
+/// The era of a hard fork combinator index, if we know of it.
+pub fn era_of_index(index: u16) -> Option<Era> {
+    ERAS.get(index as usize).copied()
+}
+
+pub fn era_index(era: Era) -> u16 {
+    ERAS.iter()
+        .position(|known| *known == era)
+        .map_or(LATEST_ERA, |index| index as u16)
+}
+
+/// The era to tag the transaction with: the node’s current one, unless the
+/// transaction only deserialises in an older one, e.g. a Babbage-bodied one.
+pub(in crate::node) fn tx_era(tx: &[u8], current_era: u16) -> u16 {
+    let Some(current) = ERAS.get(current_era as usize) else {
+        return current_era;
+    };
+
+    if MultiEraTx::decode_for_era(*current, tx).is_ok() {
+        return current_era;
+    }
+
+    MultiEraTx::decode(tx)
+        .ok()
+        .and_then(|decoded| ERAS.iter().position(|era| *era == decoded.era()))
+        .map(|era| era as u16)
+        .filter(|era| *era <= current_era)
+        .unwrap_or(current_era)
+}
+
+/// Rejects what cardano-submit-api rejects before submitting anything, and
+/// what isn’t even shaped like a transaction.
+pub fn check_submittable(tx: &[u8]) -> Result<(), BlockfrostError> {
+    if tx.is_empty() {
+        return Err(submit_fail("TxSubmitEmpty", TxSubmitFail::TxSubmitEmpty));
+    }
+    check_well_formed(tx)
+        .and_then(|()| check_tx_structure(tx))
+        .map_err(|e| submit_fail("TxSubmitDecodeFail", TxSubmitFail::TxSubmitDecodeFail(e)))
+}
+
+/// Catches what the node couldn’t even deserialise before submitting it, like
+/// cardano-submit-api does, so that the offset of the problem is reported.
+fn check_well_formed(tx: &[u8]) -> Result<(), DecoderError> {
+    let label = "Tx".to_string();
+    let mut decoder = Decoder::new(tx);
+
+    if let Err(e) = decoder.skip() {
+        let offset = e.position().unwrap_or(decoder.position()) as u64;
+        return Err(DecoderError::DecoderErrorDeserialiseFailure(
+            label,
+            DeserialiseFailure(offset, e.to_string()),
+        ));
+    }
+
+    let leftover = &tx[decoder.position()..];
+    if !leftover.is_empty() {
+        return Err(DecoderError::DecoderErrorLeftover(
+            label,
+            leftover.to_vec().into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks the outer structure of a well-formed `tx`: `[body, witnesses,
+/// is_valid, auxiliary_data]` with maps for the first two, `is_valid` only
+/// since Alonzo, or a Byron `[tx, witnesses]` of arrays.
+fn check_tx_structure(tx: &[u8]) -> Result<(), DecoderError> {
+    let mut decoder = Decoder::new(tx);
+
+    let result = (|| {
+        let len = decoder.array()?;
+        let expected = match decoder.datatype()? {
+            Type::Map | Type::MapIndef => 3..=4,
+            Type::Array | Type::ArrayIndef => 2..=2,
+            _ => return Err(decode::Error::message("expected a transaction body")),
+        };
+
+        let mut items = 0;
+        while items < len.unwrap_or(u64::MAX) && decoder.datatype()? != Type::Break {
+            decoder.skip()?;
+            items += 1;
+        }
+        if !expected.contains(&items) {
+            return Err(decode::Error::message(format!(
+                "expected a transaction of {} to {} items, got {}",
+                expected.start(),
+                expected.end(),
+                items
+            )));
+        }
+
+        Ok(())
+    })();
+
+    result.map_err(|e| {
+        DecoderError::DecoderErrorDeserialiseFailure(
+            "Tx".to_string(),
+            DeserialiseFailure(decoder.position() as u64, e.to_string()),
+        )
+    })
+}
+
+/// The id we report for a submitted `tx`, before the node has even seen it.
+pub fn tx_id(tx: &[u8]) -> String {
+    hex::encode(Hasher::<256>::hash_cbor(&tx))
+}
+
+/// A submission that failed on the way to or from the node, which is worth
+/// retrying.
+pub fn transport_error(error: impl std::fmt::Debug) -> BlockfrostError {
+    let error = format!("{:?}", error);
+
+    BlockfrostError {
+        details: Some(serde_json::json!({ "transport": error })),
+        ..BlockfrostError::service_unavailable(format!(
+            "Error during transaction submission: {}",
+            error
+        ))
+    }
+}
+
+/// Whether `err` is a submission failing on the node connection, rather than
+/// the node rejecting it, see [`transport_error`] and
+/// [`BlockfrostError::node_timeout`].
+pub fn is_transport_error(err: &BlockfrostError) -> bool {
+    err.details.as_ref().is_some_and(|details| {
+        details.get("transport").is_some() || details.get("timeout").is_some()
+    })
+}
+
+pub fn submit_fail(message: &str, fail: TxSubmitFail) -> BlockfrostError {
+    match serde_json::to_value(fail) {
+        Ok(details) => BlockfrostError::custom_400_details(message.to_string(), details),
+        Err(_) => BlockfrostError::custom_400(message.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cbor::haskell_types::{
+            ApplyConwayTxPredError::*, ApplyTxErr, ShelleyBasedEra::*, TxValidationError::*,
+        },
+        node::mock::CONWAY_TX,
+    };
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[test]
+    fn test_tx_era() {
+        let tx = hex::decode(CONWAY_TX).unwrap();
+
+        assert_eq!(tx_era(&tx, 6), 6);
+        // Never newer than the node’s era
+        assert_eq!(tx_era(&tx, 5), 5);
+        assert_eq!(tx_era(&[0x80], 6), 6);
+        assert_eq!(tx_era(&tx, 42), 42);
+    }
+
+    #[test]
+    fn test_check_well_formed() {
+        assert_eq!(check_well_formed(&[0x82, 0x01, 0x02]), Ok(()));
+
+        match check_well_formed(&[0x83, 0x01, 0x02]) {
+            Err(DecoderError::DecoderErrorDeserialiseFailure(label, DeserialiseFailure(3, _))) => {
+                assert_eq!(label, "Tx")
+            }
+            other => panic!("Expected DecoderErrorDeserialiseFailure, got {:?}", other),
+        }
+
+        let leftover = check_well_formed(&[0x82, 0x01, 0x02, 0xff, 0x00]).unwrap_err();
+        assert_eq!(
+            serde_json::to_value(TxSubmitFail::TxSubmitDecodeFail(leftover)).unwrap(),
+            serde_json::json!({
+                "tag": "TxSubmitDecodeFail",
+                "contents": {
+                    "tag": "DecoderErrorLeftover",
+                    "contents": ["Tx", "ff00"]
+                }
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(CONWAY_TX, None)]
+    // Byron
+    #[case("828380808080", None)]
+    // Without `is_valid`, as before Alonzo, indefinite-length
+    #[case("9fa0a0f6ff", None)]
+    #[case("820102", Some(1))]
+    #[case("82a0a0", Some(3))]
+    #[case("85a0a0f5f601", Some(6))]
+    fn test_check_tx_structure(#[case] tx: &str, #[case] offset: Option<u64>) {
+        let result = check_tx_structure(&hex::decode(tx).unwrap());
+
+        match (result, offset) {
+            (Ok(()), None) => {}
+            (
+                Err(DecoderError::DecoderErrorDeserialiseFailure(_, DeserialiseFailure(at, _))),
+                Some(offset),
+            ) => assert_eq!(at, offset),
+            (other, _) => panic!("Unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_submit_timings_header_value() {
+        let timings = SubmitTimings {
+            queue_wait: Duration::from_micros(120),
+            node_connect: Duration::ZERO,
+            era_query: Duration::from_millis(3),
+            submit_rtt: Duration::from_micros(41_256),
+            decode: Duration::from_micros(6),
+        };
+
+        assert_eq!(
+            timings.header_value(),
+            "queue_wait;dur=0.12, node_connect;dur=0.00, era_query;dur=3.00, submit_rtt;dur=41.26, decode;dur=0.01"
+        );
+    }
+
+    #[test]
+    fn test_submit_fail_with_multiple_errors() {
+        let validation_error = ShelleyTxValidationError {
+            error: ApplyTxErr(vec![
+                MempoolFailure("error1".to_string()),
+                MempoolFailure("error2".to_string()),
+            ]),
+            era: ShelleyBasedEraConway,
+        };
+
+        let error_string = serde_json::to_string(&TxSubmitFail::from(validation_error))
+            .expect("Failed to convert error to JSON");
+        let expected_error_string = r#"{"tag":"TxSubmitFail","contents":{"tag":"TxCmdTxSubmitValidationError","contents":{"tag":"TxValidationErrorInCardanoMode","contents":{"kind":"ShelleyTxValidationError","error":["MempoolFailure (error1)","MempoolFailure (error2)"],"era":"ShelleyBasedEraConway"}}}}"#;
+
+        assert_eq!(error_string, expected_error_string);
+    }
+
+    #[rstest]
+    #[case("UtxowFailure (UtxoFailure (BadInputsUTxO (fromList [TxIn (TxId {unTxId = SafeHash \"0f\"}) (TxIx {unTxIx = 3})])))", Some("BadInputsUTxO"))]
+    #[case("UtxowFailure (UtxoFailure (AlonzoInBabbageUtxoPredFailure (ValueNotConservedUTxO (MaryValue (Coin 1) (MultiAsset (fromList []))))))", Some("ValueNotConservedUTxO"))]
+    #[case(
+        "UtxowFailure (MissingVKeyWitnessesUTXOW (fromList []))",
+        Some("MissingVKeyWitnessesUTXOW")
+    )]
+    #[case(
+        "UtxowFailure (UtxoFailure (UtxosFailure (CollectErrors [",
+        Some("CollectErrors")
+    )]
+    #[case("MempoolFailure (error1)", Some("MempoolFailure"))]
+    #[case("fromList []", None)]
+    fn test_predicate_failure(#[case] shown: &str, #[case] expected: Option<&str>) {
+        assert_eq!(predicate_failure(shown), expected);
+    }
+
+    #[test]
+    fn test_predicate_failures() {
+        let validation_error = ShelleyTxValidationError {
+            error: ApplyTxErr(vec![
+                MempoolFailure("error1".to_string()),
+                TxRefScriptsSizeTooBig(1),
+            ]),
+            era: ShelleyBasedEraConway,
+        };
+        let json = serde_json::to_value(TxSubmitFail::from(validation_error)).unwrap();
+
+        assert_eq!(
+            predicate_failures(&json),
+            vec!["MempoolFailure", "TxRefScriptsSizeTooBig"]
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use metrics::counter;
+use rand::Rng;
+use std::{
+    fmt::Display,
+    future::Future,
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+/// How a subsystem retries what failed: with “decorrelated jitter”, i.e. each
+/// delay somewhere between `base` and three times the previous one, capped at
+/// `max`, so that instances failing together don’t retry in lockstep.
+///
+/// Every retry is counted under `retries_total`, and every time we give up
+/// for running out of retries or time under `retries_exhausted_total`, both
+/// labelled with the `subsystem`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backoff {
+    pub subsystem: &'static str,
+    pub base: Duration,
+    pub max: Duration,
+    /// Unlimited if [`None`]
+    pub max_retries: Option<u32>,
+    /// Since the first attempt, unlimited if [`None`]
+    pub max_elapsed: Option<Duration>,
+}
+
+impl Backoff {
+    pub const fn new(subsystem: &'static str, base: Duration, max: Duration) -> Self {
+        Self {
+            subsystem,
+            base,
+            max,
+            max_retries: None,
+            max_elapsed: None,
+        }
+    }
+
+    pub const fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    pub const fn with_max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    /// Starts counting the attempts of an operation, for callers driving the
+    /// retries themselves.
+    pub fn start(&self) -> Attempts {
+        Attempts {
+            backoff: *self,
+            retries: 0,
+            previous: self.base,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Runs `operation` until it succeeds, fails in a way `is_retryable` says
+    /// not to retry, or we run out of retries or time.
+    ///
+    /// Dropping the returned future cancels it, also while it waits.
+    pub async fn retry<T, E, F>(
+        &self,
+        mut operation: impl FnMut() -> F,
+        is_retryable: impl Fn(&E) -> bool,
+    ) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        let mut attempts = self.start();
+
+        loop {
+            let err = match operation().await {
+                Err(err) if is_retryable(&err) => err,
+                result => return result,
+            };
+
+            let Some(delay) = attempts.next_delay() else {
+                return Err(err);
+            };
+            warn!(
+                "{}: {}, retrying in {:?} ({})",
+                self.subsystem,
+                err,
+                delay,
+                attempts.retries()
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// The retries of one operation so far, see [`Backoff::start`].
+#[derive(Debug, Clone)]
+pub struct Attempts {
+    backoff: Backoff,
+    retries: u32,
+    previous: Duration,
+    started_at: Instant,
+}
+
+impl Attempts {
+    /// Retries so far, i.e. not counting the first attempt.
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// How long to wait before retrying, or [`None`] if we’re out of retries
+    /// or the wait would take us past `max_elapsed`.
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        let backoff = &self.backoff;
+        let upper = self
+            .previous
+            .saturating_mul(3)
+            .min(backoff.max)
+            .max(backoff.base);
+        let delay = rand::thread_rng()
+            .gen_range(backoff.base..=upper)
+            .min(backoff.max);
+
+        let out_of_retries = backoff
+            .max_retries
+            .is_some_and(|max_retries| self.retries >= max_retries);
+        let out_of_time = backoff
+            .max_elapsed
+            .is_some_and(|max_elapsed| self.started_at.elapsed() + delay > max_elapsed);
+
+        if out_of_retries || out_of_time {
+            counter!("retries_exhausted_total", "subsystem" => backoff.subsystem).increment(1);
+            return None;
+        }
+
+        counter!("retries_total", "subsystem" => backoff.subsystem).increment(1);
+        self.retries += 1;
+        self.previous = delay;
+
+        Some(delay)
+    }
+
+    /// Starts over, e.g. once a long-running operation got going again.
+    pub fn reset(&mut self) {
+        *self = self.backoff.start();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const BACKOFF: Backoff = Backoff::new(
+        "test",
+        Duration::from_millis(100),
+        Duration::from_millis(1_000),
+    );
+
+    #[test]
+    fn test_next_delay() {
+        for _ in 0..100 {
+            let mut attempts = BACKOFF.start();
+            let mut previous = BACKOFF.base;
+
+            for _ in 0..20 {
+                let delay = attempts.next_delay().unwrap();
+
+                assert!(delay >= BACKOFF.base);
+                assert!(delay <= (previous * 3).min(BACKOFF.max));
+                previous = delay;
+            }
+            assert_eq!(attempts.retries(), 20);
+
+            attempts.reset();
+            assert_eq!(attempts.retries(), 0);
+            assert!(attempts.next_delay().unwrap() <= BACKOFF.base * 3);
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut attempts = BACKOFF.with_max_retries(2).start();
+        assert!(attempts.next_delay().is_some());
+        assert!(attempts.next_delay().is_some());
+        assert_eq!(attempts.next_delay(), None);
+
+        let mut attempts = BACKOFF.with_max_elapsed(Duration::from_millis(50)).start();
+        assert_eq!(attempts.next_delay(), None);
+
+        let mut attempts = Backoff::new("test", Duration::ZERO, Duration::ZERO).start();
+        assert_eq!(attempts.next_delay(), Some(Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let backoff = Backoff::new("test", Duration::from_millis(1), Duration::from_millis(1))
+            .with_max_retries(3);
+        let calls = AtomicU32::new(0);
+        let operation = || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err("transient"),
+                1 => Err("permanent"),
+                _ => Ok(()),
+            }
+        };
+
+        let result = backoff.retry(operation, |err| *err == "transient").await;
+        assert_eq!(result, Err("permanent"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let result = backoff.retry(operation, |_| true).await;
+        assert_eq!(result, Ok(()));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let result = backoff
+            .retry(|| async { Err::<(), _>("down") }, |_| true)
+            .await;
+        assert_eq!(result, Err("down"));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::AppError;
+use serde::Serialize;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tracing::{info, warn};
+
+/// A kind of data kept under the data directory. Each one lives in its own
+/// subdirectory and is pruned independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    /// Rejection reasons from the node that we failed to decode.
+    DecodeFailures,
+    /// Accepted transactions followed until they’re deep enough on-chain.
+    Submissions,
+    /// Submissions waiting for the node to be reachable again.
+    Spool,
+    /// Where the chain follower got to, to pick up from after a restart.
+    ChainSync,
+}
+
+impl Component {
+    pub const ALL: &'static [Component] = &[
+        Component::DecodeFailures,
+        Component::Submissions,
+        Component::Spool,
+        Component::ChainSync,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Component::DecodeFailures => "decode-failures",
+            Component::Submissions => "submissions",
+            Component::Spool => "spool",
+            Component::ChainSync => "chainsync",
+        }
+    }
+}
+
+/// The on-disk state of the platform, kept under `--data-dir`.
+///
+/// Every component has the same size quota; when it’s exceeded, the oldest
+/// files of that component are removed first.
+#[derive(Clone, Debug)]
+pub struct DataDir {
+    root: PathBuf,
+    quota_bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct ComponentUsage {
+    pub name: &'static str,
+    pub path: String,
+    pub files: u64,
+    pub bytes: u64,
+    pub quota_bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct StorageReport {
+    pub data_dir: String,
+    pub total_bytes: u64,
+    pub components: Vec<ComponentUsage>,
+}
+
+impl DataDir {
+    /// Creates the data directory (and its component subdirectories) if needed.
+    pub fn new(root: &Path, quota_bytes: u64) -> Result<Self, AppError> {
+        let data_dir = Self {
+            root: root.to_path_buf(),
+            quota_bytes,
+        };
+
+        for component in Component::ALL {
+            let path = data_dir.path(*component);
+            fs::create_dir_all(&path).map_err(|e| {
+                AppError::Server(format!(
+                    "Failed to create the data directory {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        }
+
+        info!("Using {} as the data directory", root.display());
+
+        Ok(data_dir)
+    }
+
+    pub fn path(&self, component: Component) -> PathBuf {
+        self.root.join(component.name())
+    }
+
+    /// Writes a single file of `component`, pruning old files if the quota is exceeded.
+    pub fn write(&self, component: Component, file_name: &str, contents: &[u8]) -> io::Result<()> {
+        fs::write(self.path(component).join(file_name), contents)?;
+        self.prune(component)?;
+
+        Ok(())
+    }
+
+    /// Removes the oldest files of `component` until it fits its quota.
+    /// Returns the number of bytes removed.
+    pub fn prune(&self, component: Component) -> io::Result<u64> {
+        let removed = prune_dir(&self.path(component), self.quota_bytes)?;
+
+        if removed > 0 {
+            info!(
+                "Pruned {} bytes from the {} storage",
+                removed,
+                component.name()
+            );
+        }
+
+        Ok(removed)
+    }
+
+    /// Prunes all components, logging (but not failing on) errors.
+    pub fn prune_all(&self) {
+        for component in Component::ALL {
+            if let Err(e) = self.prune(*component) {
+                warn!("Failed to prune the {} storage: {}", component.name(), e);
+            }
+        }
+    }
+
+    pub fn usage(&self, component: Component) -> io::Result<ComponentUsage> {
+        let path = self.path(component);
+        let files = list_files(&path)?;
+
+        Ok(ComponentUsage {
+            name: component.name(),
+            path: path.to_string_lossy().to_string(),
+            files: files.len() as u64,
+            bytes: files.iter().map(|(_, len, _)| len).sum(),
+            quota_bytes: self.quota_bytes,
+        })
+    }
+
+    pub fn report(&self) -> io::Result<StorageReport> {
+        let components = Component::ALL
+            .iter()
+            .map(|component| self.usage(*component))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(StorageReport {
+            data_dir: self.root.to_string_lossy().to_string(),
+            total_bytes: components.iter().map(|c| c.bytes).sum(),
+            components,
+        })
+    }
+}
+
+/// Removes the oldest files directly under `dir` until they fit in `quota_bytes`.
+/// Returns the number of bytes removed.
+pub fn prune_dir(dir: &Path, quota_bytes: u64) -> io::Result<u64> {
+    let mut files = list_files(dir)?;
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    let mut removed = 0;
+
+    // Oldest first
+    files.sort_by_key(|(_, _, modified)| *modified);
+
+    for (path, len, _) in files {
+        if total <= quota_bytes {
+            break;
+        }
+
+        fs::remove_file(&path)?;
+        total -= len;
+        removed += len;
+    }
+
+    Ok(removed)
+}
+
+fn list_files(dir: &Path) -> io::Result<Vec<(PathBuf, u64, SystemTime)>> {
+    let mut files = vec![];
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        if metadata.is_file() {
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            files.push((entry.path(), metadata.len(), modified));
+        }
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_data_dir_prunes_oldest_first() {
+        let root = std::env::temp_dir().join(format!("bf-data-dir-{}", std::process::id()));
+        let data_dir = DataDir::new(&root, 10).unwrap();
+        let component = Component::DecodeFailures;
+
+        data_dir.write(component, "a", b"12345").unwrap();
+        // Make sure the modification times differ
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        data_dir.write(component, "b", b"12345").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        data_dir.write(component, "c", b"12345").unwrap();
+
+        let usage = data_dir.usage(component).unwrap();
+        assert_eq!(usage.files, 2);
+        assert_eq!(usage.bytes, 10);
+        assert!(!data_dir.path(component).join("a").exists());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
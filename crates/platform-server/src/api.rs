@@ -0,0 +1,18 @@
+pub mod accounts;
+pub mod admin;
+pub mod blocks;
+pub mod debug;
+pub mod events;
+pub mod governance;
+pub mod health;
+pub mod metrics;
+pub mod network;
+pub mod pools;
+pub mod protocol_params;
+pub mod proxy;
+pub mod root;
+pub mod stats;
+pub mod tx_confirmations;
+pub mod tx_status;
+pub mod tx_submit;
+pub mod utils;
@@ -0,0 +1,151 @@
+use crate::{
+    cbor::haskell_types::Credential,
+    encoding::{encode_as, Bech32Kind},
+    node::{api::NodeApi, chain::AccountState},
+    BlockfrostError, NodePool,
+};
+use axum::{extract::Path, response::IntoResponse, Extension, Json};
+use pallas::ledger::addresses::Address;
+use serde::{Deserialize, Serialize};
+
+/// Blockfrost’s `/accounts/{stake_address}`. The ledger state only knows the
+/// present, so the history sums are `null`, and addresses that aren’t
+/// registered are just inactive.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct AccountResponse {
+    pub stake_address: String,
+    pub active: bool,
+    pub active_epoch: Option<u32>,
+    pub controlled_amount: Option<String>,
+    pub rewards_sum: Option<String>,
+    pub withdrawals_sum: Option<String>,
+    pub reserves_sum: Option<String>,
+    pub treasury_sum: Option<String>,
+    pub withdrawable_amount: String,
+    pub pool_id: Option<String>,
+    pub drep_id: Option<String>,
+}
+
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Path(stake_address): Path<String>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let credential = parse_stake_address(&stake_address)?;
+    let mut node = node.get().await?;
+
+    Ok(Json(account(&mut *node, stake_address, credential).await?))
+}
+
+fn parse_stake_address(stake_address: &str) -> Result<Credential, BlockfrostError> {
+    match Address::from_bech32(stake_address) {
+        Ok(Address::Stake(address)) => Ok(Credential::from(address.payload())),
+        _ => Err(BlockfrostError::custom_400(format!(
+            "Invalid stake address: {stake_address}"
+        ))),
+    }
+}
+
+async fn account(
+    node: &mut impl NodeApi,
+    stake_address: String,
+    credential: Credential,
+) -> Result<AccountResponse, BlockfrostError> {
+    let state = node.account_state(credential).await?;
+
+    let pool_id = match state.as_ref().and_then(|state| state.pool.as_ref()) {
+        Some(pool) => Some(
+            encode_as(Bech32Kind::Pool, pool).map_err(BlockfrostError::internal_server_error)?,
+        ),
+        None => None,
+    };
+
+    Ok(AccountResponse {
+        stake_address,
+        active: state.is_some(),
+        active_epoch: None,
+        controlled_amount: None,
+        rewards_sum: None,
+        withdrawals_sum: None,
+        reserves_sum: None,
+        treasury_sum: None,
+        withdrawable_amount: state
+            .map(|AccountState { rewards, .. }| rewards)
+            .unwrap_or_default()
+            .to_string(),
+        pool_id,
+        drep_id: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cbor::haskell_types::DisplayKeyHash, node::mock::MockNode};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const STAKE_ADDRESS: &str = "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw";
+    const STAKE_KEY_HASH: &str = "337b62cfff6403a06a3acbc34f8c46003c69fe79a3628cefa9c47251";
+    const POOL: &str = "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy";
+
+    fn credential() -> Credential {
+        Credential::KeyHashObj(DisplayKeyHash(STAKE_KEY_HASH.parse().unwrap()))
+    }
+
+    #[test]
+    fn test_parse_stake_address() {
+        assert_eq!(parse_stake_address(STAKE_ADDRESS).unwrap(), credential());
+    }
+
+    #[rstest]
+    #[case("stake1")]
+    #[case(POOL)]
+    #[case("addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8")]
+    fn test_parse_stake_address_errors(#[case] stake_address: &str) {
+        assert_eq!(
+            parse_stake_address(stake_address).unwrap_err().status_code,
+            400
+        );
+    }
+
+    #[tokio::test]
+    async fn test_account() {
+        let mut node = MockNode::new().with_account(
+            credential(),
+            AccountState {
+                rewards: 1_234_567,
+                pool: Some(
+                    hex::decode("0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735")
+                        .unwrap(),
+                ),
+            },
+        );
+
+        let response = account(&mut node, STAKE_ADDRESS.to_string(), credential())
+            .await
+            .unwrap();
+
+        assert!(response.active);
+        assert_eq!(response.withdrawable_amount, "1234567");
+        assert_eq!(response.pool_id.as_deref(), Some(POOL));
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_account() {
+        let mut node = MockNode::new().with_account(
+            Credential::KeyHashObj(DisplayKeyHash([0; 28].into())),
+            AccountState {
+                rewards: 1,
+                pool: None,
+            },
+        );
+
+        let response = account(&mut node, STAKE_ADDRESS.to_string(), credential())
+            .await
+            .unwrap();
+
+        assert!(!response.active);
+        assert_eq!(response.withdrawable_amount, "0");
+        assert_eq!(response.pool_id, None);
+    }
+}
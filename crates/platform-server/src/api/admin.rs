@@ -0,0 +1,15 @@
+use crate::{storage::DataDir, BlockfrostError};
+use axum::{response::IntoResponse, Extension, Json};
+
+/// Reports the disk usage of every data directory component.
+pub async fn storage_route(
+    Extension(data_dir): Extension<Option<DataDir>>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let data_dir = data_dir.ok_or_else(BlockfrostError::not_found)?;
+
+    let report = data_dir.report().map_err(|e| {
+        BlockfrostError::internal_server_error(format!("Failed to read the data directory: {}", e))
+    })?;
+
+    Ok(Json(report))
+}
@@ -0,0 +1,209 @@
+use crate::{
+    cbor::slot::SlotNo,
+    cli::Config,
+    node::{
+        api::NodeApi,
+        chain::ChainTip,
+        chainsync::{RecentChain, WindowBlock},
+    },
+    BlockfrostError, NodePool,
+};
+use axum::{extract::Path, response::IntoResponse, Extension, Json};
+use pallas_crypto::hash::Hash;
+use pallas_traverse::wellknown::GenesisValues;
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, sync::Arc};
+
+/// A block in the shape of Blockfrost’s `/blocks/{hash_or_number}`. What local
+/// state queries don’t tell about it is `null`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct BlockResponse {
+    pub time: u64,
+    pub height: Option<u64>,
+    pub hash: String,
+    pub slot: Option<SlotNo>,
+    pub epoch: Option<u32>,
+    pub epoch_slot: Option<u64>,
+    pub slot_leader: Option<String>,
+    pub size: Option<u64>,
+    pub tx_count: Option<u64>,
+    pub output: Option<String>,
+    pub fees: Option<String>,
+    pub block_vrf: Option<String>,
+    pub op_cert: Option<String>,
+    pub op_cert_counter: Option<String>,
+    pub previous_block: Option<String>,
+    pub next_block: Option<String>,
+    pub confirmations: u64,
+}
+
+/// The block at the node’s tip, so that clients can check it’s synced.
+pub async fn latest_route(
+    Extension(node): Extension<NodePool>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let mut node = node.get().await?;
+
+    Ok(Json(latest_block(&mut *node, &config.node.genesis).await?))
+}
+
+async fn latest_block(
+    node: &mut impl NodeApi,
+    genesis: &GenesisValues,
+) -> Result<BlockResponse, BlockfrostError> {
+    let tip = node.chain_tip().await?;
+
+    Ok(block_response(&tip, genesis))
+}
+
+/// A block of the chain follower’s recent window, `404 Not Found` if it’s
+/// older than that or rolled back.
+pub async fn block_route(
+    Extension(chain): Extension<RecentChain>,
+    Extension(config): Extension<Arc<Config>>,
+    Path(hash): Path<String>,
+) -> Result<Json<BlockResponse>, BlockfrostError> {
+    let found = find_block(&chain, &hash)?;
+
+    Ok(Json(window_block_response(
+        &found,
+        chain.tip_height(),
+        &config.node.genesis,
+    )))
+}
+
+/// Hashes of the transactions of a block of the chain follower’s recent window.
+pub async fn block_txs_route(
+    Extension(chain): Extension<RecentChain>,
+    Path(hash): Path<String>,
+) -> Result<Json<Vec<String>>, BlockfrostError> {
+    let found = find_block(&chain, &hash)?;
+
+    Ok(Json(
+        found.block.tx_hashes.iter().map(Hash::to_string).collect(),
+    ))
+}
+
+fn find_block(chain: &RecentChain, hash: &str) -> Result<WindowBlock, BlockfrostError> {
+    let hash = Hash::<32>::from_str(hash)
+        .map_err(|_| BlockfrostError::custom_400(format!("Invalid block hash: {}", hash)))?;
+
+    chain.find_block(&hash).ok_or_else(|| {
+        BlockfrostError::custom_404("The block isn't among the recent ones followed".to_string())
+    })
+}
+
+fn window_block_response(
+    found: &WindowBlock,
+    tip_height: Option<u64>,
+    genesis: &GenesisValues,
+) -> BlockResponse {
+    let block = &found.block;
+    let (epoch, epoch_slot) = block.slot.epoch(genesis);
+
+    BlockResponse {
+        time: block.slot.to_time(genesis).unwrap_or_default(),
+        height: Some(block.height),
+        hash: block.hash.to_string(),
+        slot: Some(block.slot),
+        epoch: Some(epoch as u32),
+        epoch_slot: Some(epoch_slot),
+        slot_leader: None,
+        size: None,
+        tx_count: Some(block.tx_hashes.len() as u64),
+        output: None,
+        fees: None,
+        block_vrf: None,
+        op_cert: None,
+        op_cert_counter: None,
+        previous_block: found.previous.map(|hash| hash.to_string()),
+        next_block: found.next.map(|hash| hash.to_string()),
+        confirmations: tip_height
+            .unwrap_or(block.height)
+            .saturating_sub(block.height),
+    }
+}
+
+fn block_response(tip: &ChainTip, genesis: &GenesisValues) -> BlockResponse {
+    BlockResponse {
+        time: tip.slot.to_time(genesis).unwrap_or_default(),
+        height: Some(tip.height),
+        hash: tip.hash.clone(),
+        slot: Some(tip.slot),
+        epoch: Some(tip.epoch),
+        epoch_slot: Some(tip.slot.epoch(genesis).1),
+        slot_leader: None,
+        size: None,
+        tx_count: None,
+        output: None,
+        fees: None,
+        block_vrf: None,
+        op_cert: None,
+        op_cert_counter: None,
+        previous_block: None,
+        next_block: None,
+        confirmations: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::{chainsync::BlockSummary, mock::MockNode};
+    use pallas_network::miniprotocols::chainsync::Tip;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn test_latest_block() {
+        let mut node = MockNode::new().with_chain_tip(ChainTip {
+            slot: SlotNo(139000000),
+            hash: "ab".repeat(32),
+            height: 11000000,
+            epoch: 519,
+            era: 6,
+        });
+
+        let block = latest_block(&mut node, &GenesisValues::mainnet())
+            .await
+            .unwrap();
+
+        assert_eq!(block.time, 1730566291);
+        assert_eq!(block.height, Some(11000000));
+        assert_eq!(block.hash, "ab".repeat(32));
+        assert_eq!(block.epoch, Some(519));
+        // Epoch 519 started at slot 138844800
+        assert_eq!(block.epoch_slot, Some(155200));
+        assert_eq!(block.next_block, None);
+    }
+
+    #[test]
+    fn test_window_block() {
+        let block = |height: u64, txs: u8| BlockSummary {
+            slot: SlotNo(139000000 + height),
+            hash: Hash::new([height as u8; 32]),
+            height,
+            tx_hashes: (0..txs).map(|tx| Hash::new([0xf0 + tx; 32])).collect(),
+        };
+        let chain = RecentChain::new(3);
+        for height in 1..=4 {
+            chain.roll_forward(block(height, 2), Tip(block(height, 2).point(), 10));
+        }
+
+        let found = find_block(&chain, &"03".repeat(32)).unwrap();
+        let response = window_block_response(&found, chain.tip_height(), &GenesisValues::mainnet());
+        assert_eq!(response.hash, "03".repeat(32));
+        assert_eq!(response.height, Some(3));
+        assert_eq!(response.epoch, Some(519));
+        assert_eq!(response.epoch_slot, Some(155203));
+        assert_eq!(response.tx_count, Some(2));
+        assert_eq!(response.previous_block, Some("02".repeat(32)));
+        assert_eq!(response.next_block, Some("04".repeat(32)));
+        assert_eq!(response.confirmations, 7);
+
+        // Out of the window
+        let error = find_block(&chain, &"01".repeat(32)).unwrap_err();
+        assert_eq!(error.status_code, 404);
+        let error = find_block(&chain, "latest").unwrap_err();
+        assert_eq!(error.status_code, 400);
+    }
+}
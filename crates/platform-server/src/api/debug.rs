@@ -0,0 +1,8 @@
+use crate::cbor::coverage::{decoder_coverage, EnumCoverage};
+use axum::Json;
+
+/// Lists every failure type and variant the native decoder supports, and the
+/// known-unsupported ones, i.e. those always going to the fallback decoder.
+pub async fn decoder_coverage_route() -> Json<Vec<EnumCoverage>> {
+    Json(decoder_coverage())
+}
@@ -0,0 +1,335 @@
+use crate::{
+    cbor::slot::SlotNo,
+    hooks::{SubmissionHook, SubmissionOutcome, SubmittedTx},
+    node::{
+        chainsync::{ChainEvent, RecentChain},
+        transactions::tx_id,
+    },
+    BlockfrostError,
+};
+use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    response::Response,
+    Extension,
+};
+use pallas_crypto::hash::Hash;
+use pallas_network::miniprotocols::Point;
+use pallas_traverse::MultiEraTx;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// How many events a slow client may fall behind before missing some.
+const EVENTS_CAPACITY: usize = 256;
+
+/// What `/ws` pushes to its clients, one JSON text message each.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    Tip {
+        slot: SlotNo,
+        hash: String,
+        height: u64,
+    },
+    /// Every block after this one is gone, [`None`] meaning back to genesis
+    Rollback { slot: SlotNo, hash: Option<String> },
+    /// The last [`Event::Tip`] was the first block of `epoch`
+    EpochBoundary { epoch: u64, slot: SlotNo },
+    /// A transaction submitted through us made it into a block
+    TxConfirmed {
+        tx_id: String,
+        block_hash: String,
+        height: u64,
+    },
+    TxRejected {
+        tx_id: String,
+        error: BlockfrostError,
+    },
+    /// The client fell behind and missed this many events
+    Lagged { missed: u64 },
+}
+
+/// Turns what the chain follower sees, and the outcomes of submissions, into
+/// [`Event`]s for every `/ws` client.
+#[derive(Clone)]
+pub struct EventStream {
+    events: broadcast::Sender<Event>,
+    submitted: Arc<Mutex<Submitted>>,
+}
+
+/// Transactions accepted by the node, keyed by body hash, with the slot of
+/// the block they were seen in, so that a rollback can take it back.
+struct Submitted {
+    by_hash: HashMap<Hash<32>, (String, Option<SlotNo>)>,
+    order: VecDeque<Hash<32>>,
+    capacity: usize,
+}
+
+impl EventStream {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: broadcast::channel(EVENTS_CAPACITY).0,
+            submitted: Arc::new(Mutex::new(Submitted {
+                by_hash: HashMap::new(),
+                order: VecDeque::new(),
+                capacity,
+            })),
+        }
+    }
+
+    /// Like [`EventStream::new`], following `chain` in the background.
+    pub fn spawn(capacity: usize, chain: &RecentChain) -> Self {
+        let stream = Self::new(capacity);
+        let mut chain_events = chain.subscribe();
+
+        let forwarded = stream.clone();
+        tokio::spawn(async move {
+            loop {
+                match chain_events.recv().await {
+                    Ok(event) => forwarded.on_chain_event(event),
+                    // Missed some blocks, the next one is a new tip anyway
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+
+        stream
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.events.subscribe()
+    }
+
+    fn send(&self, event: Event) {
+        // Nobody listening is fine
+        let _ = self.events.send(event);
+    }
+
+    pub fn on_chain_event(&self, event: ChainEvent) {
+        match event {
+            ChainEvent::NewBlock(block) => {
+                let confirmed: Vec<String> = {
+                    let mut submitted = self.submitted.lock().unwrap_or_else(|e| e.into_inner());
+                    block
+                        .tx_hashes
+                        .iter()
+                        .filter_map(|hash| {
+                            let (txid, seen_at) = submitted.by_hash.get_mut(hash)?;
+                            *seen_at = Some(block.slot);
+                            Some(txid.clone())
+                        })
+                        .collect()
+                };
+
+                self.send(Event::Tip {
+                    slot: block.slot,
+                    hash: block.hash.to_string(),
+                    height: block.height,
+                });
+                for tx_id in confirmed {
+                    self.send(Event::TxConfirmed {
+                        tx_id,
+                        block_hash: block.hash.to_string(),
+                        height: block.height,
+                    });
+                }
+            }
+            ChainEvent::Rollback(point) => {
+                let slot = SlotNo(point.slot_or_default());
+                for (_, seen_at) in self
+                    .submitted
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .by_hash
+                    .values_mut()
+                {
+                    if seen_at.is_some_and(|seen_at| seen_at > slot) {
+                        *seen_at = None;
+                    }
+                }
+
+                self.send(Event::Rollback {
+                    slot,
+                    hash: match point {
+                        Point::Origin => None,
+                        Point::Specific(_, hash) => Some(hex::encode(hash)),
+                    },
+                });
+            }
+            ChainEvent::EpochBoundary { epoch, slot } => {
+                self.send(Event::EpochBoundary { epoch, slot });
+            }
+        }
+    }
+}
+
+impl Submitted {
+    fn insert(&mut self, hash: Hash<32>, txid: String) {
+        if self.by_hash.insert(hash, (txid, None)).is_none() {
+            self.order.push_back(hash);
+        }
+
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.by_hash.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Watches for accepted transactions to show up in blocks, and passes on
+/// rejections right away.
+impl SubmissionHook for EventStream {
+    fn post_submit(&self, tx: &SubmittedTx, outcome: &SubmissionOutcome) {
+        match outcome {
+            SubmissionOutcome::Accepted(txid) => {
+                if let Ok(decoded) = MultiEraTx::decode(tx.cbor) {
+                    self.submitted
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .insert(decoded.hash(), txid.to_string());
+                }
+            }
+            SubmissionOutcome::Rejected(error) => self.send(Event::TxRejected {
+                tx_id: tx_id(tx.cbor),
+                error: (*error).clone(),
+            }),
+        }
+    }
+}
+
+pub async fn route(ws: WebSocketUpgrade, Extension(stream): Extension<EventStream>) -> Response {
+    let events = stream.subscribe();
+
+    ws.on_upgrade(move |socket| push_events(socket, events))
+}
+
+/// Sends every event to the client until either side is gone. Whatever the
+/// client sends is ignored, besides closing.
+async fn push_events(mut socket: WebSocket, mut events: broadcast::Receiver<Event>) {
+    loop {
+        let event = tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => event,
+                Err(RecvError::Lagged(missed)) => Event::Lagged { missed },
+                Err(RecvError::Closed) => return,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+        };
+
+        let Ok(json) = serde_json::to_string(&event) else {
+            continue;
+        };
+        if socket.send(Message::Text(json.into())).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::{chainsync::BlockSummary, mock::CONWAY_TX};
+    use pretty_assertions::assert_eq;
+
+    fn block(height: u64, tx_hashes: Vec<Hash<32>>) -> BlockSummary {
+        BlockSummary {
+            slot: SlotNo(height * 20),
+            hash: Hash::new([height as u8; 32]),
+            height,
+            tx_hashes,
+        }
+    }
+
+    fn drain(events: &mut broadcast::Receiver<Event>) -> Vec<Event> {
+        std::iter::from_fn(|| events.try_recv().ok()).collect()
+    }
+
+    #[test]
+    fn test_tx_confirmed() {
+        let tx = hex::decode(CONWAY_TX).unwrap();
+        let hash = MultiEraTx::decode(&tx).unwrap().hash();
+        let stream = EventStream::new(10);
+        let mut events = stream.subscribe();
+
+        stream.post_submit(
+            &SubmittedTx {
+                cbor: &tx,
+                signer: None,
+            },
+            &SubmissionOutcome::Accepted("a"),
+        );
+        stream.on_chain_event(ChainEvent::NewBlock(block(1, vec![])));
+        stream.on_chain_event(ChainEvent::NewBlock(block(2, vec![hash])));
+
+        let tip = |height: u64| Event::Tip {
+            slot: SlotNo(height * 20),
+            hash: Hash::<32>::new([height as u8; 32]).to_string(),
+            height,
+        };
+        let confirmed = |height: u64| Event::TxConfirmed {
+            tx_id: "a".to_string(),
+            block_hash: Hash::<32>::new([height as u8; 32]).to_string(),
+            height,
+        };
+        assert_eq!(drain(&mut events), vec![tip(1), tip(2), confirmed(2)]);
+
+        // Rolled back, and in another block again
+        stream.on_chain_event(ChainEvent::Rollback(block(1, vec![]).point()));
+        stream.on_chain_event(ChainEvent::NewBlock(block(3, vec![hash])));
+        assert_eq!(
+            drain(&mut events),
+            vec![
+                Event::Rollback {
+                    slot: SlotNo(20),
+                    hash: Some(Hash::<32>::new([1; 32]).to_string()),
+                },
+                tip(3),
+                confirmed(3),
+            ]
+        );
+
+        stream.on_chain_event(ChainEvent::EpochBoundary {
+            epoch: 1,
+            slot: SlotNo(60),
+        });
+        assert_eq!(
+            drain(&mut events),
+            vec![Event::EpochBoundary {
+                epoch: 1,
+                slot: SlotNo(60)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_tx_rejected() {
+        let stream = EventStream::new(10);
+        let mut events = stream.subscribe();
+        let error = BlockfrostError::custom_400("rejected".to_string());
+
+        stream.post_submit(
+            &SubmittedTx {
+                cbor: &[0x84],
+                signer: None,
+            },
+            &SubmissionOutcome::Rejected(&error),
+        );
+
+        assert_eq!(
+            serde_json::to_value(drain(&mut events)).unwrap(),
+            serde_json::json!([{
+                "type": "tx_rejected",
+                "tx_id": tx_id(&[0x84]),
+                "error": error,
+            }])
+        );
+    }
+}
@@ -0,0 +1,920 @@
+use crate::{
+    api::pools::{paginate, PaginationQuery},
+    cbor::haskell_types::{Credential, DisplayKeyHash, DisplayScriptHash},
+    encoding::{encode_as, Bech32Kind},
+    node::{
+        api::NodeApi,
+        governance::{
+            CommitteeState, DRepState, GovernanceParams, HotCredAuthStatus, MemberStatus, Proposal,
+        },
+    },
+    BlockfrostError, NodePool,
+};
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Extension, Json,
+};
+use pallas_crypto::hash::Hash;
+use pallas_primitives::{
+    conway::{Anchor, DRep, DRepVotingThresholds, GovAction, ProtocolParamUpdate, Vote},
+    RationalNumber,
+};
+use serde::{Deserialize, Serialize};
+
+/// An item of Blockfrost’s `/governance/dreps`, with what the ledger state
+/// has on it besides.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct DRepResponse {
+    pub drep_id: String,
+    pub hex: String,
+    pub has_script: bool,
+    pub deposit: String,
+    pub expiry: u64,
+    pub metadata_url: Option<String>,
+    pub metadata_hash: Option<String>,
+}
+
+/// An item of Blockfrost’s `/governance/proposals`, with what the ledger
+/// state has on it besides.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ProposalResponse {
+    pub tx_hash: String,
+    pub cert_index: u32,
+    pub governance_type: String,
+    pub deposit: String,
+    pub return_address: String,
+    pub proposed_epoch: u64,
+    pub expiration: u64,
+    pub metadata_url: String,
+    pub metadata_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct CommitteeResponse {
+    pub epoch: u64,
+    pub threshold: Option<f64>,
+    pub members: Vec<CommitteeMemberResponse>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct CommitteeMemberResponse {
+    pub cold_hex: String,
+    pub cold_has_script: bool,
+    pub hot_hex: Option<String>,
+    pub hot_has_script: Option<bool>,
+    /// `authorized`, `not_authorized`, or `resigned`
+    pub authorization: String,
+    /// `active`, `expired`, or `unrecognized`, i.e. not enacted yet
+    pub status: String,
+    pub expiration: Option<u64>,
+}
+
+/// Whether a governance action would be ratified at the next epoch boundary,
+/// were the votes cast so far final.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct RatificationResponse {
+    pub tx_hash: String,
+    pub cert_index: u32,
+    pub governance_type: String,
+    pub epoch: u64,
+    /// Whether every group voting on the action meets its threshold
+    pub will_pass: bool,
+    /// `null` for the groups that don’t vote on the action
+    pub committee: Option<VotingResult>,
+    pub drep: Option<VotingResult>,
+    pub pool: Option<VotingResult>,
+}
+
+/// How a group of voters stands on an action: members of the committee, and
+/// lovelace of active stake for DReps and pools.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct VotingResult {
+    pub yes: String,
+    pub no: String,
+    pub abstain: String,
+    /// Of the yes votes to the yes and no votes together
+    pub ratio: f64,
+    /// `null` if the action can’t be ratified by this group at all
+    pub threshold: Option<f64>,
+    pub passes: bool,
+}
+
+pub async fn dreps_route(
+    Extension(node): Extension<NodePool>,
+    Query(query): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let mut node = node.get().await?;
+
+    Ok(Json(dreps(&mut *node, &query).await?))
+}
+
+pub async fn proposals_route(
+    Extension(node): Extension<NodePool>,
+    Query(query): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let mut node = node.get().await?;
+
+    Ok(Json(proposals(&mut *node, &query).await?))
+}
+
+pub async fn committee_route(
+    Extension(node): Extension<NodePool>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let mut node = node.get().await?;
+
+    Ok(Json(committee(&mut *node).await?))
+}
+
+pub async fn ratification_route(
+    Extension(node): Extension<NodePool>,
+    Path((tx_hash, cert_index)): Path<(String, u32)>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let mut node = node.get().await?;
+
+    Ok(Json(ratification(&mut *node, &tx_hash, cert_index).await?))
+}
+
+async fn dreps(
+    node: &mut impl NodeApi,
+    query: &PaginationQuery,
+) -> Result<Vec<DRepResponse>, BlockfrostError> {
+    let mut dreps = node.drep_states().await?;
+    dreps.sort_by_key(|drep| credential_hex(&drep.credential));
+
+    paginate(dreps, query)?
+        .into_iter()
+        .map(|drep| drep_response(drep).map_err(BlockfrostError::internal_server_error))
+        .collect()
+}
+
+async fn proposals(
+    node: &mut impl NodeApi,
+    query: &PaginationQuery,
+) -> Result<Vec<ProposalResponse>, BlockfrostError> {
+    let mut proposals = node.proposals().await?;
+    proposals.sort_by_key(|proposal| (proposal.id.transaction_id, proposal.id.action_index));
+
+    paginate(proposals, query)?
+        .into_iter()
+        .map(|proposal| proposal_response(proposal).map_err(BlockfrostError::internal_server_error))
+        .collect()
+}
+
+async fn committee(node: &mut impl NodeApi) -> Result<CommitteeResponse, BlockfrostError> {
+    let CommitteeState {
+        mut members,
+        threshold,
+        epoch,
+    } = node.committee_state().await?;
+    members.sort_by_key(|member| credential_hex(&member.cold_credential));
+
+    let members = members
+        .into_iter()
+        .map(|member| CommitteeMemberResponse {
+            cold_hex: credential_hex(&member.cold_credential),
+            cold_has_script: has_script(&member.cold_credential),
+            hot_hex: member.hot_credential.as_ref().map(credential_hex),
+            hot_has_script: member.hot_credential.as_ref().map(has_script),
+            authorization: match member.authorization {
+                HotCredAuthStatus::Authorized => "authorized",
+                HotCredAuthStatus::NotAuthorized => "not_authorized",
+                HotCredAuthStatus::Resigned => "resigned",
+            }
+            .to_string(),
+            status: match member.status {
+                MemberStatus::Active => "active",
+                MemberStatus::Expired => "expired",
+                MemberStatus::Unrecognized => "unrecognized",
+            }
+            .to_string(),
+            expiration: member.expiration,
+        })
+        .collect();
+
+    Ok(CommitteeResponse {
+        epoch,
+        threshold: threshold
+            .filter(|(_, denominator)| *denominator != 0)
+            .map(|(numerator, denominator)| numerator as f64 / denominator as f64),
+        members,
+    })
+}
+
+/// Tallies the votes on the proposal `tx_hash#cert_index` the way the ledger
+/// would at the next epoch boundary. Only the votes are considered, not
+/// whether the action is next in line after those of its purpose enacted
+/// before, nor what the treasury holds.
+///
+/// Pools that didn’t vote count as voting no, as for hard forks. On other
+/// actions the ledger lets their reward accounts’ DRep delegation decide, so
+/// there the pools’ ratio may be understated.
+async fn ratification(
+    node: &mut impl NodeApi,
+    tx_hash: &str,
+    cert_index: u32,
+) -> Result<RatificationResponse, BlockfrostError> {
+    let proposal = node
+        .proposals()
+        .await?
+        .into_iter()
+        .find(|proposal| {
+            hex::encode(proposal.id.transaction_id) == tx_hash
+                && proposal.id.action_index == cert_index
+        })
+        .ok_or_else(BlockfrostError::not_found)?;
+    let committee_state = node.committee_state().await?;
+    let params = node.governance_params().await?;
+    let dreps = node.drep_states().await?;
+    let drep_stake = node.drep_stake_distribution().await?;
+    let pool_stake = node.pool_stake_distribution().await?;
+
+    let action = &proposal.procedure.gov_action;
+    let epoch = committee_state.epoch;
+    // Protocol version 9, when DReps don’t vote yet
+    let bootstrap = params.protocol_major == 9;
+    let thresholds = Thresholds::of(action, &params, committee_state.threshold);
+
+    let committee = thresholds.committee.map(|threshold| {
+        let threshold = threshold.filter(|_| {
+            bootstrap || active_committee_size(&committee_state) >= params.min_committee_size
+        });
+        voting_result(committee_tally(&proposal, &committee_state), threshold)
+    });
+    let drep = thresholds.drep.map(|threshold| {
+        let threshold = if bootstrap { Some((0, 1)) } else { threshold };
+        voting_result(drep_tally(&proposal, &dreps, &drep_stake, epoch), threshold)
+    });
+    let pool = thresholds
+        .pool
+        .map(|threshold| voting_result(pool_tally(&proposal, &pool_stake, bootstrap), threshold));
+
+    let will_pass = [&committee, &drep, &pool]
+        .into_iter()
+        .flatten()
+        .all(|result| result.passes);
+
+    Ok(RatificationResponse {
+        tx_hash: hex::encode(proposal.id.transaction_id),
+        cert_index: proposal.id.action_index,
+        governance_type: governance_type(action).to_string(),
+        epoch,
+        will_pass,
+        committee,
+        drep,
+        pool,
+    })
+}
+
+/// A fraction, as numerator and denominator.
+type Ratio = (u64, u64);
+
+/// The threshold of each group voting on an action, `Some(None)` for a
+/// group voting on something it can never ratify.
+struct Thresholds {
+    committee: Option<Option<Ratio>>,
+    drep: Option<Option<Ratio>>,
+    pool: Option<Option<Ratio>>,
+}
+
+impl Thresholds {
+    /// Without a `committee_threshold`, there’s no committee to vote.
+    fn of(
+        action: &GovAction,
+        params: &GovernanceParams,
+        committee_threshold: Option<Ratio>,
+    ) -> Self {
+        let ratio = |rational: &RationalNumber| Some((rational.numerator, rational.denominator));
+        let pools = &params.pool_voting_thresholds;
+        let dreps = &params.drep_voting_thresholds;
+        let committee = Some(committee_threshold);
+        let has_committee = committee_threshold.is_some();
+
+        match action {
+            GovAction::ParameterChange(_, update, _) => Self {
+                committee,
+                drep: Some(
+                    parameter_groups(update, dreps)
+                        .into_iter()
+                        .max_by(|a, b| cmp_ratio(*a, *b)),
+                ),
+                pool: is_security_relevant(update).then(|| ratio(&pools.security_voting_threshold)),
+            },
+            GovAction::HardForkInitiation(..) => Self {
+                committee,
+                drep: Some(ratio(&dreps.hard_fork_initiation)),
+                pool: Some(ratio(&pools.hard_fork_initiation)),
+            },
+            GovAction::TreasuryWithdrawals(..) => Self {
+                committee,
+                drep: Some(ratio(&dreps.treasury_withdrawal)),
+                pool: None,
+            },
+            GovAction::NoConfidence(..) => Self {
+                committee: None,
+                drep: Some(ratio(&dreps.motion_no_confidence)),
+                pool: Some(ratio(&pools.motion_no_confidence)),
+            },
+            GovAction::UpdateCommittee(..) if has_committee => Self {
+                committee: None,
+                drep: Some(ratio(&dreps.committee_normal)),
+                pool: Some(ratio(&pools.committee_normal)),
+            },
+            GovAction::UpdateCommittee(..) => Self {
+                committee: None,
+                drep: Some(ratio(&dreps.committee_no_confidence)),
+                pool: Some(ratio(&pools.committee_no_confidence)),
+            },
+            GovAction::NewConstitution(..) => Self {
+                committee,
+                drep: Some(ratio(&dreps.update_constitution)),
+                pool: None,
+            },
+            // Voted on by everyone, but never ratified
+            GovAction::Information => Self {
+                committee: Some(None),
+                drep: Some(None),
+                pool: Some(None),
+            },
+        }
+    }
+}
+
+/// The DRep thresholds of the groups of the parameters that `update` changes.
+fn parameter_groups(update: &ProtocolParamUpdate, thresholds: &DRepVotingThresholds) -> Vec<Ratio> {
+    let network = update.max_block_body_size.is_some()
+        || update.max_transaction_size.is_some()
+        || update.max_block_header_size.is_some()
+        || update.max_value_size.is_some()
+        || update.max_tx_ex_units.is_some()
+        || update.max_block_ex_units.is_some()
+        || update.max_collateral_inputs.is_some();
+    let economic = update.minfee_a.is_some()
+        || update.minfee_b.is_some()
+        || update.key_deposit.is_some()
+        || update.pool_deposit.is_some()
+        || update.expansion_rate.is_some()
+        || update.treasury_growth_rate.is_some()
+        || update.min_pool_cost.is_some()
+        || update.ada_per_utxo_byte.is_some()
+        || update.execution_costs.is_some()
+        || update.minfee_refscript_cost_per_byte.is_some();
+    let technical = update.maximum_epoch.is_some()
+        || update.desired_number_of_stake_pools.is_some()
+        || update.pool_pledge_influence.is_some()
+        || update.cost_models_for_script_languages.is_some()
+        || update.collateral_percentage.is_some();
+    let governance = update.pool_voting_thresholds.is_some()
+        || update.drep_voting_thresholds.is_some()
+        || update.min_committee_size.is_some()
+        || update.committee_term_limit.is_some()
+        || update.governance_action_validity_period.is_some()
+        || update.governance_action_deposit.is_some()
+        || update.drep_deposit.is_some()
+        || update.drep_inactivity_period.is_some();
+
+    [
+        (network, &thresholds.pp_network_group),
+        (economic, &thresholds.pp_economic_group),
+        (technical, &thresholds.pp_technical_group),
+        (governance, &thresholds.pp_governance_group),
+    ]
+    .into_iter()
+    .filter(|(changed, _)| *changed)
+    .map(|(_, threshold)| (threshold.numerator, threshold.denominator))
+    .collect()
+}
+
+/// Whether `update` changes any of the parameters pools vote on too.
+fn is_security_relevant(update: &ProtocolParamUpdate) -> bool {
+    update.max_block_body_size.is_some()
+        || update.max_transaction_size.is_some()
+        || update.max_block_header_size.is_some()
+        || update.max_value_size.is_some()
+        || update.max_block_ex_units.is_some()
+        || update.minfee_a.is_some()
+        || update.minfee_b.is_some()
+        || update.ada_per_utxo_byte.is_some()
+        || update.governance_action_deposit.is_some()
+        || update.minfee_refscript_cost_per_byte.is_some()
+}
+
+fn cmp_ratio((a, b): Ratio, (c, d): Ratio) -> std::cmp::Ordering {
+    (u128::from(a) * u128::from(d)).cmp(&(u128::from(c) * u128::from(b)))
+}
+
+/// Votes of a group, in members or lovelace.
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    yes: u64,
+    no: u64,
+    abstain: u64,
+}
+
+impl Tally {
+    fn add(&mut self, vote: Option<&Vote>, weight: u64) {
+        match vote {
+            Some(Vote::Yes) => self.yes += weight,
+            Some(Vote::Abstain) => self.abstain += weight,
+            Some(Vote::No) | None => self.no += weight,
+        }
+    }
+}
+
+fn voting_result(tally: Tally, threshold: Option<Ratio>) -> VotingResult {
+    let counted = tally.yes + tally.no;
+    let passes = threshold.is_some_and(|threshold| {
+        if counted == 0 {
+            threshold.0 == 0
+        } else {
+            cmp_ratio((tally.yes, counted), threshold).is_ge()
+        }
+    });
+
+    VotingResult {
+        yes: tally.yes.to_string(),
+        no: tally.no.to_string(),
+        abstain: tally.abstain.to_string(),
+        ratio: if counted == 0 {
+            0.0
+        } else {
+            tally.yes as f64 / counted as f64
+        },
+        threshold: threshold
+            .filter(|(_, denominator)| *denominator != 0)
+            .map(|(numerator, denominator)| numerator as f64 / denominator as f64),
+        passes,
+    }
+}
+
+/// Members with a hot key and a term not over yet.
+fn active_committee_size(committee: &CommitteeState) -> u64 {
+    committee
+        .members
+        .iter()
+        .filter(|member| {
+            member.status == MemberStatus::Active
+                && member.authorization == HotCredAuthStatus::Authorized
+        })
+        .count() as u64
+}
+
+/// Members who didn’t vote count as voting no, and those without a hot key
+/// or with their term over as abstaining.
+fn committee_tally(proposal: &Proposal, committee: &CommitteeState) -> Tally {
+    let mut tally = Tally::default();
+    for member in &committee.members {
+        match (&member.hot_credential, member.status) {
+            (Some(hot), MemberStatus::Active) => {
+                let vote = proposal
+                    .committee_votes
+                    .iter()
+                    .find(|(voter, _)| voter == hot)
+                    .map(|(_, vote)| vote);
+                tally.add(vote, 1);
+            }
+            (_, MemberStatus::Unrecognized) => {}
+            _ => tally.abstain += 1,
+        }
+    }
+    tally
+}
+
+/// DReps that didn’t vote count as voting no, and those inactive not at
+/// all. The stake delegated to always abstain abstains, and that to always
+/// no confidence votes yes on no confidence, and no on anything else.
+fn drep_tally(
+    proposal: &Proposal,
+    dreps: &[DRepState],
+    drep_stake: &[(DRep, u64)],
+    epoch: u64,
+) -> Tally {
+    let no_confidence = matches!(proposal.procedure.gov_action, GovAction::NoConfidence(..));
+    let mut tally = Tally::default();
+    for (drep, stake) in drep_stake {
+        let credential = match drep {
+            DRep::Key(hash) => Credential::KeyHashObj(DisplayKeyHash(*hash)),
+            DRep::Script(hash) => Credential::ScriptHashObj(DisplayScriptHash(*hash)),
+            DRep::Abstain => {
+                tally.abstain += stake;
+                continue;
+            }
+            DRep::NoConfidence if no_confidence => {
+                tally.yes += stake;
+                continue;
+            }
+            DRep::NoConfidence => {
+                tally.no += stake;
+                continue;
+            }
+        };
+
+        let active = dreps
+            .iter()
+            .any(|state| state.credential == credential && state.expiry >= epoch);
+        if active {
+            let vote = proposal
+                .drep_votes
+                .iter()
+                .find(|(voter, _)| *voter == credential)
+                .map(|(_, vote)| vote);
+            tally.add(vote, *stake);
+        }
+    }
+    tally
+}
+
+/// Pools that didn’t vote count as voting no, or while bootstrapping as
+/// abstaining, except on hard forks.
+fn pool_tally(proposal: &Proposal, pool_stake: &[(Hash<28>, u64)], bootstrap: bool) -> Tally {
+    let hard_fork = matches!(
+        proposal.procedure.gov_action,
+        GovAction::HardForkInitiation(..)
+    );
+    let mut tally = Tally::default();
+    for (pool, stake) in pool_stake {
+        let vote = proposal
+            .pool_votes
+            .iter()
+            .find(|(voter, _)| voter == pool)
+            .map(|(_, vote)| vote);
+        match vote {
+            None if bootstrap && !hard_fork => tally.abstain += stake,
+            vote => tally.add(vote, *stake),
+        }
+    }
+    tally
+}
+
+fn credential_hex(credential: &Credential) -> String {
+    match credential {
+        Credential::KeyHashObj(hash) => hex::encode(hash.0),
+        Credential::ScriptHashObj(hash) => hex::encode(hash.0),
+    }
+}
+
+fn has_script(credential: &Credential) -> bool {
+    matches!(credential, Credential::ScriptHashObj(_))
+}
+
+fn drep_response(drep: DRepState) -> Result<DRepResponse, String> {
+    let drep_id = match &drep.credential {
+        Credential::KeyHashObj(hash) => encode_as(Bech32Kind::Drep, hash.0.as_ref())?,
+        Credential::ScriptHashObj(hash) => encode_as(Bech32Kind::DrepScript, hash.0.as_ref())?,
+    };
+
+    Ok(DRepResponse {
+        drep_id,
+        hex: credential_hex(&drep.credential),
+        has_script: has_script(&drep.credential),
+        deposit: drep.deposit.to_string(),
+        expiry: drep.expiry,
+        metadata_url: drep.anchor.as_ref().map(|anchor| anchor.url.clone()),
+        metadata_hash: drep
+            .anchor
+            .as_ref()
+            .map(|anchor| hex::encode(anchor.content_hash)),
+    })
+}
+
+fn proposal_response(proposal: Proposal) -> Result<ProposalResponse, String> {
+    let Proposal {
+        id,
+        procedure,
+        proposed_in,
+        expires_after,
+        ..
+    } = proposal;
+    let Anchor { url, content_hash } = procedure.anchor;
+
+    Ok(ProposalResponse {
+        tx_hash: hex::encode(id.transaction_id),
+        cert_index: id.action_index,
+        governance_type: governance_type(&procedure.gov_action).to_string(),
+        deposit: procedure.deposit.to_string(),
+        return_address: encode_as(Bech32Kind::Address, &procedure.reward_account)?,
+        proposed_epoch: proposed_in,
+        expiration: expires_after,
+        metadata_url: url,
+        metadata_hash: hex::encode(content_hash),
+    })
+}
+
+/// Blockfrost’s names of the governance actions.
+fn governance_type(action: &GovAction) -> &'static str {
+    match action {
+        GovAction::ParameterChange(..) => "parameter_change",
+        GovAction::HardForkInitiation(..) => "hard_fork_initiation",
+        GovAction::TreasuryWithdrawals(..) => "treasury_withdrawals",
+        GovAction::NoConfidence(..) => "no_confidence",
+        GovAction::UpdateCommittee(..) => "new_committee",
+        GovAction::NewConstitution(..) => "new_constitution",
+        GovAction::Information => "info_action",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::{governance::CommitteeMember, mock::MockNode};
+    use pallas_codec::{minicbor, utils::Nullable};
+    use pallas_primitives::conway::{GovActionId, PoolVotingThresholds, ProposalProcedure};
+    use pretty_assertions::assert_eq;
+
+    fn drep(credential: Credential) -> DRepState {
+        DRepState {
+            credential,
+            expiry: 600,
+            anchor: Some(Anchor {
+                url: "https://example.com/drep.json".to_string(),
+                content_hash: [7; 32].into(),
+            }),
+            deposit: 500_000_000,
+        }
+    }
+
+    fn proposal(transaction_id: u8, action_index: u32) -> Proposal {
+        Proposal {
+            id: GovActionId {
+                transaction_id: [transaction_id; 32].into(),
+                action_index,
+            },
+            committee_votes: vec![],
+            drep_votes: vec![],
+            pool_votes: vec![],
+            procedure: ProposalProcedure {
+                deposit: 100_000_000_000,
+                reward_account: [&[0xe1][..], &[0x11; 28]].concat().into(),
+                gov_action: GovAction::Information,
+                anchor: Anchor {
+                    url: "https://example.com/info.json".to_string(),
+                    content_hash: [8; 32].into(),
+                },
+            },
+            proposed_in: 500,
+            expires_after: 506,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dreps() {
+        let mut node = MockNode::new().with_dreps(vec![
+            drep(Credential::ScriptHashObj(DisplayScriptHash(
+                [0x01; 28].into(),
+            ))),
+            drep(Credential::KeyHashObj(DisplayKeyHash([0x00; 28].into()))),
+        ]);
+
+        let response = dreps(&mut node, &PaginationQuery::default()).await.unwrap();
+
+        assert_eq!(response.len(), 2);
+        assert_eq!(response[0].hex, "00".repeat(28));
+        assert!(!response[0].has_script);
+        assert!(response[1].has_script);
+        assert!(response
+            .iter()
+            .all(|drep| drep.drep_id.starts_with("drep1")));
+        assert_eq!(response[0].deposit, "500000000");
+        assert_eq!(
+            response[0].metadata_url.as_deref(),
+            Some("https://example.com/drep.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_proposals() {
+        let mut node = MockNode::new().with_proposals(vec![proposal(2, 0), proposal(1, 1)]);
+        let query = PaginationQuery {
+            count: Some(1),
+            ..Default::default()
+        };
+
+        let response = proposals(&mut node, &query).await.unwrap();
+
+        assert_eq!(
+            response,
+            vec![ProposalResponse {
+                tx_hash: "01".repeat(32),
+                cert_index: 1,
+                governance_type: "info_action".to_string(),
+                deposit: "100000000000".to_string(),
+                return_address: encode_as(
+                    Bech32Kind::Address,
+                    &[&[0xe1][..], &[0x11; 28]].concat()
+                )
+                .unwrap(),
+                proposed_epoch: 500,
+                expiration: 506,
+                metadata_url: "https://example.com/info.json".to_string(),
+                metadata_hash: "08".repeat(32),
+            }]
+        );
+    }
+
+    fn key_hash(byte: u8) -> Credential {
+        Credential::KeyHashObj(DisplayKeyHash([byte; 28].into()))
+    }
+
+    fn member(hot: u8, authorization: HotCredAuthStatus, status: MemberStatus) -> CommitteeMember {
+        CommitteeMember {
+            cold_credential: key_hash(hot + 0x80),
+            hot_credential: (authorization == HotCredAuthStatus::Authorized).then(|| key_hash(hot)),
+            authorization,
+            status,
+            expiration: Some(700),
+        }
+    }
+
+    fn governance_params(protocol_major: u64) -> GovernanceParams {
+        let ratio = |numerator, denominator| RationalNumber {
+            numerator,
+            denominator,
+        };
+
+        GovernanceParams {
+            protocol_major,
+            pool_voting_thresholds: PoolVotingThresholds {
+                motion_no_confidence: ratio(51, 100),
+                committee_normal: ratio(51, 100),
+                committee_no_confidence: ratio(51, 100),
+                hard_fork_initiation: ratio(51, 100),
+                security_voting_threshold: ratio(51, 100),
+            },
+            drep_voting_thresholds: DRepVotingThresholds {
+                motion_no_confidence: ratio(67, 100),
+                committee_normal: ratio(67, 100),
+                committee_no_confidence: ratio(3, 5),
+                update_constitution: ratio(3, 4),
+                hard_fork_initiation: ratio(3, 5),
+                pp_network_group: ratio(67, 100),
+                pp_economic_group: ratio(67, 100),
+                pp_technical_group: ratio(67, 100),
+                pp_governance_group: ratio(3, 4),
+                treasury_withdrawal: ratio(67, 100),
+            },
+            min_committee_size: 2,
+        }
+    }
+
+    /// A node voting on a hard fork, proposed by `proposal(1, 0)`.
+    fn voting_node(protocol_major: u64) -> MockNode {
+        let mut hard_fork = Proposal {
+            committee_votes: vec![(key_hash(1), Vote::Yes)],
+            drep_votes: vec![(key_hash(0x11), Vote::Yes), (key_hash(0x12), Vote::Yes)],
+            pool_votes: vec![([0x21; 28].into(), Vote::Yes)],
+            ..proposal(1, 0)
+        };
+        let mut info = proposal(2, 0);
+        info.drep_votes = vec![(key_hash(0x11), Vote::Yes)];
+        hard_fork.procedure.gov_action = GovAction::HardForkInitiation(Nullable::Null, (10, 0));
+        let expired = DRepState {
+            expiry: 500,
+            ..drep(key_hash(0x12))
+        };
+
+        MockNode::new()
+            .with_proposals(vec![hard_fork, info])
+            .with_committee(CommitteeState {
+                members: vec![
+                    member(1, HotCredAuthStatus::Authorized, MemberStatus::Active),
+                    member(2, HotCredAuthStatus::Authorized, MemberStatus::Active),
+                    member(3, HotCredAuthStatus::Resigned, MemberStatus::Active),
+                ],
+                threshold: Some((2, 3)),
+                epoch: 550,
+            })
+            .with_governance_params(governance_params(protocol_major))
+            .with_dreps(vec![drep(key_hash(0x11)), expired, drep(key_hash(0x13))])
+            .with_drep_stake(vec![
+                (DRep::Key([0x11; 28].into()), 60),
+                (DRep::Key([0x12; 28].into()), 1000),
+                (DRep::Key([0x13; 28].into()), 30),
+                (DRep::Abstain, 50),
+                (DRep::NoConfidence, 10),
+            ])
+            .with_pool_stake(vec![([0x21; 28].into(), 70), ([0x22; 28].into(), 30)])
+    }
+
+    #[tokio::test]
+    async fn test_ratification() {
+        let response = ratification(&mut voting_node(10), &"01".repeat(32), 0)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "tx_hash": "01".repeat(32),
+                "cert_index": 0,
+                "governance_type": "hard_fork_initiation",
+                "epoch": 550,
+                "will_pass": false,
+                // The one who didn’t vote counts as no
+                "committee": {
+                    "yes": "1",
+                    "no": "1",
+                    "abstain": "1",
+                    "ratio": 0.5,
+                    "threshold": 2.0 / 3.0,
+                    "passes": false,
+                },
+                // Neither the expired DRep’s stake counts, nor its vote
+                "drep": {
+                    "yes": "60",
+                    "no": "40",
+                    "abstain": "50",
+                    "ratio": 0.6,
+                    "threshold": 0.6,
+                    "passes": true,
+                },
+                "pool": {
+                    "yes": "70",
+                    "no": "30",
+                    "abstain": "0",
+                    "ratio": 0.7,
+                    "threshold": 0.51,
+                    "passes": true,
+                },
+            })
+        );
+
+        // Info actions are never ratified
+        let response = ratification(&mut voting_node(10), &"02".repeat(32), 0)
+            .await
+            .unwrap();
+        assert!(!response.will_pass);
+        assert_eq!(response.drep.map(|drep| drep.threshold), Some(None));
+
+        let error = ratification(&mut voting_node(10), &"01".repeat(32), 1)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status_code, 404);
+    }
+
+    #[tokio::test]
+    async fn test_ratification_bootstrap() {
+        let response = ratification(&mut voting_node(9), &"01".repeat(32), 0)
+            .await
+            .unwrap();
+
+        // DReps don’t vote yet
+        assert_eq!(response.drep.map(|drep| drep.passes), Some(true));
+        // Nor does the committee need its minimum size
+        assert_eq!(
+            response.committee.map(|committee| committee.ratio),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn test_parameter_groups() {
+        let thresholds = governance_params(10).drep_voting_thresholds;
+        // Changing `minfee_a`, then `maximum_epoch` and `drep_deposit`
+        let min_fee: ProtocolParamUpdate =
+            minicbor::decode(&hex::decode("a100182c").unwrap()).unwrap();
+        let epochs: ProtocolParamUpdate =
+            minicbor::decode(&hex::decode("a20712181f1a1dcd6500").unwrap()).unwrap();
+
+        assert_eq!(parameter_groups(&min_fee, &thresholds), vec![(67, 100)]);
+        assert!(is_security_relevant(&min_fee));
+        assert_eq!(
+            parameter_groups(&epochs, &thresholds),
+            vec![(67, 100), (3, 4)]
+        );
+        assert!(!is_security_relevant(&epochs));
+    }
+
+    #[tokio::test]
+    async fn test_committee() {
+        let mut node = MockNode::new().with_committee(CommitteeState {
+            members: vec![CommitteeMember {
+                cold_credential: Credential::ScriptHashObj(DisplayScriptHash([0x01; 28].into())),
+                hot_credential: Some(Credential::KeyHashObj(DisplayKeyHash([0x02; 28].into()))),
+                authorization: HotCredAuthStatus::Authorized,
+                status: MemberStatus::Active,
+                expiration: Some(700),
+            }],
+            threshold: Some((2, 3)),
+            epoch: 550,
+        });
+
+        let response = committee(&mut node).await.unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "epoch": 550,
+                "threshold": 2.0 / 3.0,
+                "members": [{
+                    "cold_hex": "01".repeat(28),
+                    "cold_has_script": true,
+                    "hot_hex": "02".repeat(28),
+                    "hot_has_script": false,
+                    "authorization": "authorized",
+                    "status": "active",
+                    "expiration": 700,
+                }],
+            })
+        );
+    }
+}
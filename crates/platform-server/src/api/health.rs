@@ -0,0 +1,298 @@
+use crate::{
+    circuit_breaker::BreakerState,
+    cli::Config,
+    icebreakers_api::IcebreakersAPI,
+    node::{
+        api::NodeApi,
+        health::{NodeHealth, NodeHealthReport},
+    },
+    NodePool,
+};
+use axum::{http::StatusCode, response::IntoResponse, Extension, Json};
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Instant};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct HealthResponse {
+    /// Whether the node is connected, which doesn’t change the status code
+    pub healthy: bool,
+    pub node: NodeHealthReport,
+    pub observability: ObservabilityHealth,
+}
+
+/// What we failed to set up for operators to observe us with, which doesn’t
+/// keep us from serving, so it doesn’t make us unhealthy either.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ObservabilityHealth {
+    pub degraded: bool,
+    /// Why there are no metrics even though they were asked for
+    pub metrics_error: Option<String>,
+}
+
+impl ObservabilityHealth {
+    pub fn metrics_failed(error: String) -> Self {
+        Self {
+            degraded: true,
+            metrics_error: Some(error),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ReadyResponse {
+    pub ready: bool,
+    pub checks: ReadyChecks,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ReadyChecks {
+    pub node: NodeCheck,
+    pub sync: SyncCheck,
+    /// Unless in solitary mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icebreakers: Option<IcebreakersCheck>,
+}
+
+/// Whether a pooled connection to the node answers a ping.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct NodeCheck {
+    pub ok: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Whether the node is close enough to the tip to serve what clients expect.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SyncCheck {
+    pub ok: bool,
+    /// In percent, unless the node couldn’t tell
+    pub progress: Option<f64>,
+    pub min_progress: f64,
+    pub error: Option<String>,
+}
+
+/// Whether we registered with the Icebreakers API, which we’d have failed to
+/// start otherwise, and aren’t cut off from it.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct IcebreakersCheck {
+    pub ok: bool,
+    pub registered: bool,
+    pub route: String,
+    pub circuit_breaker: BreakerState,
+}
+
+impl IcebreakersCheck {
+    fn of(api: &IcebreakersAPI) -> Self {
+        let circuit_breaker = api.breaker_state();
+
+        Self {
+            ok: circuit_breaker != BreakerState::Open,
+            registered: true,
+            route: api.api_prefix.clone(),
+            circuit_breaker,
+        }
+    }
+}
+
+/// The liveness check: answers 200 as long as we’re serving, without touching
+/// the node, whose last known state is only reported. Taking the instance out
+/// of rotation while the node is down is up to `/ready`.
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Extension(observability): Extension<ObservabilityHealth>,
+) -> impl IntoResponse {
+    Json(health(node.health(), observability))
+}
+
+/// Unlike `/health`, asks the node itself, and answers 503 unless every check
+/// passes, so that orchestrators hold traffic back until we can serve it.
+pub async fn ready_route(
+    Extension(node): Extension<NodePool>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(icebreakers_api): Extension<Option<Arc<IcebreakersAPI>>>,
+) -> impl IntoResponse {
+    let min_progress = config.server.ready_min_sync_progress;
+    let (node, sync) = match node.get().await {
+        Ok(mut node) => check_node(&mut *node, min_progress).await,
+        Err(e) => unreachable(e.to_string(), min_progress),
+    };
+
+    let checks = ReadyChecks {
+        node,
+        sync,
+        icebreakers: icebreakers_api.map(|api| IcebreakersCheck::of(&api)),
+    };
+    let ready = checks.node.ok
+        && checks.sync.ok
+        && checks.icebreakers.as_ref().is_none_or(|check| check.ok);
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(ReadyResponse { ready, checks }))
+}
+
+async fn check_node(node: &mut impl NodeApi, min_progress: f64) -> (NodeCheck, SyncCheck) {
+    let started_at = Instant::now();
+    if let Err(e) = node.ping().await {
+        return unreachable(e.message, min_progress);
+    }
+
+    let node_check = NodeCheck {
+        ok: true,
+        latency_ms: Some(started_at.elapsed().as_millis() as u64),
+        error: None,
+    };
+    let sync_check = match node.sync_progress().await {
+        Ok(node_info) => SyncCheck {
+            ok: node_info.sync_progress >= min_progress,
+            progress: Some(node_info.sync_progress),
+            min_progress,
+            error: None,
+        },
+        Err(e) => SyncCheck {
+            ok: false,
+            progress: None,
+            min_progress,
+            error: Some(e.message),
+        },
+    };
+
+    (node_check, sync_check)
+}
+
+fn unreachable(error: String, min_progress: f64) -> (NodeCheck, SyncCheck) {
+    (
+        NodeCheck {
+            ok: false,
+            latency_ms: None,
+            error: Some(error),
+        },
+        SyncCheck {
+            ok: false,
+            progress: None,
+            min_progress,
+            error: Some("The node is unreachable".to_string()),
+        },
+    )
+}
+
+fn health(health: &NodeHealth, observability: ObservabilityHealth) -> HealthResponse {
+    let node = health.report();
+
+    HealthResponse {
+        healthy: node.connected,
+        node,
+        observability,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        node::{mock::MockNode, sync_progress::NodeInfo},
+        BlockfrostError,
+    };
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[test]
+    fn test_health() {
+        let node_health = NodeHealth::default();
+        node_health.record_failure("Connection refused".to_string());
+
+        let response = health(&node_health, ObservabilityHealth::default());
+        assert!(!response.healthy);
+        assert_eq!(
+            response.node.last_error.as_deref(),
+            Some("Connection refused")
+        );
+
+        node_health.record_connected();
+        assert!(health(&node_health, ObservabilityHealth::default()).healthy);
+    }
+
+    #[test]
+    fn test_health_node_down() {
+        // Before the first connection, as at startup
+        let node_health = NodeHealth::default();
+        let response = Json(health(&node_health, ObservabilityHealth::default())).into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        node_health.record_failure("Connection refused".to_string());
+        let response = Json(health(&node_health, ObservabilityHealth::default())).into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_health_degraded_observability() {
+        let node_health = NodeHealth::default();
+        node_health.record_connected();
+
+        let observability = ObservabilityHealth::metrics_failed("already installed".to_string());
+        let response = health(&node_health, observability.clone());
+
+        assert!(response.healthy);
+        assert_eq!(response.observability, observability);
+        assert!(response.observability.degraded);
+    }
+
+    fn node_info(sync_progress: f64) -> NodeInfo {
+        NodeInfo {
+            block: String::new(),
+            epoch: 520,
+            era: "Conway".to_string(),
+            slot: Default::default(),
+            sync_progress,
+            n2c_version: 16,
+        }
+    }
+
+    #[rstest]
+    #[case(100.0, true)]
+    #[case(99.0, true)]
+    #[case(42.5, false)]
+    #[tokio::test]
+    async fn test_check_node(#[case] sync_progress: f64, #[case] ok: bool) {
+        let mut node = MockNode::new()
+            .with_ping_result(Ok(()))
+            .with_node_info(node_info(sync_progress));
+
+        let (node_check, sync_check) = check_node(&mut node, 99.0).await;
+        assert!(node_check.ok);
+        assert!(node_check.latency_ms.is_some());
+        assert_eq!(
+            sync_check,
+            SyncCheck {
+                ok,
+                progress: Some(sync_progress),
+                min_progress: 99.0,
+                error: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_node_unreachable() {
+        let mut node = MockNode::new()
+            .with_ping_result(Err(BlockfrostError::internal_server_error(
+                "Connection refused".to_string(),
+            )))
+            .with_node_info(node_info(100.0));
+
+        let (node_check, sync_check) = check_node(&mut node, 99.0).await;
+        assert_eq!(
+            node_check,
+            NodeCheck {
+                ok: false,
+                latency_ms: None,
+                error: Some("Connection refused".to_string()),
+            }
+        );
+        assert!(!sync_check.ok);
+        assert_eq!(sync_check.progress, None);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{cli::Config, AppError, BlockfrostError, NodePool};
+use axum::response::{Extension, IntoResponse};
+use metrics::{describe_counter, describe_gauge, describe_histogram, gauge};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn route(
+    Extension(prometheus_handle): Extension<Option<Arc<RwLock<PrometheusHandle>>>>,
+    Extension(node): Extension<NodePool>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    // Disabled, or the recorder failed to install
+    let prometheus_handle = prometheus_handle.ok_or_else(BlockfrostError::not_found)?;
+
+    node.record_gauges();
+
+    let handle = prometheus_handle.write().await;
+
+    Ok(handle.render().into_response())
+}
+
+/// Installs the global Prometheus recorder. Fails if another one already is.
+pub fn setup_metrics_recorder(config: &Config) -> Result<Arc<RwLock<PrometheusHandle>>, AppError> {
+    let mut builder = PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full("tx_submit_duration_seconds".to_string()),
+            &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0],
+        )
+        .map_err(|e| AppError::Server(format!("Invalid histogram buckets: {}", e)))?;
+
+    // Lets the Icebreakers backend attribute submissions of operators running several instances
+    if let Some(icebreakers) = &config.icebreakers {
+        builder =
+            builder.add_global_label("reward_address_hash", icebreakers.reward_address_hash());
+    }
+
+    let builder = builder.install_recorder().map_err(|e| {
+        AppError::Server(format!("Failed to install the Prometheus recorder: {}", e))
+    })?;
+
+    describe_counter!(
+        "http_requests_total",
+        "HTTP calls made to blockfrost-platform API"
+    );
+
+    describe_counter!(
+        "http_requests_throttled_total",
+        "HTTP calls answered 429 for exceeding a rate limit, by class (default, expensive or submit) and scope (ip or global)"
+    );
+
+    describe_counter!(
+        "tx_submit_total",
+        "Transactions submitted to the node, by outcome: accepted, rejected or transport_error"
+    );
+
+    describe_histogram!(
+        "tx_submit_duration_seconds",
+        "Time it took the node to accept or reject a transaction, from the era query to the decoded rejection"
+    );
+
+    describe_counter!(
+        "tx_submit_rejections_total",
+        "Ledger rule failures in rejected transactions, by the innermost predicate failure, e.g. BadInputsUTxO"
+    );
+
+    describe_counter!(
+        "tx_submit_error_decoding_total",
+        "Rejected transactions by the decoder that explained the rejection: native, fallback or failed"
+    );
+
+    describe_gauge!(
+        "tx_submit_availability",
+        "Ratio of successful transaction submissions over a rolling window, not counting client errors"
+    );
+
+    describe_gauge!(
+        "tx_submit_error_budget_remaining",
+        "Fraction of the transaction submission error budget left over a rolling window"
+    );
+
+    describe_counter!(
+        "retries_total",
+        "Retries of failed operations, by subsystem: node_reconnect, chain_follower, tx_submit, icebreakers, epoch_webhook or fallback_decoder"
+    );
+
+    describe_counter!(
+        "retries_exhausted_total",
+        "Operations given up on after running out of retries or time, by subsystem"
+    );
+
+    describe_gauge!(
+        "cardano_node_connections",
+        "Number of currently open Cardano node N2C connections"
+    );
+
+    describe_gauge!(
+        "cardano_node_handshake_seconds",
+        "Time it took to open and handshake each pooled N2C connection"
+    );
+
+    describe_gauge!(
+        "cardano_node_last_success_timestamp_seconds",
+        "UNIX time of the last successful operation on each pooled N2C connection"
+    );
+
+    describe_gauge!(
+        "cardano_node_connected",
+        "Whether the node is reachable (1) or being reconnected to with backoff (0)"
+    );
+
+    describe_gauge!(
+        "cardano_node_pool_connections",
+        "Pooled N2C connections, by state: busy (borrowed by a request or task) or idle"
+    );
+
+    describe_counter!(
+        "cardano_node_connects_total",
+        "N2C connections opened to the node, or failing to open; reconnects of a flapping socket show up in its rate"
+    );
+
+    // Otherwise it’s not present under `GET /metrics` if we start with a failing cardano-node:
+    gauge!("cardano_node_connections").set(0);
+    gauge!("cardano_node_connected").set(0);
+
+    Ok(Arc::new(RwLock::new(builder)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Args;
+    use clap::Parser;
+
+    #[test]
+    fn test_setup_metrics_recorder_twice() {
+        let args = Args::parse_from([
+            "blockfrost-platform",
+            "--network",
+            "preview",
+            "--node-socket-path",
+            "/run/cardano-node/node.socket",
+            "--solitary",
+        ]);
+        let config = Config::from_args(args).unwrap();
+
+        // Whoever installed the global recorder first, it’s taken now
+        let _ = setup_metrics_recorder(&config);
+        let err = setup_metrics_recorder(&config).unwrap_err();
+
+        assert!(err.to_string().contains("Prometheus recorder"));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    node::{api::NodeApi, chain::NetworkState, transactions::era_of_index},
+    BlockfrostError, NodePool,
+};
+use axum::{response::IntoResponse, Extension, Json};
+use serde::{Deserialize, Serialize};
+
+/// Blockfrost’s `/network`. Figures the node’s ledger state doesn’t give away
+/// are `null`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct NetworkResponse {
+    pub supply: NetworkSupply,
+    pub stake: NetworkStake,
+    /// The node’s era as last seen by a submission, `null` until there was one
+    pub era: Option<NetworkEra>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct NetworkSupply {
+    pub max: String,
+    pub total: Option<String>,
+    pub circulating: Option<String>,
+    pub locked: Option<String>,
+    pub treasury: Option<String>,
+    pub reserves: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct NetworkStake {
+    pub live: Option<String>,
+    pub active: String,
+}
+
+/// Not in Blockfrost’s `/network`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct NetworkEra {
+    /// Of the hard fork combinator
+    pub index: u16,
+    pub name: Option<String>,
+}
+
+pub async fn route(
+    Extension(node_pool): Extension<NodePool>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let era = node_pool.era_cache().get();
+    let mut node = node_pool.get().await?;
+
+    Ok(Json(network(&mut *node, era).await?))
+}
+
+async fn network(
+    node: &mut impl NodeApi,
+    era: Option<u16>,
+) -> Result<NetworkResponse, BlockfrostError> {
+    let NetworkState {
+        max_supply,
+        active_stake,
+    } = node.network_state().await?;
+
+    Ok(NetworkResponse {
+        supply: NetworkSupply {
+            max: max_supply.to_string(),
+            total: None,
+            circulating: None,
+            locked: None,
+            treasury: None,
+            reserves: None,
+        },
+        stake: NetworkStake {
+            live: None,
+            active: active_stake.to_string(),
+        },
+        era: era.map(|index| NetworkEra {
+            index,
+            name: era_of_index(index).map(|era| era.to_string()),
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::mock::MockNode;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn test_network() {
+        let mut node = MockNode::new().with_network_state(NetworkState {
+            max_supply: 45_000_000_000_000_000,
+            active_stake: 21_000_000_000_000_000,
+        });
+
+        assert_eq!(
+            serde_json::to_value(network(&mut node, None).await.unwrap()).unwrap(),
+            serde_json::json!({
+                "supply": {
+                    "max": "45000000000000000",
+                    "total": null,
+                    "circulating": null,
+                    "locked": null,
+                    "treasury": null,
+                    "reserves": null
+                },
+                "stake": {
+                    "live": null,
+                    "active": "21000000000000000"
+                },
+                "era": null
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_network_era() {
+        let mut node = MockNode::new().with_network_state(NetworkState {
+            max_supply: 45_000_000_000_000_000,
+            active_stake: 21_000_000_000_000_000,
+        });
+
+        assert_eq!(
+            network(&mut node, Some(6)).await.unwrap().era,
+            Some(NetworkEra {
+                index: 6,
+                name: Some("Conway".to_string()),
+            })
+        );
+    }
+}
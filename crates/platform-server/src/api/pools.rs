@@ -0,0 +1,259 @@
+use crate::{
+    encoding::{decode_bech32, encode_as, Bech32Kind},
+    node::{api::NodeApi, chain::StakePool},
+    BlockfrostError, NodePool,
+};
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+/// Blockfrost’s pagination parameters.
+#[derive(Deserialize, Debug, Default)]
+pub struct PaginationQuery {
+    pub count: Option<usize>,
+    pub page: Option<usize>,
+    pub order: Option<Order>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    #[default]
+    Asc,
+    Desc,
+}
+
+const MAX_COUNT: usize = 100;
+
+/// Blockfrost’s `/pools/{pool_id}`. Figures the node’s ledger state doesn’t
+/// give away are `null`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PoolResponse {
+    pub pool_id: String,
+    pub hex: String,
+    pub vrf_key: String,
+    pub blocks_minted: Option<u64>,
+    pub blocks_epoch: Option<u64>,
+    pub live_stake: Option<String>,
+    pub live_size: Option<f64>,
+    pub live_saturation: Option<f64>,
+    pub live_delegators: Option<u64>,
+    pub active_stake: Option<String>,
+    pub active_size: Option<f64>,
+    pub declared_pledge: String,
+    pub live_pledge: Option<String>,
+    pub margin_cost: f64,
+    pub fixed_cost: String,
+    pub reward_account: String,
+    pub owners: Vec<String>,
+    pub registration: Option<Vec<String>>,
+    pub retirement: Option<Vec<String>>,
+}
+
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Query(query): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let mut node = node.get().await?;
+
+    Ok(Json(pools(&mut *node, &query).await?))
+}
+
+pub async fn pool_route(
+    Extension(node): Extension<NodePool>,
+    Path(pool_id): Path<String>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let mut node = node.get().await?;
+
+    Ok(Json(pool(&mut *node, &pool_id).await?))
+}
+
+async fn pools(
+    node: &mut impl NodeApi,
+    query: &PaginationQuery,
+) -> Result<Vec<String>, BlockfrostError> {
+    let mut pool_ids = node.stake_pools().await?;
+    pool_ids.sort();
+
+    paginate(pool_ids, query)?
+        .iter()
+        .map(|pool| {
+            encode_as(Bech32Kind::Pool, pool).map_err(BlockfrostError::internal_server_error)
+        })
+        .collect()
+}
+
+/// The requested page of `items`, which are in ascending order.
+pub fn paginate<T>(mut items: Vec<T>, query: &PaginationQuery) -> Result<Vec<T>, BlockfrostError> {
+    let count = query.count.unwrap_or(MAX_COUNT);
+    let page = query.page.unwrap_or(1);
+    if !(1..=MAX_COUNT).contains(&count) || page < 1 {
+        return Err(BlockfrostError::custom_400(format!(
+            "count must be within 1..={MAX_COUNT}, and page at least 1"
+        )));
+    }
+
+    if query.order == Some(Order::Desc) {
+        items.reverse();
+    }
+
+    Ok(items
+        .into_iter()
+        .skip((page - 1).saturating_mul(count))
+        .take(count)
+        .collect())
+}
+
+async fn pool(node: &mut impl NodeApi, pool_id: &str) -> Result<PoolResponse, BlockfrostError> {
+    let hash = parse_pool_id(pool_id)?;
+    let pool = node
+        .stake_pool(hash.clone())
+        .await?
+        .ok_or_else(BlockfrostError::not_found)?;
+
+    pool_response(&hash, pool).map_err(BlockfrostError::internal_server_error)
+}
+
+/// Takes either a `pool1…` id, or the hex of its cold key hash.
+fn parse_pool_id(pool_id: &str) -> Result<Vec<u8>, BlockfrostError> {
+    let invalid = || BlockfrostError::custom_400(format!("Invalid pool id: {pool_id}"));
+
+    let hash = match decode_bech32(pool_id) {
+        Ok((hrp, bytes)) if hrp == "pool" => bytes,
+        Ok(_) => return Err(invalid()),
+        Err(_) => hex::decode(pool_id).map_err(|_| invalid())?,
+    };
+
+    if hash.len() != 28 {
+        return Err(invalid());
+    }
+
+    Ok(hash)
+}
+
+fn pool_response(hash: &[u8], pool: StakePool) -> Result<PoolResponse, String> {
+    // Owners are stake key hashes, on the network of the reward account
+    let network = pool.reward_account.first().copied().unwrap_or_default() & 0x0f;
+    let owners = pool
+        .owners
+        .iter()
+        .map(|owner| {
+            encode_as(
+                Bech32Kind::Address,
+                &[&[0xe0 | network], &owner[..]].concat(),
+            )
+        })
+        .collect::<Result<_, _>>()?;
+
+    let fraction = |(numerator, denominator): (u64, u64)| {
+        if denominator == 0 {
+            0.0
+        } else {
+            numerator as f64 / denominator as f64
+        }
+    };
+
+    Ok(PoolResponse {
+        pool_id: encode_as(Bech32Kind::Pool, hash)?,
+        hex: hex::encode(hash),
+        vrf_key: hex::encode(&pool.vrf_key_hash),
+        blocks_minted: None,
+        blocks_epoch: None,
+        live_stake: None,
+        live_size: None,
+        live_saturation: None,
+        live_delegators: None,
+        active_stake: None,
+        active_size: pool.active_size.map(fraction),
+        declared_pledge: pool.pledge.to_string(),
+        live_pledge: None,
+        margin_cost: fraction(pool.margin),
+        fixed_cost: pool.cost.to_string(),
+        reward_account: encode_as(Bech32Kind::Address, &pool.reward_account)?,
+        owners,
+        registration: None,
+        retirement: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::mock::MockNode;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const POOL: &str = "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy";
+    const POOL_HEX: &str = "0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735";
+
+    fn stake_pool() -> StakePool {
+        StakePool {
+            vrf_key_hash: vec![0xab; 32],
+            pledge: 500_000_000_000,
+            cost: 340_000_000,
+            margin: (1, 100),
+            reward_account: [&[0xe1][..], &[0x11; 28]].concat(),
+            owners: vec![vec![0x11; 28]],
+            active_size: Some((1, 1000)),
+        }
+    }
+
+    fn node() -> MockNode {
+        let hash = hex::decode(POOL_HEX).unwrap();
+
+        MockNode::new()
+            .with_stake_pool(hash, stake_pool())
+            .with_stake_pool(vec![0x00; 28], stake_pool())
+            .with_stake_pool(vec![0xff; 28], stake_pool())
+    }
+
+    #[rstest]
+    #[case(POOL)]
+    #[case(POOL_HEX)]
+    #[tokio::test]
+    async fn test_pool(#[case] pool_id: &str) {
+        let response = pool(&mut node(), pool_id).await.unwrap();
+
+        assert_eq!(response.pool_id, POOL);
+        assert_eq!(response.hex, POOL_HEX);
+        assert_eq!(response.active_size, Some(0.001));
+        assert_eq!(response.margin_cost, 0.01);
+        assert_eq!(response.declared_pledge, "500000000000");
+        assert_eq!(response.reward_account, response.owners[0]);
+        assert!(response.reward_account.starts_with("stake1"));
+    }
+
+    #[rstest]
+    #[case("pool1", 400)]
+    #[case("stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw", 400)]
+    #[case(&"11".repeat(28), 404)]
+    #[tokio::test]
+    async fn test_pool_errors(#[case] pool_id: &str, #[case] status_code: u16) {
+        let err = pool(&mut node(), pool_id).await.unwrap_err();
+
+        assert_eq!(err.status_code, status_code);
+    }
+
+    #[rstest]
+    #[case(PaginationQuery::default(), 3, Some(0x00))]
+    #[case(PaginationQuery { order: Some(Order::Desc), ..Default::default() }, 3, Some(0xff))]
+    #[case(PaginationQuery { count: Some(2), page: Some(2), ..Default::default() }, 1, Some(0xff))]
+    #[case(PaginationQuery { page: Some(2), ..Default::default() }, 0, None)]
+    #[tokio::test]
+    async fn test_pools(
+        #[case] query: PaginationQuery,
+        #[case] len: usize,
+        #[case] first: Option<u8>,
+    ) {
+        let pool_ids = pools(&mut node(), &query).await.unwrap();
+
+        assert_eq!(pool_ids.len(), len);
+        assert_eq!(
+            pool_ids.first().map(|id| decode_bech32(id).unwrap().1[0]),
+            first
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    node::protocol_params::{ParamChange, ProtocolParamsHistory},
+    BlockfrostError,
+};
+use axum::{extract::Query, response::IntoResponse, Extension, Json};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Deserialize)]
+pub struct DiffQuery {
+    pub from: u32,
+    pub to: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DiffResponse {
+    pub from: u32,
+    pub to: u32,
+    pub changes: BTreeMap<String, ParamChange>,
+}
+
+pub async fn diff_route(
+    Extension(history): Extension<ProtocolParamsHistory>,
+    Query(query): Query<DiffQuery>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let params_at = |epoch: u32| {
+        history.get(epoch).ok_or_else(|| {
+            BlockfrostError::custom_404(format!(
+                "Protocol parameters for epoch {} have not been observed by this instance.",
+                epoch
+            ))
+        })
+    };
+
+    let from = params_at(query.from)?;
+    let to = params_at(query.to)?;
+
+    Ok(Json(DiffResponse {
+        from: query.from,
+        to: query.to,
+        changes: from.diff(&to),
+    }))
+}
@@ -0,0 +1,269 @@
+use crate::{
+    circuit_breaker::CircuitBreaker,
+    cli::{Config, Network},
+    node::chainsync::{ChainEvent, RecentChain},
+    BlockfrostError,
+};
+use axum::{
+    body::Bytes,
+    http::{header, Method, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::broadcast::error::RecvError;
+
+/// How many responses we keep cached at most.
+const CACHE_ENTRIES: usize = 1024;
+
+/// No single upstream call may take longer than this.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Serves the read endpoints we don’t have locally from a Blockfrost project,
+/// so that SDKs can use a single base URL.
+pub struct BlockfrostProxy {
+    client: reqwest::Client,
+    base_url: String,
+    project_id: String,
+    cache_ttl: Duration,
+    cache: Mutex<HashMap<String, CachedResponse>>,
+    breaker: CircuitBreaker,
+}
+
+#[derive(Clone)]
+struct CachedResponse {
+    fetched_at: Instant,
+    content_type: Option<String>,
+    body: Bytes,
+}
+
+impl BlockfrostProxy {
+    /// Returns `None` unless `--blockfrost-project-id` is set.
+    pub fn new(config: &Config) -> Option<Arc<Self>> {
+        let project_id = config.server.blockfrost_project_id.clone()?;
+        let network = match config.node.network {
+            Network::Mainnet => "mainnet",
+            Network::Preprod => "preprod",
+            Network::Preview => "preview",
+            Network::Sanchonet => "sanchonet",
+            // Refused by `Config::from_args`
+            Network::Custom => return None,
+        };
+
+        Some(Arc::new(Self {
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            base_url: format!("https://cardano-{}.blockfrost.io/api/v0", network),
+            project_id,
+            cache_ttl: Duration::from_secs(config.limits.blockfrost_cache_secs),
+            cache: Mutex::new(HashMap::new()),
+            breaker: CircuitBreaker::new("Blockfrost API", 5, Duration::from_secs(30)),
+        }))
+    }
+
+    /// Drops every cached response whenever `chain` rolls back or enters a new
+    /// epoch, as they may describe blocks or parameters that no longer hold.
+    pub fn invalidate_on(self: &Arc<Self>, chain: &RecentChain) {
+        let mut events = chain.subscribe();
+        let proxy = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(ChainEvent::NewBlock(_)) => {}
+                    Ok(ChainEvent::Rollback(_) | ChainEvent::EpochBoundary { .. })
+                    | Err(RecvError::Lagged(_)) => proxy.clear(),
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+    }
+
+    fn clear(&self) {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    fn cached(&self, path_and_query: &str) -> Option<CachedResponse> {
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+
+        cache
+            .get(path_and_query)
+            .filter(|cached| cached.fetched_at.elapsed() < self.cache_ttl)
+            .cloned()
+    }
+
+    fn store(&self, path_and_query: &str, response: CachedResponse) {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+
+        cache.retain(|_, cached| cached.fetched_at.elapsed() < self.cache_ttl);
+
+        if cache.len() >= CACHE_ENTRIES {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.fetched_at)
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+
+        cache.insert(path_and_query.to_string(), response);
+    }
+
+    /// Fetches `path_and_query` from Blockfrost, answering from the cache if possible.
+    /// Only successful responses are cached.
+    pub async fn get(&self, path_and_query: &str) -> Result<Response, BlockfrostError> {
+        if let Some(cached) = self.cached(path_and_query) {
+            return Ok(into_response(
+                StatusCode::OK,
+                cached.content_type,
+                cached.body,
+            ));
+        }
+
+        if !self.breaker.allow() {
+            return Err(BlockfrostError::internal_server_error(
+                "Blockfrost API is unavailable, circuit breaker is open".to_string(),
+            ));
+        }
+
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, path_and_query))
+            .header("project_id", &self.project_id)
+            .send()
+            .await
+            .inspect_err(|_| self.breaker.record_failure())
+            .map_err(|e| {
+                BlockfrostError::internal_server_error(format!("Blockfrost API error: {}", e))
+            })?;
+
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let body = response.bytes().await.map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Blockfrost API error: {}", e))
+        })?;
+
+        if status.is_server_error() {
+            self.breaker.record_failure();
+        } else {
+            self.breaker.record_success();
+        }
+
+        if status == StatusCode::OK {
+            self.store(
+                path_and_query,
+                CachedResponse {
+                    fetched_at: Instant::now(),
+                    content_type: content_type.clone(),
+                    body: body.clone(),
+                },
+            );
+        }
+
+        Ok(into_response(status, content_type, body))
+    }
+}
+
+fn into_response(status: StatusCode, content_type: Option<String>, body: Bytes) -> Response {
+    let mut response = (status, body).into_response();
+
+    if let Some(content_type) = content_type.and_then(|ct| ct.parse().ok()) {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type);
+    }
+
+    response
+}
+
+/// Fallback for routes we don’t serve: reads are proxied to Blockfrost if
+/// configured, everything else is a 404.
+pub async fn fallback_route(
+    Extension(proxy): Extension<Option<Arc<BlockfrostProxy>>>,
+    method: Method,
+    uri: Uri,
+) -> Result<Response, BlockfrostError> {
+    match proxy {
+        Some(proxy) if method == Method::GET => {
+            let path_and_query = uri
+                .path_and_query()
+                .map(|pq| pq.as_str())
+                .unwrap_or(uri.path());
+
+            proxy.get(path_and_query).await
+        }
+        _ => Err(BlockfrostError::not_found()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pallas_network::miniprotocols::{chainsync::Tip, Point};
+    use pretty_assertions::assert_eq;
+
+    fn proxy(cache_ttl: Duration) -> BlockfrostProxy {
+        BlockfrostProxy {
+            client: reqwest::Client::new(),
+            base_url: "http://localhost".to_string(),
+            project_id: "test".to_string(),
+            cache_ttl,
+            cache: Mutex::new(HashMap::new()),
+            breaker: CircuitBreaker::new("test", 1, Duration::from_secs(1)),
+        }
+    }
+
+    fn cached_response() -> CachedResponse {
+        CachedResponse {
+            fetched_at: Instant::now(),
+            content_type: None,
+            body: Bytes::from_static(b"{}"),
+        }
+    }
+
+    #[test]
+    fn test_proxy_cache_expiry() {
+        let proxy = proxy(Duration::from_millis(20));
+
+        proxy.store("/blocks/latest", cached_response());
+
+        assert_eq!(
+            proxy.cached("/blocks/latest").map(|c| c.body),
+            Some(Bytes::from_static(b"{}"))
+        );
+        assert!(proxy.cached("/blocks/latest?page=2").is_none());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(proxy.cached("/blocks/latest").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_proxy_cache_invalidation() {
+        let proxy = Arc::new(proxy(Duration::from_secs(60)));
+        let chain = RecentChain::new(3);
+        proxy.invalidate_on(&chain);
+
+        proxy.store("/epochs/latest/parameters", cached_response());
+        chain.roll_backward(Point::Origin, Tip(Point::Origin, 0));
+
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while proxy.cached("/epochs/latest/parameters").is_some() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+}
@@ -1,6 +1,10 @@
-use crate::{node::sync_progress::NodeInfo, BlockfrostError, NodePool};
+use crate::{
+    circuit_breaker::BreakerState, icebreakers_api::IcebreakersAPI, node::sync_progress::NodeInfo,
+    BlockfrostError, NodePool,
+};
 use axum::{response::IntoResponse, Extension, Json};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Serialize, Deserialize)]
 pub struct RootResponse {
@@ -10,10 +14,14 @@ pub struct RootResponse {
     #[serde(rename = "nodeInfo")]
     pub node_info: NodeInfo,
     pub errors: Vec<String>,
+    /// Circuit breaker state of the Icebreakers API, unless in solitary mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icebreakers_api: Option<BreakerState>,
 }
 
 pub async fn route(
     Extension(node): Extension<NodePool>,
+    Extension(icebreakers_api): Extension<Option<Arc<IcebreakersAPI>>>,
 ) -> Result<impl IntoResponse, BlockfrostError> {
     let errors = vec![];
     let mut node = node.get().await?;
@@ -25,6 +33,7 @@ pub async fn route(
         node_info,
         healthy: errors.is_empty(),
         errors,
+        icebreakers_api: icebreakers_api.map(|api| api.breaker_state()),
     };
 
     Ok(Json(response))
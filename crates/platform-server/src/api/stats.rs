@@ -0,0 +1,346 @@
+use crate::{
+    cli::Config,
+    hooks::{SubmissionHook, SubmissionOutcome, SubmittedTx},
+    node::transactions::is_transport_error,
+    BlockfrostError,
+};
+use axum::{
+    response::{Extension, IntoResponse},
+    Json,
+};
+use metrics::gauge;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// In-memory request statistics, so that operators get a per-route breakdown
+/// without running an external metrics stack.
+pub struct Stats {
+    started_at: Instant,
+    routes: Mutex<BTreeMap<(String, String), RouteStats>>,
+    slo_target: f64,
+    submits: Mutex<VecDeque<SloBucket>>,
+}
+
+/// Rolling windows the submit SLO is computed over, with their names.
+const SLO_WINDOWS: &[(&str, Duration)] = &[
+    ("1h", Duration::from_secs(60 * 60)),
+    ("24h", Duration::from_secs(24 * 60 * 60)),
+];
+
+/// Granularity of the SLO windows.
+const SLO_BUCKET: Duration = Duration::from_secs(60);
+
+/// Submit outcomes during a single `SLO_BUCKET`.
+#[derive(Default, Clone)]
+struct SloBucket {
+    index: u64,
+    successful: u64,
+    client_errors: u64,
+    server_errors: u64,
+}
+
+#[derive(Default, Clone, Serialize)]
+pub struct RouteStats {
+    pub calls: u64,
+    pub status_2xx: u64,
+    pub status_4xx: u64,
+    pub status_5xx: u64,
+    #[serde(skip)]
+    latency_total: Duration,
+    #[serde(skip)]
+    latency_max: Duration,
+}
+
+#[derive(Serialize)]
+pub struct RouteStatsResponse {
+    pub method: String,
+    pub path: String,
+    #[serde(flatten)]
+    pub stats: RouteStats,
+    pub latency_avg_ms: f64,
+    pub latency_max_ms: f64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct SloWindow {
+    pub window: &'static str,
+    pub successful: u64,
+    /// Not counted against availability, as they are caused by the client
+    pub client_errors: u64,
+    pub server_errors: u64,
+    pub availability: f64,
+    /// Fraction of the error budget left; negative once it’s exhausted
+    pub error_budget_remaining: f64,
+}
+
+#[derive(Serialize)]
+pub struct SloResponse {
+    pub target: f64,
+    pub windows: Vec<SloWindow>,
+}
+
+#[derive(Serialize)]
+pub struct StatsResponse {
+    pub uptime_secs: u64,
+    pub requests_total: u64,
+    pub routes: Vec<RouteStatsResponse>,
+}
+
+impl Stats {
+    pub fn new(slo_target: f64) -> Arc<Self> {
+        Arc::new(Self {
+            started_at: Instant::now(),
+            routes: Mutex::new(BTreeMap::new()),
+            slo_target,
+            submits: Mutex::new(VecDeque::new()),
+        })
+    }
+
+    /// Records a single finished request.
+    pub fn record(&self, method: &str, path: &str, status: u16, latency: Duration) {
+        let mut routes = self.routes.lock().unwrap_or_else(|e| e.into_inner());
+        let route = routes
+            .entry((method.to_string(), path.to_string()))
+            .or_default();
+
+        route.calls += 1;
+        match status {
+            200..=299 => route.status_2xx += 1,
+            400..=499 => route.status_4xx += 1,
+            500..=599 => route.status_5xx += 1,
+            _ => {}
+        }
+        route.latency_total += latency;
+        route.latency_max = route.latency_max.max(latency);
+    }
+
+    /// Records how a submission went once it's decided, whichever route it
+    /// came through and however long it waited in the queue or the spool. A
+    /// failure on the node connection, e.g. a mux or bearer error, counts
+    /// against the SLO whatever its status.
+    fn record_submit(&self, outcome: &SubmissionOutcome) {
+        let index = self.started_at.elapsed().as_secs() / SLO_BUCKET.as_secs();
+        let mut submits = self.submits.lock().unwrap_or_else(|e| e.into_inner());
+
+        if submits.back().is_none_or(|bucket| bucket.index != index) {
+            submits.push_back(SloBucket {
+                index,
+                ..Default::default()
+            });
+        }
+
+        // Drop the buckets that fell out of the longest window
+        let longest = SLO_WINDOWS
+            .iter()
+            .map(|(_, w)| w)
+            .max()
+            .unwrap_or(&SLO_BUCKET);
+        let buckets = longest.as_secs() / SLO_BUCKET.as_secs();
+        while submits
+            .front()
+            .is_some_and(|bucket| bucket.index + buckets <= index)
+        {
+            submits.pop_front();
+        }
+
+        if let Some(bucket) = submits.back_mut() {
+            match outcome {
+                SubmissionOutcome::Accepted(_) => bucket.successful += 1,
+                SubmissionOutcome::Rejected(err) if is_transport_error(err) => {
+                    bucket.server_errors += 1
+                }
+                SubmissionOutcome::Rejected(err) if err.status_code < 500 => {
+                    bucket.client_errors += 1
+                }
+                SubmissionOutcome::Rejected(_) => bucket.server_errors += 1,
+            }
+        }
+        drop(submits);
+
+        for window in self.slo().windows {
+            let labels = [("window", window.window)];
+            gauge!("tx_submit_availability", &labels).set(window.availability);
+            gauge!("tx_submit_error_budget_remaining", &labels).set(window.error_budget_remaining);
+        }
+    }
+
+    /// Availability of transaction submission over the rolling windows, i.e.
+    /// the ratio of successful submits, not counting client-caused errors.
+    pub fn slo(&self) -> SloResponse {
+        let index = self.started_at.elapsed().as_secs() / SLO_BUCKET.as_secs();
+        let submits = self.submits.lock().unwrap_or_else(|e| e.into_inner());
+
+        let windows = SLO_WINDOWS
+            .iter()
+            .map(|(name, window)| {
+                let buckets = window.as_secs() / SLO_BUCKET.as_secs();
+                let mut sum = submits
+                    .iter()
+                    .filter(|bucket| bucket.index + buckets > index)
+                    .fold(SloBucket::default(), |mut sum, bucket| {
+                        sum.successful += bucket.successful;
+                        sum.client_errors += bucket.client_errors;
+                        sum.server_errors += bucket.server_errors;
+                        sum
+                    });
+                sum.index = index;
+
+                let counted = sum.successful + sum.server_errors;
+                let error_rate = if counted == 0 {
+                    0.0
+                } else {
+                    sum.server_errors as f64 / counted as f64
+                };
+                let error_budget = 1.0 - self.slo_target;
+
+                SloWindow {
+                    window: name,
+                    successful: sum.successful,
+                    client_errors: sum.client_errors,
+                    server_errors: sum.server_errors,
+                    availability: 1.0 - error_rate,
+                    error_budget_remaining: if error_budget > 0.0 {
+                        1.0 - error_rate / error_budget
+                    } else if error_rate > 0.0 {
+                        -1.0
+                    } else {
+                        1.0
+                    },
+                }
+            })
+            .collect();
+
+        SloResponse {
+            target: self.slo_target,
+            windows,
+        }
+    }
+
+    pub fn snapshot(&self) -> StatsResponse {
+        let routes = self.routes.lock().unwrap_or_else(|e| e.into_inner());
+
+        let routes: Vec<RouteStatsResponse> = routes
+            .iter()
+            .map(|((method, path), stats)| RouteStatsResponse {
+                method: method.clone(),
+                path: path.clone(),
+                latency_avg_ms: if stats.calls == 0 {
+                    0.0
+                } else {
+                    duration_ms(stats.latency_total) / stats.calls as f64
+                },
+                latency_max_ms: duration_ms(stats.latency_max),
+                stats: stats.clone(),
+            })
+            .collect();
+
+        StatsResponse {
+            uptime_secs: self.started_at.elapsed().as_secs(),
+            requests_total: routes.iter().map(|r| r.stats.calls).sum(),
+            routes,
+        }
+    }
+}
+
+/// Feeds the submit SLO.
+impl SubmissionHook for Stats {
+    fn post_submit(&self, _tx: &SubmittedTx, outcome: &SubmissionOutcome) {
+        self.record_submit(outcome);
+    }
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    // Round to two decimal places
+    (duration.as_secs_f64() * 1000.0 * 100.0).round() / 100.0
+}
+
+pub async fn route(
+    Extension(stats): Extension<Arc<Stats>>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    if !config.observability.metrics {
+        return Err(BlockfrostError::not_found());
+    }
+
+    Ok(Json(stats.snapshot()))
+}
+
+pub async fn slo_route(
+    Extension(stats): Extension<Arc<Stats>>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    if !config.observability.metrics {
+        return Err(BlockfrostError::not_found());
+    }
+
+    Ok(Json(stats.slo()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::transactions::transport_error;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_stats_per_route_breakdown() {
+        let stats = Stats::new(0.999);
+
+        stats.record("POST", "/tx/submit", 200, Duration::from_millis(10));
+        stats.record("POST", "/tx/submit", 400, Duration::from_millis(30));
+        stats.record("GET", "/", 500, Duration::from_millis(5));
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.requests_total, 3);
+        assert_eq!(snapshot.routes.len(), 2);
+
+        let submit = snapshot
+            .routes
+            .iter()
+            .find(|r| r.path == "/tx/submit")
+            .unwrap();
+        assert_eq!(submit.stats.calls, 2);
+        assert_eq!(submit.stats.status_2xx, 1);
+        assert_eq!(submit.stats.status_4xx, 1);
+        assert_eq!(submit.latency_avg_ms, 20.0);
+        assert_eq!(submit.latency_max_ms, 30.0);
+    }
+
+    #[test]
+    fn test_stats_submit_slo() {
+        let stats = Stats::new(0.99);
+        let tx = SubmittedTx {
+            cbor: &[0x84],
+            signer: None,
+        };
+        let rejected =
+            |err: BlockfrostError| stats.post_submit(&tx, &SubmissionOutcome::Rejected(&err));
+
+        for _ in 0..98 {
+            stats.post_submit(&tx, &SubmissionOutcome::Accepted("abcd"));
+        }
+        rejected(BlockfrostError::custom_400("rejected".to_string()));
+        rejected(BlockfrostError::internal_server_error(
+            "Connection reset".to_string(),
+        ));
+        // Not the client’s fault, whatever the status
+        rejected(transport_error("Connection reset"));
+        // Requests only count once their submission is decided, e.g. not a `202 Accepted`
+        stats.record("POST", "/tx/submit", 202, Duration::ZERO);
+        stats.record("POST", "/tx/submit", 500, Duration::ZERO);
+
+        let slo = stats.slo();
+        let hour = &slo.windows[0];
+
+        assert_eq!(hour.window, "1h");
+        assert_eq!(hour.successful, 98);
+        assert_eq!(hour.client_errors, 1);
+        assert_eq!(hour.server_errors, 2);
+        assert!((hour.availability - 98.0 / 100.0).abs() < 1e-9);
+        assert!((hour.error_budget_remaining - (1.0 - (2.0 / 100.0) / 0.01)).abs() < 1e-9);
+    }
+}
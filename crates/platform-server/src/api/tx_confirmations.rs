@@ -0,0 +1,370 @@
+use crate::{
+    hooks::{SubmissionHook, SubmissionOutcome, SubmittedTx},
+    node::api::NodeApi,
+    storage::{Component, DataDir},
+    BlockfrostError,
+};
+use axum::{extract::Path, Extension, Json};
+use pallas_crypto::hash::Hash;
+use pallas_traverse::MultiEraTx;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use tracing::{info, warn};
+
+/// Where the tracked submissions are kept under the data directory.
+const FILE_NAME: &str = "confirmations.json";
+
+/// Transactions accepted by the node, followed until they’re in a block and
+/// then how deep, for clients to poll at `/tx/{txid}/confirmations`.
+///
+/// Kept in memory, and with a data directory also on disk, so that a restart
+/// doesn’t forget them.
+#[derive(Clone)]
+pub struct ConfirmationTracker(Arc<Mutex<Tracker>>);
+
+struct Tracker {
+    by_txid: HashMap<String, Submission>,
+    order: VecDeque<String>,
+    tip_height: Option<u64>,
+    capacity: usize,
+    data_dir: Option<DataDir>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Submission {
+    tx_id: String,
+    /// Hash of the transaction body, as found in the mempool and UTxO set
+    tx_hash: String,
+    outputs: u64,
+    /// Height of the tip when the transaction was first seen out of the
+    /// mempool with its outputs in the UTxO set
+    seen_at_height: Option<u64>,
+}
+
+/// What’s written to the data directory, the submissions oldest first.
+#[derive(Serialize, Deserialize, Default)]
+struct Persisted {
+    tip_height: Option<u64>,
+    submissions: Vec<Submission>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ConfirmationsResponse {
+    pub tx_id: String,
+    /// Blocks from the tip down to the one the transaction was seen in, 0
+    /// until it’s seen in one. Never overstated, as it’s only seen there
+    /// some time after the block was made.
+    pub confirmations: u64,
+    pub seen_at_height: Option<u64>,
+    pub tip_height: Option<u64>,
+}
+
+impl ConfirmationTracker {
+    /// Starts with the submissions left in `data_dir` by a previous run, if any.
+    pub fn new(capacity: usize, data_dir: Option<DataDir>) -> Self {
+        let persisted = data_dir
+            .as_ref()
+            .map(|data_dir| data_dir.path(Component::Submissions).join(FILE_NAME))
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|json| match serde_json::from_slice::<Persisted>(&json) {
+                Ok(persisted) => Some(persisted),
+                Err(e) => {
+                    warn!("Ignoring unreadable {}: {}", FILE_NAME, e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        if !persisted.submissions.is_empty() {
+            info!(
+                "Following {} submission(s) from the data directory",
+                persisted.submissions.len()
+            );
+        }
+
+        let mut tracker = Tracker {
+            by_txid: HashMap::new(),
+            order: VecDeque::new(),
+            tip_height: persisted.tip_height,
+            capacity,
+            data_dir,
+        };
+        for submission in persisted.submissions {
+            tracker.insert(submission);
+        }
+
+        Self(Arc::new(Mutex::new(tracker)))
+    }
+
+    /// Starts following `tx`, just accepted by the node under `txid`.
+    pub fn track(&self, txid: &str, tx: &[u8]) {
+        let Ok(decoded) = MultiEraTx::decode(tx) else {
+            return;
+        };
+
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(Submission {
+                tx_id: txid.to_string(),
+                tx_hash: decoded.hash().to_string(),
+                outputs: decoded.outputs().len() as u64,
+                seen_at_height: None,
+            });
+    }
+
+    pub fn get(&self, txid: &str) -> Option<ConfirmationsResponse> {
+        let tracker = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let submission = tracker.by_txid.get(txid)?;
+
+        let confirmations = match (submission.seen_at_height, tracker.tip_height) {
+            (Some(seen_at), Some(tip)) => (tip + 1).saturating_sub(seen_at),
+            _ => 0,
+        };
+
+        Some(ConfirmationsResponse {
+            tx_id: submission.tx_id.clone(),
+            confirmations,
+            seen_at_height: submission.seen_at_height,
+            tip_height: tracker.tip_height,
+        })
+    }
+
+    /// Looks for the transactions not seen on-chain yet, and notes the tip
+    /// height for those that left the mempool with their outputs in the UTxO
+    /// set. Those seen above the tip were rolled back, and are looked for
+    /// again.
+    pub async fn observe(&self, node: &mut impl NodeApi) -> Result<(), BlockfrostError> {
+        if self
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .order
+            .is_empty()
+        {
+            return Ok(());
+        }
+
+        let tip = node.chain_tip().await?;
+
+        let unseen: Vec<(String, Hash<32>, u64)> = {
+            let mut tracker = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            tracker.tip_height = Some(tip.height);
+
+            tracker
+                .by_txid
+                .values_mut()
+                .filter_map(|submission| {
+                    if submission.seen_at_height > Some(tip.height) {
+                        submission.seen_at_height = None;
+                    }
+                    if submission.seen_at_height.is_some() {
+                        return None;
+                    }
+                    let hash = Hash::from_str(&submission.tx_hash).ok()?;
+                    Some((submission.tx_id.clone(), hash, submission.outputs))
+                })
+                .collect()
+        };
+
+        if !unseen.is_empty() {
+            let mempool = node.mempool_tx_hashes().await?;
+            let left: Vec<_> = unseen
+                .into_iter()
+                .filter(|(_, hash, _)| !mempool.contains(hash))
+                .collect();
+
+            if !left.is_empty() {
+                let txs = left.iter().map(|(_, hash, outputs)| (*hash, *outputs));
+                let unspent = node.unspent_tx_hashes(txs.collect()).await?;
+
+                let mut tracker = self.0.lock().unwrap_or_else(|e| e.into_inner());
+                for (txid, hash, _) in left {
+                    if let Some(submission) = tracker.by_txid.get_mut(&txid) {
+                        if unspent.contains(&hash) {
+                            submission.seen_at_height = Some(tip.height);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.persist();
+
+        Ok(())
+    }
+
+    /// Writes the submissions to the data directory, if there’s one.
+    fn persist(&self) {
+        let tracker = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(data_dir) = &tracker.data_dir else {
+            return;
+        };
+
+        let persisted = Persisted {
+            tip_height: tracker.tip_height,
+            submissions: tracker
+                .order
+                .iter()
+                .filter_map(|txid| tracker.by_txid.get(txid).cloned())
+                .collect(),
+        };
+
+        let result = serde_json::to_vec(&persisted)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                data_dir
+                    .write(Component::Submissions, FILE_NAME, &json)
+                    .map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            warn!("Failed to persist the tracked submissions: {}", e);
+        }
+    }
+}
+
+impl Tracker {
+    fn insert(&mut self, submission: Submission) {
+        let txid = submission.tx_id.clone();
+        if self.by_txid.insert(txid.clone(), submission).is_none() {
+            self.order.push_back(txid);
+        }
+
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.by_txid.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Tracks every transaction the node accepted, however it was submitted.
+impl SubmissionHook for ConfirmationTracker {
+    fn post_submit(&self, tx: &SubmittedTx, outcome: &SubmissionOutcome) {
+        if let SubmissionOutcome::Accepted(txid) = outcome {
+            self.track(txid, tx.cbor);
+        }
+    }
+}
+
+pub async fn route(
+    Extension(tracker): Extension<ConfirmationTracker>,
+    Path(txid): Path<String>,
+) -> Result<Json<ConfirmationsResponse>, BlockfrostError> {
+    let confirmations = tracker.get(&txid).ok_or_else(BlockfrostError::not_found)?;
+
+    Ok(Json(confirmations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cbor::slot::SlotNo,
+        node::{
+            chain::ChainTip,
+            mock::{MockNode, CONWAY_TX},
+        },
+    };
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeSet;
+
+    fn tip(height: u64) -> ChainTip {
+        ChainTip {
+            slot: SlotNo(height * 20),
+            hash: String::new(),
+            height,
+            epoch: 0,
+            era: 6,
+        }
+    }
+
+    fn node(height: u64, in_mempool: bool, unspent: bool, hash: Hash<32>) -> MockNode {
+        let only_if = |cond: bool| BTreeSet::from_iter(cond.then_some(hash));
+
+        MockNode::new()
+            .with_chain_tip(tip(height))
+            .with_mempool(only_if(in_mempool))
+            .with_unspent(only_if(unspent))
+    }
+
+    #[tokio::test]
+    async fn test_observe() {
+        let tx = hex::decode(CONWAY_TX).unwrap();
+        let hash = MultiEraTx::decode(&tx).unwrap().hash();
+        let tracker = ConfirmationTracker::new(10, None);
+        tracker.track("a", &tx);
+
+        let confirmations = |tracker: &ConfirmationTracker| tracker.get("a").unwrap().confirmations;
+
+        tracker
+            .observe(&mut node(100, true, false, hash))
+            .await
+            .unwrap();
+        assert_eq!(confirmations(&tracker), 0);
+
+        tracker
+            .observe(&mut node(101, false, true, hash))
+            .await
+            .unwrap();
+        assert_eq!(confirmations(&tracker), 1);
+
+        // Not looked for again, so it doesn’t matter that its outputs got spent
+        tracker
+            .observe(&mut node(105, false, false, hash))
+            .await
+            .unwrap();
+        assert_eq!(
+            tracker.get("a"),
+            Some(ConfirmationsResponse {
+                tx_id: "a".to_string(),
+                confirmations: 5,
+                seen_at_height: Some(101),
+                tip_height: Some(105),
+            })
+        );
+
+        // Rolled back
+        tracker
+            .observe(&mut node(100, true, false, hash))
+            .await
+            .unwrap();
+        assert_eq!(confirmations(&tracker), 0);
+        assert_eq!(tracker.get("b"), None);
+    }
+
+    #[tokio::test]
+    async fn test_observe_nothing() {
+        let tracker = ConfirmationTracker::new(10, None);
+
+        // Doesn’t even ask the node
+        tracker.observe(&mut MockNode::new()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_persisted() {
+        let root = std::env::temp_dir().join(format!("bf-confirmations-{}", std::process::id()));
+        let data_dir = DataDir::new(&root, 1024 * 1024).unwrap();
+        let tx = hex::decode(CONWAY_TX).unwrap();
+        let hash = MultiEraTx::decode(&tx).unwrap().hash();
+
+        let tracker = ConfirmationTracker::new(1, Some(data_dir.clone()));
+        tracker.track("a", &tx);
+        tracker.track("b", &tx);
+        tracker
+            .observe(&mut node(7, false, true, hash))
+            .await
+            .unwrap();
+
+        let restarted = ConfirmationTracker::new(10, Some(data_dir));
+        assert_eq!(restarted.get("a"), None);
+        assert_eq!(restarted.get("b").map(|c| c.confirmations), Some(1));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,355 @@
+use crate::{
+    api::utils::TxDeadline,
+    hooks::{SubmissionHook, SubmissionOutcome, SubmittedTx},
+    node::api::NodeApi,
+    BlockfrostError,
+};
+use axum::{extract::Path, Extension, Json};
+use pallas_crypto::hash::Hash;
+use pallas_traverse::{wellknown::GenesisValues, MultiEraTx};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+/// How many submission outcomes we remember by default, the oldest being
+/// forgotten first.
+const MAX_STATUSES: usize = 10_000;
+
+/// Outcomes of the submitted transactions, followed through the mempool until
+/// they’re in a block or evicted, for clients to poll at `/tx/{txid}/status`.
+#[derive(Clone, Default)]
+pub struct SubmissionStatuses(Arc<Mutex<Statuses>>);
+
+struct Statuses {
+    by_txid: HashMap<String, Tracked>,
+    order: VecDeque<String>,
+    capacity: usize,
+    /// Of the network, for the deadlines of transactions
+    genesis: Option<GenesisValues>,
+}
+
+impl Default for Statuses {
+    fn default() -> Self {
+        Self {
+            by_txid: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: MAX_STATUSES,
+            genesis: None,
+        }
+    }
+}
+
+struct Tracked {
+    status: SubmissionStatus,
+    /// Hash, output count and deadline of the transaction, once the node
+    /// accepted it
+    tx: Option<(Hash<32>, u64, Option<TxDeadline>)>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SubmissionStatus {
+    /// Waiting in the submission queue for a node connection
+    Queued,
+    /// Waiting in the offline spool for the node to be reachable again
+    QueuedOffline,
+    /// Handed to a node, which hasn’t answered yet
+    Submitted,
+    /// Accepted by the node, and not looked for in its mempool yet
+    Accepted,
+    InMempool,
+    InBlock,
+    /// Accepted, but then dropped from the mempool without making it into a
+    /// block, e.g. during congestion
+    Evicted,
+    /// Not in a block by the end of its validity interval, so it never will
+    /// be, and isn’t submitted anymore
+    Expired,
+    Rejected {
+        error: BlockfrostError,
+    },
+}
+
+#[derive(Serialize)]
+pub struct SubmissionStatusResponse {
+    pub tx_id: String,
+    #[serde(flatten)]
+    pub status: SubmissionStatus,
+}
+
+impl SubmissionStatuses {
+    pub fn new(capacity: usize, genesis: GenesisValues) -> Self {
+        Self(Arc::new(Mutex::new(Statuses {
+            capacity,
+            genesis: Some(genesis),
+            ..Default::default()
+        })))
+    }
+
+    pub fn get(&self, txid: &str) -> Option<SubmissionStatus> {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .by_txid
+            .get(txid)
+            .map(|tracked| tracked.status.clone())
+    }
+
+    pub fn set(&self, txid: &str, status: SubmissionStatus) {
+        let mut statuses = self.0.lock().unwrap_or_else(|e| e.into_inner());
+
+        match statuses.by_txid.get_mut(txid) {
+            Some(tracked) => tracked.status = status,
+            None => {
+                let tracked = Tracked { status, tx: None };
+                statuses.by_txid.insert(txid.to_string(), tracked);
+                statuses.order.push_back(txid.to_string());
+            }
+        }
+
+        while statuses.order.len() > statuses.capacity {
+            if let Some(oldest) = statuses.order.pop_front() {
+                statuses.by_txid.remove(&oldest);
+            }
+        }
+    }
+
+    /// Records the outcome of submitting `tx`, which was [`SubmissionStatus::Queued`],
+    /// [`SubmissionStatus::QueuedOffline`] or [`SubmissionStatus::Submitted`].
+    /// A failure past its deadline is [`SubmissionStatus::Expired`], whatever
+    /// the reason.
+    pub fn finish(&self, txid: &str, tx: &[u8], result: &Result<String, BlockfrostError>) {
+        let status = match result {
+            Ok(_) => SubmissionStatus::Accepted,
+            Err(_)
+                if self
+                    .deadline(tx)
+                    .is_some_and(|deadline| deadline.has_passed()) =>
+            {
+                SubmissionStatus::Expired
+            }
+            Err(error) => SubmissionStatus::Rejected {
+                error: error.clone(),
+            },
+        };
+
+        self.set(txid, status);
+    }
+
+    /// Starts following `tx`, just accepted by the node, through the mempool.
+    pub fn accepted(&self, txid: &str, tx: &[u8]) {
+        self.set(txid, SubmissionStatus::Accepted);
+
+        if let Ok(decoded) = MultiEraTx::decode(tx) {
+            let outputs = decoded.outputs().len() as u64;
+            let deadline = self.deadline(tx);
+            if let Some(tracked) = self
+                .0
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .by_txid
+                .get_mut(txid)
+            {
+                tracked.tx = Some((decoded.hash(), outputs, deadline));
+            }
+        }
+    }
+
+    pub fn deadline(&self, tx: &[u8]) -> Option<TxDeadline> {
+        TxDeadline::of(
+            tx,
+            self.0
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .genesis
+                .as_ref()?,
+        )
+    }
+
+    /// Looks for the accepted transactions in the node’s mempool, and for
+    /// those that left it, whether they’re in a block, i.e. their outputs are
+    /// in the UTxO set. A transaction whose outputs were all spent already
+    /// passes for evicted, or expired once past its deadline.
+    pub async fn settle(&self, node: &mut impl NodeApi) -> Result<(), BlockfrostError> {
+        type Unsettled = (String, Hash<32>, u64, Option<TxDeadline>);
+        let unsettled: Vec<Unsettled> = {
+            let statuses = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            statuses
+                .by_txid
+                .iter()
+                .filter(|(_, tracked)| {
+                    matches!(
+                        tracked.status,
+                        SubmissionStatus::Accepted | SubmissionStatus::InMempool
+                    )
+                })
+                .filter_map(|(txid, tracked)| {
+                    tracked
+                        .tx
+                        .map(|(hash, outputs, deadline)| (txid.clone(), hash, outputs, deadline))
+                })
+                .collect()
+        };
+        if unsettled.is_empty() {
+            return Ok(());
+        }
+
+        let mempool = node.mempool_tx_hashes().await?;
+        let (in_mempool, left): (Vec<_>, Vec<_>) = unsettled
+            .into_iter()
+            .partition(|(_, hash, _, _)| mempool.contains(hash));
+
+        let unspent = if left.is_empty() {
+            Default::default()
+        } else {
+            let txs = left.iter().map(|(_, hash, outputs, _)| (*hash, *outputs));
+            node.unspent_tx_hashes(txs.collect()).await?
+        };
+
+        for (txid, _, _, _) in in_mempool {
+            self.set(&txid, SubmissionStatus::InMempool);
+        }
+        for (txid, hash, _, deadline) in left {
+            if unspent.contains(&hash) {
+                self.set(&txid, SubmissionStatus::InBlock);
+            } else if deadline.is_some_and(|deadline| deadline.has_passed()) {
+                self.set(&txid, SubmissionStatus::Expired);
+            } else {
+                self.set(&txid, SubmissionStatus::Evicted);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Follows the transactions submitted synchronously, too.
+impl SubmissionHook for SubmissionStatuses {
+    fn post_submit(&self, tx: &SubmittedTx, outcome: &SubmissionOutcome) {
+        if let SubmissionOutcome::Accepted(txid) = outcome {
+            self.accepted(txid, tx.cbor);
+        }
+    }
+}
+
+pub async fn route(
+    Extension(statuses): Extension<SubmissionStatuses>,
+    Path(txid): Path<String>,
+) -> Result<Json<SubmissionStatusResponse>, BlockfrostError> {
+    let status = statuses.get(&txid).ok_or_else(BlockfrostError::not_found)?;
+
+    Ok(Json(SubmissionStatusResponse {
+        tx_id: txid,
+        status,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::{
+        mock::MockNode,
+        mock::{CONWAY_TX, CONWAY_TX_EXPIRED},
+    };
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_statuses() {
+        let statuses = SubmissionStatuses::default();
+        statuses.set("a", SubmissionStatus::Queued);
+        assert_eq!(statuses.get("a"), Some(SubmissionStatus::Queued));
+
+        statuses.finish("a", &[0x84], &Ok("a".to_string()));
+        assert_eq!(statuses.get("a"), Some(SubmissionStatus::Accepted));
+        assert_eq!(statuses.get("b"), None);
+
+        for i in 0..MAX_STATUSES {
+            statuses.set(&i.to_string(), SubmissionStatus::Queued);
+        }
+        assert_eq!(statuses.get("a"), None);
+        assert_eq!(statuses.get("0"), Some(SubmissionStatus::Queued));
+    }
+
+    #[test]
+    fn test_finish_expired() {
+        let statuses = SubmissionStatuses::new(MAX_STATUSES, GenesisValues::preview());
+        let tx = hex::decode(CONWAY_TX_EXPIRED).unwrap();
+        let error = Err(BlockfrostError::custom_400("rejected".to_string()));
+
+        statuses.finish("a", &tx, &error);
+        assert_eq!(statuses.get("a"), Some(SubmissionStatus::Expired));
+
+        statuses.finish("b", &hex::decode(CONWAY_TX).unwrap(), &error);
+        assert!(matches!(
+            statuses.get("b"),
+            Some(SubmissionStatus::Rejected { .. })
+        ));
+    }
+
+    #[test]
+    fn test_status_response() {
+        let response = SubmissionStatusResponse {
+            tx_id: "abcd".to_string(),
+            status: SubmissionStatus::Rejected {
+                error: BlockfrostError::custom_400("rejected".to_string()),
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "tx_id": "abcd",
+                "status": "rejected",
+                "error": {
+                    "status_code": 400,
+                    "error": "Bad Request",
+                    "message": "rejected",
+                    "details": null,
+                },
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(CONWAY_TX, true, false, SubmissionStatus::InMempool)]
+    #[case(CONWAY_TX, false, true, SubmissionStatus::InBlock)]
+    #[case(CONWAY_TX, false, false, SubmissionStatus::Evicted)]
+    #[case(CONWAY_TX_EXPIRED, false, true, SubmissionStatus::InBlock)]
+    #[case(CONWAY_TX_EXPIRED, false, false, SubmissionStatus::Expired)]
+    #[tokio::test]
+    async fn test_settle(
+        #[case] tx: &str,
+        #[case] in_mempool: bool,
+        #[case] unspent: bool,
+        #[case] expected: SubmissionStatus,
+    ) {
+        let tx = hex::decode(tx).unwrap();
+        let hash = MultiEraTx::decode(&tx).unwrap().hash();
+        let only_if = |cond: bool| BTreeSet::from_iter(cond.then_some(hash));
+
+        let statuses = SubmissionStatuses::new(MAX_STATUSES, GenesisValues::preview());
+        statuses.accepted("a", &tx);
+        statuses.set("b", SubmissionStatus::Queued);
+
+        let mut node = MockNode::new()
+            .with_mempool(only_if(in_mempool))
+            .with_unspent(only_if(unspent));
+        statuses.settle(&mut node).await.unwrap();
+
+        assert_eq!(statuses.get("a"), Some(expected));
+        assert_eq!(statuses.get("b"), Some(SubmissionStatus::Queued));
+    }
+
+    #[tokio::test]
+    async fn test_settle_nothing() {
+        let statuses = SubmissionStatuses::default();
+        statuses.set("a", SubmissionStatus::InBlock);
+
+        // Doesn’t even ask the node
+        statuses.settle(&mut MockNode::new()).await.unwrap();
+    }
+}
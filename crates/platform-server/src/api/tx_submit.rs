@@ -0,0 +1,1347 @@
+use crate::{
+    api::utils::TxDeadline,
+    cbor::haskell_types::{
+        ApplyConwayTxPredError, ApplyTxErr, ConwayUtxoPredFailure, ConwayUtxoWPredFailure,
+        ShelleyBasedEra, TxSubmitFail, TxValidationError,
+    },
+    cli::{Config, NodeConfig},
+    common::validate_content_type,
+    encoding::{find_shown_assets, find_shown_ids},
+    hooks::{SubmissionHooks, SubmissionOutcome, SubmittedTx},
+    logging::tx_span,
+    node::{
+        api::NodeApi,
+        chainsync::RecentChain,
+        pool_manager::NodePoolManager,
+        protocol_params::ProtocolParamsHistory,
+        relay::Relay,
+        transactions::{check_submittable, submit_fail, tx_id, SubmitTimings},
+    },
+    pre_validate::{pre_validate, LedgerView},
+    retry::Backoff,
+    signature::{self, SIGNATURE_HEADER, SIGNATURE_KEY_HEADER},
+    spool::OfflineSpool,
+    submission_dedup::SubmissionDedup,
+    submission_queue::SubmissionQueue,
+    AppError, BlockfrostError, NodePool,
+};
+use axum::{
+    body::Bytes,
+    extract::Query,
+    http::{
+        header::{CONTENT_ENCODING, CONTENT_TYPE, LOCATION},
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use deadpool::managed::Object;
+use flate2::read::GzDecoder;
+use pallas_traverse::{wellknown::GenesisValues, MultiEraTx};
+use serde::{Deserialize, Serialize};
+use std::{
+    future::Future,
+    io::Read,
+    ops::DerefMut,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::MutexGuard;
+use tracing::{info, warn, Instrument};
+
+/// Sending this request header (with any value) gets the latency breakdown
+/// back in [`TIMING_HEADER`], and on the request’s tracing span.
+pub const DEBUG_TIMING_HEADER: &str = "x-debug-timing";
+pub const TIMING_HEADER: &str = "x-timing";
+
+/// Asks for the transaction to be submitted in the background, see [RFC 7240].
+///
+/// [RFC 7240]: https://www.rfc-editor.org/rfc/rfc7240#section-4.1
+const RESPOND_ASYNC: &str = "respond-async";
+
+/// Cap on decompressed request bodies, well above the largest transaction
+/// there is, even in hex.
+const MAX_DECOMPRESSED_BODY: u64 = 256 * 1024;
+
+/// Most transactions `/tx/submit/batch` takes at once, and the cap on its
+/// decompressed body.
+const MAX_BATCH_TXS: usize = 64;
+const MAX_DECOMPRESSED_BATCH: u64 = 4 * 1024 * 1024;
+
+/// How a submission is carried out, beyond the transaction itself.
+#[derive(Debug, Clone, Default)]
+pub struct SubmitOptions {
+    pub enrich_responses: bool,
+    pub retry: RetryPolicy,
+}
+
+impl SubmitOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            enrich_responses: config.server.enrich_responses,
+            retry: RetryPolicy::from_config(&config.node),
+        }
+    }
+
+    /// Adds what [`enrich_error`] finds to `err`, if asked to.
+    pub fn enrich(&self, err: BlockfrostError) -> BlockfrostError {
+        if self.enrich_responses {
+            enrich_error(err)
+        } else {
+            err
+        }
+    }
+}
+
+/// Longest wait between retries of a submission, unless the shortest is longer.
+const MAX_SUBMIT_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Retrying submissions that failed on the way to the node, never those the
+/// ledger rejected, nor those past their deadline. None by default.
+#[derive(Debug, Clone, Default)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    /// The shortest wait between retries, see [`Backoff`]
+    pub backoff: Duration,
+    /// Of the network, to tell when a transaction’s deadline has passed
+    pub genesis: Option<GenesisValues>,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &NodeConfig) -> Self {
+        Self {
+            max_retries: config.submit_retries,
+            backoff: Duration::from_millis(config.submit_retry_backoff_ms),
+            genesis: Some(config.genesis.clone()),
+        }
+    }
+
+    /// Transport failures and timeouts, unlike rejections, are our 5xx.
+    fn should_retry(&self, err: &BlockfrostError, deadline: Option<TxDeadline>) -> bool {
+        err.status_code >= 500 && !deadline.is_some_and(|deadline| deadline.has_passed())
+    }
+
+    fn backoff(&self) -> Backoff {
+        Backoff::new(
+            "tx_submit",
+            self.backoff,
+            MAX_SUBMIT_BACKOFF.max(self.backoff),
+        )
+        .with_max_retries(self.max_retries)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SubmitQuery {
+    /// Queue the transaction instead of waiting for the node’s answer
+    #[serde(rename = "async", default)]
+    pub r#async: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(hooks): Extension<SubmissionHooks>,
+    Extension(history): Extension<ProtocolParamsHistory>,
+    Extension(queue): Extension<SubmissionQueue>,
+    Extension(spool): Extension<Option<OfflineSpool>>,
+    Extension(chain): Extension<RecentChain>,
+    Extension(dedup): Extension<SubmissionDedup>,
+    Query(query): Query<SubmitQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, BlockfrostError> {
+    let body = decode_body(&headers, body, MAX_DECOMPRESSED_BODY)?;
+    let tx = parse_tx_body(&headers, &body)?;
+    check_tx(&tx, &config, &chain, &history)?;
+    let signer = verify_signer(&config, &headers, &tx)?;
+
+    let prefer_async = prefers_async(&headers);
+    if let Some(spool) = spool.filter(|spool| spool.holds(&node)) {
+        hooks.pre_submit(&SubmittedTx {
+            cbor: &tx,
+            signer: signer.as_deref(),
+        })?;
+        let txid = spool.push(tx)?;
+
+        return Ok(accepted_later(&txid, prefer_async));
+    }
+
+    if query.r#async || prefer_async {
+        return submit_async(&queue, &hooks, prefer_async, tx, signer.as_deref());
+    }
+
+    submit(
+        || node.get(),
+        node.relay(),
+        SubmitOptions::from_config(&config),
+        &dedup,
+        &hooks,
+        &history,
+        &headers,
+        tx,
+        signer.as_deref(),
+    )
+    .await
+}
+
+/// Rejects oversized, malformed and, with `--pre-validate`, invalid
+/// transactions right away, before they take a node connection, a queue slot
+/// or a spool entry.
+fn check_tx(
+    tx: &[u8],
+    config: &Config,
+    chain: &RecentChain,
+    history: &ProtocolParamsHistory,
+) -> Result<(), BlockfrostError> {
+    check_size(tx, config.limits.max_tx_size)?;
+    check_submittable(tx)?;
+
+    if config.server.pre_validate {
+        pre_validate(
+            tx,
+            &LedgerView {
+                network: &config.node.network,
+                tip: chain.latest().map(|block| block.slot),
+                params: history.latest().as_ref(),
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+fn check_size(tx: &[u8], max_tx_size: usize) -> Result<(), BlockfrostError> {
+    if tx.len() > max_tx_size {
+        return Err(BlockfrostError::payload_too_large(format!(
+            "Transaction of {} bytes exceeds --max-tx-size of {}",
+            tx.len(),
+            max_tx_size
+        )));
+    }
+
+    Ok(())
+}
+
+/// The stake address that signed `tx`, with `--verify-signatures`.
+fn verify_signer(
+    config: &Config,
+    headers: &HeaderMap,
+    tx: &[u8],
+) -> Result<Option<String>, BlockfrostError> {
+    if !config.server.verify_signatures {
+        return Ok(None);
+    }
+
+    let signer = signature::verify(headers, tx, &config.node.network)?;
+    if let Some(signer) = &signer {
+        info!("Submission of {} signed by {}", tx_id(tx), signer);
+    }
+
+    Ok(signer)
+}
+
+/// Undoes the `Content-Encoding`, of which we only take `gzip`, as some SDKs
+/// compress by default, up to `max` bytes.
+fn decode_body(headers: &HeaderMap, body: Bytes, max: u64) -> Result<Bytes, BlockfrostError> {
+    let encoding = headers
+        .get(CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_ascii_lowercase());
+
+    match encoding.as_deref() {
+        None | Some("identity") => Ok(body),
+        Some("gzip") | Some("x-gzip") => {
+            let mut decompressed = vec![];
+            GzDecoder::new(&body[..])
+                .take(max + 1)
+                .read_to_end(&mut decompressed)
+                .map_err(|e| BlockfrostError::custom_400(format!("Invalid gzip body: {e}")))?;
+
+            if decompressed.len() as u64 > max {
+                return Err(BlockfrostError::payload_too_large(format!(
+                    "Decompressed body exceeds {max} bytes"
+                )));
+            }
+
+            Ok(decompressed.into())
+        }
+        Some(other) => Err(BlockfrostError::custom_400(format!(
+            "Content-Encoding must be one of: {:?}, got {:?}",
+            ["gzip", "identity"],
+            other
+        ))),
+    }
+}
+
+/// Negotiates the transaction encoding on the `Content-Type`:
+/// * `application/cbor` is binary CBOR, or hex as we used to require,
+/// * `text/plain` is hex,
+/// * `application/json` is a string of hex.
+fn parse_tx_body(headers: &HeaderMap, body: &[u8]) -> Result<Vec<u8>, BlockfrostError> {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or_default().trim());
+
+    let decode_hex =
+        |hex: &str| hex::decode(hex.trim()).map_err(|e| BlockfrostError::custom_400(e.to_string()));
+
+    match content_type {
+        None | Some("application/cbor") => {
+            // A CBOR transaction starts with an array header, never a hex digit
+            if !body.is_empty() && body.iter().all(|b| b.is_ascii_hexdigit()) {
+                decode_hex(&String::from_utf8_lossy(body))
+            } else {
+                Ok(body.to_vec())
+            }
+        }
+        Some("text/plain") => decode_hex(&String::from_utf8_lossy(body)),
+        Some("application/json") => {
+            let hex: String = serde_json::from_slice(body)
+                .map_err(|e| BlockfrostError::custom_400(e.to_string()))?;
+            decode_hex(&hex)
+        }
+        Some(other) => Err(BlockfrostError::custom_400(format!(
+            "Content-Type must be one of: {:?}, got {:?}",
+            ["application/cbor", "text/plain", "application/json"],
+            other
+        ))),
+    }
+}
+
+/// `POST /api/submit/tx`, as in cardano-submit-api, taking the transaction
+/// as binary CBOR rather than hex, so that its clients work unmodified.
+#[allow(clippy::too_many_arguments)]
+pub async fn submit_api_route(
+    Extension(node): Extension<NodePool>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(hooks): Extension<SubmissionHooks>,
+    Extension(history): Extension<ProtocolParamsHistory>,
+    Extension(chain): Extension<RecentChain>,
+    Extension(dedup): Extension<SubmissionDedup>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    // Allow only application/cbor content type
+    validate_content_type(&headers, &["application/cbor"])?;
+    let body = decode_body(&headers, body, MAX_DECOMPRESSED_BODY)?;
+    check_tx(&body, &config, &chain, &history)?;
+    let signer = verify_signer(&config, &headers, &body)?;
+
+    submit(
+        || node.get(),
+        node.relay(),
+        SubmitOptions::from_config(&config),
+        &dedup,
+        &hooks,
+        &history,
+        &headers,
+        body.to_vec(),
+        signer.as_deref(),
+    )
+    .await
+}
+
+/// `POST /tx/submit/batch`, taking a JSON array of hex transactions, which
+/// are submitted in order over a single node connection, answering how each
+/// went, in the same order. A failed transaction doesn’t stop the rest, e.g.
+/// those not spending its outputs.
+#[allow(clippy::too_many_arguments)]
+pub async fn batch_route(
+    Extension(node): Extension<NodePool>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(hooks): Extension<SubmissionHooks>,
+    Extension(history): Extension<ProtocolParamsHistory>,
+    Extension(chain): Extension<RecentChain>,
+    Extension(dedup): Extension<SubmissionDedup>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Vec<BatchResult>>, BlockfrostError> {
+    validate_content_type(&headers, &["application/json"])?;
+    check_unsigned(&headers)?;
+    let body = decode_body(&headers, body, MAX_DECOMPRESSED_BATCH)?;
+    let txs = parse_batch(&body)?;
+
+    // Only connected to once a transaction passes our checks, then kept until
+    // it fails, e.g. closed after a transport error
+    let connection: tokio::sync::Mutex<Option<Object<NodePoolManager>>> =
+        tokio::sync::Mutex::new(None);
+    let (connection, pool) = (&connection, &node);
+    let get_node = move || async move {
+        let mut connection = connection.lock().await;
+        if !connection
+            .as_ref()
+            .is_some_and(|connection| connection.is_reusable())
+        {
+            *connection = Some(pool.get().await?);
+        }
+        Ok::<_, AppError>(MutexGuard::map(connection, |connection| {
+            &mut **connection.as_mut().expect("connected above")
+        }))
+    };
+    let forget_node = move || {
+        if let Ok(mut connection) = connection.try_lock() {
+            *connection = None;
+        }
+    };
+
+    let results = submit_batch(
+        get_node,
+        forget_node,
+        node.relay(),
+        &SubmitOptions::from_config(&config),
+        &dedup,
+        &hooks,
+        &history,
+        txs,
+        |tx| check_tx(tx, &config, &chain, &history),
+    )
+    .await;
+
+    Ok(Json(results))
+}
+
+/// Refuses signatures for batches, as they’re of a single transaction, see
+/// [`signature::verify`], rather than leaving them unchecked.
+fn check_unsigned(headers: &HeaderMap) -> Result<(), BlockfrostError> {
+    if [SIGNATURE_HEADER, SIGNATURE_KEY_HEADER]
+        .iter()
+        .any(|name| headers.contains_key(*name))
+    {
+        return Err(BlockfrostError::custom_400(format!(
+            "{} and {} aren’t supported for batches, submit signed transactions to /tx/submit",
+            SIGNATURE_HEADER, SIGNATURE_KEY_HEADER
+        )));
+    }
+
+    Ok(())
+}
+
+/// The transactions of a batch, at most [`MAX_BATCH_TXS`] of them.
+fn parse_batch(body: &[u8]) -> Result<Vec<Vec<u8>>, BlockfrostError> {
+    let txs: Vec<String> =
+        serde_json::from_slice(body).map_err(|e| BlockfrostError::custom_400(e.to_string()))?;
+
+    if txs.len() > MAX_BATCH_TXS {
+        return Err(BlockfrostError::payload_too_large(format!(
+            "Batch of {} transactions exceeds {}",
+            txs.len(),
+            MAX_BATCH_TXS
+        )));
+    }
+
+    txs.iter()
+        .enumerate()
+        .map(|(index, tx)| {
+            hex::decode(tx.trim()).map_err(|e| {
+                BlockfrostError::custom_400(format!("Invalid transaction at {index}: {e}"))
+            })
+        })
+        .collect()
+}
+
+/// How a transaction of a batch went.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct BatchResult {
+    pub txid: String,
+    /// What `/tx/submit` would have answered, if not accepted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BlockfrostError>,
+}
+
+/// Submits `txs` one after the other, each as [`submit`] would, but all with
+/// `get_node`, once they pass the `check` and the hooks. After a failure that
+/// isn’t the transaction’s, `forget_node` lets go of the node.
+#[allow(clippy::too_many_arguments)]
+async fn submit_batch<N, F>(
+    mut get_node: impl FnMut() -> F,
+    forget_node: impl Fn(),
+    relay: Option<&Relay>,
+    options: &SubmitOptions,
+    dedup: &SubmissionDedup,
+    hooks: &SubmissionHooks,
+    history: &ProtocolParamsHistory,
+    txs: Vec<Vec<u8>>,
+    check: impl Fn(&[u8]) -> Result<(), BlockfrostError>,
+) -> Vec<BatchResult>
+where
+    F: Future<Output = Result<N, AppError>>,
+    N: DerefMut<Target: NodeApi>,
+{
+    let mut results = Vec::with_capacity(txs.len());
+
+    for tx in txs {
+        let txid = tx_id(&tx);
+        let checked = check(&tx).and_then(|()| {
+            hooks.pre_submit(&SubmittedTx {
+                cbor: &tx,
+                signer: None,
+            })
+        });
+        let result = match checked {
+            Err(err) => Err(err),
+            Ok(()) => {
+                submit_result(
+                    &mut get_node,
+                    relay,
+                    &options.retry,
+                    Some(dedup),
+                    hooks,
+                    history,
+                    tx,
+                    None,
+                    &mut SubmitTimings::default(),
+                )
+                .await
+            }
+        };
+
+        if result.as_ref().is_err_and(|err| err.status_code >= 500) {
+            forget_node();
+        }
+
+        results.push(BatchResult {
+            txid,
+            error: result.err().map(|err| options.enrich(err)),
+        });
+    }
+
+    results
+}
+
+/// Whether the client asked for `Prefer: respond-async`.
+fn prefers_async(headers: &HeaderMap) -> bool {
+    headers
+        .get_all("prefer")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|preference| preference.trim().eq_ignore_ascii_case(RESPOND_ASYNC))
+}
+
+/// Replies `202 Accepted` with the transaction id right away, and leaves
+/// `tx` to the `queue`, where the client can follow it at the `Location`.
+fn submit_async(
+    queue: &SubmissionQueue,
+    hooks: &SubmissionHooks,
+    prefer_async: bool,
+    tx: Vec<u8>,
+    signer: Option<&str>,
+) -> Result<Response, BlockfrostError> {
+    hooks.pre_submit(&SubmittedTx { cbor: &tx, signer })?;
+
+    let txid = queue.enqueue(tx)?;
+
+    Ok(accepted_later(&txid, prefer_async))
+}
+
+/// `202 Accepted` with the transaction id, and where to follow it.
+fn accepted_later(txid: &str, prefer_async: bool) -> Response {
+    // Relative to `/tx/submit`, so that it works wherever we're nested
+    let location = format!("{txid}/status");
+    let mut response = (StatusCode::ACCEPTED, Json(txid)).into_response();
+    if let Ok(location) = HeaderValue::from_str(&location) {
+        response.headers_mut().insert(LOCATION, location);
+    }
+    if prefer_async {
+        response.headers_mut().insert(
+            "preference-applied",
+            HeaderValue::from_static(RESPOND_ASYNC),
+        );
+    }
+
+    response
+}
+
+/// Submits `tx` to a node from `get_node`, which is only called if the
+/// transaction passes our own checks, or if there’s none, to the `relay`.
+/// The same transaction submitted meanwhile, or just before, is submitted
+/// only once, see [`SubmissionDedup`].
+#[allow(clippy::too_many_arguments)]
+async fn submit<N, F>(
+    get_node: impl FnMut() -> F,
+    relay: Option<&Relay>,
+    options: SubmitOptions,
+    dedup: &SubmissionDedup,
+    hooks: &SubmissionHooks,
+    history: &ProtocolParamsHistory,
+    headers: &HeaderMap,
+    tx: Vec<u8>,
+    signer: Option<&str>,
+) -> Result<Response, BlockfrostError>
+where
+    F: Future<Output = Result<N, AppError>>,
+    N: DerefMut<Target: NodeApi>,
+{
+    hooks.pre_submit(&SubmittedTx { cbor: &tx, signer })?;
+
+    let mut timings = SubmitTimings::default();
+    let result = submit_result(
+        get_node,
+        relay,
+        &options.retry,
+        Some(dedup),
+        hooks,
+        history,
+        tx,
+        signer,
+        &mut timings,
+    )
+    .await;
+
+    let mut response = result
+        .map(Json)
+        .map_err(|err| options.enrich(err))
+        .into_response();
+
+    if headers.contains_key(DEBUG_TIMING_HEADER) {
+        add_timing(&mut response, &timings);
+    }
+
+    Ok(response)
+}
+
+/// Checks `tx` against the protocol parameters, submits it, retrying as the
+/// `retry` policy allows, and tells the hooks how that went, all within its
+/// [`tx_span`]. With a `dedup`, only the submission itself is shared with
+/// identical ones, so that the hooks of every submitter still run.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn submit_result<N, F>(
+    get_node: impl FnMut() -> F,
+    relay: Option<&Relay>,
+    retry: &RetryPolicy,
+    dedup: Option<&SubmissionDedup>,
+    hooks: &SubmissionHooks,
+    history: &ProtocolParamsHistory,
+    tx: Vec<u8>,
+    signer: Option<&str>,
+    timings: &mut SubmitTimings,
+) -> Result<String, BlockfrostError>
+where
+    F: Future<Output = Result<N, AppError>>,
+    N: DerefMut<Target: NodeApi>,
+{
+    let span = tx_span(&tx_id(&tx));
+
+    async move {
+        let submitting = submit_checked(get_node, relay, retry, history, &tx, timings);
+        let result = match dedup {
+            Some(dedup) => dedup.submit(&tx_id(&tx), submitting).await,
+            None => submitting.await,
+        };
+
+        hooks.post_submit(
+            &SubmittedTx { cbor: &tx, signer },
+            &SubmissionOutcome::of(&result),
+        );
+
+        result
+    }
+    .instrument(span)
+    .await
+}
+
+/// Checks `tx` against the protocol parameters and submits it, retrying as
+/// the `retry` policy allows.
+async fn submit_checked<N, F>(
+    mut get_node: impl FnMut() -> F,
+    relay: Option<&Relay>,
+    retry: &RetryPolicy,
+    history: &ProtocolParamsHistory,
+    tx: &[u8],
+    timings: &mut SubmitTimings,
+) -> Result<String, BlockfrostError>
+where
+    F: Future<Output = Result<N, AppError>>,
+    N: DerefMut<Target: NodeApi>,
+{
+    let max_tx_size = history.latest().and_then(|params| params.max_tx_size);
+    match check_max_tx_size(tx.len() as u64, max_tx_size) {
+        Err(err) => Err(err),
+        Ok(()) => {
+            let deadline = retry
+                .genesis
+                .as_ref()
+                .and_then(|genesis| TxDeadline::of(tx, genesis));
+            let mut attempts = retry.backoff().start();
+            loop {
+                let is_retry = attempts.retries() > 0;
+                match submit_once(get_node(), relay, is_retry, tx, timings).await {
+                    Err(err) if retry.should_retry(&err, deadline) => {
+                        let Some(delay) = attempts.next_delay() else {
+                            break Err(err);
+                        };
+                        warn!(
+                            "Submitting {} failed: {}, retrying in {:?} ({}/{})",
+                            tx_id(tx),
+                            err.message,
+                            delay,
+                            attempts.retries(),
+                            retry.max_retries
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    result => break result,
+                }
+            }
+        }
+    }
+}
+
+/// A single attempt at submitting `tx`, to the node or else to the `relay`.
+///
+/// A retry may be rejected only because an earlier attempt got through after
+/// all, e.g. with its inputs spent by itself, so then the transaction is
+/// looked for in the mempool.
+async fn submit_once<N>(
+    get_node: impl Future<Output = Result<N, AppError>>,
+    relay: Option<&Relay>,
+    is_retry: bool,
+    tx: &[u8],
+    timings: &mut SubmitTimings,
+) -> Result<String, BlockfrostError>
+where
+    N: DerefMut<Target: NodeApi>,
+{
+    let started_at = Instant::now();
+
+    match get_node.await {
+        Ok(mut node) => {
+            timings.node_connect = node.take_connect_duration().unwrap_or_default();
+            timings.queue_wait = started_at.elapsed().saturating_sub(timings.node_connect);
+
+            match node.submit_transaction_timed(tx.to_vec(), timings).await {
+                Err(err)
+                    if is_retry && err.status_code == 400 && in_mempool(&mut *node, tx).await =>
+                {
+                    info!(
+                        "Transaction {} rejected on retry, but already in the mempool",
+                        tx_id(tx)
+                    );
+                    Ok(tx_id(tx))
+                }
+                result => result,
+            }
+        }
+        Err(err) => match relay {
+            Some(relay) => {
+                warn!(
+                    "No node to submit to ({}), falling back to relay {}",
+                    err,
+                    relay.address()
+                );
+                relay.submit_transaction(tx.to_vec()).await
+            }
+            None => Err(err.into()),
+        },
+    }
+}
+
+async fn in_mempool<T: NodeApi + ?Sized>(node: &mut T, tx: &[u8]) -> bool {
+    let Ok(decoded) = MultiEraTx::decode(tx) else {
+        return false;
+    };
+
+    node.mempool_tx_hashes()
+        .await
+        .is_ok_and(|hashes| hashes.contains(&decoded.hash()))
+}
+
+/// Rejects transactions the node would reject for their size anyway, the
+/// same way it would, without a round trip to it, or even if it’s down.
+fn check_max_tx_size(size: u64, max_tx_size: Option<u32>) -> Result<(), BlockfrostError> {
+    match max_tx_size {
+        Some(max) if size > max as u64 => {
+            let error = TxValidationError::ShelleyTxValidationError {
+                error: ApplyTxErr(vec![ApplyConwayTxPredError::UtxowFailure(
+                    ConwayUtxoWPredFailure::UtxoFailure(ConwayUtxoPredFailure::MaxTxSizeUTxO(size)),
+                )]),
+                era: ShelleyBasedEra::ShelleyBasedEraConway,
+            };
+
+            Err(submit_fail("TxSubmitFail", TxSubmitFail::from(error)))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn add_timing(response: &mut Response, timings: &SubmitTimings) {
+    let value = timings.header_value();
+    tracing::Span::current().record("timing", value.as_str());
+
+    if let Ok(value) = HeaderValue::from_str(&value) {
+        response.headers_mut().insert(TIMING_HEADER, value);
+    }
+}
+
+/// Lists the assets mentioned anywhere in the error details under `assets`,
+/// and the governance credentials and pool ids under `friendly`.
+fn enrich_error(mut err: BlockfrostError) -> BlockfrostError {
+    let mut texts = vec![];
+    if let Some(details) = &err.details {
+        collect_strings(details, &mut texts);
+    }
+
+    let mut assets: Vec<_> = texts
+        .iter()
+        .flat_map(|text| find_shown_assets(text))
+        .collect();
+    assets.sort();
+    assets.dedup();
+
+    let mut friendly: Vec<_> = texts.iter().flat_map(|text| find_shown_ids(text)).collect();
+    friendly.sort();
+    friendly.dedup();
+
+    if let Some(serde_json::Value::Object(details)) = &mut err.details {
+        if !assets.is_empty() {
+            details.insert("assets".to_string(), serde_json::json!(assets));
+        }
+        if !friendly.is_empty() {
+            details.insert("friendly".to_string(), serde_json::json!(friendly));
+        }
+    }
+
+    err
+}
+
+fn collect_strings<'a>(value: &'a serde_json::Value, out: &mut Vec<&'a str>) {
+    match value {
+        serde_json::Value::String(s) => out.push(s),
+        serde_json::Value::Array(values) => values.iter().for_each(|v| collect_strings(v, out)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| collect_strings(v, out)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            stats::Stats,
+            tx_status::{SubmissionStatus, SubmissionStatuses},
+        },
+        node::{
+            mock::{MockNode, CONWAY_TX, CONWAY_TX_EXPIRED},
+            protocol_params::ProtocolParams,
+            timeouts::{NodeOperation, NodeTimeout},
+            transactions::transport_error,
+        },
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use std::cell::{RefCell, RefMut};
+
+    async fn submit_to(
+        node: &mut MockNode,
+        history: &ProtocolParamsHistory,
+        tx: Vec<u8>,
+    ) -> Response {
+        submit_with(node, history, SubmitOptions::default(), tx).await
+    }
+
+    async fn submit_with(
+        node: &mut MockNode,
+        history: &ProtocolParamsHistory,
+        options: SubmitOptions,
+        tx: Vec<u8>,
+    ) -> Response {
+        let node = RefCell::new(node);
+        let node = &node;
+
+        submit(
+            move || async move { Ok::<_, AppError>(RefMut::map(node.borrow_mut(), |node| &mut **node)) },
+            None,
+            options,
+            &SubmissionDedup::default(),
+            &SubmissionHooks::new(),
+            history,
+            &HeaderMap::new(),
+            tx,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_submit() {
+        let mut node = MockNode::new()
+            .with_submit_result(Ok("abcd".to_string()))
+            .with_submit_result(Err(BlockfrostError::custom_400("rejected".to_string())));
+        let history = ProtocolParamsHistory::default();
+
+        let response = submit_to(&mut node, &history, vec![0x84, 0x01]).await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            to_bytes(response.into_body(), usize::MAX).await.unwrap(),
+            r#""abcd""#
+        );
+
+        let response = submit_to(&mut node, &history, vec![0x84, 0x02]).await;
+        assert_eq!(response.status(), 400);
+
+        assert_eq!(node.submitted, vec![vec![0x84, 0x01], vec![0x84, 0x02]]);
+    }
+
+    #[derive(Clone, Default)]
+    struct HookLog(Arc<std::sync::Mutex<Vec<&'static str>>>);
+
+    impl crate::hooks::SubmissionHook for HookLog {
+        fn pre_submit(&self, _tx: &SubmittedTx) -> Result<(), BlockfrostError> {
+            self.0.lock().unwrap().push("pre");
+            Ok(())
+        }
+
+        fn post_submit(&self, _tx: &SubmittedTx, _outcome: &SubmissionOutcome) {
+            self.0.lock().unwrap().push("post");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_deduplicated_runs_hooks() {
+        let mut node = MockNode::new().with_submit_result(Ok("abcd".to_string()));
+        let node = RefCell::new(&mut node);
+        let node = &node;
+        let dedup = SubmissionDedup::default();
+        let log = HookLog::default();
+        let hooks = SubmissionHooks::new().with(log.clone());
+        let history = ProtocolParamsHistory::default();
+
+        for _ in 0..2 {
+            let response = submit(
+                move || async move {
+                    Ok::<_, AppError>(RefMut::map(node.borrow_mut(), |node| &mut **node))
+                },
+                None,
+                SubmitOptions::default(),
+                &dedup,
+                &hooks,
+                &history,
+                &HeaderMap::new(),
+                vec![0x84, 0x01],
+                None,
+            )
+            .await
+            .unwrap();
+            assert_eq!(response.status(), 200);
+        }
+
+        // Submitted once, but with the hooks of both submitters
+        assert_eq!(node.borrow().submitted, vec![vec![0x84, 0x01]]);
+        assert_eq!(*log.0.lock().unwrap(), vec!["pre", "post", "pre", "post"]);
+    }
+
+    fn retry_once() -> SubmitOptions {
+        SubmitOptions {
+            enrich_responses: false,
+            retry: RetryPolicy {
+                max_retries: 1,
+                backoff: Duration::from_millis(1),
+                genesis: Some(GenesisValues::preview()),
+            },
+        }
+    }
+
+    #[rstest]
+    #[case::transport(BlockfrostError::internal_server_error("Connection reset".to_string()), 200, 2)]
+    #[case::node_transport(transport_error("Connection reset"), 200, 2)]
+    #[case::timeout(
+        BlockfrostError::node_timeout(NodeTimeout {
+            operation: NodeOperation::Submit,
+            after: Duration::from_secs(30),
+        }),
+        200,
+        2
+    )]
+    #[case::rejection(BlockfrostError::custom_400("rejected".to_string()), 400, 1)]
+    #[tokio::test]
+    async fn test_submit_retries(
+        #[case] first: BlockfrostError,
+        #[case] status: u16,
+        #[case] attempts: usize,
+    ) {
+        let mut node = MockNode::new()
+            .with_submit_result(Err(first))
+            .with_submit_result(Ok("abcd".to_string()));
+
+        let response = submit_with(
+            &mut node,
+            &ProtocolParamsHistory::default(),
+            retry_once(),
+            vec![0x84, 0x01],
+        )
+        .await;
+
+        assert_eq!(response.status(), status);
+        assert_eq!(node.submitted.len(), attempts);
+    }
+
+    #[tokio::test]
+    async fn test_submit_no_retry_past_deadline() {
+        let mut node = MockNode::new()
+            .with_submit_result(Err(BlockfrostError::internal_server_error(
+                "Connection reset".to_string(),
+            )))
+            .with_submit_result(Ok("abcd".to_string()));
+
+        let response = submit_with(
+            &mut node,
+            &ProtocolParamsHistory::default(),
+            retry_once(),
+            hex::decode(CONWAY_TX_EXPIRED).unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), 500);
+        assert_eq!(node.submitted.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_retry_finds_earlier_attempt() {
+        let tx = hex::decode(CONWAY_TX).unwrap();
+        let hash = MultiEraTx::decode(&tx).unwrap().hash();
+        let mut node = MockNode::new()
+            .with_submit_result(Err(BlockfrostError::internal_server_error(
+                "Connection reset".to_string(),
+            )))
+            .with_submit_result(Err(BlockfrostError::custom_400(
+                "BadInputsUTxO".to_string(),
+            )))
+            .with_mempool([hash].into());
+
+        let response = submit_with(
+            &mut node,
+            &ProtocolParamsHistory::default(),
+            retry_once(),
+            tx.clone(),
+        )
+        .await;
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            to_bytes(response.into_body(), usize::MAX).await.unwrap(),
+            format!("\"{}\"", tx_id(&tx))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_over_max_tx_size() {
+        let mut node = MockNode::new();
+        let history = ProtocolParamsHistory::default();
+        history.record(
+            500,
+            ProtocolParams {
+                max_tx_size: Some(1),
+                ..Default::default()
+            },
+        );
+
+        let response = submit_to(&mut node, &history, vec![0x84, 0x00]).await;
+
+        assert_eq!(response.status(), 400);
+        assert!(node.submitted.is_empty());
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_submit_async(#[case] prefer_async: bool) {
+        let statuses = SubmissionStatuses::default();
+        let (queue, receiver) = SubmissionQueue::new(1, statuses.clone());
+        let tx = vec![0x84, 0x01];
+        let txid = tx_id(&tx);
+
+        let response =
+            submit_async(&queue, &SubmissionHooks::new(), prefer_async, tx, None).unwrap();
+
+        assert_eq!(response.status(), 202);
+        assert_eq!(response.headers()[LOCATION], format!("{txid}/status"));
+        assert_eq!(
+            response.headers().contains_key("preference-applied"),
+            prefer_async
+        );
+        assert_eq!(statuses.get(&txid), Some(SubmissionStatus::Queued));
+
+        // Nobody drained it yet
+        let response = submit_async(
+            &queue,
+            &SubmissionHooks::new(),
+            prefer_async,
+            vec![0x84],
+            None,
+        );
+        assert_eq!(response.unwrap_err().status_code, 503);
+        assert_eq!(
+            receiver.try_lock().unwrap().try_recv().unwrap().0,
+            vec![0x84, 0x01]
+        );
+    }
+
+    #[rstest]
+    #[case(None, false)]
+    #[case(Some("respond-async"), true)]
+    #[case(Some("wait=10, Respond-Async"), true)]
+    #[case(Some("return=minimal"), false)]
+    fn test_prefers_async(#[case] prefer: Option<&str>, #[case] expected: bool) {
+        let mut headers = HeaderMap::new();
+        if let Some(prefer) = prefer {
+            headers.insert("prefer", HeaderValue::from_str(prefer).unwrap());
+        }
+
+        assert_eq!(prefers_async(&headers), expected);
+    }
+
+    #[rstest]
+    #[case(Some("application/cbor"), &[0x84, 0xa3, 0x00])]
+    #[case(Some("application/cbor"), b"84a300")]
+    #[case(None, b"84a300")]
+    #[case(Some("text/plain; charset=utf-8"), b"84a300\n")]
+    #[case(Some("application/json"), br#""84a300""#)]
+    fn test_parse_tx_body(#[case] content_type: Option<&str>, #[case] body: &[u8]) {
+        let mut headers = HeaderMap::new();
+        if let Some(content_type) = content_type {
+            headers.insert(CONTENT_TYPE, content_type.parse().unwrap());
+        }
+
+        assert_eq!(
+            parse_tx_body(&headers, body).unwrap(),
+            vec![0x84, 0xa3, 0x00]
+        );
+    }
+
+    #[rstest]
+    #[case(CONWAY_TX, 65_536, None)]
+    #[case(CONWAY_TX, 100, Some(413))]
+    #[case("", 65_536, Some(400))]
+    #[case("820102", 65_536, Some(400))]
+    #[case("84a300", 65_536, Some(400))]
+    fn test_check_tx(#[case] tx: &str, #[case] max_tx_size: usize, #[case] status: Option<u16>) {
+        let tx = hex::decode(tx).unwrap();
+        let result = check_size(&tx, max_tx_size).and_then(|()| check_submittable(&tx));
+
+        assert_eq!(result.err().map(|e| e.status_code), status);
+    }
+
+    #[test]
+    fn test_parse_tx_body_rejects() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+        assert!(parse_tx_body(&headers, b"84a3zz").is_err());
+
+        headers.insert(CONTENT_TYPE, "application/xml".parse().unwrap());
+        assert!(parse_tx_body(&headers, b"84a300").is_err());
+    }
+
+    #[test]
+    fn test_check_max_tx_size() {
+        assert!(check_max_tx_size(16384, Some(16384)).is_ok());
+        assert!(check_max_tx_size(20000, None).is_ok());
+
+        let err = check_max_tx_size(16385, Some(16384)).unwrap_err();
+        let details = err.details.unwrap();
+
+        assert_eq!(err.message, "TxSubmitFail");
+        assert_eq!(
+            details["contents"]["contents"]["contents"]["error"],
+            serde_json::json!(["UtxowFailure (UtxoFailure (MaxTxSizeUTxO (16385)))"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_batch() {
+        let mut node = MockNode::new()
+            .with_submit_result(Ok(tx_id(&[0x84, 0x01])))
+            .with_submit_result(Err(BlockfrostError::custom_400("rejected".to_string())))
+            .with_submit_result(Err(transport_error("Connection reset")));
+        let node = RefCell::new(&mut node);
+        let node = &node;
+        let forgotten = std::cell::Cell::new(0);
+        let txs = vec![
+            vec![0x84, 0x01],
+            vec![0x84, 0x02],
+            vec![0x84, 0x03],
+            vec![0x84, 0x04],
+        ];
+        let stats = Stats::new(0.99);
+
+        let results =
+            submit_batch(
+                move || async move {
+                    Ok::<_, AppError>(RefMut::map(node.borrow_mut(), |node| &mut **node))
+                },
+                || forgotten.set(forgotten.get() + 1),
+                None,
+                &SubmitOptions::default(),
+                &SubmissionDedup::default(),
+                &SubmissionHooks::new().with(stats.clone()),
+                &ProtocolParamsHistory::default(),
+                txs.clone(),
+                |tx| match tx {
+                    [0x84, 0x02] => Err(BlockfrostError::custom_400("too big".to_string())),
+                    _ => Ok(()),
+                },
+            )
+            .await;
+
+        assert_eq!(
+            results,
+            vec![
+                BatchResult {
+                    txid: tx_id(&txs[0]),
+                    error: None,
+                },
+                BatchResult {
+                    txid: tx_id(&txs[1]),
+                    error: Some(BlockfrostError::custom_400("too big".to_string())),
+                },
+                BatchResult {
+                    txid: tx_id(&txs[2]),
+                    error: Some(BlockfrostError::custom_400("rejected".to_string())),
+                },
+                BatchResult {
+                    txid: tx_id(&txs[3]),
+                    error: Some(transport_error("Connection reset")),
+                },
+            ]
+        );
+        // Only those that passed the checks, in order
+        assert_eq!(
+            node.borrow().submitted,
+            vec![txs[0].clone(), txs[2].clone(), txs[3].clone()]
+        );
+        // Only after the transport error
+        assert_eq!(forgotten.get(), 1);
+        // Each submitted one counts towards the submit SLO
+        let hour = &stats.slo().windows[0];
+        assert_eq!(
+            (hour.successful, hour.client_errors, hour.server_errors),
+            (1, 1, 1)
+        );
+    }
+
+    #[rstest]
+    #[case(r#"[]"#, Ok(vec![]))]
+    #[case(r#"["84a300", " 8400 "]"#, Ok(vec![vec![0x84, 0xa3, 0x00], vec![0x84, 0x00]]))]
+    #[case(r#"["84a300", "xyz"]"#, Err(400))]
+    #[case(r#""84a300""#, Err(400))]
+    fn test_parse_batch(#[case] body: &str, #[case] expected: Result<Vec<Vec<u8>>, u16>) {
+        assert_eq!(
+            parse_batch(body.as_bytes()).map_err(|err| err.status_code),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_check_unsigned() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(check_unsigned(&headers), Ok(()));
+
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_static("84"));
+        assert_eq!(check_unsigned(&headers).unwrap_err().status_code, 400);
+    }
+
+    #[test]
+    fn test_parse_batch_too_many() {
+        let body = serde_json::to_vec(&vec!["8400"; MAX_BATCH_TXS + 1]).unwrap();
+
+        assert_eq!(parse_batch(&body).unwrap_err().status_code, 413);
+    }
+
+    fn gzip(bytes: &[u8]) -> Bytes {
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        std::io::Write::write_all(&mut encoder, bytes).unwrap();
+        encoder.finish().unwrap().into()
+    }
+
+    #[rstest]
+    #[case(None, Bytes::from_static(b"84a300"), Ok(b"84a300".to_vec()))]
+    #[case(Some("gzip"), gzip(b"84a300"), Ok(b"84a300".to_vec()))]
+    #[case(Some("GZIP"), gzip(&[0x84, 0xa3]), Ok(vec![0x84, 0xa3]))]
+    #[case(Some("gzip"), Bytes::from_static(b"84a300"), Err(400))]
+    #[case(Some("gzip"), gzip(&[0; MAX_DECOMPRESSED_BODY as usize + 1]), Err(413))]
+    #[case(Some("br"), Bytes::from_static(b"84a300"), Err(400))]
+    fn test_decode_body(
+        #[case] encoding: Option<&str>,
+        #[case] body: Bytes,
+        #[case] expected: Result<Vec<u8>, u16>,
+    ) {
+        let mut headers = HeaderMap::new();
+        if let Some(encoding) = encoding {
+            headers.insert(CONTENT_ENCODING, HeaderValue::from_str(encoding).unwrap());
+        }
+
+        let result = decode_body(&headers, body, MAX_DECOMPRESSED_BODY)
+            .map(|body| body.to_vec())
+            .map_err(|err| err.status_code);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_add_timing() {
+        let mut response = Json("txid").into_response();
+        add_timing(&mut response, &SubmitTimings::default());
+
+        assert_eq!(
+            response.headers()[TIMING_HEADER],
+            "queue_wait;dur=0.00, node_connect;dur=0.00, era_query;dur=0.00, submit_rtt;dur=0.00, decode;dur=0.00"
+        );
+    }
+
+    #[test]
+    fn test_enrich_error() {
+        let details = serde_json::json!({
+            "tag": "TxValidationErrorInCardanoMode",
+            "contents": {
+                "kind": "ShelleyTxValidationError",
+                "error": ["ConwayUtxowFailure (UtxoFailure (ValueNotConservedUTxO (MaryValue (Coin 1) (MultiAsset (fromList [(PolicyID {policyID = ScriptHash \"7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373\"},fromList [(\"504154415445\",1)])]))) (MaryValue (Coin 2) (MultiAsset (fromList [])))))"]
+            }
+        });
+
+        let err = enrich_error(BlockfrostError::custom_400_details(
+            "tx submission failed".to_string(),
+            details.clone(),
+        ));
+        let enriched = err.details.unwrap();
+
+        assert_eq!(enriched["contents"], details["contents"]);
+        assert_eq!(
+            enriched["assets"][0]["fingerprint"],
+            "asset13n25uv0yaf5kus35fm2k86cqy60z58d9xmde92"
+        );
+        assert_eq!(enriched["assets"][0]["asset_name_utf8"], "PATATE");
+        assert_eq!(enriched.get("friendly"), None);
+    }
+
+    #[test]
+    fn test_enrich_error_friendly() {
+        let details = serde_json::json!({
+            "tag": "TxValidationErrorInCardanoMode",
+            "contents": {
+                "kind": "ShelleyTxValidationError",
+                "error": ["ConwayCertsFailure (CertFailure (GovCertFailure (ConwayDRepNotRegistered (KeyHashObj (KeyHash {unKeyHash = \"0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735\"})))))", "ConwayTxCertGov (ConwayUnRegDRep (KeyHashObj (KeyHash {unKeyHash = \"0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735\"})) (Coin 500000000))"]
+            }
+        });
+
+        let err = enrich_error(BlockfrostError::custom_400_details(
+            "tx submission failed".to_string(),
+            details,
+        ));
+        let enriched = err.details.unwrap();
+
+        assert_eq!(enriched["friendly"].as_array().map(Vec::len), Some(1));
+        assert_eq!(enriched["friendly"][0]["role"], "drep");
+        assert_eq!(
+            enriched["friendly"][0]["hex"],
+            "0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735"
+        );
+        assert!(enriched["friendly"][0]["id"]
+            .as_str()
+            .is_some_and(|id| id.starts_with("drep1")));
+    }
+}
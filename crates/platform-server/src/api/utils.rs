@@ -0,0 +1,534 @@
+use crate::{
+    cbor::slot::SlotNo,
+    cli::Config,
+    encoding::{decode_bech32, encode_as, Bech32Kind},
+    native_script::{self, Evaluation},
+    node::{api::NodeApi, protocol_params::ProtocolParamsHistory},
+    BlockfrostError, NodePool,
+};
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Extension, Json,
+};
+use pallas::ledger::addresses::Address;
+use pallas_codec::{
+    minicbor,
+    utils::{Bytes, CborWrap, NonEmptyKeyValuePairs, PositiveCoin},
+};
+use pallas_crypto::hash::{Hash, Hasher};
+use pallas_primitives::conway::{
+    DatumOption, PlutusData, PostAlonzoTransactionOutput, ScriptRef, TransactionOutput, Value,
+};
+use pallas_traverse::{wellknown::GenesisValues, MultiEraTx};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Two hours on all well-known networks.
+const DEFAULT_TTL_OFFSET_SLOTS: u64 = 7200;
+
+/// Bytes the ledger adds to the size of every output when computing its
+/// minimum lovelace, for the UTxO entry overhead.
+const UTXO_ENTRY_OVERHEAD: u64 = 160;
+
+#[derive(Deserialize)]
+pub struct TtlQuery {
+    pub offset_slots: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TtlResponse {
+    /// The slot the node is currently at
+    pub slot: SlotNo,
+    pub offset_slots: u64,
+    /// Suggested upper bound of the transaction validity interval
+    pub invalid_hereafter: SlotNo,
+    /// UNIX time of `invalid_hereafter`
+    pub expires_at: u64,
+}
+
+/// Suggests a TTL (`invalid_hereafter`) relative to the node's current tip.
+pub async fn ttl_route(
+    Extension(node): Extension<NodePool>,
+    Extension(config): Extension<Arc<Config>>,
+    Query(query): Query<TtlQuery>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let offset_slots = query.offset_slots.unwrap_or(DEFAULT_TTL_OFFSET_SLOTS);
+
+    let mut node = node.get().await?;
+
+    Ok(Json(
+        suggest_ttl(&mut *node, &config.node.genesis, offset_slots).await?,
+    ))
+}
+
+async fn suggest_ttl(
+    node: &mut impl NodeApi,
+    genesis: &GenesisValues,
+    offset_slots: u64,
+) -> Result<TtlResponse, BlockfrostError> {
+    let slot = node.sync_progress().await?.slot;
+
+    let invalid_hereafter = slot.checked_add(offset_slots).ok_or_else(|| {
+        BlockfrostError::custom_400(format!("Invalid offset_slots: {}", offset_slots))
+    })?;
+
+    Ok(TtlResponse {
+        slot,
+        offset_slots,
+        invalid_hereafter,
+        expires_at: invalid_hereafter.to_time(genesis).ok_or_else(|| {
+            BlockfrostError::custom_400(format!("Invalid offset_slots: {}", offset_slots))
+        })?,
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SlotTimeResponse {
+    pub slot: SlotNo,
+    /// UNIX time of the beginning of `slot`
+    pub time: u64,
+}
+
+pub async fn slot_to_time_route(
+    Extension(config): Extension<Arc<Config>>,
+    Path(slot): Path<SlotNo>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let time = slot
+        .to_time(&config.node.genesis)
+        .ok_or_else(|| BlockfrostError::custom_400(format!("Invalid slot: {}", slot)))?;
+
+    Ok(Json(SlotTimeResponse { slot, time }))
+}
+
+pub async fn time_to_slot_route(
+    Extension(config): Extension<Arc<Config>>,
+    Path(time): Path<u64>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let slot = SlotNo::from_time(&config.node.genesis, time).ok_or_else(|| {
+        BlockfrostError::custom_400(format!("Timestamp {} is before the network start", time))
+    })?;
+
+    Ok(Json(SlotTimeResponse { slot, time }))
+}
+
+#[derive(Deserialize)]
+pub struct Bech32EncodeRequest {
+    pub kind: Bech32Kind,
+    pub hex: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Bech32EncodeResponse {
+    pub bech32: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Bech32DecodeRequest {
+    pub bech32: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Bech32DecodeResponse {
+    pub hrp: String,
+    pub hex: String,
+}
+
+pub async fn bech32_encode_route(
+    Json(request): Json<Bech32EncodeRequest>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let bytes =
+        hex::decode(&request.hex).map_err(|e| BlockfrostError::custom_400(e.to_string()))?;
+    let bech32 = encode_as(request.kind, &bytes).map_err(BlockfrostError::custom_400)?;
+
+    Ok(Json(Bech32EncodeResponse { bech32 }))
+}
+
+pub async fn bech32_decode_route(
+    Json(request): Json<Bech32DecodeRequest>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let (hrp, bytes) = decode_bech32(&request.bech32).map_err(BlockfrostError::custom_400)?;
+
+    Ok(Json(Bech32DecodeResponse {
+        hrp,
+        hex: hex::encode(bytes),
+    }))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MinUtxoAmount {
+    /// `lovelace`, or the policy id followed by the hex-encoded asset name
+    pub unit: String,
+    pub quantity: String,
+}
+
+/// A transaction output, shaped like in the Blockfrost API `/txs/{hash}/utxos`
+/// response, with the datum and reference script in full.
+#[derive(Serialize, Deserialize)]
+pub struct MinUtxoRequest {
+    /// Bech32 or hex
+    pub address: String,
+    pub amount: Vec<MinUtxoAmount>,
+    /// Hex-encoded datum hash
+    pub data_hash: Option<String>,
+    /// CBOR of the inline datum
+    pub inline_datum: Option<String>,
+    /// CBOR of the reference script, e.g. `[3, h'…']` for Plutus V3
+    pub reference_script: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct MinUtxoResponse {
+    /// The least lovelace the output must hold
+    pub min_lovelace: String,
+    /// Serialized size of the output once holding `min_lovelace`, or more if
+    /// requested, in bytes
+    pub output_size: u64,
+    pub coins_per_utxo_size: String,
+}
+
+/// Estimates the minimum lovelace of an output from the latest protocol
+/// parameters, so that tx builders don’t get `BabbageOutputTooSmallUTxO`.
+pub async fn min_utxo_route(
+    Extension(history): Extension<ProtocolParamsHistory>,
+    Json(request): Json<MinUtxoRequest>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let coins_per_utxo_size = history
+        .latest()
+        .and_then(|params| params.coins_per_utxo_size)
+        .and_then(|coins| coins.parse().ok())
+        .ok_or_else(|| {
+            BlockfrostError::service_unavailable(
+                "Protocol parameters have not been observed yet".to_string(),
+            )
+        })?;
+
+    Ok(Json(min_utxo(&request, coins_per_utxo_size)?))
+}
+
+/// Babbage onwards, an output needs `coinsPerUTxOByte` for each of its bytes
+/// and the entry overhead. As the lovelace is part of the output, raising it
+/// might grow the output, so we do until it’s enough.
+fn min_utxo(
+    request: &MinUtxoRequest,
+    coins_per_utxo_size: u64,
+) -> Result<MinUtxoResponse, BlockfrostError> {
+    let bad_request = |what: &str, e: &dyn std::fmt::Display| {
+        BlockfrostError::custom_400(format!("Invalid {}: {}", what, e))
+    };
+
+    let address = Address::from_bech32(&request.address)
+        .or_else(|_| Address::from_hex(&request.address))
+        .map_err(|e| bad_request("address", &e))?;
+
+    let mut lovelace = 0u64;
+    let mut assets: BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, u64>> = BTreeMap::new();
+    for amount in &request.amount {
+        let quantity: u64 = amount
+            .quantity
+            .parse()
+            .map_err(|e| bad_request("quantity", &e))?;
+
+        if amount.unit == "lovelace" {
+            lovelace = lovelace.saturating_add(quantity);
+            continue;
+        }
+
+        let unit = hex::decode(&amount.unit).map_err(|e| bad_request("unit", &e))?;
+        if unit.len() < 28 || unit.len() > 28 + 32 {
+            return Err(bad_request(
+                "unit",
+                &"expected a 28-byte policy id followed by an asset name of up to 32 bytes",
+            ));
+        }
+        let (policy_id, asset_name) = unit.split_at(28);
+        let total = assets
+            .entry(policy_id.to_vec())
+            .or_default()
+            .entry(asset_name.to_vec())
+            .or_default();
+        *total = total.saturating_add(quantity);
+    }
+
+    let multiasset = NonEmptyKeyValuePairs::from_vec(
+        assets
+            .into_iter()
+            .filter_map(|(policy_id, names)| {
+                let names = names
+                    .into_iter()
+                    .filter_map(|(name, quantity)| {
+                        Some((Bytes::from(name), PositiveCoin::try_from(quantity).ok()?))
+                    })
+                    .collect();
+                Some((
+                    policy_id.as_slice().into(),
+                    NonEmptyKeyValuePairs::from_vec(names)?,
+                ))
+            })
+            .collect(),
+    );
+
+    let datum_option = match (&request.data_hash, &request.inline_datum) {
+        (Some(_), Some(_)) => {
+            return Err(BlockfrostError::custom_400(
+                "An output has either a data_hash or an inline_datum, not both".to_string(),
+            ))
+        }
+        (Some(data_hash), None) => {
+            let hash = hex::decode(data_hash).map_err(|e| bad_request("data_hash", &e))?;
+            if hash.len() != 32 {
+                return Err(bad_request("data_hash", &"expected 32 bytes"));
+            }
+            Some(DatumOption::Hash(hash.as_slice().into()))
+        }
+        (None, Some(inline_datum)) => {
+            let cbor = hex::decode(inline_datum).map_err(|e| bad_request("inline_datum", &e))?;
+            let datum: PlutusData =
+                minicbor::decode(&cbor).map_err(|e| bad_request("inline_datum", &e))?;
+            Some(DatumOption::Data(CborWrap(datum)))
+        }
+        (None, None) => None,
+    };
+
+    let script_ref = match &request.reference_script {
+        Some(script) => {
+            let cbor = hex::decode(script).map_err(|e| bad_request("reference_script", &e))?;
+            let script: ScriptRef =
+                minicbor::decode(&cbor).map_err(|e| bad_request("reference_script", &e))?;
+            Some(CborWrap(script))
+        }
+        None => None,
+    };
+
+    let mut output = PostAlonzoTransactionOutput {
+        address: address.to_vec().into(),
+        value: Value::Coin(lovelace),
+        datum_option,
+        script_ref,
+    };
+
+    let mut coin = lovelace;
+    loop {
+        output.value = match &multiasset {
+            Some(multiasset) => Value::Multiasset(coin, multiasset.clone()),
+            None => Value::Coin(coin),
+        };
+        let output_size = minicbor::to_vec(TransactionOutput::PostAlonzo(output.clone()))
+            .map_err(|e| BlockfrostError::internal_server_error(e.to_string()))?
+            .len() as u64;
+        let min_lovelace = (UTXO_ENTRY_OVERHEAD + output_size).saturating_mul(coins_per_utxo_size);
+
+        if coin >= min_lovelace {
+            return Ok(MinUtxoResponse {
+                min_lovelace: min_lovelace.to_string(),
+                output_size,
+                coins_per_utxo_size: coins_per_utxo_size.to_string(),
+            });
+        }
+        coin = min_lovelace;
+    }
+}
+
+#[derive(Deserialize)]
+pub struct NativeScriptRequest {
+    /// cardano-cli’s JSON, or hex-encoded CBOR
+    pub script: serde_json::Value,
+    /// Key hashes, or verification keys, of whoever signs the transaction
+    #[serde(default)]
+    pub signers: Vec<String>,
+    pub invalid_before: Option<SlotNo>,
+    pub invalid_hereafter: Option<SlotNo>,
+}
+
+#[derive(Serialize)]
+pub struct NativeScriptResponse {
+    pub valid: bool,
+    pub script_hash: String,
+    /// The outcome of each sub-script, telling which branch failed
+    pub evaluation: Evaluation,
+}
+
+/// Evaluates a native script against a transaction’s validity interval and
+/// signers, e.g. to debug `ScriptWitnessNotValidatingUTXOW`.
+pub async fn native_script_route(
+    Json(request): Json<NativeScriptRequest>,
+) -> Result<impl IntoResponse, BlockfrostError> {
+    let (script, cbor) =
+        native_script::parse(&request.script).map_err(BlockfrostError::custom_400)?;
+
+    let signers = request
+        .signers
+        .iter()
+        .map(|signer| match hex::decode(signer).as_deref() {
+            Ok(key_hash) if key_hash.len() == 28 => Ok(Hash::from(key_hash)),
+            Ok(key) if key.len() == 32 => Ok(Hasher::<224>::hash(key)),
+            _ => Err(BlockfrostError::custom_400(format!(
+                "Invalid signer, expected a key hash or a verification key: {}",
+                signer
+            ))),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let evaluation = native_script::evaluate(
+        &script,
+        &native_script::Context {
+            invalid_before: request.invalid_before,
+            invalid_hereafter: request.invalid_hereafter,
+            signers,
+        },
+    );
+
+    Ok(Json(NativeScriptResponse {
+        valid: evaluation.valid,
+        script_hash: native_script::script_hash(&cbor).to_string(),
+        evaluation,
+    }))
+}
+
+/// The end of a transaction’s validity interval, after which it can neither
+/// make it into a block nor be worth submitting again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TxDeadline {
+    /// Its `invalid_hereafter`, the first slot it’s no longer valid in
+    pub slot: SlotNo,
+    /// UNIX time of `slot`
+    pub time: u64,
+}
+
+impl TxDeadline {
+    /// [`None`] for a transaction valid indefinitely, or one we can’t decode.
+    pub fn of(tx: &[u8], genesis: &GenesisValues) -> Option<Self> {
+        let slot = SlotNo(MultiEraTx::decode(tx).ok()?.ttl()?);
+
+        Some(Self {
+            slot,
+            time: slot.to_time(genesis)?,
+        })
+    }
+
+    pub fn has_passed(&self) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        now >= self.time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::{mock::MockNode, sync_progress::NodeInfo};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[test]
+    fn test_tx_deadline() {
+        use crate::node::mock::{CONWAY_TX, CONWAY_TX_EXPIRED};
+        let tx = hex::decode(CONWAY_TX_EXPIRED).unwrap();
+        let deadline = TxDeadline::of(&tx, &GenesisValues::preview()).unwrap();
+
+        assert_eq!(
+            deadline,
+            TxDeadline {
+                slot: SlotNo(100_000),
+                time: 1_666_756_000,
+            }
+        );
+        assert!(deadline.has_passed());
+        assert!(!TxDeadline {
+            slot: SlotNo(0),
+            time: u64::MAX
+        }
+        .has_passed());
+
+        let tx = hex::decode(CONWAY_TX).unwrap();
+        assert_eq!(TxDeadline::of(&tx, &GenesisValues::preview()), None);
+    }
+
+    #[tokio::test]
+    async fn test_suggest_ttl() {
+        let mut node = MockNode::new().with_node_info(NodeInfo {
+            block: String::new(),
+            epoch: 520,
+            era: "Conway".to_string(),
+            slot: SlotNo(139000000),
+            sync_progress: 100.0,
+            n2c_version: 16,
+        });
+        let genesis = GenesisValues::mainnet();
+
+        let ttl = suggest_ttl(&mut node, &genesis, 7200).await.unwrap();
+        assert_eq!(ttl.invalid_hereafter, SlotNo(139007200));
+        assert_eq!(ttl.expires_at, 1730566291 + 7200);
+
+        assert!(suggest_ttl(&mut node, &genesis, u64::MAX).await.is_err());
+    }
+
+    const ADDRESS: &str = "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x";
+    const POLICY_ID: &str = "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373";
+
+    fn output(amount: &[(&str, &str)]) -> MinUtxoRequest {
+        MinUtxoRequest {
+            address: ADDRESS.to_string(),
+            amount: amount
+                .iter()
+                .map(|(unit, quantity)| MinUtxoAmount {
+                    unit: unit.to_string(),
+                    quantity: quantity.to_string(),
+                })
+                .collect(),
+            data_hash: None,
+            inline_datum: None,
+            reference_script: None,
+        }
+    }
+
+    #[rstest]
+    #[case(output(&[]), 67, 978370)]
+    #[case(output(&[("lovelace", "2000000")]), 67, 978370)]
+    // Holding more than the minimum can only grow the output
+    #[case(output(&[("lovelace", "5000000000")]), 71, 995610)]
+    #[case(output(&[("lovelace", "0"), (&format!("{}504154415445", POLICY_ID), "1")]), 108, 1155080)]
+    #[case(MinUtxoRequest { inline_datum: Some("182a".to_string()), ..output(&[]) }, 75, 1012850)]
+    #[case(
+        MinUtxoRequest { data_hash: Some("00".repeat(32)), ..output(&[]) },
+        104,
+        1137840
+    )]
+    fn test_min_utxo(
+        #[case] request: MinUtxoRequest,
+        #[case] output_size: u64,
+        #[case] min_lovelace: u64,
+    ) {
+        assert_eq!(
+            min_utxo(&request, 4310).unwrap(),
+            MinUtxoResponse {
+                min_lovelace: min_lovelace.to_string(),
+                output_size,
+                coins_per_utxo_size: "4310".to_string(),
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(MinUtxoRequest { address: "addr1".to_string(), ..output(&[]) })]
+    #[case(output(&[("lovelace", "-1")]))]
+    #[case(output(&[(POLICY_ID, "x")]))]
+    #[case(output(&[("00", "1")]))]
+    #[case(MinUtxoRequest { inline_datum: Some("ff".to_string()), ..output(&[]) })]
+    #[case(MinUtxoRequest { data_hash: Some("00".to_string()), ..output(&[]) })]
+    #[case(MinUtxoRequest {
+        data_hash: Some("00".repeat(32)),
+        inline_datum: Some("182a".to_string()),
+        ..output(&[])
+    })]
+    fn test_min_utxo_invalid(#[case] request: MinUtxoRequest) {
+        assert_eq!(min_utxo(&request, 4310).unwrap_err().status_code, 400);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    api::{stats::Stats, tx_confirmations::ConfirmationTracker, tx_status::SubmissionStatuses},
+    icebreakers_api::{IcebreakersAPI, LoadCounter, LOAD_REPORT_INTERVAL},
+    node::{
+        chainsync::{ChainEvent, RecentChain},
+        protocol_params::ProtocolParamsHistory,
+    },
+    retry::Backoff,
+    BlockfrostError, NodePool,
+};
+use serde_json::json;
+use std::sync::Arc;
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    time::{self, Duration},
+};
+use tracing::{info, warn};
+
+/// Keeps checking that the node is reachable, i.e. that a pooled connection
+/// still answers a ping or a new one can be opened, reconnecting with
+/// exponential backoff when the socket disappears, e.g. on a node restart.
+pub async fn node_supervisor_task(node: NodePool) {
+    loop {
+        let delay = match node.get().await {
+            Ok(_) => {
+                if !node.health().report().connected {
+                    info!("Connected to the node");
+                }
+                node.health().record_connected();
+                Duration::from_secs(10)
+            }
+            Err(e) => {
+                // The error itself is already logged by the node pool
+                let delay = node.health().record_failure(e.to_string());
+                warn!("Node unreachable, reconnecting in {:?}", delay);
+                delay
+            }
+        };
+
+        time::sleep(delay).await;
+    }
+}
+
+/// Closes idle node connections before the node half-closes them, so that the
+/// first request after a quiet period doesn’t get a dead one, and those past
+/// their maximum age.
+pub async fn node_reaper_task(node: NodePool) {
+    let period = (node.idle_timeout() / 2).max(Duration::from_secs(1));
+
+    loop {
+        time::sleep(period).await;
+
+        let reaped = node.reap_stale();
+        if reaped > 0 {
+            info!("Closed {} idle or aged node connection(s)", reaped);
+        }
+    }
+}
+
+/// Reports the load to the Icebreakers API every [`LOAD_REPORT_INTERVAL`],
+/// unless opted out of with `--no-telemetry`.
+pub async fn load_report_task(api: Arc<IcebreakersAPI>, stats: Arc<Stats>, node: NodePool) {
+    let mut counter = LoadCounter::default();
+
+    loop {
+        time::sleep(LOAD_REPORT_INTERVAL).await;
+
+        let sync_progress = match node.get().await {
+            Ok(mut node) => node.sync_progress().await.ok(),
+            Err(_) => None,
+        };
+        let report = counter.report(&stats.snapshot(), sync_progress.map(|i| i.sync_progress));
+
+        if let Err(e) = api.report_load(&report).await {
+            warn!("Failed to report the load: {}", e);
+        }
+    }
+}
+
+/// Follows the accepted transactions through the node’s mempool, until
+/// they’re in a block or evicted.
+pub async fn mempool_tracker_task(node: NodePool, statuses: SubmissionStatuses) {
+    loop {
+        time::sleep(Duration::from_secs(10)).await;
+
+        let result = match node.get().await {
+            Ok(mut node) => statuses.settle(&mut *node).await,
+            Err(e) => Err(e.into()),
+        };
+
+        if let Err(e) = result {
+            warn!("Mempool tracker: failed to query the node: {}", e);
+        }
+    }
+}
+
+/// Follows the accepted transactions onto the chain, and how deep they get,
+/// whenever a block is added or rolled back.
+pub async fn confirmation_tracker_task(
+    node: NodePool,
+    tracker: ConfirmationTracker,
+    chain: RecentChain,
+) {
+    let mut events = chain.subscribe();
+
+    loop {
+        if !next_chain_change(&mut events, |event| {
+            matches!(event, ChainEvent::NewBlock(_) | ChainEvent::Rollback(_))
+        })
+        .await
+        {
+            return;
+        }
+        // While catching up, only the newest block matters
+        events = events.resubscribe();
+
+        let result = match node.get().await {
+            Ok(mut node) => tracker.observe(&mut *node).await,
+            Err(e) => Err(e.into()),
+        };
+
+        if let Err(e) = result {
+            warn!("Confirmation tracker: failed to query the node: {}", e);
+        }
+    }
+}
+
+/// Retrying the epoch webhook until well before the next one is due.
+const WEBHOOK_BACKOFF: Backoff = Backoff::new(
+    "epoch_webhook",
+    Duration::from_secs(1),
+    Duration::from_secs(30),
+)
+.with_max_elapsed(Duration::from_secs(300));
+
+/// Records the protocol parameters of the current epoch into `history`, and
+/// again at every epoch boundary `chain` sees. If `webhook_url` is set, a JSON
+/// notification is POSTed to it whenever an epoch boundary is crossed,
+/// together with the protocol parameters that changed with the new epoch.
+pub async fn epoch_tracker_task(
+    node: NodePool,
+    history: ProtocolParamsHistory,
+    webhook_url: Option<String>,
+    chain: RecentChain,
+) {
+    let client = reqwest::Client::new();
+    let mut events = chain.subscribe();
+
+    loop {
+        match check_epoch_boundary(&node, &history).await {
+            Ok(Some(payload)) => {
+                if let Some(webhook_url) = &webhook_url {
+                    info!("Epoch boundary crossed, notifying {}", webhook_url);
+
+                    let result = WEBHOOK_BACKOFF
+                        .retry(
+                            || async {
+                                client
+                                    .post(webhook_url)
+                                    .json(&payload)
+                                    .send()
+                                    .await
+                                    .and_then(|response| response.error_for_status())
+                            },
+                            |e: &reqwest::Error| {
+                                e.is_connect()
+                                    || e.is_timeout()
+                                    || e.status().is_some_and(|status| status.is_server_error())
+                            },
+                        )
+                        .await;
+
+                    if let Err(e) = result {
+                        warn!("Failed to deliver the epoch webhook: {}", e);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!("Epoch tracker: failed to query the node: {}", e);
+                time::sleep(Duration::from_secs(20)).await;
+                continue;
+            }
+        }
+
+        if !next_chain_change(&mut events, |event| {
+            matches!(event, ChainEvent::EpochBoundary { .. })
+        })
+        .await
+        {
+            return;
+        }
+    }
+}
+
+/// Waits for an event of `chain` that `is_relevant`, or for some to have been
+/// missed, which might have been. [`false`] once there are no more.
+async fn next_chain_change(
+    events: &mut broadcast::Receiver<ChainEvent>,
+    is_relevant: impl Fn(&ChainEvent) -> bool,
+) -> bool {
+    loop {
+        match events.recv().await {
+            Ok(event) if is_relevant(&event) => return true,
+            Ok(_) => {}
+            Err(RecvError::Lagged(_)) => return true,
+            Err(RecvError::Closed) => return false,
+        }
+    }
+}
+
+/// Returns the webhook payload if the epoch changed since the last call.
+async fn check_epoch_boundary(
+    node: &NodePool,
+    history: &ProtocolParamsHistory,
+) -> Result<Option<serde_json::Value>, BlockfrostError> {
+    let mut node = node.get().await?;
+    let node_info = node.sync_progress().await?;
+
+    if history.latest_epoch() == Some(node_info.epoch) {
+        return Ok(None);
+    }
+
+    let params = node.protocol_params().await?;
+    let previous = history.record(node_info.epoch, params.clone());
+
+    Ok(previous.map(|(previous_epoch, previous_params)| {
+        json!({
+            "event": "epoch_boundary",
+            "epoch": node_info.epoch,
+            "previous_epoch": previous_epoch,
+            "era": node_info.era,
+            "slot": node_info.slot,
+            "block": node_info.block,
+            "protocol_parameters_changes": previous_params.diff(&params),
+        })
+    }))
+}
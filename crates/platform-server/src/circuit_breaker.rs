@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+/// State of a `CircuitBreaker`, as reported in health responses.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    /// Calls go through.
+    Closed,
+    /// Too many consecutive failures, calls are rejected until the cooldown passes.
+    Open,
+    /// The cooldown passed, the next call decides whether to close again.
+    HalfOpen,
+}
+
+/// Stops calling a failing upstream for a while, so that callers fail fast
+/// instead of piling up behind timeouts.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: &'static str,
+    failure_threshold: u32,
+    cooldown: Duration,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: &'static str, failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            name,
+            failure_threshold,
+            cooldown,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    pub fn state(&self) -> BreakerState {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        match inner.opened_at {
+            None => BreakerState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.cooldown => BreakerState::Open,
+            Some(_) => BreakerState::HalfOpen,
+        }
+    }
+
+    /// Whether a call to the upstream should be attempted now.
+    pub fn allow(&self) -> bool {
+        self.state() != BreakerState::Open
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+    }
+
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        inner.consecutive_failures += 1;
+
+        // A failure while half-open re-opens the breaker right away
+        if inner.consecutive_failures >= self.failure_threshold || inner.opened_at.is_some() {
+            if inner.opened_at.is_none() {
+                warn!(
+                    "{}: {} consecutive failures, opening the circuit breaker for {:?}",
+                    self.name, inner.consecutive_failures, self.cooldown
+                );
+            }
+
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_circuit_breaker_transitions() {
+        let breaker = CircuitBreaker::new("test", 2, Duration::from_millis(20));
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert!(!breaker.allow());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Open);
+
+        std::thread::sleep(Duration::from_millis(30));
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+}
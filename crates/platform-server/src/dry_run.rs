@@ -0,0 +1,237 @@
+use crate::{
+    cbor::fallback_decoder::FallbackDecoder,
+    cli::Config,
+    icebreakers_api::IcebreakersAPI,
+    node::timeouts::{NodeOperation, NodeTimeouts},
+    server::tls,
+    storage::DataDir,
+    AppError,
+};
+use pallas_network::facades::{Error as FacadeError, NodeClient as NodeClientFacade};
+use std::{fmt, path::Path};
+
+/// What `--dry-run` found, one line per check, for a deployment pipeline to
+/// act on before the instance is started for real.
+#[derive(Default)]
+pub struct Report {
+    checks: Vec<Check>,
+}
+
+struct Check {
+    name: &'static str,
+    outcome: Result<String, String>,
+}
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.outcome.is_ok())
+    }
+
+    fn push(&mut self, name: &'static str, outcome: Result<String, AppError>) {
+        self.checks.push(Check {
+            name,
+            outcome: outcome.map_err(|e| e.to_string()),
+        });
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            let (status, detail) = match &check.outcome {
+                Ok(detail) => ("ok", detail),
+                Err(detail) => ("FAIL", detail),
+            };
+            writeln!(f, "{:<6}{:<20}{}", status, check.name, detail)?;
+        }
+
+        write!(
+            f,
+            "{}",
+            if self.passed() {
+                "All checks passed"
+            } else {
+                "Some checks failed"
+            }
+        )
+    }
+}
+
+/// Checks everything the platform needs to start with `config`, without
+/// serving anything or registering with the Icebreakers API.
+pub async fn run(config: &Config) -> Report {
+    let mut report = Report::default();
+
+    report.push(
+        "configuration",
+        Ok(format!(
+            "{:?} in {} mode",
+            config.node.network, config.server.mode
+        )),
+    );
+    report.push("listen address", check_listen(config).await);
+    report.push("tls certificate", check_tls(config).await);
+    report.push("data directory", check_data_dir(config));
+    report.push(
+        "fallback decoder",
+        FallbackDecoder::locate_child_binary()
+            .map(|path| path.display().to_string())
+            .map_err(AppError::Server),
+    );
+    if config.node.socket_path.is_empty() {
+        report.push(
+            "node socket",
+            Ok("none, submitting through the relay only".to_string()),
+        );
+    } else {
+        report.push("node socket", check_socket(&config.node.socket_path));
+        report.push("node handshake", check_handshake(config).await);
+    }
+    report.push(
+        "icebreakers",
+        match &config.icebreakers {
+            Some(icebreakers) => IcebreakersAPI::check(icebreakers, &config.node.network).await,
+            None => Ok("solitary mode, nothing to check".to_string()),
+        },
+    );
+
+    report
+}
+
+async fn check_listen(config: &Config) -> Result<String, AppError> {
+    let address = format!("{}:{}", config.server.address, config.server.port);
+    tokio::net::TcpListener::bind(&address)
+        .await
+        .map_err(|e| AppError::Server(format!("Cannot listen on {}: {}", address, e)))?;
+
+    Ok(address)
+}
+
+async fn check_tls(config: &Config) -> Result<String, AppError> {
+    match &config.tls {
+        Some(tls_config) => {
+            tls::load(tls_config).await?;
+            Ok(tls_config.cert.display().to_string())
+        }
+        None => Ok("none, serving plain HTTP".to_string()),
+    }
+}
+
+fn check_data_dir(config: &Config) -> Result<String, AppError> {
+    match &config.server.data_dir {
+        Some(path) => {
+            DataDir::new(path, config.limits.data_dir_quota_mb * 1024 * 1024)?;
+            Ok(path.display().to_string())
+        }
+        None => Ok("none, nothing kept on disk".to_string()),
+    }
+}
+
+fn check_socket(socket_path: &str) -> Result<String, AppError> {
+    let metadata = Path::new(socket_path)
+        .metadata()
+        .map_err(|e| AppError::Node(format!("{}: {}", socket_path, e)))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+
+        if !metadata.file_type().is_socket() {
+            return Err(AppError::Node(format!("{} is not a socket", socket_path)));
+        }
+    }
+    #[cfg(windows)]
+    let _ = metadata;
+
+    Ok(socket_path.to_string())
+}
+
+/// A node of another network refuses the handshake, so its acceptance also
+/// verifies the network magic.
+async fn check_handshake(config: &Config) -> Result<String, AppError> {
+    let network_magic = config.node.network_magic();
+    let client = NodeTimeouts::from_config(&config.node)
+        .run(
+            NodeOperation::Connect,
+            NodeClientFacade::connect(&config.node.socket_path, network_magic),
+        )
+        .await
+        .map_err(AppError::NodeTimeout)?
+        .map_err(|e| match e {
+            FacadeError::IncompatibleVersion => AppError::Node(format!(
+                "Handshake refused, is the node on {:?} (network magic {})?",
+                config.node.network, network_magic
+            )),
+            e => AppError::from(e),
+        })?;
+
+    client.abort().await;
+
+    Ok(format!("accepted with network magic {}", network_magic))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::TlsConfig;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_report() {
+        let mut report = Report::default();
+        report.push("first", Ok("fine".to_string()));
+        assert!(report.passed());
+
+        report.push("second", Err(AppError::Node("gone".to_string())));
+        assert!(!report.passed());
+        assert_eq!(
+            report.to_string(),
+            "ok    first               fine\n\
+             FAIL  second              Node connection error: gone\n\
+             Some checks failed"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_check_socket() {
+        let dir = std::env::temp_dir().join(format!("bf-dry-run-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket = dir.join("node.socket");
+        let file = dir.join("node.file");
+        let _listener = tokio::net::UnixListener::bind(&socket).unwrap();
+        std::fs::write(&file, b"").unwrap();
+
+        assert!(check_socket(socket.to_str().unwrap()).is_ok());
+        assert!(check_socket(file.to_str().unwrap()).is_err());
+        assert!(check_socket(dir.join("missing").to_str().unwrap()).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_tls() {
+        let dir = std::env::temp_dir().join(format!("bf-dry-run-tls-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let not_pem = dir.join("not.pem");
+        std::fs::write(&not_pem, b"not a certificate").unwrap();
+
+        let missing = TlsConfig {
+            cert: dir.join("missing-cert.pem"),
+            key: dir.join("missing-key.pem"),
+        };
+        assert!(tls::load(&missing)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to load --tls-cert"));
+
+        let garbage = TlsConfig {
+            cert: not_pem.clone(),
+            key: not_pem,
+        };
+        assert!(tls::load(&garbage).await.is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
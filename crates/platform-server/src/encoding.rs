@@ -0,0 +1,422 @@
+use bech32::{FromBase32, ToBase32, Variant};
+use pallas::ledger::addresses::Address;
+use pallas_crypto::hash::Hasher;
+use serde::{Deserialize, Serialize};
+
+/// What kind of entity a bech32 string represents. Decides the human-readable
+/// part, and how the payload is validated.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Bech32Kind {
+    /// A Shelley address; the human-readable part follows from its header.
+    Address,
+    /// A stake pool id (`pool1…`), from its 28-byte cold key hash.
+    Pool,
+    /// A CIP-129 DRep id (`drep1…`), from a 28-byte key hash.
+    Drep,
+    /// A CIP-129 DRep id (`drep1…`), from a 28-byte script hash.
+    DrepScript,
+    /// A CIP-129 constitutional committee cold id (`cc_cold1…`), from a
+    /// 28-byte key hash.
+    CommitteeCold,
+    /// Likewise, from a 28-byte script hash.
+    CommitteeColdScript,
+    /// A CIP-129 constitutional committee hot id (`cc_hot1…`), from a
+    /// 28-byte key hash.
+    CommitteeHot,
+    /// Likewise, from a 28-byte script hash.
+    CommitteeHotScript,
+    /// A CIP-14 asset fingerprint (`asset1…`), from a unit, i.e. the policy id
+    /// followed by the asset name.
+    Asset,
+}
+
+/// CIP-129 header bytes for governance credentials.
+const CIP129_CC_HOT_KEY_HASH: u8 = 0x02;
+const CIP129_CC_HOT_SCRIPT_HASH: u8 = 0x03;
+const CIP129_CC_COLD_KEY_HASH: u8 = 0x12;
+const CIP129_CC_COLD_SCRIPT_HASH: u8 = 0x13;
+const CIP129_DREP_KEY_HASH: u8 = 0x22;
+const CIP129_DREP_SCRIPT_HASH: u8 = 0x23;
+
+const HASH_28_LEN: usize = 28;
+
+pub fn encode_bech32(hrp: &str, bytes: &[u8]) -> Result<String, String> {
+    bech32::encode(hrp, bytes.to_base32(), Variant::Bech32).map_err(|e| e.to_string())
+}
+
+/// Returns the human-readable part and the payload of a bech32 string.
+pub fn decode_bech32(input: &str) -> Result<(String, Vec<u8>), String> {
+    let (hrp, data, _variant) = bech32::decode(input).map_err(|e| e.to_string())?;
+    let bytes = Vec::<u8>::from_base32(&data).map_err(|e| e.to_string())?;
+
+    Ok((hrp, bytes))
+}
+
+/// Encodes `bytes` according to `kind`.
+pub fn encode_as(kind: Bech32Kind, bytes: &[u8]) -> Result<String, String> {
+    match kind {
+        Bech32Kind::Address => Address::from_bytes(bytes)
+            .and_then(|address| address.to_bech32())
+            .map_err(|e| format!("Invalid address: {}", e)),
+        Bech32Kind::Pool => {
+            expect_hash_28(bytes)?;
+            encode_bech32("pool", bytes)
+        }
+        Bech32Kind::Drep
+        | Bech32Kind::DrepScript
+        | Bech32Kind::CommitteeCold
+        | Bech32Kind::CommitteeColdScript
+        | Bech32Kind::CommitteeHot
+        | Bech32Kind::CommitteeHotScript => {
+            expect_hash_28(bytes)?;
+            let (hrp, header) = match kind {
+                Bech32Kind::Drep => ("drep", CIP129_DREP_KEY_HASH),
+                Bech32Kind::DrepScript => ("drep", CIP129_DREP_SCRIPT_HASH),
+                Bech32Kind::CommitteeCold => ("cc_cold", CIP129_CC_COLD_KEY_HASH),
+                Bech32Kind::CommitteeColdScript => ("cc_cold", CIP129_CC_COLD_SCRIPT_HASH),
+                Bech32Kind::CommitteeHot => ("cc_hot", CIP129_CC_HOT_KEY_HASH),
+                _ => ("cc_hot", CIP129_CC_HOT_SCRIPT_HASH),
+            };
+            encode_bech32(hrp, &[&[header], bytes].concat())
+        }
+        Bech32Kind::Asset => {
+            if bytes.len() < HASH_28_LEN {
+                return Err("An asset unit must start with a 28-byte policy id".to_string());
+            }
+            let (policy_id, asset_name) = bytes.split_at(HASH_28_LEN);
+            Ok(asset_fingerprint(policy_id, asset_name))
+        }
+    }
+}
+
+/// An asset, described the way the Blockfrost API does it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AssetUnit {
+    /// Concatenation of the policy id and the hex-encoded asset name
+    pub unit: String,
+    pub policy_id: String,
+    /// Hex-encoded asset name
+    pub asset_name: String,
+    /// The asset name, if it’s printable UTF-8
+    pub asset_name_utf8: Option<String>,
+    /// CIP-14 asset fingerprint
+    pub fingerprint: String,
+}
+
+impl AssetUnit {
+    pub fn new(policy_id: &[u8], asset_name: &[u8]) -> Self {
+        let asset_name_utf8 = std::str::from_utf8(asset_name)
+            .ok()
+            .filter(|name| !name.is_empty() && !name.chars().any(char::is_control))
+            .map(str::to_string);
+
+        Self {
+            unit: hex::encode([policy_id, asset_name].concat()),
+            policy_id: hex::encode(policy_id),
+            asset_name: hex::encode(asset_name),
+            asset_name_utf8,
+            fingerprint: asset_fingerprint(policy_id, asset_name),
+        }
+    }
+}
+
+/// Finds all assets in a Haskell `show`n `MultiAsset`, as it appears in the
+/// cardano-submit-api error messages, e.g.
+/// `(PolicyID {policyID = ScriptHash "<hex>"},fromList [("<hex name>",1)])`.
+pub fn find_shown_assets(text: &str) -> Vec<AssetUnit> {
+    const POLICY_PREFIX: &str = "PolicyID {policyID = ScriptHash \"";
+    const ASSETS_PREFIX: &str = "\"},fromList [";
+
+    let mut assets = vec![];
+    let mut rest = text;
+
+    while let Some(start) = rest.find(POLICY_PREFIX) {
+        rest = &rest[start + POLICY_PREFIX.len()..];
+
+        let Some(policy_end) = rest.find(ASSETS_PREFIX) else {
+            break;
+        };
+        let Ok(policy_id) = hex::decode(&rest[..policy_end]) else {
+            continue;
+        };
+        rest = &rest[policy_end + ASSETS_PREFIX.len()..];
+
+        let list_end = rest.find(']').unwrap_or(rest.len());
+
+        // Entries look like `("<hex name>",<quantity>)`, separated by commas.
+        for entry in rest[..list_end].split("(\"").skip(1) {
+            if let Some(name_end) = entry.find('"') {
+                if let Ok(asset_name) = hex::decode(&entry[..name_end]) {
+                    assets.push(AssetUnit::new(&policy_id, &asset_name));
+                }
+            }
+        }
+
+        rest = &rest[list_end..];
+    }
+
+    assets.sort();
+    assets.dedup();
+    assets
+}
+
+/// What a credential shown in an error is, as its context tells.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialRole {
+    Drep,
+    CommitteeCold,
+    CommitteeHot,
+    Pool,
+}
+
+/// A key or script hash shown in an error, with its bech32 id.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FriendlyId {
+    pub role: CredentialRole,
+    pub hex: String,
+    pub id: String,
+}
+
+/// Where the `Show` output of cardano-ledger has governance credentials and
+/// pool ids, and in what role, in order.
+const SHOWN_CREDENTIALS: &[(&str, &[CredentialRole])] = {
+    use CredentialRole::*;
+
+    &[
+        ("DRepCredential (", &[Drep]),
+        ("ConwayRegDRep (", &[Drep]),
+        ("ConwayUnRegDRep (", &[Drep]),
+        ("ConwayUpdateDRep (", &[Drep]),
+        (
+            "ConwayAuthCommitteeHotKey (",
+            &[CommitteeCold, CommitteeHot],
+        ),
+        ("ConwayResignCommitteeColdKey (", &[CommitteeCold]),
+        ("DelegStake (", &[Pool]),
+        ("DelegStakeVote (", &[Pool]),
+        ("RetirePool (", &[Pool]),
+        ("DelegateeNotRegisteredDELEG (", &[Pool]),
+        ("ppId = ", &[Pool]),
+    ]
+};
+
+/// Finds the DRep, committee, and pool credentials shown in a ledger error
+/// (e.g. in a rejected certificate), with their CIP-129 and CIP-5 ids.
+pub fn find_shown_ids(text: &str) -> Vec<FriendlyId> {
+    let mut ids = vec![];
+
+    for (marker, roles) in SHOWN_CREDENTIALS {
+        for (start, _) in text.match_indices(marker) {
+            let mut rest = &text[start + marker.len()..];
+
+            for (i, role) in roles.iter().enumerate() {
+                if i > 0 {
+                    match rest.find(") (") {
+                        Some(next) => rest = &rest[next + 3..],
+                        None => break,
+                    }
+                }
+
+                let Some((is_script, hash)) = parse_shown_credential(rest, *role) else {
+                    break;
+                };
+                let kind = match (role, is_script) {
+                    (CredentialRole::Drep, false) => Bech32Kind::Drep,
+                    (CredentialRole::Drep, true) => Bech32Kind::DrepScript,
+                    (CredentialRole::CommitteeCold, false) => Bech32Kind::CommitteeCold,
+                    (CredentialRole::CommitteeCold, true) => Bech32Kind::CommitteeColdScript,
+                    (CredentialRole::CommitteeHot, false) => Bech32Kind::CommitteeHot,
+                    (CredentialRole::CommitteeHot, true) => Bech32Kind::CommitteeHotScript,
+                    (CredentialRole::Pool, _) => Bech32Kind::Pool,
+                };
+
+                if let Ok(bytes) = hex::decode(hash) {
+                    if let Ok(id) = encode_as(kind, &bytes) {
+                        ids.push(FriendlyId {
+                            role: *role,
+                            hex: hash.to_string(),
+                            id,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+/// Takes `KeyHashObj (KeyHash {unKeyHash = "…"})` or `ScriptHashObj
+/// (ScriptHash "…")`, or for pools, a bare `KeyHash {unKeyHash = "…"}` or hex.
+fn parse_shown_credential(text: &str, role: CredentialRole) -> Option<(bool, &str)> {
+    const KEY_HASH: &str = "KeyHash {unKeyHash = \"";
+    const SCRIPT_HASH: &str = "ScriptHashObj (ScriptHash \"";
+
+    fn quoted<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+        let rest = text.strip_prefix(prefix)?;
+        rest.find('"').map(|end| &rest[..end])
+    }
+
+    if role == CredentialRole::Pool {
+        return quoted(text, KEY_HASH)
+            .or_else(|| {
+                let end = text
+                    .find(|c: char| !c.is_ascii_hexdigit())
+                    .unwrap_or(text.len());
+                (end == HASH_28_LEN * 2).then(|| &text[..end])
+            })
+            .map(|hash| (false, hash));
+    }
+
+    match text.strip_prefix("KeyHashObj (") {
+        Some(rest) => quoted(rest, KEY_HASH).map(|hash| (false, hash)),
+        None => quoted(text, SCRIPT_HASH).map(|hash| (true, hash)),
+    }
+}
+
+/// CIP-14 asset fingerprint.
+pub fn asset_fingerprint(policy_id: &[u8], asset_name: &[u8]) -> String {
+    let hash = Hasher::<160>::hash(&[policy_id, asset_name].concat());
+
+    // Can’t fail: the human-readable part is valid, and the payload is short.
+    encode_bech32("asset", hash.as_ref()).unwrap_or_default()
+}
+
+fn expect_hash_28(bytes: &[u8]) -> Result<(), String> {
+    if bytes.len() == HASH_28_LEN {
+        Ok(())
+    } else {
+        Err(format!(
+            "Expected a {}-byte hash, got {} bytes",
+            HASH_28_LEN,
+            bytes.len()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    // Test vectors from CIP-14
+    #[rstest]
+    #[case(
+        "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+        "",
+        "asset1rjklcrnsdzqp65wjgrg55sy9723kw09mlgvlc3"
+    )]
+    #[case(
+        "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+        "504154415445",
+        "asset13n25uv0yaf5kus35fm2k86cqy60z58d9xmde92"
+    )]
+    #[case(
+        "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209",
+        "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+        "asset1aqrdypg669jgazruv5ah07nuyqe0wxjhe2el6f"
+    )]
+    fn test_asset_fingerprint(
+        #[case] policy_id: &str,
+        #[case] asset_name: &str,
+        #[case] expected: &str,
+    ) {
+        let policy_id = hex::decode(policy_id).unwrap();
+        let asset_name = hex::decode(asset_name).unwrap();
+
+        assert_eq!(asset_fingerprint(&policy_id, &asset_name), expected);
+        assert_eq!(
+            encode_as(Bech32Kind::Asset, &[policy_id, asset_name].concat()),
+            Ok(expected.to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_shown_assets() {
+        let error = r#"ConwayUtxowFailure (UtxoFailure (ValueNotConservedUTxO (MaryValue (Coin 9498687280) (MultiAsset (fromList [(PolicyID {policyID = ScriptHash "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373"},fromList [("",1),("504154415445",2)])]))) (MaryValue (Coin 9994617117) (MultiAsset (fromList [])))))"#;
+
+        let assets = find_shown_assets(error);
+
+        assert_eq!(assets.len(), 2);
+        assert_eq!(
+            assets[1],
+            AssetUnit {
+                unit: "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373504154415445"
+                    .to_string(),
+                policy_id: "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373".to_string(),
+                asset_name: "504154415445".to_string(),
+                asset_name_utf8: Some("PATATE".to_string()),
+                fingerprint: "asset13n25uv0yaf5kus35fm2k86cqy60z58d9xmde92".to_string(),
+            }
+        );
+        assert_eq!(assets[0].asset_name_utf8, None);
+        assert!(find_shown_assets("MultiAsset (fromList [])").is_empty());
+    }
+
+    #[test]
+    fn test_pool_id_round_trip() {
+        let hash = hex::decode("0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735").unwrap();
+        let pool_id = encode_as(Bech32Kind::Pool, &hash).unwrap();
+
+        assert_eq!(
+            pool_id,
+            "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy"
+        );
+        assert_eq!(decode_bech32(&pool_id), Ok(("pool".to_string(), hash)));
+    }
+
+    #[rstest]
+    #[case(Bech32Kind::Drep, "drep", 0x22)]
+    #[case(Bech32Kind::CommitteeColdScript, "cc_cold", 0x13)]
+    #[case(Bech32Kind::CommitteeHot, "cc_hot", 0x02)]
+    fn test_cip129_ids(#[case] kind: Bech32Kind, #[case] hrp: &str, #[case] header: u8) {
+        let id = encode_as(kind, &[0x11; 28]).unwrap();
+
+        assert_eq!(
+            decode_bech32(&id),
+            Ok((hrp.to_string(), [&[header][..], &[0x11; 28]].concat()))
+        );
+    }
+
+    #[test]
+    fn test_find_shown_ids() {
+        let cold = "11".repeat(28);
+        let hot = "22".repeat(28);
+        let pool = "0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735";
+        let error = format!(
+            r#"ConwayCertsFailure (CertFailure (GovCertFailure (ConwayCommitteeIsUnknown (ScriptHashObj (ScriptHash "{cold}"))))) ConwayTxCertGov (ConwayAuthCommitteeHotKey (ScriptHashObj (ScriptHash "{cold}")) (KeyHashObj (KeyHash {{unKeyHash = "{hot}"}}))) DelegateeNotRegisteredDELEG ({pool})"#
+        );
+
+        let ids = find_shown_ids(&error);
+
+        assert_eq!(
+            ids.iter().map(|id| id.role).collect::<Vec<_>>(),
+            vec![
+                CredentialRole::CommitteeCold,
+                CredentialRole::CommitteeHot,
+                CredentialRole::Pool
+            ]
+        );
+        assert!(ids[0].id.starts_with("cc_cold1"));
+        assert_eq!(ids[0].hex, cold);
+        assert!(ids[1].id.starts_with("cc_hot1"));
+        assert_eq!(
+            ids[2].id,
+            "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy"
+        );
+        assert!(find_shown_ids("DRepAlwaysAbstain").is_empty());
+    }
+
+    #[test]
+    fn test_encode_rejects_wrong_lengths() {
+        assert!(encode_as(Bech32Kind::Pool, &[0; 27]).is_err());
+        assert!(encode_as(Bech32Kind::Drep, &[0; 29]).is_err());
+        assert!(encode_as(Bech32Kind::Asset, &[0; 10]).is_err());
+        assert!(encode_as(Bech32Kind::Address, &[0xff; 10]).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::BlockfrostError;
+use std::sync::Arc;
+
+/// Lets crates embedding the platform act on every transaction submitted
+/// through `/tx/submit`, e.g. for their own accounting or policy, without
+/// patching the handler. Register hooks with [`crate::server::build_with_hooks`].
+pub trait SubmissionHook: Send + Sync {
+    /// Runs before the transaction reaches the node. Returning an error
+    /// rejects the submission with it, and skips the remaining hooks.
+    fn pre_submit(&self, _tx: &SubmittedTx) -> Result<(), BlockfrostError> {
+        Ok(())
+    }
+
+    /// Runs once the node has decided, or the submission failed, also when an
+    /// identical submission meanwhile was only sent to the node once.
+    fn post_submit(&self, _tx: &SubmittedTx, _outcome: &SubmissionOutcome) {}
+}
+
+impl<T: SubmissionHook + ?Sized> SubmissionHook for Arc<T> {
+    fn pre_submit(&self, tx: &SubmittedTx) -> Result<(), BlockfrostError> {
+        (**self).pre_submit(tx)
+    }
+
+    fn post_submit(&self, tx: &SubmittedTx, outcome: &SubmissionOutcome) {
+        (**self).post_submit(tx, outcome)
+    }
+}
+
+/// The transaction being submitted.
+pub struct SubmittedTx<'a> {
+    pub cbor: &'a [u8],
+    /// The stake address that signed the submission, with
+    /// `--verify-signatures`. Not kept for queued or spooled submissions, so
+    /// always [`None`] in their `post_submit`.
+    pub signer: Option<&'a str>,
+}
+
+pub enum SubmissionOutcome<'a> {
+    /// Accepted by the node, with the transaction id.
+    Accepted(&'a str),
+    /// Rejected, or not submitted at all. The `details` of a rejection by the
+    /// node hold its decoded reason.
+    Rejected(&'a BlockfrostError),
+}
+
+impl<'a> SubmissionOutcome<'a> {
+    pub fn of(result: &'a Result<String, BlockfrostError>) -> Self {
+        match result {
+            Ok(txid) => Self::Accepted(txid),
+            Err(err) => Self::Rejected(err),
+        }
+    }
+}
+
+/// The hooks registered on the server, run in registration order.
+#[derive(Clone, Default)]
+pub struct SubmissionHooks(Vec<Arc<dyn SubmissionHook>>);
+
+impl SubmissionHooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, hook: impl SubmissionHook + 'static) -> Self {
+        self.0.push(Arc::new(hook));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn pre_submit(&self, tx: &SubmittedTx) -> Result<(), BlockfrostError> {
+        self.0.iter().try_for_each(|hook| hook.pre_submit(tx))
+    }
+
+    pub fn post_submit(&self, tx: &SubmittedTx, outcome: &SubmissionOutcome) {
+        self.0.iter().for_each(|hook| hook.post_submit(tx, outcome));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Mutex;
+
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        reject: bool,
+    }
+
+    impl SubmissionHook for Recorder {
+        fn pre_submit(&self, tx: &SubmittedTx) -> Result<(), BlockfrostError> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} pre {}", self.name, hex::encode(tx.cbor)));
+
+            if self.reject {
+                Err(BlockfrostError::custom_400("policy".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+
+        fn post_submit(&self, _tx: &SubmittedTx, outcome: &SubmissionOutcome) {
+            let outcome = match outcome {
+                SubmissionOutcome::Accepted(txid) => format!("accepted {}", txid),
+                SubmissionOutcome::Rejected(err) => format!("rejected {}", err.message),
+            };
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} post {}", self.name, outcome));
+        }
+    }
+
+    #[test]
+    fn test_submission_hooks() {
+        let log = Arc::new(Mutex::new(vec![]));
+        let recorder = |name, reject| Recorder {
+            name,
+            log: log.clone(),
+            reject,
+        };
+        let tx = SubmittedTx {
+            cbor: &[0x84],
+            signer: None,
+        };
+
+        let hooks = SubmissionHooks::new()
+            .with(recorder("a", false))
+            .with(recorder("b", false));
+        hooks.pre_submit(&tx).unwrap();
+        hooks.post_submit(&tx, &SubmissionOutcome::Accepted("abcd"));
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "a pre 84",
+                "b pre 84",
+                "a post accepted abcd",
+                "b post accepted abcd"
+            ]
+        );
+
+        log.lock().unwrap().clear();
+        let hooks = SubmissionHooks::new()
+            .with(recorder("a", true))
+            .with(recorder("b", false));
+
+        assert_eq!(hooks.pre_submit(&tx).unwrap_err().message, "policy");
+        assert_eq!(*log.lock().unwrap(), vec!["a pre 84"]);
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{
+    api::stats::StatsResponse,
+    circuit_breaker::{BreakerState, CircuitBreaker},
+    cli::{Config, IcebreakersConfig, Network},
+    errors::AppError,
+    retry::Backoff,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+use tracing::{info, warn};
+
+/// No single call to the Icebreakers API may take longer than this.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Consecutive failures after which we stop calling the Icebreakers API for `BREAKER_COOLDOWN`.
+const BREAKER_FAILURE_THRESHOLD: u32 = 3;
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Registering at startup is retried until the circuit breaker would open.
+const REGISTER_BACKOFF: Backoff = Backoff::new(
+    "icebreakers",
+    Duration::from_secs(1),
+    Duration::from_secs(10),
+)
+.with_max_retries(BREAKER_FAILURE_THRESHOLD - 1);
+
+/// How often the load is reported to the Icebreakers API.
+pub const LOAD_REPORT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug)]
+pub struct IcebreakersAPI {
+    client: Client,
+    base_url: String,
+    secret: String,
+    mode: String,
+    port: u16,
+    reward_address: String,
+    breaker: CircuitBreaker,
+    pub api_prefix: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    reason: String,
+    details: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct SuccessResponse {
+    route: String,
+}
+
+/// The load since the previous report, for fleet operators to see the health
+/// of their instances. Anonymized, i.e. only counts: no routes, clients or
+/// transactions.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct LoadReport {
+    pub requests: u64,
+    /// Of the requests, those that didn’t fail on our side, i.e. weren’t a 5xx
+    pub success_rate: f64,
+    /// [`None`] if the node couldn’t be asked
+    pub sync_progress: Option<f64>,
+    pub uptime_secs: u64,
+}
+
+/// Turns the ever-growing [`StatsResponse`] counts into [`LoadReport`]s of
+/// what happened in between.
+#[derive(Default)]
+pub struct LoadCounter {
+    requests: u64,
+    server_errors: u64,
+}
+
+impl LoadCounter {
+    pub fn report(&mut self, stats: &StatsResponse, sync_progress: Option<f64>) -> LoadReport {
+        let server_errors = stats.routes.iter().map(|r| r.stats.status_5xx).sum();
+        let requests = stats.requests_total.saturating_sub(self.requests);
+        let failed = u64::saturating_sub(server_errors, self.server_errors);
+
+        self.requests = stats.requests_total;
+        self.server_errors = server_errors;
+
+        LoadReport {
+            requests,
+            success_rate: if requests == 0 {
+                1.0
+            } else {
+                1.0 - failed as f64 / requests as f64
+            },
+            sync_progress,
+            uptime_secs: stats.uptime_secs,
+        }
+    }
+}
+
+impl IcebreakersAPI {
+    /// Creates a new `IcebreakersAPI` instance or logs a warning if not configured
+    pub async fn new(config: &Config) -> Result<Option<Arc<Self>>, AppError> {
+        let api_url = api_url(&config.node.network);
+
+        match &config.icebreakers {
+            Some(icebreakers_config) => {
+                info!("Connecting to Icebreakers API...");
+
+                let client = Client::builder()
+                    .timeout(REQUEST_TIMEOUT)
+                    .build()
+                    .map_err(|e| AppError::Registration(e.to_string()))?;
+                let base_url = api_url.to_string();
+
+                let mut icebreakers_api = IcebreakersAPI {
+                    client,
+                    base_url,
+                    secret: icebreakers_config.secret.clone(),
+                    mode: config.server.mode.to_string(),
+                    port: config.server.port,
+                    reward_address: icebreakers_config.reward_address.clone(),
+                    breaker: CircuitBreaker::new(
+                        "Icebreakers API",
+                        BREAKER_FAILURE_THRESHOLD,
+                        BREAKER_COOLDOWN,
+                    ),
+                    api_prefix: "/".to_string(),
+                };
+
+                let result = REGISTER_BACKOFF
+                    .retry(|| icebreakers_api.register(), |_| true)
+                    .await?;
+
+                // Pass the route to the icebreakers_api instance
+                icebreakers_api.api_prefix = result.route;
+
+                let icebreakers_api = Arc::new(icebreakers_api);
+
+                info!("Successfully registered with Icebreakers API.");
+
+                Ok(Some(icebreakers_api))
+            }
+            None => {
+                // Logging the solitary mode warning
+                warn!(" __________________________________________ ");
+                warn!("/ Running in solitary mode.                \\");
+                warn!("|                                          |");
+                warn!("\\ You're not part of the Blockfrost fleet! /");
+                warn!(" ------------------------------------------ ");
+                warn!("        \\   ^__^");
+                warn!("         \\  (oo)\\_______");
+                warn!("            (__)\\       )\\/\\");
+                warn!("                ||----w |");
+                warn!("                ||     ||");
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// Checks the credentials as far as possible without registering: that
+    /// the secret isn’t empty, and that the Icebreakers API answers at all.
+    /// The reward address was checked by [`Config::from_args`].
+    pub async fn check(
+        icebreakers_config: &IcebreakersConfig,
+        network: &Network,
+    ) -> Result<String, AppError> {
+        if icebreakers_config.secret.trim().is_empty() {
+            return Err(AppError::Registration("The secret is empty".to_string()));
+        }
+
+        let url = api_url(network);
+        let response = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::Registration(e.to_string()))?
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| AppError::Registration(format!("{} is unreachable: {}", url, e)))?;
+
+        Ok(format!("{} answered with {}", url, response.status()))
+    }
+
+    /// State of the circuit breaker guarding calls to the Icebreakers API
+    pub fn breaker_state(&self) -> BreakerState {
+        self.breaker.state()
+    }
+
+    /// Registers with the Icebreakers API
+    pub async fn register(&self) -> Result<SuccessResponse, AppError> {
+        if !self.breaker.allow() {
+            return Err(AppError::Registration(
+                "Icebreakers API is unavailable, circuit breaker is open".to_string(),
+            ));
+        }
+
+        let result = self.try_register().await;
+
+        match &result {
+            Ok(_) => self.breaker.record_success(),
+            Err(_) => self.breaker.record_failure(),
+        }
+
+        result
+    }
+
+    async fn try_register(&self) -> Result<SuccessResponse, AppError> {
+        info!("Registering with icebreakers api...");
+
+        let url = format!("{}/register", self.base_url);
+        let body = json!({
+            "secret": self.secret,
+            "mode": self.mode,
+            "port": self.port,
+            "reward_address": self.reward_address,
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AppError::Registration(format!("Registering failed: {}", e)))?;
+
+        if response.status().is_success() {
+            let success_response = response.json::<SuccessResponse>().await.map_err(|e| {
+                AppError::Registration(format!("Failed to parse success response: {}", e))
+            })?;
+
+            Ok(success_response)
+        } else {
+            let error_response = response.json::<ErrorResponse>().await.map_err(|e| {
+                AppError::Registration(format!("Failed to parse error response: {}", e))
+            })?;
+
+            Err(AppError::Registration(format!(
+                "Failed to register with Icebreakers API: {} details: {}",
+                error_response.reason, error_response.details
+            )))
+        }
+    }
+    /// Sends a [`LoadReport`], unless the circuit breaker is open.
+    pub async fn report_load(&self, report: &LoadReport) -> Result<(), AppError> {
+        if !self.breaker.allow() {
+            return Err(AppError::Registration(
+                "Icebreakers API is unavailable, circuit breaker is open".to_string(),
+            ));
+        }
+
+        let url = format!("{}/load", self.base_url);
+        let body = json!({
+            "secret": self.secret,
+            "report": report,
+        });
+
+        let result = self
+            .client
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(|e| AppError::Registration(format!("Reporting the load failed: {}", e)));
+
+        match &result {
+            Ok(_) => self.breaker.record_success(),
+            Err(_) => self.breaker.record_failure(),
+        }
+
+        result
+    }
+}
+
+fn api_url(network: &Network) -> &'static str {
+    match network {
+        // Custom networks run solitary, see `Config::from_args`
+        Network::Preprod | Network::Preview | Network::Sanchonet | Network::Custom => {
+            "https://api-dev.icebreakers.blockfrost.io"
+        }
+        Network::Mainnet => "https://icebreakers-api.blockfrost.io",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::stats::Stats;
+
+    #[test]
+    fn test_load_report() {
+        let stats = Stats::new(0.99);
+        let mut counter = LoadCounter::default();
+
+        assert_eq!(
+            counter.report(&stats.snapshot(), None),
+            LoadReport {
+                requests: 0,
+                success_rate: 1.0,
+                sync_progress: None,
+                uptime_secs: 0,
+            }
+        );
+
+        for status in [200, 200, 404, 500] {
+            stats.record("GET", "/network", status, Duration::from_millis(3));
+        }
+        let report = counter.report(&stats.snapshot(), Some(99.5));
+        assert_eq!((report.requests, report.success_rate), (4, 0.75));
+        assert_eq!(report.sync_progress, Some(99.5));
+
+        // Only what happened since
+        stats.record("POST", "/tx/submit", 202, Duration::from_millis(3));
+        let report = counter.report(&stats.snapshot(), Some(100.0));
+        assert_eq!((report.requests, report.success_rate), (1, 1.0));
+    }
+}
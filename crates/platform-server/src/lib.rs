@@ -0,0 +1,22 @@
+pub mod api;
+pub mod background_tasks;
+pub mod circuit_breaker;
+pub mod common;
+pub mod dry_run;
+pub mod encoding;
+pub mod hooks;
+pub mod icebreakers_api;
+pub mod logging;
+pub mod middlewares;
+pub mod native_script;
+pub mod pre_validate;
+pub mod server;
+pub mod signature;
+pub mod spool;
+pub mod submission_dedup;
+pub mod submission_queue;
+
+pub use platform_cli as cli;
+pub use platform_node::{
+    cbor, errors, genesis, node, retry, storage, AppError, BlockfrostError, NodePool,
+};
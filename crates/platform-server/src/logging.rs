@@ -0,0 +1,72 @@
+use crate::AppError;
+use opentelemetry::{trace::TracerProvider as _, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace::TracerProvider, Resource};
+use tracing::{info_span, Level, Span};
+use tracing_subscriber::{
+    filter::{dynamic_filter_fn, LevelFilter},
+    fmt::format::Format,
+    layer::SubscriberExt,
+    util::SubscriberInitExt,
+    Layer,
+};
+
+/// Name of the span of every sampled request, see
+/// [`crate::middlewares::trace_sampling`].
+pub const REQUEST_SPAN: &str = "request";
+
+/// Tags everything logged while submitting a transaction, down to the node
+/// connection and the error decoders, with its id.
+pub fn tx_span(txid: &str) -> Span {
+    info_span!("tx", txid = %txid)
+}
+
+/// Sets up the tracing subscriber with the provided configuration, exporting
+/// spans to `otlp_endpoint` if there’s one. The returned provider has to be
+/// shut down before exiting, so that the last spans are sent.
+pub fn setup_tracing(
+    log_level: Level,
+    otlp_endpoint: Option<&str>,
+) -> Result<Option<TracerProvider>, AppError> {
+    let fmt = tracing_subscriber::fmt::layer().event_format(
+        Format::default()
+            .with_ansi(true)
+            .with_level(true)
+            .with_target(false)
+            .compact(),
+    );
+
+    let provider = otlp_endpoint.map(otlp_provider).transpose()?;
+
+    // Only sampled requests are exported, with whatever happens within them
+    let otlp = provider.as_ref().map(|provider| {
+        tracing_opentelemetry::layer()
+            .with_tracer(provider.tracer("blockfrost-platform"))
+            .with_filter(dynamic_filter_fn(|metadata, cx| {
+                metadata.name() == REQUEST_SPAN || cx.lookup_current().is_some()
+            }))
+    });
+
+    tracing_subscriber::registry()
+        .with(fmt.with_filter(LevelFilter::from_level(log_level)))
+        .with(otlp.with_filter(LevelFilter::from_level(log_level)))
+        .init();
+
+    Ok(provider)
+}
+
+fn otlp_provider(endpoint: &str) -> Result<TracerProvider, AppError> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| AppError::Server(format!("Failed to set up the OTLP exporter: {}", e)))?;
+
+    Ok(TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new([KeyValue::new(
+            "service.name",
+            "blockfrost-platform",
+        )]))
+        .build())
+}
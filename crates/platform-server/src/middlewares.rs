@@ -0,0 +1,10 @@
+pub mod cors;
+pub mod errors;
+pub mod metrics;
+pub mod operator_auth;
+pub mod problem_json;
+pub mod rate_limit;
+pub mod request_id;
+pub mod response_cache;
+pub mod trace_sampling;
+pub mod traffic;
@@ -0,0 +1,112 @@
+use crate::{cli::CorsConfig, middlewares::request_id::REQUEST_ID_HEADER};
+use axum::http::{HeaderValue, Method};
+use std::time::Duration;
+use tower_http::cors::{AllowHeaders, AllowOrigin, CorsLayer};
+
+/// Lets browsers call the API from the configured origins, answering their
+/// preflight requests before routing. [`None`] when no origin is allowed.
+pub fn cors_layer(config: &CorsConfig) -> Option<CorsLayer> {
+    if config.allow_origins.is_empty() {
+        return None;
+    }
+
+    let allow_origin = if config.allow_origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            config
+                .allow_origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        )
+    };
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers(AllowHeaders::mirror_request())
+            .expose_headers([REQUEST_ID_HEADER])
+            .max_age(Duration::from_secs(config.max_age_secs)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn router(allow_origins: &[&str]) -> Router {
+        let config = CorsConfig {
+            allow_origins: allow_origins.iter().map(|o| o.to_string()).collect(),
+            max_age_secs: 600,
+        };
+        let router = Router::new().route("/health", get(|| async { "ok" }));
+
+        match cors_layer(&config) {
+            Some(layer) => router.layer(layer),
+            None => router,
+        }
+    }
+
+    #[rstest]
+    #[case(&[], "https://app.example.com", None)]
+    #[case(&["*"], "https://app.example.com", Some("*"))]
+    #[case(&["https://app.example.com"], "https://app.example.com", Some("https://app.example.com"))]
+    #[case(&["https://app.example.com"], "https://evil.example.com", None)]
+    #[tokio::test]
+    async fn test_cors_allow_origin(
+        #[case] allow_origins: &[&str],
+        #[case] origin: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let response = router(allow_origins)
+            .oneshot(
+                Request::get("/health")
+                    .header(header::ORIGIN, origin)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .map(|value| value.to_str().unwrap()),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight() {
+        let response = router(&["https://app.example.com"])
+            .oneshot(
+                Request::options("/health")
+                    .header(header::ORIGIN, "https://app.example.com")
+                    .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+                    .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "project_id")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_HEADERS],
+            "project_id"
+        );
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_MAX_AGE], "600");
+    }
+}
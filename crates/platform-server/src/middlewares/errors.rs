@@ -30,6 +30,12 @@ pub async fn error_middleware(request: Request, next: Next) -> Result<Response,
         return Ok(BlockfrostError::method_not_allowed().into_response());
     }
 
+    // Deliberate unavailability, e.g. `/health` while reconnecting to the
+    // node, goes through as is, for load balancers to act on
+    if response.status() == StatusCode::SERVICE_UNAVAILABLE {
+        return Ok(response);
+    }
+
     // Transform server errors to internal server error for user
     if response.status().is_server_error() {
         handle_server_error(response, &request_path, status_code).await
@@ -1,9 +1,16 @@
-use axum::extract::{MatchedPath, Request};
+use crate::api::stats::Stats;
+use axum::extract::{MatchedPath, Request, State};
 use axum::middleware::Next;
 use axum::response::IntoResponse;
 use metrics::counter;
+use std::sync::Arc;
+use std::time::Instant;
 
-pub async fn track_http_metrics(req: Request, next: Next) -> impl IntoResponse {
+pub async fn track_http_metrics(
+    State(stats): State<Arc<Stats>>,
+    req: Request,
+    next: Next,
+) -> impl IntoResponse {
     let path = if let Some(matched_path) = req.extensions().get::<MatchedPath>() {
         matched_path.as_str().to_owned()
     } else {
@@ -11,13 +18,16 @@ pub async fn track_http_metrics(req: Request, next: Next) -> impl IntoResponse {
     };
 
     let method = req.method().clone();
+    let started_at = Instant::now();
     let response = next.run(req).await;
-    let status = response.status().as_u16().to_string();
+    let status = response.status().as_u16();
+
+    stats.record(method.as_str(), &path, status, started_at.elapsed());
 
     let labels = [
         ("method", method.to_string()),
         ("path", path),
-        ("status", status),
+        ("status", status.to_string()),
     ];
 
     counter!("http_requests_total", &labels).increment(1);
@@ -0,0 +1,87 @@
+use crate::BlockfrostError;
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+
+/// Answers `401 Unauthorized` unless the request carries the `token` of
+/// `--operator-token` as its bearer token.
+pub async fn require_operator(
+    State(token): State<Arc<str>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if bearer_token(request.headers()).is_some_and(|bearer| constant_time_eq(bearer, &token)) {
+        return next.run(request).await;
+    }
+
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        Json(BlockfrostError::unauthorized(
+            "This route needs the operator token".to_string(),
+        )),
+    )
+        .into_response()
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then_some(token.trim())
+}
+
+/// So that how long the comparison takes doesn’t tell how much of the token
+/// was guessed right.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware::from_fn_with_state, routing::get, Router};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    #[rstest]
+    #[case(None, StatusCode::UNAUTHORIZED)]
+    #[case(Some("Bearer secret"), StatusCode::OK)]
+    #[case(Some("bearer  secret "), StatusCode::OK)]
+    #[case(Some("Bearer secreT"), StatusCode::UNAUTHORIZED)]
+    #[case(Some("Bearer secret2"), StatusCode::UNAUTHORIZED)]
+    #[case(Some("Basic secret"), StatusCode::UNAUTHORIZED)]
+    #[tokio::test]
+    async fn test_require_operator(
+        #[case] authorization: Option<&str>,
+        #[case] expected: StatusCode,
+    ) {
+        let app = Router::new().route(
+            "/",
+            get(|| async { "ok" }).layer(from_fn_with_state(Arc::from("secret"), require_operator)),
+        );
+        let mut request = Request::get("/");
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), expected);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::BlockfrostError;
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{
+        header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE},
+        HeaderMap, HeaderValue,
+    },
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// An error in the [RFC 7807] format.
+///
+/// [RFC 7807]: https://www.rfc-editor.org/rfc/rfc7807
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub instance: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl Problem {
+    /// Our errors are all distinguished by their status code alone, hence
+    /// `about:blank`, with the rest of [`BlockfrostError`] as is.
+    pub fn new(err: BlockfrostError, instance: &str) -> Self {
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: err.error,
+            status: err.status_code,
+            detail: err.message,
+            instance: instance.to_string(),
+            details: err.details,
+        }
+    }
+}
+
+/// Rewrites error responses as `application/problem+json` for the clients
+/// asking for it in `Accept`, leaving everyone else with the usual format.
+pub async fn problem_json(request: Request, next: Next) -> Response {
+    if !accepts_problem_json(request.headers()) {
+        return next.run(request).await;
+    }
+
+    let instance = request.uri().path().to_string();
+    let response = next.run(request).await;
+
+    let is_json_error = (response.status().is_client_error()
+        || response.status().is_server_error())
+        && response
+            .headers()
+            .get(CONTENT_TYPE)
+            .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if !is_json_error {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+
+    let problem = serde_json::from_slice::<BlockfrostError>(&bytes)
+        .and_then(|err| serde_json::to_vec(&Problem::new(err, &instance)));
+
+    match problem {
+        Ok(problem) => {
+            parts.headers.remove(CONTENT_LENGTH);
+            parts
+                .headers
+                .insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+            Response::from_parts(parts, Body::from(problem))
+        }
+        // Not one of ours, e.g. from a proxied backend
+        Err(_) => Response::from_parts(parts, Body::from(bytes)),
+    }
+}
+
+fn accepts_problem_json(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|range| {
+            range
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .eq_ignore_ascii_case(PROBLEM_JSON)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        http::{Request, StatusCode},
+        middleware::from_fn,
+        routing::get,
+        Router,
+    };
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/fail",
+                get(|| async { Err::<(), _>(BlockfrostError::custom_400("nope".to_string())) }),
+            )
+            .route("/ok", get(|| async { "ok" }))
+            .layer(from_fn(problem_json))
+    }
+
+    #[rstest]
+    #[case("/fail", None, "application/json")]
+    #[case("/fail", Some("application/json"), "application/json")]
+    #[case(
+        "/fail",
+        Some("application/json, application/problem+json;q=0.9"),
+        PROBLEM_JSON
+    )]
+    #[case("/ok", Some(PROBLEM_JSON), "text/plain; charset=utf-8")]
+    #[tokio::test]
+    async fn test_negotiation(
+        #[case] path: &str,
+        #[case] accept: Option<&str>,
+        #[case] content_type: &str,
+    ) {
+        let mut request = Request::get(path);
+        if let Some(accept) = accept {
+            request = request.header(ACCEPT, accept);
+        }
+
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[CONTENT_TYPE], content_type);
+    }
+
+    #[tokio::test]
+    async fn test_problem_body() {
+        let response = app()
+            .oneshot(
+                Request::get("/fail")
+                    .header(ACCEPT, PROBLEM_JSON)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            serde_json::json!({
+                "type": "about:blank",
+                "title": "Bad Request",
+                "status": 400,
+                "detail": "nope",
+                "instance": "/fail",
+            })
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{cli::LimitsConfig, server::routes::RateLimitClass, BlockfrostError};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use metrics::counter;
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Queries going through large parts of the ledger state take this many
+/// tokens of the query limit, others one.
+const EXPENSIVE_COST: f64 = 10.0;
+
+/// Per-IP buckets tracked before those refilled in full are forgotten.
+const MAX_TRACKED_IPS: usize = 10_000;
+
+/// Refills at `rate` tokens per second, holding up to a second’s worth, and
+/// at least enough for one expensive query.
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        let capacity = rate.max(EXPENSIVE_COST);
+
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated_at = now;
+    }
+
+    /// Takes `cost` tokens, or tells how long until there are enough.
+    fn take(&mut self, cost: f64, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((cost - self.tokens) / self.rate))
+        }
+    }
+
+    /// Gives back `cost` tokens taken for a request that was refused after all.
+    fn refund(&mut self, cost: f64) {
+        self.tokens = (self.tokens + cost).min(self.capacity);
+    }
+
+    fn is_full(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= self.capacity
+    }
+}
+
+/// The buckets of one group of routes: one shared by all clients, and one per
+/// client IP.
+#[derive(Debug)]
+struct Limits {
+    per_ip: Option<f64>,
+    global: Option<Mutex<TokenBucket>>,
+    ips: Mutex<HashMap<IpAddr, TokenBucket>>,
+}
+
+impl Limits {
+    fn new(per_ip: Option<f64>, global: Option<f64>, now: Instant) -> Self {
+        Self {
+            per_ip,
+            global: global.map(|rate| Mutex::new(TokenBucket::new(rate, now))),
+            ips: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn take(&self, cost: f64, ip: Option<IpAddr>, now: Instant) -> Result<(), Throttled> {
+        // Per IP first, so that a flooding client doesn’t drain the global bucket
+        let per_ip = self.per_ip.zip(ip);
+        if let Some((rate, ip)) = per_ip {
+            let mut ips = self.ips.lock().unwrap_or_else(|e| e.into_inner());
+
+            if ips.len() >= MAX_TRACKED_IPS && !ips.contains_key(&ip) {
+                ips.retain(|_, bucket| !bucket.is_full(now));
+            }
+
+            ips.entry(ip)
+                .or_insert_with(|| TokenBucket::new(rate, now))
+                .take(cost, now)
+                .map_err(|retry_after| Throttled {
+                    scope: "ip",
+                    retry_after,
+                })?;
+        }
+
+        if let Some(global) = &self.global {
+            let taken = global
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take(cost, now);
+
+            if let Err(retry_after) = taken {
+                // Refused, so not to be held against the client
+                if let Some((_, ip)) = per_ip {
+                    let mut ips = self.ips.lock().unwrap_or_else(|e| e.into_inner());
+                    if let Some(bucket) = ips.get_mut(&ip) {
+                        bucket.refund(cost);
+                    }
+                }
+
+                return Err(Throttled {
+                    scope: "global",
+                    retry_after,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Throttled {
+    /// `ip` or `global`
+    scope: &'static str,
+    retry_after: Duration,
+}
+
+/// Token bucket rate limits, per client IP and for all clients together, of
+/// the query routes on one hand and the submission routes on the other.
+#[derive(Debug)]
+pub struct RateLimiter {
+    query: Limits,
+    submit: Limits,
+    /// Whether the client IP is the first one in `X-Forwarded-For`, e.g. behind
+    /// a reverse proxy, rather than the peer’s.
+    forwarded_for: bool,
+}
+
+impl RateLimiter {
+    /// [`None`] unless some limit is configured.
+    pub fn new(config: &LimitsConfig) -> Option<Arc<Self>> {
+        let rates = [
+            config.rate_limit_query_per_ip,
+            config.rate_limit_query_global,
+            config.rate_limit_submit_per_ip,
+            config.rate_limit_submit_global,
+        ];
+        if rates.iter().all(Option::is_none) {
+            return None;
+        }
+
+        let now = Instant::now();
+
+        Some(Arc::new(Self {
+            query: Limits::new(
+                config.rate_limit_query_per_ip,
+                config.rate_limit_query_global,
+                now,
+            ),
+            submit: Limits::new(
+                config.rate_limit_submit_per_ip,
+                config.rate_limit_submit_global,
+                now,
+            ),
+            forwarded_for: config.rate_limit_forwarded_for,
+        }))
+    }
+
+    fn check(
+        &self,
+        class: RateLimitClass,
+        ip: Option<IpAddr>,
+        now: Instant,
+    ) -> Result<(), Throttled> {
+        match class {
+            RateLimitClass::Default => self.query.take(1.0, ip, now),
+            RateLimitClass::Expensive => self.query.take(EXPENSIVE_COST, ip, now),
+            RateLimitClass::Submit => self.submit.take(1.0, ip, now),
+        }
+    }
+
+    fn client_ip(&self, request: &Request) -> Option<IpAddr> {
+        if self.forwarded_for {
+            if let Some(ip) = forwarded_for(request.headers()) {
+                return Some(ip);
+            }
+        }
+
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(address)| address.ip())
+    }
+}
+
+/// The client of a request, as appended to `X-Forwarded-For` by the reverse
+/// proxy in front of us. Entries before it are whatever the client sent, so
+/// they can’t be trusted.
+fn forwarded_for(headers: &HeaderMap) -> Option<IpAddr> {
+    headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .last()?
+        .to_str()
+        .ok()?
+        .rsplit(',')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Answers `429 Too Many Requests`, with a `Retry-After`, once the route’s
+/// rate limit is exhausted.
+pub async fn rate_limit(
+    State((limiter, class)): State<(Arc<RateLimiter>, RateLimitClass)>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let ip = limiter.client_ip(&request);
+
+    match limiter.check(class, ip, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(throttled) => {
+            counter!(
+                "http_requests_throttled_total",
+                "class" => class.name(),
+                "scope" => throttled.scope,
+            )
+            .increment(1);
+
+            let retry_after = throttled.retry_after.as_secs_f64().ceil().max(1.0) as u64;
+
+            // Not through `BlockfrostError::into_response`, which would log every
+            // throttled request of a flood
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                Json(BlockfrostError::too_many_requests(format!(
+                    "Rate limit exceeded, retry in {} s",
+                    retry_after
+                ))),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware::from_fn_with_state, routing::get, Router};
+    use pretty_assertions::assert_eq;
+    use tower::ServiceExt;
+
+    fn limiter(per_ip: Option<f64>, global: Option<f64>) -> RateLimiter {
+        let now = Instant::now();
+
+        RateLimiter {
+            query: Limits::new(per_ip, global, now),
+            submit: Limits::new(per_ip, global, now),
+            forwarded_for: false,
+        }
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, now);
+
+        // A burst of at least one expensive query
+        assert_eq!(bucket.take(EXPENSIVE_COST, now), Ok(()));
+        assert_eq!(bucket.take(1.0, now), Err(Duration::from_millis(500)));
+
+        let later = now + Duration::from_secs(1);
+        assert_eq!(bucket.take(2.0, later), Ok(()));
+        assert_eq!(bucket.take(1.0, later), Err(Duration::from_millis(500)));
+        assert!(!bucket.is_full(later));
+        assert!(bucket.is_full(later + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_per_ip() {
+        let limiter = limiter(Some(10.0), None);
+        let now = Instant::now();
+        let (a, b) = ("10.0.0.1".parse().ok(), "10.0.0.2".parse().ok());
+
+        assert_eq!(limiter.check(RateLimitClass::Expensive, a, now), Ok(()));
+        assert_eq!(
+            limiter.check(RateLimitClass::Default, a, now),
+            Err(Throttled {
+                scope: "ip",
+                retry_after: Duration::from_millis(100)
+            })
+        );
+        // Other clients, and other route groups, have their own
+        assert_eq!(limiter.check(RateLimitClass::Default, b, now), Ok(()));
+        assert_eq!(limiter.check(RateLimitClass::Submit, a, now), Ok(()));
+        // Without a known IP, only the global limit applies
+        assert_eq!(limiter.check(RateLimitClass::Expensive, None, now), Ok(()));
+        assert_eq!(limiter.check(RateLimitClass::Expensive, None, now), Ok(()));
+    }
+
+    #[test]
+    fn test_global() {
+        let limiter = limiter(Some(100.0), Some(10.0));
+        let now = Instant::now();
+        let (a, b) = ("10.0.0.1".parse().ok(), "10.0.0.2".parse().ok());
+
+        assert_eq!(limiter.check(RateLimitClass::Expensive, a, now), Ok(()));
+        assert_eq!(
+            limiter
+                .check(RateLimitClass::Default, b, now)
+                .map_err(|t| t.scope),
+            Err("global")
+        );
+
+        // Refused globally, `b` didn’t spend any of its own tokens
+        let bucket = limiter.query.ips.lock().unwrap()[&b.unwrap()].clone();
+        assert_eq!(bucket.tokens, bucket.capacity);
+    }
+
+    #[test]
+    fn test_forwarded_for() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(forwarded_for(&headers), None);
+
+        headers.insert("x-forwarded-for", "203.0.113.7".parse().unwrap());
+        assert_eq!(forwarded_for(&headers), "203.0.113.7".parse().ok());
+
+        // Spoofed by the client, before what the proxy appended
+        headers.insert("x-forwarded-for", "10.0.0.1, 203.0.113.7".parse().unwrap());
+        assert_eq!(forwarded_for(&headers), "203.0.113.7".parse().ok());
+        headers.append("x-forwarded-for", "198.51.100.1".parse().unwrap());
+        assert_eq!(forwarded_for(&headers), "198.51.100.1".parse().ok());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let limiter = Arc::new(limiter(None, Some(1.0)));
+        let app = Router::new().route(
+            "/",
+            get(|| async { "ok" }).layer(from_fn_with_state(
+                (limiter, RateLimitClass::Expensive),
+                rate_limit,
+            )),
+        );
+        let request = || Request::get("/").body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "10");
+    }
+}
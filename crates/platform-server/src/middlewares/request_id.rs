@@ -0,0 +1,132 @@
+use crate::cli::Network;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use pallas_crypto::hash::Hasher;
+use tracing::{field, info_span, Instrument};
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// The Blockfrost API key, which clients going through the Blockfrost
+/// gateway are known by.
+const CLIENT_KEY_HEADER: &str = "project_id";
+
+/// Longest `X-Request-Id` of a client that we take over.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// The id of a request, in its extensions, as taken from the client or made
+/// up otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    fn from_request(req: &Request) -> Self {
+        req.headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|id| {
+                !id.is_empty()
+                    && id.len() <= MAX_REQUEST_ID_LEN
+                    && id.chars().all(|c| c.is_ascii_graphic())
+            })
+            .map(|id| Self(id.to_string()))
+            .unwrap_or_else(|| Self(format!("{:032x}", rand::random::<u128>())))
+    }
+}
+
+/// Identifies the client by its API key, without the key itself ever
+/// showing up in the logs.
+fn client_id(headers: &HeaderMap) -> Option<String> {
+    let key = headers.get(CLIENT_KEY_HEADER)?.as_bytes();
+
+    Some(hex::encode(&Hasher::<160>::hash(key)[..8]))
+}
+
+/// Tags everything logged while handling a request with its id, the network
+/// and the client, and sends the id back in `X-Request-Id`, so that a
+/// client’s report can be matched to our logs and traces.
+pub async fn request_id(State(network): State<Network>, mut req: Request, next: Next) -> Response {
+    let request_id = RequestId::from_request(&req);
+    req.extensions_mut().insert(request_id.clone());
+
+    let span = info_span!(
+        "request_id",
+        id = %request_id.0,
+        network = %network,
+        client = field::Empty,
+    );
+    if let Some(client) = client_id(req.headers()) {
+        span.record("client", client);
+    }
+    let mut response = next.run(req).instrument(span).await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id.0) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware::from_fn_with_state, routing::get, Extension, Router};
+    use pretty_assertions::assert_eq;
+    use tower::ServiceExt;
+
+    async fn echo(Extension(RequestId(id)): Extension<RequestId>) -> String {
+        id
+    }
+
+    async fn call(client_id: Option<&str>) -> (String, String) {
+        let app = Router::new()
+            .route("/", get(echo))
+            .layer(from_fn_with_state(Network::Preview, request_id));
+
+        let mut req = Request::get("/");
+        if let Some(client_id) = client_id {
+            req = req.header(&REQUEST_ID_HEADER, client_id);
+        }
+        let response = app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+
+        let header = response.headers()[&REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        (header, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_request_id() {
+        assert_eq!(
+            call(Some("abc-123")).await,
+            ("abc-123".to_string(), "abc-123".to_string())
+        );
+
+        let (header, body) = call(None).await;
+        assert_eq!(header.len(), 32);
+        assert_eq!(header, body);
+
+        let (header, _) = call(Some(&"a".repeat(MAX_REQUEST_ID_LEN + 1))).await;
+        assert_eq!(header.len(), 32);
+    }
+
+    #[test]
+    fn test_client_id() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(client_id(&headers), None);
+
+        headers.insert(CLIENT_KEY_HEADER, HeaderValue::from_static("mainnetXYZ"));
+        let client = client_id(&headers).unwrap();
+        assert_eq!(client.len(), 16);
+        assert!(!client.contains("mainnetXYZ"));
+        assert_eq!(client_id(&headers), Some(client));
+    }
+}
@@ -0,0 +1,256 @@
+use crate::BlockfrostError;
+use axum::{
+    body::{to_bytes, Bytes},
+    extract::{Request, State},
+    http::{HeaderMap, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use metrics::counter;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::OnceCell;
+
+/// How many responses we keep cached at most, of all routes together.
+const CACHE_ENTRIES: usize = 1024;
+
+/// Successful responses of the routes querying the node’s ledger state, each
+/// kept as long as its route allows, so that bursts of the same query don’t
+/// queue up for node connections. Those arriving while the first one is still
+/// being answered wait for its response.
+#[derive(Debug, Default)]
+pub struct ResponseCache {
+    entries: Mutex<HashMap<String, Arc<Entry>>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    ttl: Duration,
+    /// `None` if the response wasn’t one to cache
+    response: OnceCell<Option<CachedResponse>>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    cached_at: Instant,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl Entry {
+    fn is_fresh(&self) -> bool {
+        match self.response.get() {
+            // Still being answered
+            None => true,
+            Some(cached) => cached
+                .as_ref()
+                .is_some_and(|cached| cached.cached_at.elapsed() < self.ttl),
+        }
+    }
+
+    fn cached_at(&self) -> Option<Instant> {
+        Some(self.response.get()?.as_ref()?.cached_at)
+    }
+}
+
+impl ResponseCache {
+    /// The entry of `key`, replaced with a new one unless it’s fresh.
+    fn entry(&self, key: &str, ttl: Duration) -> Arc<Entry> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(entry) = entries.get(key).filter(|entry| entry.is_fresh()) {
+            return entry.clone();
+        }
+
+        entries.retain(|_, entry| entry.is_fresh());
+
+        if entries.len() >= CACHE_ENTRIES {
+            let oldest = entries
+                .iter()
+                .filter_map(|(key, entry)| Some((entry.cached_at()?, key)))
+                .min()
+                .map(|(_, key)| key.clone());
+
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        let entry = Arc::new(Entry {
+            ttl,
+            response: OnceCell::new(),
+        });
+        entries.insert(key.to_string(), entry.clone());
+
+        entry
+    }
+}
+
+/// Answers `GET` requests of the `route` from the [`ResponseCache`] if it
+/// can, otherwise keeping the `200 OK` for `ttl`.
+pub async fn cache_response(
+    State((cache, route, ttl)): State<(Arc<ResponseCache>, &'static str, Duration)>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+
+    let key = request
+        .uri()
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str().to_string())
+        .unwrap_or_default();
+    let entry = cache.entry(&key, ttl);
+
+    // Taken if it’s us answering the entry
+    let mut pending = Some((request, next));
+    let mut uncached = None;
+    let (pending_ref, uncached_ref) = (&mut pending, &mut uncached);
+    let cached = entry
+        .response
+        .get_or_init(|| async move {
+            let (request, next) = pending_ref.take().expect("only answered once");
+
+            match cacheable(next.run(request).await).await {
+                Ok(cached) => Some(cached),
+                Err(response) => {
+                    *uncached_ref = Some(response);
+                    None
+                }
+            }
+        })
+        .await;
+
+    let (result, response) = match (cached, uncached, pending) {
+        (_, Some(response), _) => ("miss", response),
+        (Some(cached), None, None) => ("miss", cached.to_response()),
+        (Some(cached), None, Some(_)) => ("hit", cached.to_response()),
+        // What we waited for wasn’t cached, so we have to ask ourselves
+        (None, None, Some((request, next))) => ("miss", next.run(request).await),
+        (None, None, None) => unreachable!("an answered entry is either cached or not"),
+    };
+
+    counter!("http_response_cache_total", "route" => route, "result" => result).increment(1);
+
+    response
+}
+
+/// Buffers `response` if it’s one to cache, i.e. a `200 OK`.
+async fn cacheable(response: Response) -> Result<CachedResponse, Response> {
+    if response.status() != StatusCode::OK {
+        return Err(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    match to_bytes(body, usize::MAX).await {
+        Ok(body) => Ok(CachedResponse {
+            cached_at: Instant::now(),
+            headers: parts.headers,
+            body,
+        }),
+        Err(e) => Err(BlockfrostError::internal_server_error(e.to_string()).into_response()),
+    }
+}
+
+impl CachedResponse {
+    fn to_response(&self) -> Response {
+        (StatusCode::OK, self.headers.clone(), self.body.clone()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Path, middleware::from_fn_with_state, routing::get, Router};
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    /// A route counting how often it actually answered, and failing for `fail`.
+    fn app(ttl: Duration) -> (Router, Arc<AtomicUsize>) {
+        let answered = Arc::new(AtomicUsize::new(0));
+        let counter = answered.clone();
+        let handler = move |Path(name): Path<String>| async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+
+            match name.as_str() {
+                "fail" => Err(StatusCode::SERVICE_UNAVAILABLE),
+                _ => Ok(format!("hello {name}")),
+            }
+        };
+        let app = Router::new().route(
+            "/{name}",
+            get(handler).layer(from_fn_with_state(
+                (Arc::new(ResponseCache::default()), "/{name}", ttl),
+                cache_response,
+            )),
+        );
+
+        (app, answered)
+    }
+
+    async fn get_body(app: &Router, uri: &str) -> (StatusCode, String) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_cached() {
+        let (app, answered) = app(Duration::from_secs(60));
+
+        let expected = (StatusCode::OK, "hello a".to_string());
+        assert_eq!(get_body(&app, "/a").await, expected);
+        assert_eq!(get_body(&app, "/a").await, expected);
+        assert_eq!(answered.load(Ordering::SeqCst), 1);
+
+        // Per path and query
+        get_body(&app, "/b").await;
+        get_body(&app, "/a?page=2").await;
+        assert_eq!(answered.load(Ordering::SeqCst), 3);
+
+        // Failures aren’t cached
+        let expected = (StatusCode::SERVICE_UNAVAILABLE, String::new());
+        assert_eq!(get_body(&app, "/fail").await, expected);
+        assert_eq!(get_body(&app, "/fail").await, expected);
+        assert_eq!(answered.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_expired() {
+        let (app, answered) = app(Duration::ZERO);
+
+        get_body(&app, "/a").await;
+        get_body(&app, "/a").await;
+        assert_eq!(answered.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent() {
+        let (app, answered) = app(Duration::from_secs(60));
+
+        let (a, b, c) = tokio::join!(
+            get_body(&app, "/a"),
+            get_body(&app, "/a"),
+            get_body(&app, "/a")
+        );
+        assert_eq!(a, (StatusCode::OK, "hello a".to_string()));
+        assert_eq!((&a, &a), (&b, &c));
+        assert_eq!(answered.load(Ordering::SeqCst), 1);
+
+        // Those waiting on a failure try for themselves
+        let (a, b) = tokio::join!(get_body(&app, "/fail"), get_body(&app, "/fail"));
+        assert_eq!(a.0, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(b.0, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(answered.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,123 @@
+use super::request_id::RequestId;
+use crate::logging::REQUEST_SPAN;
+use axum::extract::{MatchedPath, Request, State};
+use axum::middleware::Next;
+use axum::response::IntoResponse;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::{info, info_span, Instrument};
+
+/// Decides which requests get a tracing span.
+///
+/// Sampling is deterministic: with a rate of 0.01, exactly every 100th
+/// request of a route is traced, so rare routes aren’t starved by busy ones.
+pub struct TraceSampler {
+    default_rate: f64,
+    route_rates: HashMap<String, f64>,
+    counters: Mutex<HashMap<String, u64>>,
+}
+
+impl TraceSampler {
+    pub fn new(default_rate: f64, route_rates: HashMap<String, f64>) -> Arc<Self> {
+        Arc::new(Self {
+            default_rate,
+            route_rates,
+            counters: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn rate(&self, path: &str) -> f64 {
+        self.route_rates
+            .get(path)
+            .copied()
+            .unwrap_or(self.default_rate)
+    }
+
+    pub fn should_sample(&self, path: &str) -> bool {
+        let rate = self.rate(path);
+
+        if rate >= 1.0 {
+            return true;
+        }
+        if rate <= 0.0 {
+            return false;
+        }
+
+        let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        let counter = counters.entry(path.to_string()).or_default();
+        let n = *counter;
+        *counter += 1;
+
+        // Sample whenever the running total of `rate` crosses an integer
+        ((n + 1) as f64 * rate).floor() > (n as f64 * rate).floor()
+    }
+}
+
+pub async fn trace_requests(
+    State(sampler): State<Arc<TraceSampler>>,
+    req: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let path = if let Some(matched_path) = req.extensions().get::<MatchedPath>() {
+        matched_path.as_str().to_owned()
+    } else {
+        req.uri().path().to_owned()
+    };
+
+    if !sampler.should_sample(&path) {
+        return next.run(req).await;
+    }
+
+    // `timing` is recorded by `/tx/submit` when asked to, see `DEBUG_TIMING_HEADER`
+    let request_id = req
+        .extensions()
+        .get::<RequestId>()
+        .map(|RequestId(id)| id.clone())
+        .unwrap_or_default();
+    let span = info_span!(
+        REQUEST_SPAN,
+        method = %req.method(),
+        path = %path,
+        request_id = %request_id,
+        timing = tracing::field::Empty
+    );
+
+    async move {
+        let started_at = Instant::now();
+        let response = next.run(req).await;
+
+        info!(
+            status = response.status().as_u16(),
+            latency_ms = started_at.elapsed().as_millis() as u64,
+            "finished request"
+        );
+
+        response
+    }
+    .instrument(span)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_trace_sampler_rates() {
+        let sampler = TraceSampler::new(
+            1.0,
+            HashMap::from([
+                ("/tx/submit".to_string(), 0.01),
+                ("/metrics".to_string(), 0.0),
+            ]),
+        );
+
+        let sampled = |path| (0..1000).filter(|_| sampler.should_sample(path)).count();
+
+        assert_eq!(sampled("/tx/submit"), 10);
+        assert_eq!(sampled("/metrics"), 0);
+        assert_eq!(sampled("/admin/storage"), 1000);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{storage::prune_dir, AppError};
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use pallas_crypto::hash::Hasher;
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+use tracing::warn;
+
+/// Bodies larger than this aren’t recorded (nor served, as we have to buffer them).
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Headers that must never end up on disk.
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "cookie", "project_id", "secret"];
+
+/// Writes request/response pairs to a directory, one JSON file each, for
+/// reproducing integrator-specific failures.
+pub struct TrafficRecorder {
+    dir: PathBuf,
+    quota_bytes: u64,
+    record_bodies: bool,
+    reward_address_hash: Option<String>,
+    sequence: AtomicU64,
+}
+
+#[derive(Serialize)]
+struct Exchange {
+    timestamp: String,
+    method: String,
+    uri: String,
+    request_headers: Vec<(String, String)>,
+    #[serde(flatten)]
+    request_body: RecordedBody,
+    status: u16,
+    response_headers: Vec<(String, String)>,
+    response_body: String,
+    latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reward_address_hash: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+enum RecordedBody {
+    #[serde(rename = "request_body")]
+    Plain(String),
+    /// Transaction CBOR is only recorded as its hash, unless `--record-bodies`
+    #[serde(rename = "request_body_blake2b_256")]
+    Hashed(String),
+}
+
+impl TrafficRecorder {
+    pub fn new(
+        dir: &Path,
+        quota_bytes: u64,
+        record_bodies: bool,
+        reward_address_hash: Option<String>,
+    ) -> Result<Arc<Self>, AppError> {
+        fs::create_dir_all(dir).map_err(|e| {
+            AppError::Server(format!(
+                "Failed to create the traffic directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        Ok(Arc::new(Self {
+            dir: dir.to_path_buf(),
+            quota_bytes,
+            record_bodies,
+            reward_address_hash,
+            sequence: AtomicU64::new(0),
+        }))
+    }
+
+    fn request_body(&self, headers: &HeaderMap, body: &Bytes) -> RecordedBody {
+        let is_cbor = headers
+            .get(header::CONTENT_TYPE)
+            .is_some_and(|ct| ct.as_bytes().starts_with(b"application/cbor"));
+
+        if is_cbor && !self.record_bodies {
+            RecordedBody::Hashed(hex::encode(Hasher::<256>::hash(body)))
+        } else {
+            RecordedBody::Plain(String::from_utf8_lossy(body).to_string())
+        }
+    }
+
+    fn write(&self, exchange: &Exchange) {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        let file_name = format!(
+            "{}-{:06}.json",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.3f"),
+            sequence
+        );
+
+        let result = serde_json::to_vec_pretty(exchange)
+            .map_err(std::io::Error::other)
+            .and_then(|json| fs::write(self.dir.join(file_name), json))
+            .and_then(|_| prune_dir(&self.dir, self.quota_bytes));
+
+        if let Err(e) = result {
+            warn!("Failed to record traffic: {}", e);
+        }
+    }
+}
+
+fn sanitized(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                "<redacted>".to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).to_string()
+            };
+
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+pub async fn record_traffic(
+    State(recorder): State<Arc<TrafficRecorder>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let started_at = Instant::now();
+    let (parts, body) = req.into_parts();
+
+    let Ok(request_body) = to_bytes(body, MAX_BODY_BYTES).await else {
+        return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+    };
+
+    let recorded_request_body = recorder.request_body(&parts.headers, &request_body);
+    let method = parts.method.to_string();
+    let uri = parts.uri.to_string();
+    let request_headers = sanitized(&parts.headers);
+
+    let response = next
+        .run(Request::from_parts(parts, Body::from(request_body)))
+        .await;
+
+    let (parts, body) = response.into_parts();
+    let response_body = to_bytes(body, MAX_BODY_BYTES).await.unwrap_or_default();
+
+    let exchange = Exchange {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        method,
+        uri,
+        request_headers,
+        request_body: recorded_request_body,
+        status: parts.status.as_u16(),
+        response_headers: sanitized(&parts.headers),
+        response_body: String::from_utf8_lossy(&response_body).to_string(),
+        latency_ms: started_at.elapsed().as_millis() as u64,
+        reward_address_hash: recorder.reward_address_hash.clone(),
+    };
+
+    tokio::task::spawn_blocking(move || recorder.write(&exchange));
+
+    Response::from_parts(parts, Body::from(response_body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_traffic_recorder_hashes_cbor() {
+        let dir = std::env::temp_dir().join(format!("bf-traffic-{}", std::process::id()));
+        let recorder = TrafficRecorder::new(&dir, 1024, false, None).unwrap();
+        let body = Bytes::from_static(b"84a300");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/cbor"),
+        );
+        headers.insert("project_id", HeaderValue::from_static("mainnetXYZ"));
+
+        assert_eq!(
+            recorder.request_body(&headers, &body),
+            RecordedBody::Hashed(hex::encode(Hasher::<256>::hash(&body)))
+        );
+        assert!(sanitized(&headers).contains(&("project_id".to_string(), "<redacted>".to_string())));
+        assert_eq!(
+            recorder.request_body(&HeaderMap::new(), &body),
+            RecordedBody::Plain("84a300".to_string())
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,329 @@
+use crate::cbor::slot::SlotNo;
+use pallas_codec::minicbor;
+use pallas_crypto::hash::{Hash, Hasher};
+use pallas_primitives::conway::NativeScript;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A native script in the JSON of cardano-cli, e.g.
+/// `{"type": "atLeast", "required": 2, "scripts": [{"type": "sig", "keyHash": "…"}, …]}`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ScriptJson {
+    #[serde(rename_all = "camelCase")]
+    Sig {
+        key_hash: String,
+    },
+    All {
+        scripts: Vec<ScriptJson>,
+    },
+    Any {
+        scripts: Vec<ScriptJson>,
+    },
+    AtLeast {
+        required: u32,
+        scripts: Vec<ScriptJson>,
+    },
+    /// Valid from `slot` on, i.e. `InvalidBefore`
+    After {
+        slot: u64,
+    },
+    /// Valid until before `slot`, i.e. `InvalidHereafter`
+    Before {
+        slot: u64,
+    },
+}
+
+impl TryFrom<ScriptJson> for NativeScript {
+    type Error = String;
+
+    fn try_from(script: ScriptJson) -> Result<Self, String> {
+        let all = |scripts: Vec<ScriptJson>| {
+            scripts
+                .into_iter()
+                .map(NativeScript::try_from)
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(match script {
+            ScriptJson::Sig { key_hash } => NativeScript::ScriptPubkey(
+                key_hash
+                    .parse()
+                    .map_err(|_| format!("Invalid keyHash: {}", key_hash))?,
+            ),
+            ScriptJson::All { scripts } => NativeScript::ScriptAll(all(scripts)?),
+            ScriptJson::Any { scripts } => NativeScript::ScriptAny(all(scripts)?),
+            ScriptJson::AtLeast { required, scripts } => {
+                NativeScript::ScriptNOfK(required, all(scripts)?)
+            }
+            ScriptJson::After { slot } => NativeScript::InvalidBefore(slot),
+            ScriptJson::Before { slot } => NativeScript::InvalidHereafter(slot),
+        })
+    }
+}
+
+/// Parses either cardano-cli’s JSON or hex-encoded CBOR, returning the script
+/// together with its CBOR, which its hash is of.
+pub fn parse(script: &serde_json::Value) -> Result<(NativeScript, Vec<u8>), String> {
+    match script {
+        serde_json::Value::String(cbor) => {
+            let cbor = hex::decode(cbor).map_err(|e| format!("Invalid script CBOR: {}", e))?;
+            let script = minicbor::decode(&cbor).map_err(|e| format!("Invalid script: {}", e))?;
+
+            Ok((script, cbor))
+        }
+        json => {
+            let script: NativeScript = serde_json::from_value::<ScriptJson>(json.clone())
+                .map_err(|e| format!("Invalid script: {}", e))?
+                .try_into()?;
+            let cbor = minicbor::to_vec(&script).map_err(|e| e.to_string())?;
+
+            Ok((script, cbor))
+        }
+    }
+}
+
+/// The hash of a native script, as in script addresses and policy ids.
+pub fn script_hash(cbor: &[u8]) -> Hash<28> {
+    Hasher::<224>::hash_tagged(cbor, 0)
+}
+
+/// What of a transaction native scripts see: its validity interval and who
+/// signed it.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    pub invalid_before: Option<SlotNo>,
+    pub invalid_hereafter: Option<SlotNo>,
+    pub signers: HashSet<Hash<28>>,
+}
+
+/// The outcome of a script and of each of its sub-scripts, shaped like
+/// cardano-cli’s JSON.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    pub valid: bool,
+    #[serde(flatten)]
+    pub script: EvaluatedScript,
+    /// Why it doesn’t validate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum EvaluatedScript {
+    #[serde(rename_all = "camelCase")]
+    Sig {
+        key_hash: String,
+    },
+    All {
+        scripts: Vec<Evaluation>,
+    },
+    Any {
+        scripts: Vec<Evaluation>,
+    },
+    AtLeast {
+        required: u32,
+        scripts: Vec<Evaluation>,
+    },
+    After {
+        slot: SlotNo,
+    },
+    Before {
+        slot: SlotNo,
+    },
+}
+
+/// Evaluates `script` like the ledger does. Time locks hold for the whole
+/// validity interval of the transaction, or not at all: `after` needs it to
+/// start no earlier, and `before` to end no later.
+pub fn evaluate(script: &NativeScript, context: &Context) -> Evaluation {
+    let evaluate_all =
+        |scripts: &[NativeScript]| scripts.iter().map(|s| evaluate(s, context)).collect();
+    let count_valid = |scripts: &[Evaluation]| scripts.iter().filter(|s| s.valid).count();
+
+    let (valid, script, reason) = match script {
+        NativeScript::ScriptPubkey(key_hash) => {
+            let valid = context.signers.contains(key_hash);
+            (
+                valid,
+                EvaluatedScript::Sig {
+                    key_hash: key_hash.to_string(),
+                },
+                (!valid).then(|| format!("Not signed by {}", key_hash)),
+            )
+        }
+        NativeScript::ScriptAll(scripts) => {
+            let scripts: Vec<_> = evaluate_all(scripts);
+            let failing = scripts.len() - count_valid(&scripts);
+            (
+                failing == 0,
+                EvaluatedScript::All { scripts },
+                (failing > 0).then(|| format!("{} of its scripts fail", failing)),
+            )
+        }
+        NativeScript::ScriptAny(scripts) => {
+            let scripts: Vec<_> = evaluate_all(scripts);
+            let valid = count_valid(&scripts) > 0;
+            (
+                valid,
+                EvaluatedScript::Any { scripts },
+                (!valid).then(|| "None of the scripts validates".to_string()),
+            )
+        }
+        NativeScript::ScriptNOfK(required, scripts) => {
+            let scripts: Vec<_> = evaluate_all(scripts);
+            let validating = count_valid(&scripts);
+            let valid = validating >= *required as usize;
+            (
+                valid,
+                EvaluatedScript::AtLeast {
+                    required: *required,
+                    scripts,
+                },
+                (!valid).then(|| {
+                    format!(
+                        "Only {} of the required {} scripts validate",
+                        validating, required
+                    )
+                }),
+            )
+        }
+        NativeScript::InvalidBefore(slot) => {
+            let slot = SlotNo(*slot);
+            let valid = context.invalid_before.is_some_and(|start| slot <= start);
+            (
+                valid,
+                EvaluatedScript::After { slot },
+                (!valid).then(|| format!("Needs invalid_before to be at least {}", slot)),
+            )
+        }
+        NativeScript::InvalidHereafter(slot) => {
+            let slot = SlotNo(*slot);
+            let valid = context.invalid_hereafter.is_some_and(|end| end <= slot);
+            (
+                valid,
+                EvaluatedScript::Before { slot },
+                (!valid).then(|| format!("Needs invalid_hereafter to be at most {}", slot)),
+            )
+        }
+    };
+
+    Evaluation {
+        valid,
+        script,
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const ALICE: &str = "e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a";
+    const BOB: &str = "a687dcc24e00dd3caafbeb5e68f97ca8ef269cb6fe971345eb951756";
+
+    fn multisig() -> serde_json::Value {
+        json!({
+            "type": "all",
+            "scripts": [
+                {
+                    "type": "atLeast",
+                    "required": 2,
+                    "scripts": [
+                        { "type": "sig", "keyHash": ALICE },
+                        { "type": "sig", "keyHash": BOB },
+                    ],
+                },
+                { "type": "after", "slot": 1000 },
+                { "type": "before", "slot": 2000 },
+            ],
+        })
+    }
+
+    fn context(signers: &[&str], interval: (Option<u64>, Option<u64>)) -> Context {
+        Context {
+            invalid_before: interval.0.map(SlotNo),
+            invalid_hereafter: interval.1.map(SlotNo),
+            signers: signers.iter().map(|s| s.parse().unwrap()).collect(),
+        }
+    }
+
+    #[rstest]
+    #[case(&[ALICE, BOB], (Some(1000), Some(2000)), true)]
+    #[case(&[ALICE, BOB], (Some(1500), Some(1600)), true)]
+    #[case(&[ALICE], (Some(1000), Some(2000)), false)]
+    #[case(&[ALICE, BOB], (Some(999), Some(2000)), false)]
+    #[case(&[ALICE, BOB], (Some(1000), Some(2001)), false)]
+    #[case(&[ALICE, BOB], (None, Some(2000)), false)]
+    #[case(&[ALICE, BOB], (Some(1000), None), false)]
+    fn test_evaluate(
+        #[case] signers: &[&str],
+        #[case] interval: (Option<u64>, Option<u64>),
+        #[case] valid: bool,
+    ) {
+        let (script, _) = parse(&multisig()).unwrap();
+
+        assert_eq!(evaluate(&script, &context(signers, interval)).valid, valid);
+    }
+
+    #[test]
+    fn test_evaluate_failing_branch() {
+        let (script, _) = parse(&multisig()).unwrap();
+        let evaluation = evaluate(&script, &context(&[ALICE], (Some(1000), Some(2000))));
+
+        assert_eq!(
+            serde_json::to_value(evaluation).unwrap(),
+            json!({
+                "type": "all",
+                "valid": false,
+                "reason": "1 of its scripts fail",
+                "scripts": [
+                    {
+                        "type": "atLeast",
+                        "required": 2,
+                        "valid": false,
+                        "reason": "Only 1 of the required 2 scripts validate",
+                        "scripts": [
+                            { "type": "sig", "keyHash": ALICE, "valid": true },
+                            {
+                                "type": "sig",
+                                "keyHash": BOB,
+                                "valid": false,
+                                "reason": format!("Not signed by {}", BOB),
+                            },
+                        ],
+                    },
+                    { "type": "after", "slot": 1000, "valid": true },
+                    { "type": "before", "slot": 2000, "valid": true },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let (script, cbor) = parse(&multisig()).unwrap();
+
+        // The same script, from its CBOR
+        assert_eq!(
+            parse(&json!(hex::encode(&cbor))).unwrap(),
+            (script, cbor.clone())
+        );
+
+        assert_eq!(
+            script_hash(
+                &minicbor::to_vec(NativeScript::ScriptPubkey(ALICE.parse().unwrap())).unwrap()
+            )
+            .to_string(),
+            "208bdcaf2d83ae026964e23659c703a377473168a39cbdc2b0241115"
+        );
+
+        assert!(parse(&json!({ "type": "sig", "keyHash": "00" })).is_err());
+        assert!(parse(&json!({ "type": "unknown" })).is_err());
+        assert!(parse(&json!("ff")).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{
+    cbor::{
+        haskell_types::{
+            ApplyConwayTxPredError, ApplyTxErr, ConwayUtxoPredFailure, ConwayUtxoWPredFailure,
+            DecoderError, DeserialiseFailure, Network as LedgerNetwork, ShelleyBasedEra,
+            TxSubmitFail, TxValidationError, ValidityInterval,
+        },
+        slot::SlotNo,
+    },
+    cli::Network,
+    node::{protocol_params::ProtocolParams, transactions::submit_fail},
+    BlockfrostError,
+};
+use pallas::ledger::addresses::{Address, Network as AddressNetwork};
+use pallas_traverse::{Era, MultiEraTx};
+
+/// What `--pre-validate` checks a transaction against, without asking the node.
+pub struct LedgerView<'a> {
+    pub network: &'a Network,
+    /// The slot of the node’s tip, as the chain follower last saw it
+    pub tip: Option<SlotNo>,
+    pub params: Option<&'a ProtocolParams>,
+}
+
+/// Checks the ledger rules of `tx` that need neither its inputs nor scripts,
+/// failing like the node would, so that obviously invalid transactions don’t
+/// take a node connection. Only Conway transactions are checked beyond their
+/// decoding, and only what `view` knows of.
+pub fn pre_validate(tx: &[u8], view: &LedgerView) -> Result<(), BlockfrostError> {
+    let decoded = MultiEraTx::decode(tx).map_err(|e| {
+        submit_fail(
+            "TxSubmitDecodeFail",
+            TxSubmitFail::TxSubmitDecodeFail(DecoderError::DecoderErrorDeserialiseFailure(
+                "Tx".to_string(),
+                DeserialiseFailure(0, e.to_string()),
+            )),
+        )
+    })?;
+    if decoded.era() != Era::Conway {
+        return Ok(());
+    }
+
+    let failures = utxo_failures(&decoded, tx.len(), view);
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    let error = TxValidationError::ShelleyTxValidationError {
+        error: ApplyTxErr(
+            failures
+                .into_iter()
+                .map(|failure| {
+                    ApplyConwayTxPredError::UtxowFailure(ConwayUtxoWPredFailure::UtxoFailure(
+                        failure,
+                    ))
+                })
+                .collect(),
+        ),
+        era: ShelleyBasedEra::ShelleyBasedEraConway,
+    };
+
+    Err(submit_fail("TxSubmitFail", TxSubmitFail::from(error)))
+}
+
+fn utxo_failures(tx: &MultiEraTx, size: usize, view: &LedgerView) -> Vec<ConwayUtxoPredFailure> {
+    let mut failures = vec![];
+
+    if tx.inputs().is_empty() {
+        failures.push(ConwayUtxoPredFailure::InputSetEmptyUTxO());
+    }
+
+    // The mempool applies it on top of the tip, i.e. in the next slot
+    if let Some(slot) = view.tip.and_then(|tip| tip.checked_add(1)) {
+        let invalid_before = tx.validity_start().map(SlotNo);
+        let invalid_hereafter = tx.ttl().map(SlotNo);
+
+        if invalid_before.is_some_and(|before| slot < before)
+            || invalid_hereafter.is_some_and(|hereafter| slot >= hereafter)
+        {
+            failures.push(ConwayUtxoPredFailure::OutsideValidityIntervalUTxO(
+                ValidityInterval {
+                    invalid_before,
+                    invalid_hereafter,
+                },
+                slot,
+            ));
+        }
+    }
+
+    // Only a lower bound of the minimum fee, without reference scripts and
+    // execution units, so this never rejects what the node would accept
+    if let Some((min_fee_a, min_fee_b)) = view
+        .params
+        .and_then(|params| Some((params.min_fee_a?, params.min_fee_b?)))
+    {
+        let min_fee = u64::from(min_fee_a) * size as u64 + u64::from(min_fee_b);
+        let fee = tx.fee().unwrap_or_default();
+
+        if fee < min_fee {
+            failures.push(ConwayUtxoPredFailure::FeeTooSmallUTxO(min_fee, fee));
+        }
+    }
+
+    let (expected, ledger_network) = match view.network {
+        Network::Mainnet => (AddressNetwork::Mainnet, LedgerNetwork::Mainnet),
+        _ => (AddressNetwork::Testnet, LedgerNetwork::Testnet),
+    };
+    let wrong_network: Vec<_> = tx
+        .outputs()
+        .iter()
+        .filter_map(|output| match output.address() {
+            Ok(address @ Address::Shelley(_)) if address.network() != Some(expected) => {
+                Some(address.to_vec().into())
+            }
+            _ => None,
+        })
+        .collect();
+    if !wrong_network.is_empty() {
+        failures.push(ConwayUtxoPredFailure::WrongNetwork(
+            ledger_network,
+            wrong_network,
+        ));
+    }
+
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::mock::{CONWAY_TX, CONWAY_TX_EXPIRED};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn failures(tx: &str, network: Network, tip: Option<u64>, min_fee_a: u32) -> Vec<String> {
+        let params = ProtocolParams {
+            min_fee_a: Some(min_fee_a),
+            min_fee_b: Some(155_381),
+            ..Default::default()
+        };
+        let view = LedgerView {
+            network: &network,
+            tip: tip.map(SlotNo),
+            params: Some(&params),
+        };
+
+        match pre_validate(&hex::decode(tx).unwrap(), &view) {
+            Ok(()) => vec![],
+            Err(e) => {
+                let details = e.details.unwrap();
+                let failures = &details["contents"]["contents"]["contents"]["error"];
+                failures
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|failure| failure.as_str().unwrap().to_string())
+                    .collect()
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(CONWAY_TX, Network::Preview, Some(70_000_000), 44, vec![])]
+    // Unknown tip
+    #[case(CONWAY_TX_EXPIRED, Network::Preview, None, 44, vec![])]
+    #[case(
+        CONWAY_TX_EXPIRED,
+        Network::Preview,
+        Some(70_000_000),
+        44,
+        vec!["UtxowFailure (UtxoFailure (OutsideValidityIntervalUTxO (ValidityInterval { invalid_before: None, invalid_hereafter: 100000 }, 70000001)))"]
+    )]
+    #[case(
+        CONWAY_TX,
+        Network::Mainnet,
+        None,
+        44,
+        vec![
+            "UtxowFailure (UtxoFailure (WrongNetwork (Mainnet, 00ddf1eb9ce2a1561e8f156991486b97873fb6969190cbc99ddcb3816621dcb03574152623414ed354d2d8f50e310f3f2e7d167cb20e575427 0099a5cb0fa8f19aba38cacf8a243d632149129f882df3a8e67f6bd512bcb0cde66a545e9fbc7ca4492f39bca1f4f265cc1503b4f7d6ff205c)))"
+        ]
+    )]
+    #[case(
+        CONWAY_TX,
+        Network::Preview,
+        None,
+        1_000,
+        vec!["UtxowFailure (UtxoFailure (FeeTooSmallUTxO (449381, 172717)))"]
+    )]
+    fn test_pre_validate(
+        #[case] tx: &str,
+        #[case] network: Network,
+        #[case] tip: Option<u64>,
+        #[case] min_fee_a: u32,
+        #[case] expected: Vec<&str>,
+    ) {
+        assert_eq!(failures(tx, network, tip, min_fee_a), expected);
+    }
+
+    #[test]
+    fn test_pre_validate_undecodable() {
+        let view = LedgerView {
+            network: &Network::Preview,
+            tip: None,
+            params: None,
+        };
+        // An empty body, without even a fee
+        let err = pre_validate(&[0x83, 0xa0, 0xa0, 0xf6], &view).unwrap_err();
+
+        assert_eq!(err.status_code, 400);
+        assert_eq!(err.details.unwrap()["tag"], "TxSubmitDecodeFail");
+    }
+}
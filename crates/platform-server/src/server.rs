@@ -0,0 +1,365 @@
+use crate::{
+    api::{
+        events::EventStream, health::ObservabilityHealth, metrics::setup_metrics_recorder, proxy,
+        proxy::BlockfrostProxy, stats::Stats, tx_confirmations::ConfirmationTracker,
+        tx_status::SubmissionStatuses, tx_submit::SubmitOptions,
+    },
+    background_tasks::load_report_task,
+    cbor::fallback_decoder::FallbackDecoder,
+    cli::Config,
+    errors::AppError,
+    hooks::SubmissionHooks,
+    icebreakers_api::IcebreakersAPI,
+    middlewares::{
+        cors::cors_layer,
+        errors::error_middleware,
+        metrics::track_http_metrics,
+        operator_auth::require_operator,
+        problem_json::problem_json,
+        rate_limit::{rate_limit, RateLimiter},
+        request_id::request_id,
+        response_cache::{cache_response, ResponseCache},
+        trace_sampling::{trace_requests, TraceSampler},
+        traffic::{record_traffic, TrafficRecorder},
+    },
+    node::{
+        chainsync::{ChainFollower, RecentChain},
+        pool::NodePool,
+        protocol_params::ProtocolParamsHistory,
+    },
+    spool::OfflineSpool,
+    storage::DataDir,
+    submission_dedup::SubmissionDedup,
+    submission_queue::{SubmissionQueue, Submitter},
+};
+use axum::{
+    middleware::{from_fn, from_fn_with_state},
+    Extension, Router,
+};
+use routes::Auth;
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::normalize_path::{NormalizePath, NormalizePathLayer};
+use tracing::warn;
+
+pub mod routes;
+pub mod tls;
+
+/// What [`build`] sets up: the `router` to serve, and the state it shares with
+/// the background tasks.
+pub struct App {
+    pub router: NormalizePath<Router>,
+    pub node_pool: NodePool,
+    pub protocol_params_history: ProtocolParamsHistory,
+    pub submission_statuses: SubmissionStatuses,
+    pub confirmation_tracker: ConfirmationTracker,
+    pub recent_chain: RecentChain,
+}
+
+/// Builds and configures the Axum `Router`.
+/// Returns `Ok(App)` on success or an `AppError` if a step fails.
+pub async fn build(config: Arc<Config>) -> Result<App, AppError> {
+    build_with_hooks(config, SubmissionHooks::new()).await
+}
+
+/// Like [`build`], for crates embedding the platform, running their `hooks`
+/// around every transaction submission.
+pub async fn build_with_hooks(
+    config: Arc<Config>,
+    hooks: SubmissionHooks,
+) -> Result<App, AppError> {
+    // Set up fallback decoder
+    let fallback_decoder = FallbackDecoder::spawn()?;
+
+    fallback_decoder
+        .startup_sanity_test()
+        .await
+        .map_err(AppError::Server)?;
+
+    // Set up optional data directory
+    let data_dir = match &config.server.data_dir {
+        Some(path) => {
+            let data_dir = DataDir::new(path, config.limits.data_dir_quota_mb * 1024 * 1024)?;
+            data_dir.prune_all();
+            Some(data_dir)
+        }
+        None => None,
+    };
+
+    // Create node pool
+    let node_conn_pool = NodePool::new(&config.node, fallback_decoder, data_dir.clone())?;
+
+    // Set up optional Icebreakers API (solitary option in CLI)
+    let icebreakers_api = IcebreakersAPI::new(&config).await?;
+
+    // Metrics recorder
+    let mut observability_health = ObservabilityHealth::default();
+    let prometheus_handle = if config.observability.metrics {
+        // Serving matters more than being observed
+        match setup_metrics_recorder(&config) {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                warn!("{}, continuing without metrics", e);
+                observability_health = ObservabilityHealth::metrics_failed(e.to_string());
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Protocol parameters seen per epoch, filled in by a background task
+    let protocol_params_history = ProtocolParamsHistory::default();
+
+    // Outcomes of submissions, followed until they're in a block
+    let submission_statuses = SubmissionStatuses::new(
+        config.limits.submission_statuses,
+        config.node.genesis.clone(),
+    );
+    let hooks = hooks.with(submission_statuses.clone());
+
+    // Accepted transactions, followed until they're deep enough in the chain
+    let confirmation_tracker =
+        ConfirmationTracker::new(config.limits.submission_statuses, data_dir.clone());
+    let hooks = hooks.with(confirmation_tracker.clone());
+
+    // Recent blocks of the node, and events about them and submissions for `/ws`,
+    // left empty in compact mode
+    let recent_chain = if config.node.socket_path.is_empty() || !config.server.mode.follows_chain()
+    {
+        RecentChain::new(config.limits.recent_blocks)
+    } else {
+        ChainFollower::spawn(&config.node, config.limits.recent_blocks, data_dir.clone())
+    };
+    let event_stream = EventStream::spawn(config.limits.submission_statuses, &recent_chain);
+    let hooks = hooks.with(event_stream.clone());
+
+    // Per-route request statistics, and the submit SLO, from how submissions
+    // end up rather than what the client was answered
+    let stats = Stats::new(config.observability.slo_target);
+    let hooks = hooks.with(stats.clone());
+
+    // The node's era, cached for submissions, until a hard fork may change it
+    node_conn_pool.era_cache().invalidate_on(&recent_chain);
+
+    // Asynchronous submissions, drained into the node pool in the background
+    let submitter = Submitter {
+        relay: node_conn_pool.relay().cloned(),
+        options: SubmitOptions::from_config(&config),
+        hooks: hooks.clone(),
+        history: protocol_params_history.clone(),
+        statuses: submission_statuses.clone(),
+    };
+    let submission_queue = SubmissionQueue::spawn(
+        config.limits.submission_queue,
+        config.node.max_pool_connections,
+        node_conn_pool.clone(),
+        submitter.clone(),
+    );
+
+    // Optional spool of submissions for while the node is unreachable
+    let offline_spool = match (&data_dir, config.server.spool_offline) {
+        (Some(data_dir), true) => Some(OfflineSpool::spawn(
+            &config,
+            data_dir.clone(),
+            node_conn_pool.clone(),
+            submitter,
+        )),
+        _ => None,
+    };
+
+    // Anonymized load reports to the Icebreakers API, unless opted out of
+    if let (Some(api), Some(true)) = (
+        &icebreakers_api,
+        config.icebreakers.as_ref().map(|i| i.telemetry),
+    ) {
+        tokio::spawn(load_report_task(
+            api.clone(),
+            stats.clone(),
+            node_conn_pool.clone(),
+        ));
+    }
+
+    // Request tracing, sampled per route
+    let trace_sampler = TraceSampler::new(
+        config.observability.trace_sample_rate,
+        config
+            .observability
+            .trace_sample_routes
+            .iter()
+            .cloned()
+            .collect(),
+    );
+
+    // Optional proxy to Blockfrost for the read endpoints we don't serve
+    let blockfrost_proxy = BlockfrostProxy::new(&config);
+    if let Some(proxy) = &blockfrost_proxy {
+        proxy.invalidate_on(&recent_chain);
+    }
+
+    // Optional traffic recording, rotated with the same quota as the data directory
+    let traffic_recorder = match &config.server.record_traffic {
+        Some(dir) => Some(TrafficRecorder::new(
+            dir,
+            config.limits.data_dir_quota_mb * 1024 * 1024,
+            config.server.record_bodies,
+            config.icebreakers.as_ref().map(|i| i.reward_address_hash()),
+        )?),
+        None => None,
+    };
+
+    // Build a prefix
+    let api_prefix = if let Some(api) = &icebreakers_api {
+        api.api_prefix.clone()
+    } else {
+        "/".to_string()
+    };
+
+    // Cached responses of the routes querying the ledger state, each for its TTL
+    let response_cache = Arc::new(ResponseCache::default());
+    let specs = routes::registry().into_iter().map(|mut spec| {
+        if let Some(ttl) = spec.cache_ttl {
+            spec.handler = spec.handler.layer(from_fn_with_state(
+                (response_cache.clone(), spec.path, ttl),
+                cache_response,
+            ));
+        }
+        spec
+    });
+
+    // Operator routes behind `--operator-token`, or public without one
+    let operator_token: Option<Arc<str>> = config.server.operator_token.as_deref().map(Arc::from);
+    let specs = specs.map(|mut spec| {
+        if spec.auth == Auth::Operator {
+            match &operator_token {
+                Some(token) => {
+                    spec.handler = spec
+                        .handler
+                        .layer(from_fn_with_state(token.clone(), require_operator))
+                }
+                None => spec.auth = Auth::Public,
+            }
+        }
+        spec
+    });
+
+    // Optional rate limits, applied to each route according to its class, cached
+    // responses included
+    let specs = match RateLimiter::new(&config.limits) {
+        Some(limiter) => specs
+            .map(|mut spec| {
+                spec.handler = spec.handler.layer(from_fn_with_state(
+                    (limiter.clone(), spec.rate_limit),
+                    rate_limit,
+                ));
+                spec
+            })
+            .collect(),
+        None => specs.collect(),
+    };
+
+    // Routes
+    let network = config.node.network.clone();
+    let cors = cors_layer(&config.cors);
+    let api_routes = routes::router(specs, config.server.read_only, config.server.mode)
+        .layer(Extension(prometheus_handle))
+        .layer(Extension(observability_health))
+        .layer(Extension(stats.clone()))
+        .layer(Extension(protocol_params_history.clone()))
+        .layer(Extension(data_dir))
+        .layer(Extension(config))
+        .layer(Extension(hooks))
+        .layer(Extension(submission_statuses.clone()))
+        .layer(Extension(submission_queue))
+        .layer(Extension(SubmissionDedup::default()))
+        .layer(Extension(offline_spool))
+        .layer(Extension(confirmation_tracker.clone()))
+        .layer(Extension(event_stream))
+        .layer(Extension(recent_chain.clone()))
+        .layer(Extension(node_conn_pool.clone()))
+        .fallback(proxy::fallback_route)
+        .layer(Extension(blockfrost_proxy))
+        .layer(Extension(icebreakers_api))
+        .layer(from_fn(error_middleware))
+        .layer(from_fn(problem_json))
+        .route_layer(from_fn_with_state(stats, track_http_metrics))
+        .route_layer(from_fn_with_state(trace_sampler, trace_requests))
+        .layer(from_fn_with_state(network, request_id));
+
+    // Record traffic outermost, so that it sees what clients see
+    let api_routes = match traffic_recorder {
+        Some(recorder) => api_routes.layer(from_fn_with_state(recorder, record_traffic)),
+        None => api_routes,
+    };
+
+    // Nest prefix
+    let app = if api_prefix == "/" || api_prefix.is_empty() {
+        Router::new().merge(api_routes)
+    } else {
+        Router::new().nest(&api_prefix, api_routes)
+    };
+
+    // Answer CORS preflight requests before routing
+    let app = match cors {
+        Some(layer) => app.layer(layer),
+        None => app,
+    };
+
+    // Final layers (e.g., trim trailing slash)
+    let app = ServiceBuilder::new()
+        .layer(NormalizePathLayer::trim_trailing_slash())
+        .service(app);
+
+    Ok(App {
+        router: app,
+        node_pool: node_conn_pool,
+        protocol_params_history,
+        submission_statuses,
+        confirmation_tracker,
+        recent_chain,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Mode;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    #[rstest]
+    #[case("/tx/submit", false, false)]
+    #[case("/tx/submit", true, true)]
+    #[case("/api/submit/tx", false, false)]
+    #[case("/api/submit/tx", true, true)]
+    #[tokio::test]
+    async fn test_read_only_routes(
+        #[case] path: &str,
+        #[case] read_only: bool,
+        #[case] not_found: bool,
+    ) {
+        let response = routes::router(routes::registry(), read_only, Mode::Compact)
+            .oneshot(Request::post(path).body(Body::from("84a300")).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status() == StatusCode::NOT_FOUND, not_found);
+
+        // Still served either way
+        let response = routes::router(routes::registry(), read_only, Mode::Compact)
+            .oneshot(
+                Request::post("/utils/bech32/decode")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_ne!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
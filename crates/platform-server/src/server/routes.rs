@@ -0,0 +1,406 @@
+use crate::{
+    api::{
+        accounts, admin, blocks, debug, events, governance, health, metrics, network, pools,
+        protocol_params, root, stats, tx_confirmations, tx_status, tx_submit, utils,
+    },
+    cli::Mode,
+};
+use axum::{
+    handler::Handler,
+    http::Method,
+    routing::{self, MethodRouter},
+    Json, Router,
+};
+use serde_json::{json, Map, Value};
+use std::time::Duration;
+
+const ALL_MODES: &[Mode] = &[Mode::Compact, Mode::Light, Mode::Full];
+/// Those following the node’s chain, see [`Mode::follows_chain`].
+const FOLLOWING_MODES: &[Mode] = &[Mode::Light, Mode::Full];
+
+/// One API route, with everything deciding where and how it’s served.
+pub struct RouteSpec {
+    pub method: Method,
+    pub path: &'static str,
+    pub handler: MethodRouter,
+    /// Routes changing state, i.e. submitting transactions, aren’t there at
+    /// all in `--read-only` mode.
+    pub writes: bool,
+    pub modes: &'static [Mode],
+    pub auth: Auth,
+    pub rate_limit: RateLimitClass,
+    /// How long its successful responses are cached, if at all, see
+    /// [`ResponseCache`](crate::middlewares::response_cache::ResponseCache)
+    pub cache_ttl: Option<Duration>,
+    pub summary: &'static str,
+    pub tag: &'static str,
+}
+
+/// Who a route is meant for, as declared in `/openapi.json`. Operator routes
+/// need `--operator-token`, see
+/// [`require_operator`](crate::middlewares::operator_auth::require_operator),
+/// and are public without it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Auth {
+    Public,
+    /// Reveals the operator’s setup, e.g. metrics and storage
+    Operator,
+}
+
+/// How costly a route is to the node, to group routes for rate limiting with
+/// the `--rate-limit-*` flags. Also declared in `/openapi.json`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateLimitClass {
+    Default,
+    /// Queries going through large parts of the ledger state
+    Expensive,
+    Submit,
+}
+
+impl RateLimitClass {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            RateLimitClass::Default => "default",
+            RateLimitClass::Expensive => "expensive",
+            RateLimitClass::Submit => "submit",
+        }
+    }
+}
+
+impl RouteSpec {
+    pub fn get<H, T>(path: &'static str, handler: H) -> Self
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        Self::new(Method::GET, path, routing::get(handler))
+    }
+
+    pub fn post<H, T>(path: &'static str, handler: H) -> Self
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        Self::new(Method::POST, path, routing::post(handler))
+    }
+
+    fn new(method: Method, path: &'static str, handler: MethodRouter) -> Self {
+        Self {
+            method,
+            path,
+            handler,
+            writes: false,
+            modes: ALL_MODES,
+            auth: Auth::Public,
+            rate_limit: RateLimitClass::Default,
+            cache_ttl: None,
+            summary: "",
+            tag: "",
+        }
+    }
+
+    pub fn writes(mut self) -> Self {
+        self.writes = true;
+        self.rate_limit = RateLimitClass::Submit;
+        self
+    }
+
+    pub fn modes(mut self, modes: &'static [Mode]) -> Self {
+        self.modes = modes;
+        self
+    }
+
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    pub fn rate_limit(mut self, rate_limit: RateLimitClass) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    pub fn cache(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    pub fn doc(mut self, tag: &'static str, summary: &'static str) -> Self {
+        self.tag = tag;
+        self.summary = summary;
+        self
+    }
+
+    fn is_served(&self, read_only: bool, mode: Mode) -> bool {
+        !(read_only && self.writes) && self.modes.contains(&mode)
+    }
+}
+
+/// Every route of the API, in the order they’re documented.
+pub fn registry() -> Vec<RouteSpec> {
+    use RateLimitClass::Expensive;
+
+    vec![
+        RouteSpec::get("/", root::route)
+            .cache(Duration::from_secs(1))
+            .doc("health", "Platform and node status"),
+        RouteSpec::get("/health", health::route).doc("health", "Node connection state"),
+        RouteSpec::get("/ready", health::ready_route)
+            .doc("health", "Readiness, checking the node and Icebreakers"),
+        RouteSpec::get("/metrics", metrics::route)
+            .auth(Auth::Operator)
+            .doc("health", "Prometheus metrics"),
+        RouteSpec::get("/accounts/{stake_address}", accounts::route)
+            .rate_limit(Expensive)
+            .cache(Duration::from_secs(20))
+            .doc(
+                "accounts",
+                "Reward balance and delegation of a stake address",
+            ),
+        RouteSpec::get("/blocks/latest", blocks::latest_route).doc("blocks", "Latest block"),
+        RouteSpec::get("/blocks/{hash}", blocks::block_route)
+            .modes(FOLLOWING_MODES)
+            .doc("blocks", "A block of the followed recent window"),
+        RouteSpec::get("/blocks/{hash}/txs", blocks::block_txs_route)
+            .modes(FOLLOWING_MODES)
+            .doc(
+                "blocks",
+                "Transactions of a block of the followed recent window",
+            ),
+        RouteSpec::get("/governance/dreps", governance::dreps_route)
+            .rate_limit(Expensive)
+            .cache(Duration::from_secs(60))
+            .doc("governance", "Registered DReps"),
+        RouteSpec::get("/governance/proposals", governance::proposals_route)
+            .rate_limit(Expensive)
+            .cache(Duration::from_secs(60))
+            .doc("governance", "Governance actions being voted on"),
+        RouteSpec::get(
+            "/governance/proposals/{tx_hash}/{cert_index}/ratification",
+            governance::ratification_route,
+        )
+        .rate_limit(Expensive)
+        .cache(Duration::from_secs(60))
+        .doc(
+            "governance",
+            "Whether a governance action's votes so far meet its thresholds",
+        ),
+        RouteSpec::get("/governance/committee", governance::committee_route)
+            .rate_limit(Expensive)
+            .cache(Duration::from_secs(60))
+            .doc("governance", "Constitutional committee"),
+        RouteSpec::get("/network", network::route)
+            .cache(Duration::from_secs(20))
+            .doc("network", "Network information"),
+        RouteSpec::get("/pools", pools::route)
+            .rate_limit(Expensive)
+            .cache(Duration::from_secs(300))
+            .doc("pools", "Registered stake pools"),
+        RouteSpec::get("/pools/{pool_id}", pools::pool_route)
+            .rate_limit(Expensive)
+            .cache(Duration::from_secs(60))
+            .doc("pools", "Stake pool parameters"),
+        RouteSpec::get("/utils/ttl", utils::ttl_route).doc("utils", "Suggested transaction TTL"),
+        RouteSpec::get("/utils/slot-to-time/{slot}", utils::slot_to_time_route)
+            .doc("utils", "POSIX time of a slot"),
+        RouteSpec::get("/utils/time-to-slot/{time}", utils::time_to_slot_route)
+            .doc("utils", "Slot at a POSIX time"),
+        RouteSpec::post("/utils/bech32/encode", utils::bech32_encode_route)
+            .doc("utils", "Encode bytes as bech32"),
+        RouteSpec::post("/utils/bech32/decode", utils::bech32_decode_route)
+            .doc("utils", "Decode a bech32 string"),
+        RouteSpec::post("/utils/txs/min-utxo", utils::min_utxo_route)
+            .doc("utils", "Minimum lovelace of a transaction output"),
+        RouteSpec::post("/utils/native-script/evaluate", utils::native_script_route).doc(
+            "utils",
+            "Evaluate a native script against signers and a validity interval",
+        ),
+        RouteSpec::get("/stats", stats::route)
+            .auth(Auth::Operator)
+            .doc("health", "Per-route request statistics"),
+        RouteSpec::get("/stats/slo", stats::slo_route)
+            .auth(Auth::Operator)
+            .doc("health", "Transaction submission error budget"),
+        RouteSpec::get("/admin/storage", admin::storage_route)
+            .auth(Auth::Operator)
+            .doc("admin", "Data directory usage"),
+        RouteSpec::get("/debug/decoder-coverage", debug::decoder_coverage_route).doc(
+            "debug",
+            "Rejection reasons the native decoder knows, and its known gaps",
+        ),
+        RouteSpec::get("/protocol-parameters/diff", protocol_params::diff_route)
+            .doc("epochs", "Protocol parameter changes between epochs"),
+        RouteSpec::post("/tx/submit", tx_submit::route)
+            .writes()
+            .doc("transactions", "Submit a transaction"),
+        RouteSpec::post("/tx/submit/batch", tx_submit::batch_route)
+            .writes()
+            .doc(
+                "transactions",
+                "Submit transactions in order, over a single node connection",
+            ),
+        RouteSpec::get("/tx/{txid}/status", tx_status::route)
+            .writes()
+            .rate_limit(RateLimitClass::Default)
+            .doc("transactions", "Outcome of a submitted transaction"),
+        RouteSpec::get("/tx/{txid}/confirmations", tx_confirmations::route)
+            .writes()
+            .modes(FOLLOWING_MODES)
+            .rate_limit(RateLimitClass::Default)
+            .doc(
+                "transactions",
+                "Depth of a submitted transaction in the chain",
+            ),
+        RouteSpec::get("/ws", events::route)
+            .modes(FOLLOWING_MODES)
+            .doc(
+                "blocks",
+                "WebSocket stream of new tips, rollbacks and submitted transactions’ fate",
+            ),
+        RouteSpec::post("/api/submit/tx", tx_submit::submit_api_route)
+            .writes()
+            .doc(
+                "transactions",
+                "Submit a transaction, cardano-submit-api style",
+            ),
+    ]
+}
+
+/// The router of the `specs` served in this configuration, together with
+/// `/openapi.json` describing them.
+pub fn router(specs: Vec<RouteSpec>, read_only: bool, mode: Mode) -> Router {
+    let specs: Vec<RouteSpec> = specs
+        .into_iter()
+        .filter(|spec| spec.is_served(read_only, mode))
+        .collect();
+    let openapi = openapi(&specs);
+
+    specs
+        .into_iter()
+        .fold(Router::new(), |router, spec| {
+            router.route(spec.path, spec.handler)
+        })
+        .route(
+            "/openapi.json",
+            routing::get(move || async move { Json(openapi) }),
+        )
+}
+
+/// A minimal OpenAPI 3 document of the `specs`.
+fn openapi(specs: &[RouteSpec]) -> Value {
+    let mut paths = Map::new();
+
+    for spec in specs {
+        let parameters: Vec<Value> = spec
+            .path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" },
+                })
+            })
+            .collect();
+        let security = match spec.auth {
+            Auth::Public => json!([]),
+            Auth::Operator => json!([{ "operator": [] }]),
+        };
+
+        let operation = json!({
+            "summary": spec.summary,
+            "tags": [spec.tag],
+            "parameters": parameters,
+            "security": security,
+            "x-rate-limit-class": spec.rate_limit.name(),
+            "x-cache-ttl": spec.cache_ttl.map(|ttl| ttl.as_secs()),
+            "responses": { "200": { "description": "OK" } },
+        });
+
+        if let Value::Object(methods) = paths
+            .entry(spec.path)
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            methods.insert(spec.method.as_str().to_lowercase(), operation);
+        }
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Blockfrost Platform",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "securitySchemes": {
+                "operator": { "type": "http", "scheme": "bearer" },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_registry_paths_are_unique() {
+        let mut routes: Vec<_> = registry()
+            .iter()
+            .map(|spec| (spec.path, spec.method.clone()))
+            .collect();
+        let len = routes.len();
+        routes.sort_by_key(|(path, method)| (*path, method.to_string()));
+        routes.dedup();
+
+        assert_eq!(routes.len(), len);
+    }
+
+    #[test]
+    fn test_openapi() {
+        let specs: Vec<_> = registry()
+            .into_iter()
+            .filter(|spec| spec.is_served(true, Mode::Compact))
+            .collect();
+        let doc = openapi(&specs);
+
+        assert_eq!(
+            doc["paths"]["/pools/{pool_id}"]["get"]["parameters"][0]["name"],
+            "pool_id"
+        );
+        assert_eq!(
+            doc["paths"]["/pools/{pool_id}"]["get"]["x-rate-limit-class"],
+            "expensive"
+        );
+        assert_eq!(doc["paths"]["/pools/{pool_id}"]["get"]["x-cache-ttl"], 60);
+        assert_eq!(doc["paths"]["/health"]["get"]["x-cache-ttl"], Value::Null);
+        assert_eq!(
+            doc["paths"]["/admin/storage"]["get"]["security"],
+            json!([{ "operator": [] }])
+        );
+        assert_eq!(
+            doc["paths"]["/stats/slo"]["get"]["security"],
+            json!([{ "operator": [] }])
+        );
+        assert_eq!(doc["paths"]["/tx/submit"], Value::Null);
+        assert_eq!(doc["paths"]["/ws"], Value::Null);
+    }
+
+    #[test]
+    fn test_modes() {
+        let served = |path: &str, mode: Mode| {
+            registry()
+                .iter()
+                .any(|spec| spec.path == path && spec.is_served(false, mode))
+        };
+
+        assert!(!served("/ws", Mode::Compact));
+        assert!(served("/ws", Mode::Light));
+        assert!(served("/ws", Mode::Full));
+        assert!(served("/tx/submit", Mode::Compact));
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{cli::TlsConfig, AppError};
+use axum_server::tls_rustls::RustlsConfig;
+
+/// Reads the certificate chain and private key of `--tls-cert` and
+/// `--tls-key`, failing unless rustls takes them.
+pub async fn load(tls: &TlsConfig) -> Result<RustlsConfig, AppError> {
+    RustlsConfig::from_pem_file(&tls.cert, &tls.key)
+        .await
+        .map_err(|e| {
+            AppError::Server(format!(
+                "Failed to load --tls-cert {} and --tls-key {}: {}",
+                tls.cert.display(),
+                tls.key.display(),
+                e
+            ))
+        })
+}
@@ -0,0 +1,377 @@
+use crate::{cli::Network, BlockfrostError};
+use axum::http::HeaderMap;
+use pallas::ledger::addresses::{Address, Network as AddressNetwork, StakePayload};
+use pallas_codec::minicbor::{data::Type, Decoder, Encoder};
+use pallas_crypto::{
+    hash::Hasher,
+    key::ed25519::{PublicKey, Signature},
+};
+use pallas_traverse::MultiEraTx;
+
+/// The `signature` of a CIP-30 `signData` result, as hex.
+pub const SIGNATURE_HEADER: &str = "x-cip30-signature";
+/// The `key` of a CIP-30 `signData` result, as hex.
+pub const SIGNATURE_KEY_HEADER: &str = "x-cip30-key";
+
+/// COSE algorithm id of EdDSA
+const ALG_EDDSA: i64 = -8;
+/// COSE key parameter of an OKP public key
+const KEY_X: i64 = -2;
+
+/// Verifies the CIP-8 signature sent along with `tx`, if any, returning the
+/// stake address whose key signed it.
+///
+/// The signed payload has to be the id of `tx`, so that a signature can’t be
+/// reused for another transaction. It’s then up to the submission hooks what
+/// to make of the signer, or of its absence.
+pub fn verify(
+    headers: &HeaderMap,
+    tx: &[u8],
+    network: &Network,
+) -> Result<Option<String>, BlockfrostError> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .map(|value| {
+                value
+                    .to_str()
+                    .ok()
+                    .and_then(|value| hex::decode(value.trim()).ok())
+                    .ok_or_else(|| invalid(format!("{} must be hex", name)))
+            })
+            .transpose()
+    };
+
+    let (signature, key) = match (header(SIGNATURE_HEADER)?, header(SIGNATURE_KEY_HEADER)?) {
+        (None, None) => return Ok(None),
+        (Some(signature), Some(key)) => (signature, key),
+        _ => {
+            return Err(invalid(format!(
+                "Both {} and {} are needed",
+                SIGNATURE_HEADER, SIGNATURE_KEY_HEADER
+            )))
+        }
+    };
+
+    let txid = MultiEraTx::decode(tx)
+        .map_err(|e| invalid(format!("Cannot tell which transaction was signed: {}", e)))?
+        .hash();
+
+    let signed = CoseSign1::decode(&signature)?;
+    if signed.payload != txid.as_slice() {
+        return Err(invalid(
+            "The signed payload is not the transaction id".to_string(),
+        ));
+    }
+
+    let public_key = decode_public_key(&key)?;
+    if !public_key.verify(signed.sig_structure(), &signed.signature) {
+        return Err(invalid("The signature does not match".to_string()));
+    }
+
+    stake_address(&signed.address, &public_key, network).map(Some)
+}
+
+/// The parts of a `COSE_Sign1` we need, see RFC 9052.
+struct CoseSign1 {
+    /// The protected header map, encoded, as it’s signed that way
+    protected: Vec<u8>,
+    address: Vec<u8>,
+    payload: Vec<u8>,
+    signature: Signature,
+}
+
+impl CoseSign1 {
+    fn decode(cbor: &[u8]) -> Result<Self, BlockfrostError> {
+        let malformed = |e: &dyn std::fmt::Display| invalid(format!("Malformed COSE_Sign1: {}", e));
+
+        let mut d = Decoder::new(cbor);
+        if d.datatype().map_err(|e| malformed(&e))? == Type::Tag {
+            d.tag().map_err(|e| malformed(&e))?;
+        }
+        d.array().map_err(|e| malformed(&e))?;
+
+        let protected = d.bytes().map_err(|e| malformed(&e))?.to_vec();
+        // Where CIP-8 says whether the payload is hashed, which we don’t take
+        d.skip().map_err(|e| malformed(&e))?;
+        let payload = d.bytes().map_err(|e| malformed(&e))?.to_vec();
+        let signature = Signature::try_from(d.bytes().map_err(|e| malformed(&e))?)
+            .map_err(|e| malformed(&e))?;
+
+        let mut alg = None;
+        let mut address = None;
+        let mut d = Decoder::new(&protected);
+        for _ in 0..d.map().map_err(|e| malformed(&e))?.unwrap_or_default() {
+            match d.datatype().map_err(|e| malformed(&e))? {
+                Type::String => match d.str().map_err(|e| malformed(&e))? {
+                    "address" => address = Some(d.bytes().map_err(|e| malformed(&e))?.to_vec()),
+                    _ => d.skip().map_err(|e| malformed(&e))?,
+                },
+                _ => match d.i64().map_err(|e| malformed(&e))? {
+                    1 => alg = Some(d.i64().map_err(|e| malformed(&e))?),
+                    _ => d.skip().map_err(|e| malformed(&e))?,
+                },
+            }
+        }
+
+        if alg != Some(ALG_EDDSA) {
+            return Err(invalid("Only EdDSA signatures are supported".to_string()));
+        }
+
+        Ok(Self {
+            address: address.ok_or_else(|| invalid("No address in the signature".to_string()))?,
+            protected,
+            payload,
+            signature,
+        })
+    }
+
+    /// What was actually signed: the `Sig_structure` of RFC 9052, with no
+    /// external data.
+    fn sig_structure(&self) -> Vec<u8> {
+        let mut e = Encoder::new(Vec::new());
+        // Writing to a Vec can’t fail
+        let _ = e
+            .array(4)
+            .and_then(|e| e.str("Signature1"))
+            .and_then(|e| e.bytes(&self.protected))
+            .and_then(|e| e.bytes(&[]))
+            .and_then(|e| e.bytes(&self.payload));
+
+        e.into_writer()
+    }
+}
+
+/// The `x` of a `COSE_Key`, the only thing an Ed25519 key has.
+fn decode_public_key(cbor: &[u8]) -> Result<PublicKey, BlockfrostError> {
+    let malformed = |e: &dyn std::fmt::Display| invalid(format!("Malformed COSE_Key: {}", e));
+
+    let mut d = Decoder::new(cbor);
+    for _ in 0..d.map().map_err(|e| malformed(&e))?.unwrap_or_default() {
+        let label = match d.datatype().map_err(|e| malformed(&e))? {
+            Type::String => d.str().map(|_| None).map_err(|e| malformed(&e))?,
+            _ => Some(d.i64().map_err(|e| malformed(&e))?),
+        };
+
+        if label == Some(KEY_X) {
+            return PublicKey::try_from(d.bytes().map_err(|e| malformed(&e))?)
+                .map_err(|e| malformed(&e));
+        }
+        d.skip().map_err(|e| malformed(&e))?;
+    }
+
+    Err(invalid("No public key in the COSE_Key".to_string()))
+}
+
+/// The stake address signed with, as long as it’s one of `network`, and of
+/// the very key that signed.
+fn stake_address(
+    address: &[u8],
+    public_key: &PublicKey,
+    network: &Network,
+) -> Result<String, BlockfrostError> {
+    let Ok(Address::Stake(address)) = Address::from_bytes(address) else {
+        return Err(invalid("Not signed with a stake address".to_string()));
+    };
+
+    let expected = match network {
+        Network::Mainnet => AddressNetwork::Mainnet,
+        Network::Preprod | Network::Preview | Network::Sanchonet | Network::Custom => {
+            AddressNetwork::Testnet
+        }
+    };
+    if address.network() != expected {
+        return Err(invalid(format!(
+            "The stake address is not one of {:?}",
+            network
+        )));
+    }
+
+    match address.payload() {
+        StakePayload::Stake(hash) if *hash == Hasher::<224>::hash(public_key.as_ref()) => address
+            .to_bech32()
+            .map_err(|e| invalid(format!("Invalid stake address: {}", e))),
+        _ => Err(invalid(
+            "The stake address is not of the signing key".to_string(),
+        )),
+    }
+}
+
+fn invalid(message: String) -> BlockfrostError {
+    BlockfrostError::custom_400(format!("Invalid signature: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::mock::CONWAY_TX;
+    use pallas_crypto::key::ed25519::SecretKey;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// What a CIP-30 wallet would send for `payload`, signed with `key` for
+    /// the testnet stake address of `address_key`.
+    fn sign_data(key: &SecretKey, address_key: &SecretKey, payload: &[u8]) -> (String, String) {
+        let address = [
+            &[0xe0][..],
+            Hasher::<224>::hash(address_key.public_key().as_ref()).as_slice(),
+        ]
+        .concat();
+
+        let mut protected = Encoder::new(Vec::new());
+        protected
+            .map(2)
+            .unwrap()
+            .i64(1)
+            .unwrap()
+            .i64(ALG_EDDSA)
+            .unwrap()
+            .str("address")
+            .unwrap()
+            .bytes(&address)
+            .unwrap();
+        let protected = protected.into_writer();
+
+        let unsigned = CoseSign1 {
+            protected: protected.clone(),
+            address,
+            payload: payload.to_vec(),
+            signature: Signature::from([0; Signature::SIZE]),
+        };
+        let signature = key.sign(unsigned.sig_structure());
+
+        let mut cose_sign1 = Encoder::new(Vec::new());
+        cose_sign1
+            .array(4)
+            .unwrap()
+            .bytes(&protected)
+            .unwrap()
+            .map(1)
+            .unwrap()
+            .str("hashed")
+            .unwrap()
+            .bool(false)
+            .unwrap()
+            .bytes(payload)
+            .unwrap()
+            .bytes(signature.as_ref())
+            .unwrap();
+
+        let mut cose_key = Encoder::new(Vec::new());
+        cose_key
+            .map(4)
+            .unwrap()
+            .i64(1)
+            .unwrap()
+            .i64(1)
+            .unwrap()
+            .i64(3)
+            .unwrap()
+            .i64(ALG_EDDSA)
+            .unwrap()
+            .i64(-1)
+            .unwrap()
+            .i64(6)
+            .unwrap()
+            .i64(KEY_X)
+            .unwrap()
+            .bytes(key.public_key().as_ref())
+            .unwrap();
+
+        (
+            hex::encode(cose_sign1.into_writer()),
+            hex::encode(cose_key.into_writer()),
+        )
+    }
+
+    fn headers(signature: &str, key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        headers.insert(SIGNATURE_KEY_HEADER, key.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_verify() {
+        let tx = hex::decode(CONWAY_TX).unwrap();
+        let txid = MultiEraTx::decode(&tx).unwrap().hash();
+        let key = SecretKey::from([7; SecretKey::SIZE]);
+        let (signature, cose_key) = sign_data(&key, &key, txid.as_slice());
+
+        let signer = verify(&headers(&signature, &cose_key), &tx, &Network::Preview).unwrap();
+        assert!(signer.unwrap().starts_with("stake_test1"));
+
+        assert_eq!(verify(&HeaderMap::new(), &tx, &Network::Preview), Ok(None));
+    }
+
+    #[rstest]
+    #[case::other_payload(
+        7,
+        7,
+        false,
+        Network::Preview,
+        "The signed payload is not the transaction id"
+    )]
+    #[case::other_address(
+        7,
+        8,
+        true,
+        Network::Preview,
+        "The stake address is not of the signing key"
+    )]
+    #[case::other_network(
+        7,
+        7,
+        true,
+        Network::Mainnet,
+        "The stake address is not one of Mainnet"
+    )]
+    fn test_verify_rejects(
+        #[case] key: u8,
+        #[case] address_key: u8,
+        #[case] signs_txid: bool,
+        #[case] network: Network,
+        #[case] expected: &str,
+    ) {
+        let tx = hex::decode(CONWAY_TX).unwrap();
+        let txid = MultiEraTx::decode(&tx).unwrap().hash();
+        let (signature, cose_key) = sign_data(
+            &SecretKey::from([key; SecretKey::SIZE]),
+            &SecretKey::from([address_key; SecretKey::SIZE]),
+            if signs_txid {
+                txid.as_slice()
+            } else {
+                &[0xff; 32]
+            },
+        );
+
+        let err = verify(&headers(&signature, &cose_key), &tx, &network).unwrap_err();
+        assert_eq!(err.message, format!("Invalid signature: {}", expected));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let tx = hex::decode(CONWAY_TX).unwrap();
+        let txid = MultiEraTx::decode(&tx).unwrap().hash();
+        let (signature, _) = sign_data(
+            &SecretKey::from([7; SecretKey::SIZE]),
+            &SecretKey::from([7; SecretKey::SIZE]),
+            txid.as_slice(),
+        );
+        let (_, other_key) = sign_data(
+            &SecretKey::from([8; SecretKey::SIZE]),
+            &SecretKey::from([8; SecretKey::SIZE]),
+            txid.as_slice(),
+        );
+
+        let err = verify(&headers(&signature, &other_key), &tx, &Network::Preview).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Invalid signature: The signature does not match"
+        );
+
+        let mut headers = headers(&signature, &other_key);
+        headers.remove(SIGNATURE_KEY_HEADER);
+        assert!(verify(&headers, &tx, &Network::Preview).is_err());
+    }
+}
@@ -0,0 +1,483 @@
+use crate::{
+    api::{
+        tx_status::{SubmissionStatus, SubmissionStatuses},
+        tx_submit::submit_result,
+        utils::TxDeadline,
+    },
+    cli::Config,
+    hooks::{SubmissionHooks, SubmissionOutcome, SubmittedTx},
+    node::{
+        api::NodeApi,
+        transactions::{tx_id, SubmitTimings},
+    },
+    storage::{Component, DataDir},
+    submission_queue::Submitter,
+    AppError, BlockfrostError, NodePool,
+};
+use pallas_traverse::wellknown::GenesisValues;
+use std::{
+    collections::VecDeque,
+    fs,
+    future::Future,
+    ops::DerefMut,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{info, warn};
+
+/// How often the spool is looked at, to be submitted once the node is back.
+const DRAIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Submissions held in the data directory while the node is unreachable, to
+/// be submitted in the order they came in once it’s back.
+///
+/// Opt-in with `--spool-offline`, as they may reach the node long after the
+/// client expected, with their inputs spent or their TTL passed by then.
+#[derive(Clone)]
+pub struct OfflineSpool(Arc<Mutex<Spool>>);
+
+struct Spool {
+    /// Oldest first
+    txs: VecDeque<Spooled>,
+    max_txs: usize,
+    max_age: Duration,
+    genesis: GenesisValues,
+    data_dir: DataDir,
+    statuses: SubmissionStatuses,
+    /// Told about the submissions dropped unsubmitted
+    hooks: SubmissionHooks,
+}
+
+#[derive(Clone)]
+struct Spooled {
+    txid: String,
+    /// UNIX time in milliseconds, which also orders the files
+    spooled_at: u64,
+    deadline: Option<TxDeadline>,
+    cbor: Vec<u8>,
+}
+
+impl OfflineSpool {
+    /// Starts with the submissions left in `data_dir` by a previous run, if
+    /// any.
+    pub fn new(
+        data_dir: DataDir,
+        max_txs: usize,
+        max_age: Duration,
+        genesis: GenesisValues,
+        statuses: SubmissionStatuses,
+        hooks: SubmissionHooks,
+    ) -> Self {
+        let mut file_names: Vec<String> = fs::read_dir(data_dir.path(Component::Spool))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        file_names.sort();
+
+        let txs: VecDeque<Spooled> = file_names
+            .iter()
+            .filter_map(|file_name| {
+                let (spooled_at, _) = file_name.strip_suffix(".cbor")?.split_once('-')?;
+                let cbor = fs::read(data_dir.path(Component::Spool).join(file_name)).ok()?;
+
+                Some(Spooled::new(spooled_at.parse().ok()?, cbor, &genesis))
+            })
+            .collect();
+
+        if !txs.is_empty() {
+            info!(
+                "{} submission(s) left in the offline spool from a previous run",
+                txs.len()
+            );
+        }
+        for spooled in &txs {
+            statuses.set(&spooled.txid, SubmissionStatus::QueuedOffline);
+        }
+
+        Self(Arc::new(Mutex::new(Spool {
+            txs,
+            max_txs,
+            max_age,
+            genesis,
+            data_dir,
+            statuses,
+            hooks,
+        })))
+    }
+
+    /// Like [`OfflineSpool::new`], with the limits of `config`, submitting
+    /// the spool to `node` in the background whenever it can be reached.
+    pub fn spawn(config: &Config, data_dir: DataDir, node: NodePool, submitter: Submitter) -> Self {
+        let spool = Self::new(
+            data_dir,
+            config.limits.spool_max_txs,
+            Duration::from_secs(config.limits.spool_max_age_secs),
+            config.node.genesis.clone(),
+            submitter.statuses.clone(),
+            submitter.hooks.clone(),
+        );
+
+        let draining = spool.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(DRAIN_INTERVAL).await;
+                draining.drain(&submitter, || node.get()).await;
+            }
+        });
+
+        spool
+    }
+
+    /// Whether a submission has to wait here: while the node is known to be
+    /// unreachable with no relay to fall back to, and also while older ones
+    /// are still waiting, to keep them in order.
+    pub fn holds(&self, node: &NodePool) -> bool {
+        !self
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .txs
+            .is_empty()
+            || (node.relay().is_none() && node.health().is_down())
+    }
+
+    /// Spools `tx`, returning its id, or fails if the spool is full.
+    pub fn push(&self, tx: Vec<u8>) -> Result<String, BlockfrostError> {
+        let mut spool = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        spool.prune_expired();
+
+        if spool.txs.len() >= spool.max_txs {
+            return Err(BlockfrostError::service_unavailable(
+                "The node is unreachable, and too many transactions are waiting for it already"
+                    .to_string(),
+            ));
+        }
+
+        let spooled = Spooled::new(unix_now_millis(), tx, &spool.genesis);
+        if spool.is_expired(&spooled) {
+            return Err(BlockfrostError::service_unavailable(
+                "The node is unreachable, and the transaction’s TTL has passed".to_string(),
+            ));
+        }
+
+        spool
+            .data_dir
+            .write(Component::Spool, &spooled.file_name(), &spooled.cbor)
+            .map_err(|e| {
+                BlockfrostError::internal_server_error(format!(
+                    "Failed to spool the transaction: {}",
+                    e
+                ))
+            })?;
+
+        let txid = spooled.txid.clone();
+        spool.statuses.set(&txid, SubmissionStatus::QueuedOffline);
+        spool.txs.push_back(spooled);
+
+        Ok(txid)
+    }
+
+    /// Submits the spooled transactions one at a time, oldest first, to a
+    /// node from `get_node`, as long as one can be had. Those failing on the
+    /// way to the node, rather than accepted or rejected, stay for next time,
+    /// so that the hooks only hear about the others.
+    pub async fn drain<N, F>(&self, submitter: &Submitter, mut get_node: impl FnMut() -> F)
+    where
+        F: Future<Output = Result<N, AppError>>,
+        N: DerefMut<Target: NodeApi>,
+    {
+        loop {
+            let Some(next) = self.next() else {
+                return;
+            };
+
+            // Still unreachable, so no rejection for it
+            if get_node().await.is_err() {
+                return;
+            }
+
+            let result = submit_result(
+                &mut get_node,
+                submitter.relay.as_ref(),
+                &submitter.options.retry,
+                None,
+                &SubmissionHooks::new(),
+                &submitter.history,
+                next.cbor.clone(),
+                None,
+                &mut SubmitTimings::default(),
+            )
+            .await
+            .map_err(|err| submitter.options.enrich(err));
+
+            if let Some(err) = result.as_ref().err().filter(|err| err.status_code >= 500) {
+                warn!(
+                    "Keeping {} in the offline spool, the node failed again: {}",
+                    next.txid, err.message
+                );
+                return;
+            }
+
+            info!("Submitted {} from the offline spool", next.txid);
+            submitter.hooks.post_submit(
+                &SubmittedTx {
+                    cbor: &next.cbor,
+                    signer: None,
+                },
+                &SubmissionOutcome::of(&result),
+            );
+
+            let mut spool = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            spool.statuses.finish(&next.txid, &next.cbor, &result);
+            spool.remove(&next);
+        }
+    }
+
+    /// The oldest transaction still worth submitting.
+    fn next(&self) -> Option<Spooled> {
+        let mut spool = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        spool.prune_expired();
+        spool.txs.front().cloned()
+    }
+}
+
+impl Spool {
+    /// Past `--spool-max-age-secs`, or its deadline.
+    fn is_expired(&self, spooled: &Spooled) -> bool {
+        let age = unix_now_millis().saturating_sub(spooled.spooled_at);
+
+        age > self.max_age.as_millis() as u64
+            || spooled
+                .deadline
+                .is_some_and(|deadline| deadline.has_passed())
+    }
+
+    fn prune_expired(&mut self) {
+        while let Some(oldest) = self.txs.iter().position(|spooled| self.is_expired(spooled)) {
+            let spooled = self.txs.remove(oldest).unwrap();
+            warn!(
+                "Dropping {} from the offline spool, the node was unreachable for too long",
+                spooled.txid
+            );
+
+            self.remove_file(&spooled);
+            let result = Err(BlockfrostError::service_unavailable(
+                "Not submitted, the node was unreachable for too long".to_string(),
+            ));
+            self.statuses.finish(&spooled.txid, &spooled.cbor, &result);
+            self.hooks.post_submit(
+                &SubmittedTx {
+                    cbor: &spooled.cbor,
+                    signer: None,
+                },
+                &SubmissionOutcome::of(&result),
+            );
+        }
+    }
+
+    /// Unless it was pruned in the meantime.
+    fn remove(&mut self, submitted: &Spooled) {
+        let position = self.txs.iter().position(|spooled| {
+            spooled.spooled_at == submitted.spooled_at && spooled.txid == submitted.txid
+        });
+
+        if let Some(spooled) = position.and_then(|position| self.txs.remove(position)) {
+            self.remove_file(&spooled);
+        }
+    }
+
+    fn remove_file(&self, spooled: &Spooled) {
+        let path = self
+            .data_dir
+            .path(Component::Spool)
+            .join(spooled.file_name());
+        if let Err(e) = fs::remove_file(&path) {
+            warn!("Failed to remove {}: {}", path.display(), e);
+        }
+    }
+}
+
+impl Spooled {
+    fn new(spooled_at: u64, cbor: Vec<u8>, genesis: &GenesisValues) -> Self {
+        Self {
+            txid: tx_id(&cbor),
+            spooled_at,
+            deadline: TxDeadline::of(&cbor, genesis),
+            cbor,
+        }
+    }
+
+    fn file_name(&self) -> String {
+        format!("{:013}-{}.cbor", self.spooled_at, self.txid)
+    }
+}
+
+fn unix_now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{stats::Stats, tx_submit::SubmitOptions},
+        node::mock::MockNode,
+        node::protocol_params::ProtocolParamsHistory,
+        node::transactions::transport_error,
+    };
+    use pretty_assertions::assert_eq;
+    use std::cell::{RefCell, RefMut};
+
+    fn data_dir(name: &str) -> (std::path::PathBuf, DataDir) {
+        let root = std::env::temp_dir().join(format!("bf-spool-{}-{}", name, std::process::id()));
+        let data_dir = DataDir::new(&root, 1024 * 1024).unwrap();
+        (root, data_dir)
+    }
+
+    fn spool(data_dir: &DataDir, statuses: &SubmissionStatuses) -> OfflineSpool {
+        spool_with_hooks(data_dir, statuses, SubmissionHooks::new())
+    }
+
+    fn spool_with_hooks(
+        data_dir: &DataDir,
+        statuses: &SubmissionStatuses,
+        hooks: SubmissionHooks,
+    ) -> OfflineSpool {
+        OfflineSpool::new(
+            data_dir.clone(),
+            2,
+            Duration::from_secs(600),
+            GenesisValues::preview(),
+            statuses.clone(),
+            hooks,
+        )
+    }
+
+    fn submitter(statuses: &SubmissionStatuses) -> Submitter {
+        submitter_with_hooks(statuses, SubmissionHooks::new())
+    }
+
+    fn submitter_with_hooks(statuses: &SubmissionStatuses, hooks: SubmissionHooks) -> Submitter {
+        Submitter {
+            relay: None,
+            options: SubmitOptions::default(),
+            hooks,
+            history: ProtocolParamsHistory::default(),
+            statuses: statuses.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_and_drain() {
+        let (root, data_dir) = data_dir("drain");
+        let statuses = SubmissionStatuses::default();
+        let spool = spool(&data_dir, &statuses);
+
+        let first = spool.push(vec![0x84, 0x01]).unwrap();
+        let second = spool.push(vec![0x84, 0x02]).unwrap();
+        assert_eq!(spool.push(vec![0x84, 0x03]).unwrap_err().status_code, 503);
+        assert_eq!(statuses.get(&first), Some(SubmissionStatus::QueuedOffline));
+
+        // Still unreachable
+        spool
+            .drain(&submitter(&statuses), || async {
+                Err::<&mut MockNode, _>(AppError::Node("No connection".to_string()))
+            })
+            .await;
+        assert_eq!(statuses.get(&first), Some(SubmissionStatus::QueuedOffline));
+
+        // Survives a restart, in order
+        let spool = self::spool(&data_dir, &statuses);
+        let mut node = MockNode::new()
+            .with_submit_result(Ok(first.clone()))
+            .with_submit_result(Ok(second.clone()));
+        let node = RefCell::new(&mut node);
+        let node = &node;
+
+        spool
+            .drain(&submitter(&statuses), move || async move {
+                Ok::<_, AppError>(RefMut::map(node.borrow_mut(), |node| &mut **node))
+            })
+            .await;
+
+        assert_eq!(statuses.get(&first), Some(SubmissionStatus::Accepted));
+        assert_eq!(statuses.get(&second), Some(SubmissionStatus::Accepted));
+        assert_eq!(
+            fs::read_dir(data_dir.path(Component::Spool))
+                .unwrap()
+                .count(),
+            0
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_drain_keeps_on_node_failure() {
+        let (root, data_dir) = data_dir("keep");
+        let statuses = SubmissionStatuses::default();
+        let spool = spool(&data_dir, &statuses);
+        let txid = spool.push(vec![0x84, 0x01]).unwrap();
+        let stats = Stats::new(0.99);
+        let submitter = submitter_with_hooks(&statuses, SubmissionHooks::new().with(stats.clone()));
+
+        let mut node = MockNode::new()
+            .with_submit_result(Err(transport_error("Connection reset")))
+            .with_submit_result(Ok(txid.clone()));
+        let node = RefCell::new(&mut node);
+        let node = &node;
+        let get_node = move || async move {
+            Ok::<_, AppError>(RefMut::map(node.borrow_mut(), |node| &mut **node))
+        };
+
+        spool.drain(&submitter, get_node).await;
+        assert_eq!(statuses.get(&txid), Some(SubmissionStatus::QueuedOffline));
+        assert_eq!(spool.next().map(|next| next.txid), Some(txid.clone()));
+
+        spool.drain(&submitter, get_node).await;
+        assert_eq!(statuses.get(&txid), Some(SubmissionStatus::Accepted));
+        assert!(spool.next().is_none());
+
+        // Only the attempt that decided it counts
+        let hour = &stats.slo().windows[0];
+        assert_eq!((hour.successful, hour.server_errors), (1, 0));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_prune_expired() {
+        let (root, data_dir) = data_dir("prune");
+        let statuses = SubmissionStatuses::default();
+
+        let old = Spooled::new(
+            unix_now_millis() - 601_000,
+            vec![0x84, 0x01],
+            &GenesisValues::preview(),
+        );
+        data_dir
+            .write(Component::Spool, &old.file_name(), &old.cbor)
+            .unwrap();
+
+        let stats = Stats::new(0.99);
+        let spool = spool_with_hooks(
+            &data_dir,
+            &statuses,
+            SubmissionHooks::new().with(stats.clone()),
+        );
+        assert!(spool.next().is_none());
+        assert!(matches!(
+            statuses.get(&old.txid),
+            Some(SubmissionStatus::Rejected { error }) if error.status_code == 503
+        ));
+        assert_eq!(stats.slo().windows[0].server_errors, 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
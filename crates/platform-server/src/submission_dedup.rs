@@ -0,0 +1,279 @@
+use crate::BlockfrostError;
+use metrics::counter;
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::watch;
+
+/// How many recent outcomes are remembered, and for how long.
+const RECENT_CAPACITY: usize = 1_000;
+const RECENT_TTL: Duration = Duration::from_secs(30);
+
+type Outcome = Result<String, BlockfrostError>;
+
+/// Coalesces concurrent submissions of the same transaction into a single one,
+/// whose outcome every submitter gets, and answers resubmissions shortly
+/// after with the same outcome, so that wallets retrying in a loop don’t each
+/// cost a node round-trip.
+#[derive(Clone)]
+pub struct SubmissionDedup(Arc<Mutex<Inner>>);
+
+struct Inner {
+    in_flight: HashMap<String, watch::Receiver<Option<Outcome>>>,
+    recent: HashMap<String, (Instant, Outcome)>,
+    /// Oldest first
+    recent_order: VecDeque<String>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl Default for SubmissionDedup {
+    fn default() -> Self {
+        Self::new(RECENT_CAPACITY, RECENT_TTL)
+    }
+}
+
+impl SubmissionDedup {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self(Arc::new(Mutex::new(Inner {
+            in_flight: HashMap::new(),
+            recent: HashMap::new(),
+            recent_order: VecDeque::new(),
+            capacity,
+            ttl,
+        })))
+    }
+
+    /// Runs `submit` for `txid`, unless it’s already being submitted, or was
+    /// just now, in which case `submit` is dropped and its outcome shared.
+    pub async fn submit(&self, txid: &str, submit: impl Future<Output = Outcome>) -> Outcome {
+        loop {
+            let waiting = {
+                let mut inner = self.0.lock().unwrap_or_else(|e| e.into_inner());
+
+                if let Some(outcome) = inner.recent_outcome(txid) {
+                    counter!("tx_submit_deduplicated_total", "source" => "recent").increment(1);
+                    return outcome;
+                }
+
+                match inner.in_flight.get(txid) {
+                    Some(receiver) => Ok(receiver.clone()),
+                    None => {
+                        let (sender, receiver) = watch::channel(None);
+                        inner.in_flight.insert(txid.to_string(), receiver);
+                        Err(sender)
+                    }
+                }
+            };
+
+            let mut waiting = match waiting {
+                Ok(receiver) => receiver,
+                Err(sender) => return self.lead(txid, sender, submit).await,
+            };
+
+            counter!("tx_submit_deduplicated_total", "source" => "in_flight").increment(1);
+            let outcome = waiting
+                .wait_for(Option::is_some)
+                .await
+                .map(|outcome| outcome.clone());
+            match outcome {
+                Ok(outcome) => return outcome.expect("waited for an outcome"),
+                // The submitter went away, e.g. its client hung up: take over
+                Err(_) => continue,
+            }
+        }
+    }
+
+    async fn lead(
+        &self,
+        txid: &str,
+        sender: watch::Sender<Option<Outcome>>,
+        submit: impl Future<Output = Outcome>,
+    ) -> Outcome {
+        let mut guard = InFlight {
+            dedup: self,
+            txid,
+            finished: false,
+        };
+        let outcome = submit.await;
+
+        // At once, so that no one starts over in between
+        {
+            let mut inner = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            inner.in_flight.remove(txid);
+            if is_lasting(&outcome) {
+                inner.remember(txid, outcome.clone());
+            }
+            guard.finished = true;
+        }
+        sender.send_replace(Some(outcome.clone()));
+
+        outcome
+    }
+}
+
+/// Unregisters a submission dropped halfway, for another to take over.
+struct InFlight<'a> {
+    dedup: &'a SubmissionDedup,
+    txid: &'a str,
+    finished: bool,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let mut inner = self.dedup.0.lock().unwrap_or_else(|e| e.into_inner());
+            inner.in_flight.remove(self.txid);
+        }
+    }
+}
+
+impl Inner {
+    fn recent_outcome(&mut self, txid: &str) -> Option<Outcome> {
+        self.expire();
+        self.recent.get(txid).map(|(_, outcome)| outcome.clone())
+    }
+
+    fn remember(&mut self, txid: &str, outcome: Outcome) {
+        if self
+            .recent
+            .insert(txid.to_string(), (Instant::now(), outcome))
+            .is_some()
+        {
+            self.recent_order.retain(|recent| recent != txid);
+        }
+        self.recent_order.push_back(txid.to_string());
+
+        while self.recent_order.len() > self.capacity {
+            if let Some(oldest) = self.recent_order.pop_front() {
+                self.recent.remove(&oldest);
+            }
+        }
+    }
+
+    fn expire(&mut self) {
+        while let Some(oldest) = self.recent_order.front() {
+            match self.recent.get(oldest) {
+                Some((at, _)) if at.elapsed() < self.ttl => break,
+                _ => {
+                    if let Some(oldest) = self.recent_order.pop_front() {
+                        self.recent.remove(&oldest);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Acceptances and ledger rejections, unlike timeouts and unreachable nodes,
+/// would only be the same again.
+fn is_lasting(outcome: &Outcome) -> bool {
+    match outcome {
+        Ok(_) => true,
+        Err(err) => err.status_code == 400,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Notify;
+
+    #[tokio::test]
+    async fn test_concurrent_submissions() {
+        let dedup = SubmissionDedup::default();
+        let submitted = Arc::new(AtomicUsize::new(0));
+        let release = Arc::new(Notify::new());
+
+        let submitters: Vec<_> = (0..3)
+            .map(|_| {
+                let (dedup, submitted, release) =
+                    (dedup.clone(), submitted.clone(), release.clone());
+                tokio::spawn(async move {
+                    dedup
+                        .submit("abcd", async {
+                            submitted.fetch_add(1, Ordering::SeqCst);
+                            release.notified().await;
+                            Ok("abcd".to_string())
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        // Until all of them are waiting on the first
+        while submitted.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        release.notify_one();
+
+        for submitter in submitters {
+            assert_eq!(submitter.await.unwrap(), Ok("abcd".to_string()));
+        }
+        assert_eq!(submitted.load(Ordering::SeqCst), 1);
+
+        // And right after
+        let outcome = dedup.submit("abcd", async { unreachable!() }).await;
+        assert_eq!(outcome, Ok("abcd".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_recent_outcomes() {
+        let dedup = SubmissionDedup::new(1, Duration::from_secs(60));
+        let rejected = || BlockfrostError::custom_400("rejected".to_string());
+        let unavailable = || BlockfrostError::service_unavailable("unavailable".to_string());
+
+        dedup.submit("a", async { Err(rejected()) }).await.ok();
+        assert_eq!(
+            dedup.submit("a", async { Ok("a".to_string()) }).await,
+            Err(rejected())
+        );
+
+        // Only lasting outcomes are remembered
+        dedup.submit("b", async { Err(unavailable()) }).await.ok();
+        assert_eq!(
+            dedup.submit("b", async { Ok("b".to_string()) }).await,
+            Ok("b".to_string())
+        );
+
+        // `b` pushed `a` out
+        assert_eq!(
+            dedup.submit("a", async { Ok("a".to_string()) }).await,
+            Ok("a".to_string())
+        );
+
+        let dedup = SubmissionDedup::new(10, Duration::ZERO);
+        dedup.submit("a", async { Err(rejected()) }).await.ok();
+        assert_eq!(
+            dedup.submit("a", async { Ok("a".to_string()) }).await,
+            Ok("a".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_submission() {
+        let dedup = SubmissionDedup::default();
+
+        let abandoned = dedup.submit("abcd", std::future::pending());
+        assert!(tokio::time::timeout(Duration::from_millis(10), abandoned)
+            .await
+            .is_err());
+
+        // Taken over, rather than waited on forever
+        let outcome = tokio::time::timeout(
+            Duration::from_secs(5),
+            dedup.submit("abcd", async { Ok("abcd".to_string()) }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome, Ok("abcd".to_string()));
+    }
+}
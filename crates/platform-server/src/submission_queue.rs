@@ -0,0 +1,224 @@
+use crate::{
+    api::{
+        tx_status::{SubmissionStatus, SubmissionStatuses},
+        tx_submit::{submit_result, SubmitOptions},
+    },
+    hooks::SubmissionHooks,
+    node::{
+        api::NodeApi,
+        protocol_params::ProtocolParamsHistory,
+        relay::Relay,
+        transactions::{tx_id, SubmitTimings},
+    },
+    AppError, BlockfrostError, NodePool,
+};
+use std::{future::Future, ops::DerefMut, sync::Arc};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    Mutex,
+};
+use tracing::{info, Instrument, Span};
+
+/// A queued transaction, along with the span of the request that queued it,
+/// so that its submission is logged as part of that request.
+type Queued = (Vec<u8>, Span);
+
+/// The receiving end of a [`SubmissionQueue`], shared by its workers.
+pub type QueueReceiver = Arc<Mutex<mpsc::Receiver<Queued>>>;
+
+/// Transactions submitted asynchronously, waiting for a worker to hand them
+/// to a node. Their progress is recorded in the statuses, for clients to
+/// poll at `/tx/{txid}/status`.
+#[derive(Clone)]
+pub struct SubmissionQueue {
+    sender: mpsc::Sender<Queued>,
+    statuses: SubmissionStatuses,
+}
+
+impl SubmissionQueue {
+    /// Returns the queue along with its receiving end, for [`Submitter::drain`].
+    pub fn new(capacity: usize, statuses: SubmissionStatuses) -> (Self, QueueReceiver) {
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+
+        (Self { sender, statuses }, Arc::new(Mutex::new(receiver)))
+    }
+
+    /// Like [`SubmissionQueue::new`], with `workers` draining the queue into
+    /// the node pool, one per connection it may open.
+    pub fn spawn(capacity: usize, workers: usize, node: NodePool, submitter: Submitter) -> Self {
+        let (queue, receiver) = Self::new(capacity, submitter.statuses.clone());
+
+        for _ in 0..workers.max(1) {
+            let node = node.clone();
+            tokio::spawn(submitter.clone().drain(receiver.clone(), move || {
+                let node = node.clone();
+                async move { node.get().await }
+            }));
+        }
+
+        queue
+    }
+
+    /// Queues `tx`, returning its id, or fails right away if the queue is
+    /// full.
+    pub fn enqueue(&self, tx: Vec<u8>) -> Result<String, BlockfrostError> {
+        let permit = self.sender.try_reserve().map_err(|err| match err {
+            TrySendError::Full(()) => BlockfrostError::service_unavailable(
+                "Too many transactions waiting to be submitted, try again later".to_string(),
+            ),
+            TrySendError::Closed(()) => {
+                BlockfrostError::internal_server_error("Submission queue is closed".to_string())
+            }
+        })?;
+
+        // Before sending, so that a worker can’t get to it first
+        let txid = tx_id(&tx);
+        self.statuses.set(&txid, SubmissionStatus::Queued);
+        permit.send((tx, Span::current()));
+
+        Ok(txid)
+    }
+}
+
+/// Everything the queue workers need to submit a transaction, besides a
+/// node connection.
+#[derive(Clone)]
+pub struct Submitter {
+    pub relay: Option<Relay>,
+    pub options: SubmitOptions,
+    pub hooks: SubmissionHooks,
+    pub history: ProtocolParamsHistory,
+    pub statuses: SubmissionStatuses,
+}
+
+impl Submitter {
+    /// Submits the queued transactions one at a time to a node from
+    /// `get_node`, until the queue is dropped.
+    pub async fn drain<N, F>(self, receiver: QueueReceiver, mut get_node: impl FnMut() -> F)
+    where
+        F: Future<Output = Result<N, AppError>>,
+        N: DerefMut<Target: NodeApi>,
+    {
+        loop {
+            let Some((tx, span)) = receiver.lock().await.recv().await else {
+                info!("Submission queue closed, worker stopping");
+                return;
+            };
+
+            let txid = tx_id(&tx);
+            let (statuses, submitted) = (&self.statuses, &txid);
+            let result = submit_result(
+                || {
+                    let node = get_node();
+                    async move {
+                        let node = node.await?;
+                        statuses.set(submitted, SubmissionStatus::Submitted);
+                        Ok(node)
+                    }
+                },
+                self.relay.as_ref(),
+                &self.options.retry,
+                None,
+                &self.hooks,
+                &self.history,
+                tx.clone(),
+                None,
+                &mut SubmitTimings::default(),
+            )
+            .instrument(span)
+            .await
+            .map_err(|err| self.options.enrich(err));
+
+            self.statuses.finish(&txid, &tx, &result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::stats::Stats, node::mock::MockNode};
+    use pretty_assertions::assert_eq;
+
+    fn submitter(statuses: &SubmissionStatuses) -> Submitter {
+        Submitter {
+            relay: None,
+            options: SubmitOptions::default(),
+            hooks: SubmissionHooks::new(),
+            history: ProtocolParamsHistory::default(),
+            statuses: statuses.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drain() {
+        let statuses = SubmissionStatuses::default();
+        let (queue, receiver) = SubmissionQueue::new(2, statuses.clone());
+        let stats = Stats::new(0.99);
+
+        let accepted = queue.enqueue(vec![0x84, 0x01]).unwrap();
+        let rejected = queue.enqueue(vec![0x84, 0x02]).unwrap();
+        assert_eq!(statuses.get(&accepted), Some(SubmissionStatus::Queued));
+        drop(queue);
+
+        let mut node = MockNode::new()
+            .with_submit_result(Ok(accepted.clone()))
+            .with_submit_result(Err(BlockfrostError::custom_400("rejected".to_string())));
+        let node = std::cell::RefCell::new(&mut node);
+        let node = &node;
+
+        Submitter {
+            hooks: SubmissionHooks::new().with(stats.clone()),
+            ..submitter(&statuses)
+        }
+        .drain(receiver, move || async move {
+            Ok::<_, AppError>(std::cell::RefMut::map(node.borrow_mut(), |node| {
+                &mut **node
+            }))
+        })
+        .await;
+
+        assert_eq!(statuses.get(&accepted), Some(SubmissionStatus::Accepted));
+        assert_eq!(
+            statuses.get(&rejected),
+            Some(SubmissionStatus::Rejected {
+                error: BlockfrostError::custom_400("rejected".to_string())
+            })
+        );
+        // Once decided, rather than when accepted into the queue
+        let hour = &stats.slo().windows[0];
+        assert_eq!((hour.successful, hour.client_errors), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_drain_no_node() {
+        let statuses = SubmissionStatuses::default();
+        let (queue, receiver) = SubmissionQueue::new(1, statuses.clone());
+        let txid = queue.enqueue(vec![0x84, 0x01]).unwrap();
+        drop(queue);
+
+        submitter(&statuses)
+            .drain(receiver, || async {
+                Err::<&mut MockNode, _>(AppError::Node("No connection".to_string()))
+            })
+            .await;
+
+        assert!(matches!(
+            statuses.get(&txid),
+            Some(SubmissionStatus::Rejected { error }) if error.status_code == 500
+        ));
+    }
+
+    #[test]
+    fn test_enqueue_full() {
+        let statuses = SubmissionStatuses::default();
+        let (queue, _receiver) = SubmissionQueue::new(1, statuses.clone());
+
+        let txid = queue.enqueue(vec![0x84, 0x01]).unwrap();
+        let err = queue.enqueue(vec![0x84, 0x02]).unwrap_err();
+
+        assert_eq!(err.status_code, 503);
+        assert_eq!(statuses.get(&txid), Some(SubmissionStatus::Queued));
+        assert_eq!(statuses.get(&tx_id(&[0x84, 0x02])), None);
+    }
+}
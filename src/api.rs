@@ -1,3 +0,0 @@
-pub mod metrics;
-pub mod root;
-pub mod tx_submit;
@@ -0,0 +1,223 @@
+//! Soak/load-testing harness for a running blockfrost-platform instance.
+//!
+//! Fires transactions at `/tx/submit` at a fixed rate, and reports latency
+//! percentiles together with the number of responses that didn’t have the
+//! expected shape, e.g.:
+//!
+//! ```text
+//! cargo run --bin load-test -- --url http://localhost:3000 --rps 50 --duration-secs 60 --tx-file txs.hex
+//! ```
+
+use clap::{arg, command, Parser};
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+use tokio::{task::JoinSet, time};
+
+#[derive(Parser, Debug)]
+#[command(about = "Load test a running blockfrost-platform instance")]
+struct Args {
+    /// Base URL of the instance under test
+    #[arg(long, default_value = "http://localhost:3000")]
+    url: String,
+
+    /// Requests per second
+    #[arg(long, default_value = "10")]
+    rps: u32,
+
+    /// How long to keep firing requests
+    #[arg(long, default_value = "30")]
+    duration_secs: u64,
+
+    /// File with hex-encoded transactions, one per line, sent round-robin
+    #[arg(long)]
+    tx_file: Option<String>,
+
+    /// Fraction of requests (0.0 to 1.0) that send a malformed transaction instead
+    #[arg(long, default_value = "0.5")]
+    invalid_ratio: f64,
+
+    /// Exit with an error if the p99 latency exceeds this
+    #[arg(long)]
+    max_p99_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Valid,
+    Malformed,
+}
+
+struct Outcome {
+    kind: Kind,
+    latency: Duration,
+    status: Option<u16>,
+    correct: bool,
+}
+
+/// A malformed transaction must be rejected with a 400 Blockfrost error.
+/// Any other transaction is either accepted with its id, or rejected with a
+/// Blockfrost error whose `status_code` matches the HTTP status.
+fn is_correct(kind: Kind, status: u16, body: &str) -> bool {
+    let json: Option<serde_json::Value> = serde_json::from_str(body).ok();
+
+    let is_error_with = |status: u16| {
+        json.as_ref()
+            .and_then(|j| j.get("status_code"))
+            .and_then(|s| s.as_u64())
+            == Some(status as u64)
+    };
+
+    match (kind, status) {
+        (Kind::Malformed, 400) => is_error_with(400),
+        (Kind::Malformed, _) => false,
+        (Kind::Valid, 200) => json.as_ref().is_some_and(|j| j.is_string()),
+        (Kind::Valid, 400..=499) => is_error_with(status),
+        (Kind::Valid, _) => false,
+    }
+}
+
+async fn submit(client: reqwest::Client, url: String, kind: Kind, tx: String) -> Outcome {
+    let started_at = Instant::now();
+
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/cbor")
+        .body(tx)
+        .send()
+        .await;
+
+    let (status, correct) = match response {
+        Ok(response) => {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            (Some(status), is_correct(kind, status, &body))
+        }
+        Err(_) => (None, false),
+    };
+
+    Outcome {
+        kind,
+        latency: started_at.elapsed(),
+        status,
+        correct,
+    }
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let index = ((sorted.len() as f64 * p).ceil() as usize).clamp(1, sorted.len()) - 1;
+    sorted[index]
+}
+
+fn report(kind: Kind, outcomes: &[Outcome]) -> Duration {
+    let outcomes: Vec<_> = outcomes.iter().filter(|o| o.kind == kind).collect();
+    let mut latencies: Vec<_> = outcomes.iter().map(|o| o.latency).collect();
+    latencies.sort();
+
+    let failed = outcomes.iter().filter(|o| o.status.is_none()).count();
+    let incorrect = outcomes.iter().filter(|o| !o.correct).count();
+    let p99 = percentile(&latencies, 0.99);
+
+    println!(
+        "{:?}: {} requests, {} failed, {} incorrect, p50 {:?}, p99 {:?}, max {:?}",
+        kind,
+        outcomes.len(),
+        failed,
+        incorrect,
+        percentile(&latencies, 0.5),
+        p99,
+        latencies.last().copied().unwrap_or_default(),
+    );
+
+    p99
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+
+    let txs: Vec<String> = match &args.tx_file {
+        Some(path) => fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("Failed to read {}: {}", path, e))
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect(),
+        None => vec![],
+    };
+
+    let url = format!("{}/tx/submit", args.url.trim_end_matches('/'));
+    let client = reqwest::Client::new();
+    let total = args.rps as u64 * args.duration_secs;
+
+    println!("Sending {} requests to {} at {} rps", total, url, args.rps);
+
+    let mut interval = time::interval(Duration::from_secs_f64(1.0 / args.rps.max(1) as f64));
+    let mut tasks = JoinSet::new();
+
+    for n in 0..total {
+        interval.tick().await;
+
+        // Spread the malformed requests evenly over the run
+        let malformed = txs.is_empty()
+            || ((n + 1) as f64 * args.invalid_ratio).floor()
+                > (n as f64 * args.invalid_ratio).floor();
+
+        let (kind, tx) = if malformed {
+            // Rejected before it reaches the node
+            (Kind::Malformed, format!("not-a-transaction-{}", n))
+        } else {
+            (Kind::Valid, txs[n as usize % txs.len()].clone())
+        };
+
+        tasks.spawn(submit(client.clone(), url.clone(), kind, tx));
+    }
+
+    let outcomes = tasks.join_all().await;
+
+    let p99 = [Kind::Valid, Kind::Malformed]
+        .into_iter()
+        .map(|kind| report(kind, &outcomes))
+        .max()
+        .unwrap_or_default();
+
+    let incorrect = outcomes.iter().filter(|o| !o.correct).count();
+    let too_slow = args
+        .max_p99_ms
+        .is_some_and(|max| p99 > Duration::from_millis(max));
+
+    if incorrect > 0 || too_slow {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_percentile() {
+        let latencies: Vec<_> = (1..=100).map(Duration::from_millis).collect();
+
+        assert_eq!(percentile(&latencies, 0.5), Duration::from_millis(50));
+        assert_eq!(percentile(&latencies, 0.99), Duration::from_millis(99));
+        assert_eq!(percentile(&[], 0.99), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_is_correct() {
+        let error = r#"{"status_code":400,"error":"Bad Request","message":"x"}"#;
+
+        assert!(is_correct(Kind::Malformed, 400, error));
+        assert!(!is_correct(Kind::Malformed, 200, r#""abc""#));
+        assert!(is_correct(Kind::Valid, 200, r#""abc""#));
+        assert!(is_correct(Kind::Valid, 400, error));
+        assert!(!is_correct(Kind::Valid, 500, error));
+    }
+}
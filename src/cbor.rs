@@ -1,3 +0,0 @@
-pub mod codec;
-pub mod fallback_decoder;
-pub mod haskell_types;
@@ -1,14 +1 @@
-pub mod api;
-pub mod background_tasks;
-pub mod cbor;
-pub mod cli;
-pub mod common;
-pub mod errors;
-pub mod icebreakers_api;
-pub mod logging;
-pub mod middlewares;
-pub mod node;
-pub mod server;
-
-pub use errors::{AppError, BlockfrostError};
-pub use node::pool::NodePool;
+pub use platform_server::*;
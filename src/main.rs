@@ -1,36 +1,84 @@
 use axum::extract::Request;
 use axum::ServiceExt;
+use axum_server::Handle;
 use blockfrost_platform::{
-    background_tasks::node_health_check_task,
-    cli::{Args, Config},
+    background_tasks::{
+        confirmation_tracker_task, epoch_tracker_task, mempool_tracker_task, node_reaper_task,
+        node_supervisor_task,
+    },
+    cbor::fallback_decoder::FallbackDecoder,
+    cli::{args_with_config_files, Args, Command, Config, ConfigAction},
+    dry_run,
     logging::setup_tracing,
-    server::build,
+    server::{build, tls, App},
     AppError,
 };
-use clap::Parser;
-use std::sync::Arc;
+use clap::{CommandFactory, FromArgMatches};
+use std::{net::SocketAddr, sync::Arc};
 use tokio::signal;
-use tracing::info;
+use tracing::{info, warn};
 
 #[tokio::main]
 async fn main() -> Result<(), AppError> {
     // CLI
-    let arguments = Args::parse();
+    let matches = Args::command().get_matches_from(args_with_config_files(std::env::args_os())?);
+
+    // Checked before building `Args`, which would require e.g. `--network`
+    if matches.get_flag("print_runtime_deps") {
+        print_runtime_deps();
+        return Ok(());
+    }
+
+    let arguments = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let command = arguments.command;
+
     let config = Arc::new(Config::from_args(arguments)?);
 
+    if let Some(Command::Config {
+        action: ConfigAction::Dump,
+    }) = command
+    {
+        print!("{}", Args::dump(&matches)?);
+        return Ok(());
+    }
+
     // Logging
-    setup_tracing(config.log_level);
+    let tracer_provider = setup_tracing(
+        config.observability.log_level.into(),
+        config.observability.otlp_endpoint.as_deref(),
+    )?;
+
+    if matches.get_flag("dry_run") {
+        let report = dry_run::run(&config).await;
+        println!("{}", report);
+        std::process::exit(if report.passed() { 0 } else { 1 });
+    }
+
+    // Read the certificate before anything else, so that mistakes fail fast
+    let tls = match &config.tls {
+        Some(tls) => Some(tls::load(tls).await?),
+        None => None,
+    };
 
     // Build app
-    let (app, node_conn_pool) = build(config.clone()).await?;
+    let App {
+        router: app,
+        node_pool: node_conn_pool,
+        protocol_params_history,
+        submission_statuses,
+        confirmation_tracker,
+        recent_chain,
+    } = build(config.clone()).await?;
 
     // Bind server
-    let address = format!("{}:{}", config.server_address, config.server_port);
+    let address = format!("{}:{}", config.server.address, config.server.port);
     let listener = tokio::net::TcpListener::bind(&address).await?;
 
     info!(
-        "Server is listening on http://{}:{}/",
-        config.server_address, config.server_port
+        "Server is listening on {}://{}:{}/",
+        if tls.is_some() { "https" } else { "http" },
+        config.server.address,
+        config.server.port
     );
 
     // Shutdown signal
@@ -40,12 +88,74 @@ async fn main() -> Result<(), AppError> {
     };
 
     // Spawn background tasks
-    tokio::spawn(node_health_check_task(node_conn_pool));
+    tokio::spawn(epoch_tracker_task(
+        node_conn_pool.clone(),
+        protocol_params_history,
+        config.server.epoch_webhook_url.clone(),
+        recent_chain.clone(),
+    ));
+
+    if config.server.track_mempool {
+        tokio::spawn(mempool_tracker_task(
+            node_conn_pool.clone(),
+            submission_statuses,
+        ));
+        tokio::spawn(confirmation_tracker_task(
+            node_conn_pool.clone(),
+            confirmation_tracker,
+            recent_chain,
+        ));
+    }
+    tokio::spawn(node_reaper_task(node_conn_pool.clone()));
+    tokio::spawn(node_supervisor_task(node_conn_pool));
 
     // Serve
-    axum::serve(listener, ServiceExt::<Request>::into_make_service(app))
-        .with_graceful_shutdown(shutdown_signal)
-        .await?;
+    // With the peer address, for per-IP rate limits
+    let app = ServiceExt::<Request>::into_make_service_with_connect_info::<SocketAddr>(app);
+    match tls {
+        Some(tls) => {
+            let handle = Handle::new();
+            tokio::spawn({
+                let handle = handle.clone();
+                async move {
+                    shutdown_signal.await;
+                    handle.graceful_shutdown(None);
+                }
+            });
+            axum_server::from_tcp_rustls(listener.into_std()?, tls)
+                .handle(handle)
+                .serve(app)
+                .await?;
+        }
+        None => {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown_signal)
+                .await?;
+        }
+    }
+
+    if let Some(tracer_provider) = tracer_provider {
+        if let Err(e) = tracer_provider.shutdown() {
+            warn!("Failed to export the last spans: {}", e);
+        }
+    }
 
     Ok(())
 }
+
+fn print_runtime_deps() {
+    println!(
+        "cardano-node: a node-to-client socket (NodeToClientV_10 or newer), see --node-socket-path"
+    );
+    println!();
+    println!("testgen-hs (fallback decoder of transaction rejections), searched in order:");
+
+    for (source, path) in FallbackDecoder::search_order() {
+        println!("  {:<22}{}", source, path.display());
+    }
+
+    match FallbackDecoder::locate_child_binary() {
+        Ok(path) => println!("  found: {}", path.display()),
+        Err(e) => println!("  {}", e),
+    }
+}
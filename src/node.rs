@@ -1,5 +0,0 @@
-pub mod connection;
-pub mod pool;
-pub mod pool_manager;
-pub mod sync_progress;
-pub mod transactions;
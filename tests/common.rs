@@ -1,15 +1,17 @@
-use axum::Router;
 use blockfrost_platform::{
-    cli::{Config, LogLevel, Mode, Network},
-    server::build,
-    AppError, NodePool,
+    cli::{
+        Config, CorsConfig, LimitsConfig, LogLevel, Mode, Network, NodeConfig, ObservabilityConfig,
+        ServerConfig,
+    },
+    server::{build, App},
+    AppError,
 };
+use pallas_traverse::wellknown::GenesisValues;
 // use pretty_assertions::assert_eq;;
 use std::{
     env,
     sync::{Arc, LazyLock},
 };
-use tower_http::normalize_path::NormalizePath;
 
 static INIT_LOGGING: LazyLock<()> = LazyLock::new(|| {
     tracing_subscriber::fmt::init();
@@ -26,22 +28,70 @@ pub fn test_config() -> Arc<Config> {
         env::var("NODE_SOCKET_PATH").unwrap_or_else(|_| "/run/cardano-node/node.socket".into());
 
     let config = Config {
-        server_address: "0.0.0.0".into(),
-        server_port: 8080,
-        log_level: LogLevel::Info.into(),
-        network_magic: 2,
-        mode: Mode::Compact,
-        node_socket_path: node_socket_path_env,
-        icebreakers_config: None,
-        max_pool_connections: 10,
-        network: Network::Preview,
-        metrics: false,
+        server: ServerConfig {
+            address: "0.0.0.0".into(),
+            port: 8080,
+            mode: Mode::Compact,
+            epoch_webhook_url: None,
+            data_dir: None,
+            enrich_responses: false,
+            blockfrost_project_id: None,
+            record_traffic: None,
+            record_bodies: false,
+            read_only: false,
+            operator_token: None,
+            verify_signatures: false,
+            pre_validate: false,
+            spool_offline: false,
+            track_mempool: false,
+            ready_min_sync_progress: 99.0,
+        },
+        node: NodeConfig {
+            network: Network::Preview,
+            genesis: GenesisValues::preview(),
+            socket_path: node_socket_path_env,
+            relay: None,
+            max_pool_connections: 2,
+            idle_timeout_secs: 60,
+            max_connection_age_secs: 3600,
+            connect_timeout_secs: 10,
+            submit_timeout_secs: 30,
+            statequery_timeout_secs: 60,
+            submit_retries: 0,
+            submit_retry_backoff_ms: 250,
+        },
+        icebreakers: None,
+        limits: LimitsConfig {
+            data_dir_quota_mb: 512,
+            blockfrost_cache_secs: 20,
+            submission_statuses: 1_000,
+            recent_blocks: 180,
+            submission_queue: 1_000,
+            spool_max_txs: 100,
+            spool_max_age_secs: 900,
+            max_tx_size: 65_536,
+            rate_limit_query_per_ip: None,
+            rate_limit_query_global: None,
+            rate_limit_submit_per_ip: None,
+            rate_limit_submit_global: None,
+            rate_limit_forwarded_for: false,
+        },
+        observability: ObservabilityConfig {
+            log_level: LogLevel::Info,
+            metrics: false,
+            trace_sample_rate: 1.0,
+            trace_sample_routes: vec![],
+            otlp_endpoint: None,
+            slo_target: 0.999,
+        },
+        cors: CorsConfig::default(),
+        tls: None,
     };
 
     Arc::new(config)
 }
 
-pub async fn build_app() -> Result<(NormalizePath<Router>, NodePool), AppError> {
+pub async fn build_app() -> Result<App, AppError> {
     let config = test_config();
 
     build(config).await